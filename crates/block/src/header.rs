@@ -28,6 +28,10 @@ pub struct BlockHeader {
     pub txn_hash: String,
     pub miner_claim: Claim,
     pub claim_list_hash: String,
+    /// Root hash of the state trie the block's transactions are applied on
+    /// top of, i.e. right after the previous block was applied. Empty for
+    /// genesis blocks.
+    pub state_root_hash: String,
     pub block_reward: Reward,
     pub next_block_reward: Reward,
     pub miner_signature: primitives::Signature,
@@ -132,12 +136,14 @@ impl BlockHeader {
             txn_hash,
             miner_claim,
             claim_list_hash,
+            state_root_hash: String::new(),
             block_reward,
             next_block_reward,
             miner_signature,
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         last_block: Block,
         ref_hashes: Vec<String>,
@@ -145,6 +151,7 @@ impl BlockHeader {
         secret_key: SecretKey,
         txn_hash: String,
         claim_list_hash: String,
+        state_root_hash: String,
         adjustment_next_epoch: NextEpochAdjustment,
     ) -> Option<BlockHeader> {
         // Get the last block
@@ -206,6 +213,7 @@ impl BlockHeader {
             txn_hash,
             miner_claim,
             claim_list_hash,
+            state_root_hash,
             block_reward,
             next_block_reward
         );
@@ -223,6 +231,7 @@ impl BlockHeader {
             txn_hash,
             miner_claim,
             claim_list_hash,
+            state_root_hash,
             block_reward,
             next_block_reward,
             miner_signature,
@@ -241,12 +250,35 @@ impl BlockHeader {
             self.txn_hash,
             self.miner_claim,
             self.claim_list_hash,
+            self.state_root_hash,
             self.block_reward,
             self.next_block_reward,
             self.miner_signature
         )
     }
 
+    /// Hash of the `ConvergenceBlock` the header belongs to, which its
+    /// certificate is issued over.
+    pub fn convergence_block_hash(&self) -> String {
+        let block_hash = hash_data!(
+            self.ref_hashes,
+            self.round,
+            self.block_seed,
+            self.next_block_seed,
+            self.block_height,
+            self.timestamp,
+            self.txn_hash,
+            self.miner_claim,
+            self.claim_list_hash,
+            self.state_root_hash,
+            self.block_reward,
+            self.next_block_reward,
+            self.miner_signature
+        );
+
+        format!("{block_hash:x}")
+    }
+
     pub fn as_bytes(&self) -> Vec<u8> {
         self.to_string().as_bytes().to_vec()
     }
@@ -102,6 +102,7 @@ pub mod valid {
                 self.epoch,
                 hashable_txns,
                 self.claims,
+                self.abandoned_claims,
                 self.from
             )
             .to_vec()
@@ -1,4 +1,6 @@
-use crate::{BlockHash, ClaimList, ConvergenceBlock, QuorumCertifiedTxnList, RefHash};
+use crate::{
+    AbandonedClaims, BlockHash, ClaimList, ConvergenceBlock, QuorumCertifiedTxnList, RefHash,
+};
 use hex::FromHexError;
 use primitives::{Epoch, Signature};
 use ritelinked::LinkedHashSet;
@@ -16,6 +18,8 @@ pub struct ProposalBlock {
     pub epoch: Epoch,
     pub txns: QuorumCertifiedTxnList,
     pub claims: ClaimList,
+    #[serde(default)]
+    pub abandoned_claims: AbandonedClaims,
     pub from: Claim,
     pub hash: BlockHash,
     pub signature: Option<Signature>,
@@ -39,6 +43,8 @@ impl ProposalBlock {
     /// * `claims`: `claims` is a list of claims made by validators in the
     ///   network. It is used as one of
     /// the inputs to calculate the hash of the block being proposed.
+    /// * `abandoned_claims`: claims their owners gave up, each with the
+    ///   owner's signature over the abandonment.
     /// * `from`: The `from` parameter is of type `Claim` and represents the
     ///   claim of the harvester who is
     /// proposing the block. It is used to sign the block proposal and ensure
@@ -57,6 +63,7 @@ impl ProposalBlock {
         epoch: Epoch,
         txns: QuorumCertifiedTxnList,
         claims: ClaimList,
+        abandoned_claims: AbandonedClaims,
         from: Claim,
         mut sig_engine: SignerEngine,
     ) -> ProposalBlock {
@@ -65,7 +72,7 @@ impl ProposalBlock {
                 .map(|(k, v)| (k.digest_string(), v.clone()))
                 .collect()
         };
-        let payload = hash_data!(round, epoch, hashable_txns, claims, from);
+        let payload = hash_data!(round, epoch, hashable_txns, claims, abandoned_claims, from);
         let signature = if let Ok(signature) = sig_engine.sign(payload) {
             Some(signature)
         } else {
@@ -77,6 +84,7 @@ impl ProposalBlock {
             epoch,
             hashable_txns,
             claims,
+            abandoned_claims,
             from,
             signature
         ));
@@ -87,6 +95,7 @@ impl ProposalBlock {
             epoch,
             txns,
             claims,
+            abandoned_claims,
             hash,
             from,
             signature,
@@ -7,7 +7,7 @@ use std::{
     hash::{Hash, Hasher},
 };
 
-use primitives::{NodeId, Signature};
+use primitives::{NodeId, RawSignature, Signature};
 #[cfg(mainnet)]
 use reward::reward::GENESIS_REWARD;
 use ritelinked::{LinkedHashMap, LinkedHashSet};
@@ -31,6 +31,9 @@ pub type RefHash = String;
 pub type TxnList = LinkedHashMap<TransactionDigest, TransactionKind>;
 pub type QuorumCertifiedTxnList = LinkedHashMap<TransactionDigest, TransactionKind>;
 pub type ClaimList = LinkedHashMap<ClaimHash, Claim>;
+/// Claims given up by their owners, mapped to the owner's signature over
+/// the abandonment
+pub type AbandonedClaims = LinkedHashMap<ClaimHash, String>;
 pub type ConsolidatedTxns = LinkedHashMap<RefHash, LinkedHashSet<TransactionDigest>>;
 pub type ConsolidatedClaims = LinkedHashMap<RefHash, LinkedHashSet<ClaimHash>>;
 pub type BlockHash = String;
@@ -46,6 +49,10 @@ pub struct Certificate {
     pub inauguration: Option<QuorumMembers>,
     pub root_hash: String,
     pub block_hash: String,
+    /// Signature over `block_hash` combined from the signature shares of the
+    /// harvester quorum, verified against its public key set
+    #[serde(default)]
+    pub threshold_signature: Option<RawSignature>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
@@ -2,6 +2,7 @@ use std::path::PathBuf;
 
 use clap::{Parser, Subcommand};
 
+use crate::commands::db::DbOpts;
 use crate::commands::dev::DevOpts;
 use crate::commands::faucet::FaucetOpts;
 use crate::commands::{config::ConfigOpts, keygen::KeygenCmd, node::NodeOpts, wallet::WalletOpts};
@@ -44,4 +45,7 @@ pub enum Commands {
 
     /// Start a faucet server to transfer tokens to accounts
     Faucet(FaucetOpts),
+
+    /// Inspect and maintain a node's database without running the node
+    Db(DbOpts),
 }
@@ -0,0 +1,21 @@
+use storage::vrrbdb::VrrbDb;
+
+use super::{print_json, StoreKind};
+use crate::result::Result;
+
+pub fn exec(db: &VrrbDb, store: StoreKind) -> Result<()> {
+    match store {
+        StoreKind::Accounts => {
+            let accounts = db.state_store_factory().handle().entries()?;
+            print_json(&accounts.into_values().collect::<Vec<_>>())
+        }
+        StoreKind::Claims => {
+            let claims = db.claim_store_factory().handle().entries()?;
+            print_json(&claims.into_values().collect::<Vec<_>>())
+        }
+        StoreKind::Transactions => {
+            let transactions = db.transaction_store_factory().handle().entries()?;
+            print_json(&transactions.into_values().collect::<Vec<_>>())
+        }
+    }
+}
@@ -0,0 +1,91 @@
+mod dump;
+mod roots;
+mod verify;
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use storage::vrrbdb::{AccessMode, PruningMode, VrrbDb, VrrbDbConfig};
+
+use crate::result::{CliError, Result};
+
+#[derive(Parser, Debug)]
+pub struct DbOpts {
+    /// Path to the node's database. Defaults to the one within the node's data
+    /// directory
+    #[clap(long, value_parser)]
+    pub db_path: Option<PathBuf>,
+
+    #[clap(subcommand)]
+    pub subcommand: DbCmd,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DbCmd {
+    /// Lists the column families of every store
+    ColumnFamilies,
+
+    /// Prints the contents of a store as JSON
+    Dump {
+        #[clap(subcommand)]
+        store: StoreKind,
+    },
+
+    /// Prints the root hash of every version of each store's trie still on
+    /// disk
+    Roots,
+
+    /// Checks every value of each store against its trie's root hash
+    Verify,
+
+    /// Compacts the database. The node using it must be stopped first
+    Compact,
+}
+
+#[derive(Debug, Clone, Copy, Subcommand)]
+pub enum StoreKind {
+    Accounts,
+    Claims,
+    Transactions,
+}
+
+pub async fn exec(args: DbOpts) -> Result<()> {
+    let mut config = VrrbDbConfig::default();
+    if let Some(db_path) = args.db_path {
+        config.with_path(db_path);
+    }
+
+    match args.subcommand {
+        DbCmd::ColumnFamilies => {
+            let db = open(config, AccessMode::ReadOnly)?;
+            print_json(&db.column_families()?)
+        }
+        DbCmd::Dump { store } => dump::exec(&open(config, AccessMode::ReadOnly)?, store),
+        DbCmd::Roots => roots::exec(&open(config, AccessMode::ReadOnly)?),
+        DbCmd::Verify => verify::exec(&open(config, AccessMode::ReadOnly)?),
+        DbCmd::Compact => {
+            config.pruning = PruningMode::Archive;
+            open(config, AccessMode::ReadWrite)?.compact()?;
+            Ok(())
+        }
+    }
+}
+
+fn open(config: VrrbDbConfig, access: AccessMode) -> Result<VrrbDb> {
+    let path = config.path.clone();
+
+    VrrbDb::open(config, access).map_err(|err| {
+        CliError::Other(format!(
+            "unable to open database at {}: {err}",
+            path.display()
+        ))
+    })
+}
+
+fn print_json<T: serde::Serialize>(value: &T) -> Result<()> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| CliError::Other(e.to_string()))?;
+
+    println!("{json}");
+
+    Ok(())
+}
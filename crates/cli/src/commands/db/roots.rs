@@ -0,0 +1,19 @@
+use std::collections::BTreeMap;
+
+use storage::vrrbdb::VrrbDb;
+
+use super::print_json;
+use crate::result::Result;
+
+pub fn exec(db: &VrrbDb) -> Result<()> {
+    let roots = BTreeMap::from([
+        ("state", db.state_store_factory().root_hashes()?),
+        (
+            "transactions",
+            db.transaction_store_factory().root_hashes()?,
+        ),
+        ("claims", db.claim_store_factory().root_hashes()?),
+    ]);
+
+    print_json(&roots)
+}
@@ -0,0 +1,34 @@
+use std::collections::BTreeMap;
+
+use storage::vrrbdb::VrrbDb;
+
+use super::print_json;
+use crate::result::{CliError, Result};
+
+pub fn exec(db: &VrrbDb) -> Result<()> {
+    let reports = BTreeMap::from([
+        ("state", db.state_store_factory().verify_integrity()?),
+        (
+            "transactions",
+            db.transaction_store_factory().verify_integrity()?,
+        ),
+        ("claims", db.claim_store_factory().verify_integrity()?),
+    ]);
+
+    print_json(&reports)?;
+
+    let corrupted: Vec<&str> = reports
+        .iter()
+        .filter(|(_, report)| !report.is_valid())
+        .map(|(store, _)| *store)
+        .collect();
+
+    if !corrupted.is_empty() {
+        return Err(CliError::Other(format!(
+            "integrity check failed for the {} store(s)",
+            corrupted.join(", ")
+        )));
+    }
+
+    Ok(())
+}
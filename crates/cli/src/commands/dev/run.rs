@@ -150,6 +150,7 @@ impl From<RunOpts> for NodeConfig {
             http_api_version: opts.http_api_version,
             http_api_shutdown_timeout: default_node_config.http_api_shutdown_timeout,
             jsonrpc_server_address: opts.jsonrpc_api_address,
+            grpc_server_address: default_node_config.grpc_server_address,
             preload_mock_state: default_node_config.preload_mock_state,
             bootstrap_config,
             bootstrap_peer_data: None,
@@ -167,7 +168,16 @@ impl From<RunOpts> for NodeConfig {
             public_ip_address: opts.raptorq_gossip_address,
             quorum_config: default_node_config.quorum_config,
             enable_block_indexing: default_node_config.enable_block_indexing,
+            enable_mempool_journal: default_node_config.enable_mempool_journal,
+            mempool: default_node_config.mempool,
+            faucet_drip_amount: default_node_config.faucet_drip_amount,
+            archive_mode: default_node_config.archive_mode,
+            state_pruning_window: default_node_config.state_pruning_window,
+            state_history_window: default_node_config.state_history_window,
+            in_memory_storage: default_node_config.in_memory_storage,
             threshold_config: default_node_config.threshold_config,
+            peer_scoring: default_node_config.peer_scoring,
+            encrypt_direct_messages: default_node_config.encrypt_direct_messages,
             whitelisted_nodes: default_node_config.whitelisted_nodes,
             prometheus_bind_addr: default_node_config.prometheus_bind_addr,
             prometheus_bind_port: default_node_config.prometheus_bind_port,
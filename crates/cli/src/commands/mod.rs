@@ -1,4 +1,5 @@
 pub mod config;
+pub mod db;
 pub mod dev;
 pub mod faucet;
 pub mod keygen;
@@ -22,6 +23,7 @@ pub async fn exec(args: Args) -> Result<()> {
         Some(Commands::Wallet(wallet_args)) => wallet::exec(wallet_args).await,
         Some(Commands::Keygen(keygen_args)) => keygen::exec(keygen_args),
         Some(Commands::Faucet(faucet_args)) => faucet::exec(faucet_args).await,
+        Some(Commands::Db(db_args)) => db::exec(db_args).await,
         None => Err(CliError::NoSubcommand),
         _ => Err(CliError::InvalidCommand(format!("{cmd:?}"))),
     }
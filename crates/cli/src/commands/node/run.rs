@@ -1,4 +1,5 @@
 use config::{Config, ConfigError, File};
+use mempool::MempoolConfig;
 use node::Node;
 use primitives::{NodeType, DEFAULT_VRRB_DATA_DIR_PATH, DEFAULT_VRRB_DB_PATH};
 use serde::Deserialize;
@@ -64,6 +65,10 @@ pub struct RunOpts {
     #[clap(long, value_parser, default_value = DEFAULT_JSONRPC_ADDRESS)]
     pub jsonrpc_api_address: SocketAddr,
 
+    /// Address to serve the gRPC API from. The gRPC API is disabled unless set
+    #[clap(long, value_parser)]
+    pub grpc_api_address: Option<SocketAddr>,
+
     #[clap(long)]
     pub bootstrap: bool,
 
@@ -86,6 +91,43 @@ pub struct RunOpts {
     #[clap(long, action, default_value = "false")]
     pub disable_networking: bool,
 
+    /// Persists pending mempool transactions so they survive node restarts
+    #[clap(long, action, default_value = "false")]
+    pub enable_mempool_journal: bool,
+
+    /// Maximum number of transactions held by the mempool
+    #[clap(long)]
+    pub mempool_max_txns: Option<usize>,
+
+    /// Maximum size of the mempool, in kilobytes
+    #[clap(long)]
+    pub mempool_max_size_in_kilobytes: Option<usize>,
+
+    /// Maximum number of pending transactions a single address may have
+    #[clap(long)]
+    pub mempool_max_txns_per_sender: Option<usize>,
+
+    /// Number of seconds a transaction may stay in the mempool
+    #[clap(long)]
+    pub mempool_txn_ttl: Option<i64>,
+
+    /// Amount of tokens sent to accounts requesting a faucet drip over JSON-RPC.
+    /// The faucet is disabled unless set
+    #[clap(long)]
+    pub faucet_drip_amount: Option<u128>,
+
+    /// Keeps the full history of the state instead of pruning it
+    #[clap(long, action, default_value = "false")]
+    pub archive_mode: bool,
+
+    /// Number of state versions kept before older ones are pruned
+    #[clap(long)]
+    pub state_pruning_window: Option<u64>,
+
+    /// Number of applied blocks whose state can still be queried
+    #[clap(long)]
+    pub state_history_window: Option<usize>,
+
     #[clap(long, value_parser ,default_value=DEFAULT_OS_ASSIGNED_PORT_ADDRESS)]
     pub rendezvous_local_address: SocketAddr,
 
@@ -114,6 +156,22 @@ impl From<RunOpts> for NodeConfig {
             default_node_config.http_api_title.clone()
         };
 
+        let default_mempool_config = default_node_config.mempool.clone();
+        let mempool = MempoolConfig {
+            max_txns: opts
+                .mempool_max_txns
+                .unwrap_or(default_mempool_config.max_txns),
+            max_size_in_kilobytes: opts
+                .mempool_max_size_in_kilobytes
+                .unwrap_or(default_mempool_config.max_size_in_kilobytes),
+            max_txns_per_sender: opts
+                .mempool_max_txns_per_sender
+                .unwrap_or(default_mempool_config.max_txns_per_sender),
+            txn_ttl: opts
+                .mempool_txn_ttl
+                .unwrap_or(default_mempool_config.txn_ttl),
+        };
+
         Self {
             id: opts.id.unwrap_or(default_node_config.id),
             data_dir: opts.data_dir,
@@ -127,6 +185,7 @@ impl From<RunOpts> for NodeConfig {
             http_api_version: opts.http_api_version,
             http_api_shutdown_timeout: default_node_config.http_api_shutdown_timeout,
             jsonrpc_server_address: opts.jsonrpc_api_address,
+            grpc_server_address: opts.grpc_api_address,
             preload_mock_state: default_node_config.preload_mock_state,
             bootstrap_config: default_node_config.bootstrap_config,
             bootstrap_peer_data: default_node_config.bootstrap_peer_data,
@@ -144,7 +203,16 @@ impl From<RunOpts> for NodeConfig {
             public_ip_address: opts.raptorq_gossip_address,
             quorum_config: default_node_config.quorum_config,
             enable_block_indexing: default_node_config.enable_block_indexing,
+            enable_mempool_journal: opts.enable_mempool_journal,
+            mempool,
+            faucet_drip_amount: opts.faucet_drip_amount,
+            archive_mode: opts.archive_mode,
+            state_pruning_window: opts.state_pruning_window,
+            state_history_window: opts.state_history_window,
+            in_memory_storage: default_node_config.in_memory_storage,
             threshold_config: default_node_config.threshold_config,
+            peer_scoring: default_node_config.peer_scoring,
+            encrypt_direct_messages: default_node_config.encrypt_direct_messages,
             whitelisted_nodes: default_node_config.whitelisted_nodes,
             prometheus_bind_port: default_node_config.prometheus_bind_port,
             prometheus_bind_addr: default_node_config.prometheus_bind_addr,
@@ -171,12 +239,22 @@ impl Default for RunOpts {
             raptorq_gossip_address: ipv4_localhost_with_random_port,
             http_api_address: ipv4_localhost_with_random_port,
             jsonrpc_api_address: ipv4_localhost_with_random_port,
+            grpc_api_address: None,
             bootstrap: Default::default(),
             bootstrap_node_addresses: Default::default(),
             http_api_title: Default::default(),
             http_api_version: Default::default(),
             enable_ui: Default::default(),
             disable_networking: Default::default(),
+            enable_mempool_journal: Default::default(),
+            mempool_max_txns: None,
+            mempool_max_size_in_kilobytes: None,
+            mempool_max_txns_per_sender: None,
+            mempool_txn_ttl: None,
+            faucet_drip_amount: None,
+            archive_mode: Default::default(),
+            state_pruning_window: None,
+            state_history_window: None,
             rendezvous_local_address: ipv4_localhost_with_random_port,
             rendezvous_server_address: ipv4_localhost_with_random_port,
             public_ip_address: ipv4_localhost_with_random_port,
@@ -200,6 +278,8 @@ impl RunOpts {
             .set_default("http_api_version", "1.0.1")?
             .set_default("bootstrap_node_addresses", default_bootstrap_addresses)?
             .set_default("preload_mock_state", false)?
+            .set_default("enable_mempool_journal", false)?
+            .set_default("archive_mode", false)?
             .set_default("debug_config", false)?
             .set_default("bootstrap", false)?
             .set_default("detached", false)?
@@ -257,6 +337,7 @@ impl RunOpts {
             udp_gossip_address: other.udp_gossip_address,
             raptorq_gossip_address: other.raptorq_gossip_address,
             jsonrpc_api_address: other.jsonrpc_api_address,
+            grpc_api_address: self.grpc_api_address.or(other.grpc_api_address),
             bootstrap: other.bootstrap,
             bootstrap_node_addresses,
             http_api_address: other.http_api_address,
@@ -264,6 +345,19 @@ impl RunOpts {
             http_api_version,
             enable_ui: false,
             disable_networking: false,
+            enable_mempool_journal: self.enable_mempool_journal || other.enable_mempool_journal,
+            mempool_max_txns: self.mempool_max_txns.or(other.mempool_max_txns),
+            mempool_max_size_in_kilobytes: self
+                .mempool_max_size_in_kilobytes
+                .or(other.mempool_max_size_in_kilobytes),
+            mempool_max_txns_per_sender: self
+                .mempool_max_txns_per_sender
+                .or(other.mempool_max_txns_per_sender),
+            mempool_txn_ttl: self.mempool_txn_ttl.or(other.mempool_txn_ttl),
+            faucet_drip_amount: self.faucet_drip_amount.or(other.faucet_drip_amount),
+            archive_mode: self.archive_mode || other.archive_mode,
+            state_pruning_window: self.state_pruning_window.or(other.state_pruning_window),
+            state_history_window: self.state_history_window.or(other.state_history_window),
             rendezvous_local_address: other.rendezvous_local_address,
             rendezvous_server_address: other.rendezvous_server_address,
             public_ip_address: other.public_ip_address,
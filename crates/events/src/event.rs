@@ -1,10 +1,13 @@
+use std::net::SocketAddr;
+
 use block::GenesisReceiver;
 use block::{header::BlockHeader, Block, BlockHash, Certificate, ConvergenceBlock, ProposalBlock};
 use ethereum_types::U256;
 use hbbft::sync_key_gen::Ack;
 use hbbft::{crypto::PublicKeySet, sync_key_gen::Part};
 use primitives::{
-    Address, ConvergencePartialSig, FarmerQuorumThreshold, NodeId, Signature, RUNTIME_TOPIC_STR,
+    Address, ConvergencePartialSig, FarmerQuorumThreshold, NodeId, PublicKey, Signature,
+    RUNTIME_TOPIC_STR,
 };
 
 use serde::{Deserialize, Serialize};
@@ -69,6 +72,14 @@ pub enum Event {
     /// `ClaimReceived(Claim)` represents a claim emitted by another node
     ClaimReceived(Claim),
 
+    /// A node gave up its claim, which has to be removed from state once a
+    /// block including the abandonment is confirmed. `signature` is the
+    /// claim key's signature over the abandonment.
+    ClaimAbandoned {
+        claim: Claim,
+        signature: String,
+    },
+
     /// A peer joined the network, should be added to the node's peer list
     PeerJoined(PeerData),
 
@@ -178,6 +189,59 @@ pub enum Event {
     BlockAppended(String),
     BuildProposalBlock(ConvergenceBlock),
     BroadcastProposalBlock(ProposalBlock),
+
+    /// Starts synchronizing blocks with the peer listening at the given
+    /// address, by asking it for its chain tip
+    SyncRequested(SocketAddr),
+
+    /// A peer asked for this node's chain tip, to be sent back to the given
+    /// address
+    ChainTipRequested(SocketAddr),
+
+    /// This node's chain tip, ready to be sent to the peer that asked for it
+    ChainTipResolved {
+        peer: SocketAddr,
+        tip: Option<ChainTip>,
+    },
+
+    /// A peer reported its chain tip, blocks should be requested from it if
+    /// this node is behind
+    PeerChainTipReceived {
+        peer: SocketAddr,
+        tip: Option<ChainTip>,
+    },
+
+    /// This node is missing blocks and asks the peer for them
+    BlocksRequested {
+        peer: SocketAddr,
+        request: BlockRequest,
+    },
+
+    /// A peer asked for blocks this node may have
+    BlocksRequestReceived {
+        peer: SocketAddr,
+        request: BlockRequest,
+    },
+
+    /// Blocks found for a peer's request, ready to be sent back to it
+    BlocksResolved {
+        peer: SocketAddr,
+        blocks: Vec<Block>,
+    },
+
+    /// Blocks a peer sent back in answer to a `BlocksRequested`
+    SyncBlocksReceived {
+        peer: SocketAddr,
+        blocks: Vec<Block>,
+    },
+
+    /// A peer started a handshake with this node, the answer is sent back to
+    /// the address known for `node_id`
+    HandshakeRequested {
+        node_id: NodeId,
+        challenge: Vec<u8>,
+        ephemeral_key: PublicKey,
+    },
 }
 
 impl From<&theater::Message> for Event {
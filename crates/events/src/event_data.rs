@@ -42,6 +42,25 @@ pub struct SyncPeerData {
     pub node_type: NodeType,
 }
 
+/// Latest confirmed block of a node, exchanged with peers so a node can tell
+/// whether it has fallen behind.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
+pub struct ChainTip {
+    pub block_height: u128,
+    pub block_hash: BlockHash,
+}
+
+/// Blocks a node is missing and asks a peer for
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
+pub enum BlockRequest {
+    /// Blocks of any kind, identified by their hashes
+    ByHash(Vec<BlockHash>),
+
+    /// Confirmed blocks from height `start` to `end`, both inclusive, along
+    /// with the proposal blocks they reference
+    ByHeight { start: u128, end: u128 },
+}
+
 // NOTE: naming convention for events goes as follows:
 // <Subject><Verb, in past tense>, e.g. ObjectCreated
 // TODO: Replace Vec<u8>'s with proper data structs in enum wariants
@@ -1,3 +1,4 @@
+use primitives::Address;
 use vrrb_core::transactions::TransactionDigest;
 
 #[derive(thiserror::Error, PartialEq, Eq, Debug)]
@@ -14,4 +15,23 @@ pub enum MempoolError {
 
     #[error("transaction {0} already exists")]
     TransactionExists(TransactionDigest),
+
+    #[error("transaction {0} was refused: {1}")]
+    TransactionRefused(TransactionDigest, RefusalReason),
+
+    #[error("failed to record mempool operation in journal: {0}")]
+    JournalWriteFailed(String),
+}
+
+/// Reasons a bounded mempool refuses to admit a transaction
+#[derive(thiserror::Error, Clone, PartialEq, Eq, Debug)]
+pub enum RefusalReason {
+    #[error("mempool is full and no pending transaction pays a lower fee")]
+    PoolFull,
+
+    #[error("sender {0} already has {1} pending transactions")]
+    SenderLimitReached(Address, usize),
+
+    #[error("transaction outlived the mempool's time to live")]
+    Expired,
 }
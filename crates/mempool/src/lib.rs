@@ -1,10 +1,12 @@
 pub mod error;
 pub mod mempool;
+pub mod status;
 
 use anyhow::Context;
 use reqwest::StatusCode;
 
 pub use crate::mempool::*;
+pub use crate::status::*;
 
 pub async fn create_tx_indexer(txn_record: &TxnRecord) -> anyhow::Result<StatusCode> {
     let url = "http://localhost:3444/transactions"; // TODO: Move to config
@@ -33,16 +35,26 @@ pub async fn create_tx_indexer(txn_record: &TxnRecord) -> anyhow::Result<StatusC
 #[cfg(test)]
 mod tests {
 
-    use std::collections::{HashMap, HashSet};
+    use std::{
+        collections::{HashMap, HashSet},
+        sync::{Arc, Mutex},
+    };
 
     use primitives::{Address, Signature};
     use rand::{thread_rng, Rng};
     use secp256k1::ecdsa;
 
     use vrrb_core::keypair::KeyPair;
-    use vrrb_core::transactions::{Transaction, TransactionKind};
+    use vrrb_core::transactions::{Transaction, TransactionDigest, TransactionKind, BASE_FEE};
 
-    use crate::mempool::{LeftRightMempool, TxnRecord};
+    use crate::{
+        error::{MempoolError, RefusalReason},
+        mempool::{
+            FetchFiltered, LeftRightMempool, MempoolConfig, MempoolJournal, MempoolOp, Result,
+            TxnRecord,
+        },
+        status::TxnStatus,
+    };
 
     fn mock_txn_signature() -> Signature {
         ecdsa::Signature::from_compact(&[
@@ -82,7 +94,6 @@ mod tests {
         let mut mpooldb = LeftRightMempool::new();
         match mpooldb.insert(txn) {
             Ok(_) => {
-                std::thread::sleep(std::time::Duration::from_secs(3));
                 assert_eq!(1, mpooldb.size());
             }
             Err(_) => {
@@ -537,4 +548,296 @@ mod tests {
                 handle.join().unwrap();
             });
     }
+
+    #[test]
+    fn fetches_highest_paying_txns_first() {
+        let mut lrmpooldb = LeftRightMempool::new();
+
+        let transfer_builder = |keypair: KeyPair| {
+            TransactionKind::transfer_builder()
+                .timestamp(0)
+                .sender_address(Address::new(*keypair.get_miner_public_key()))
+                .sender_public_key(*keypair.get_miner_public_key())
+                .receiver_address(Address::new(*KeyPair::random().get_miner_public_key()))
+                .amount(0)
+                .validators(HashMap::<String, bool>::new())
+                .nonce(1)
+                .signature(mock_txn_signature())
+        };
+
+        let low = transfer_builder(KeyPair::random())
+            .build_kind()
+            .expect("Failed to build transaction");
+
+        let high = transfer_builder(KeyPair::random())
+            .priority_fee(1_000)
+            .build_kind()
+            .expect("Failed to build transaction");
+
+        let capped = transfer_builder(KeyPair::random())
+            .priority_fee(10_000)
+            .max_fee(BASE_FEE + 500)
+            .build_kind()
+            .expect("Failed to build transaction");
+
+        lrmpooldb.insert(low.clone()).unwrap();
+        lrmpooldb.insert(high.clone()).unwrap();
+        lrmpooldb.insert(capped.clone()).unwrap();
+
+        let fetched: Vec<TransactionDigest> = lrmpooldb
+            .fetch_txns(2)
+            .into_iter()
+            .map(|(digest, _)| digest)
+            .collect();
+
+        assert_eq!(fetched, vec![high.id(), capped.id()]);
+
+        let filtered: Vec<TransactionDigest> = lrmpooldb
+            .read
+            .fetch_filtered(3, |digest, _| digest != &high.id())
+            .into_iter()
+            .map(|record| record.txn_id)
+            .collect();
+
+        assert_eq!(filtered, vec![capped.id(), low.id()]);
+    }
+
+    #[test]
+    fn fetches_txns_of_a_sender_in_nonce_order() {
+        let keypair = KeyPair::random();
+        let mut lrmpooldb = LeftRightMempool::new();
+
+        // NOTE: the second transaction of the sender pays more than the first
+        // one, but cannot be processed before it
+        let first = mock_transfer(&keypair, 1, 0);
+        let second = mock_transfer(&keypair, 2, 1_000);
+        let other = mock_transfer(&KeyPair::random(), 1, 500);
+
+        lrmpooldb.insert(second.clone()).unwrap();
+        lrmpooldb.insert(first.clone()).unwrap();
+        lrmpooldb.insert(other.clone()).unwrap();
+
+        let fetched: Vec<TransactionDigest> = lrmpooldb
+            .fetch_txns(3)
+            .into_iter()
+            .map(|(digest, _)| digest)
+            .collect();
+
+        assert_eq!(fetched, vec![other.id(), first.id(), second.id()]);
+    }
+
+    fn mock_transfer(keypair: &KeyPair, nonce: u128, priority_fee: u128) -> TransactionKind {
+        TransactionKind::transfer_builder()
+            .timestamp(0)
+            .sender_address(Address::new(*keypair.get_miner_public_key()))
+            .sender_public_key(*keypair.get_miner_public_key())
+            .receiver_address(Address::new(*KeyPair::random().get_miner_public_key()))
+            .amount(0)
+            .validators(HashMap::<String, bool>::new())
+            .nonce(nonce)
+            .priority_fee(priority_fee)
+            .signature(mock_txn_signature())
+            .build_kind()
+            .expect("Failed to build transaction")
+    }
+
+    #[test]
+    fn evicts_lowest_paying_txn_when_full() {
+        let mut lrmpooldb = LeftRightMempool::with_config(MempoolConfig {
+            max_txns: 2,
+            ..Default::default()
+        });
+
+        let low = mock_transfer(&KeyPair::random(), 1, 0);
+        let mid = mock_transfer(&KeyPair::random(), 1, 10);
+        let same_as_low = mock_transfer(&KeyPair::random(), 1, 0);
+        let high = mock_transfer(&KeyPair::random(), 1, 100);
+
+        lrmpooldb.insert(low.clone()).unwrap();
+        lrmpooldb.insert(mid.clone()).unwrap();
+
+        assert_eq!(
+            lrmpooldb.insert(same_as_low.clone()),
+            Err(MempoolError::TransactionRefused(
+                same_as_low.id(),
+                RefusalReason::PoolFull
+            ))
+        );
+
+        lrmpooldb.insert(high.clone()).unwrap();
+
+        assert_eq!(lrmpooldb.size(), 2);
+        assert!(lrmpooldb.get(&low.id()).is_none());
+        assert!(lrmpooldb.get(&mid.id()).is_some());
+        assert!(lrmpooldb.get(&high.id()).is_some());
+    }
+
+    #[test]
+    fn tracks_status_of_admitted_and_evicted_txns() {
+        let mut lrmpooldb = LeftRightMempool::with_config(MempoolConfig {
+            max_txns: 1,
+            ..Default::default()
+        });
+        let factory = lrmpooldb.factory();
+
+        let low = mock_transfer(&KeyPair::random(), 1, 0);
+        let same_as_low = mock_transfer(&KeyPair::random(), 1, 0);
+        let high = mock_transfer(&KeyPair::random(), 1, 100);
+
+        lrmpooldb.insert(low.clone()).unwrap();
+        assert_eq!(factory.txn_status(&low.id()), Some(TxnStatus::Pending));
+
+        lrmpooldb.insert(same_as_low.clone()).unwrap_err();
+        assert!(matches!(
+            factory.txn_status(&same_as_low.id()),
+            Some(TxnStatus::Rejected(_))
+        ));
+
+        lrmpooldb.insert(high.clone()).unwrap();
+        assert_eq!(factory.txn_status(&high.id()), Some(TxnStatus::Pending));
+        assert!(matches!(
+            factory.txn_status(&low.id()),
+            Some(TxnStatus::Rejected(_))
+        ));
+    }
+
+    #[test]
+    fn refuses_txns_beyond_sender_limit() {
+        let keypair = KeyPair::random();
+        let sender_address = Address::new(*keypair.get_miner_public_key());
+        let mut lrmpooldb = LeftRightMempool::with_config(MempoolConfig {
+            max_txns_per_sender: 2,
+            ..Default::default()
+        });
+
+        lrmpooldb.insert(mock_transfer(&keypair, 1, 0)).unwrap();
+        lrmpooldb.insert(mock_transfer(&keypair, 2, 0)).unwrap();
+
+        let third = mock_transfer(&keypair, 3, 1_000);
+
+        assert_eq!(
+            lrmpooldb.insert(third.clone()),
+            Err(MempoolError::TransactionRefused(
+                third.id(),
+                RefusalReason::SenderLimitReached(sender_address, 2)
+            ))
+        );
+
+        lrmpooldb
+            .insert(mock_transfer(&KeyPair::random(), 1, 0))
+            .unwrap();
+
+        assert_eq!(lrmpooldb.size(), 3);
+    }
+
+    #[test]
+    fn prunes_expired_txns() {
+        let config = MempoolConfig::default();
+        let mut lrmpooldb = LeftRightMempool::with_config(config.clone());
+
+        let record = TxnRecord::new(mock_transfer(&KeyPair::random(), 1, 0));
+        lrmpooldb.insert_record(record.clone()).unwrap();
+
+        // NOTE: records expire once they stayed in the pool longer than the TTL
+        let expires_at = record.added_timestamp + config.txn_ttl + 1;
+        assert_eq!(lrmpooldb.prune_expired_at(expires_at - 1).unwrap(), 0);
+        assert_eq!(lrmpooldb.prune_expired_at(expires_at).unwrap(), 1);
+        assert!(lrmpooldb.get(&record.txn_id).is_none());
+    }
+
+    #[test]
+    fn enforces_limits_on_restored_records() {
+        let config = MempoolConfig {
+            max_txns_per_sender: 1,
+            ..Default::default()
+        };
+        let mut lrmpooldb = LeftRightMempool::with_config(config.clone());
+
+        let keypair = KeyPair::random();
+        let fresh = TxnRecord::new(mock_transfer(&keypair, 1, 0));
+        let mut stale = TxnRecord::new(mock_transfer(&KeyPair::random(), 1, 0));
+        stale.added_timestamp -= config.txn_ttl + 1;
+
+        assert_eq!(
+            lrmpooldb.extend_with_records(HashSet::from([fresh.clone(), stale.clone()])),
+            Err(MempoolError::TransactionRefused(
+                stale.txn_id.clone(),
+                RefusalReason::Expired
+            ))
+        );
+        assert_eq!(lrmpooldb.size(), 1);
+
+        let second = TxnRecord::new(mock_transfer(&keypair, 2, 0));
+        assert!(lrmpooldb
+            .extend_with_records(HashSet::from([second.clone()]))
+            .is_err());
+        assert!(lrmpooldb.get(&second.txn_id).is_none());
+    }
+
+    #[test]
+    fn keeps_added_timestamp_of_reinserted_txns() {
+        let mut lrmpooldb = LeftRightMempool::new();
+
+        let mut record = TxnRecord::new(mock_transfer(&KeyPair::random(), 1, 0));
+        record.added_timestamp -= 60;
+        lrmpooldb.insert_record(record.clone()).unwrap();
+
+        lrmpooldb.insert(record.txn.clone()).unwrap();
+
+        assert_eq!(
+            lrmpooldb.get(&record.txn_id).unwrap().added_timestamp,
+            record.added_timestamp
+        );
+    }
+
+    #[derive(Debug, Default)]
+    struct MockJournal {
+        ops: Mutex<Vec<MempoolOp>>,
+    }
+
+    impl MempoolJournal for MockJournal {
+        fn record(&self, op: &MempoolOp) -> Result<()> {
+            self.ops.lock().unwrap().push(op.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn records_operations_in_journal() {
+        let journal = Arc::new(MockJournal::default());
+        let mut lrmpooldb = LeftRightMempool::with_config(MempoolConfig {
+            max_txns: 1,
+            ..Default::default()
+        })
+        .with_journal(journal.clone());
+
+        let low = mock_transfer(&KeyPair::random(), 1, 0);
+        let high = mock_transfer(&KeyPair::random(), 1, 100);
+
+        lrmpooldb.insert(low.clone()).unwrap();
+        lrmpooldb.insert(high.clone()).unwrap();
+        lrmpooldb.remove(&high.id()).unwrap();
+
+        let recorded: Vec<(bool, TransactionDigest)> = journal
+            .ops
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|op| match op {
+                MempoolOp::Add(record) => (true, record.txn_id.clone()),
+                MempoolOp::Remove(id) => (false, id.clone()),
+            })
+            .collect();
+
+        assert_eq!(
+            recorded,
+            vec![
+                (true, low.id()),
+                (false, low.id()),
+                (true, high.id()),
+                (false, high.id()),
+            ]
+        );
+        assert!(lrmpooldb.is_empty());
+    }
 }
@@ -1,16 +1,23 @@
 use std::{
-    collections::{HashMap, HashSet},
+    cmp::Reverse,
+    collections::{BTreeSet, HashMap, HashSet},
+    fmt::Debug,
     hash::Hash,
     result::Result as StdResult,
+    sync::Arc,
 };
 
 use fxhash::FxBuildHasher;
 use indexmap::IndexMap;
 use left_right::{Absorb, ReadHandle, ReadHandleFactory, WriteHandle};
+use primitives::Address;
 use serde::{Deserialize, Serialize};
-use vrrb_core::transactions::{Transaction, TransactionDigest, TransactionKind, TxTimestamp};
+use vrrb_core::transactions::{
+    order_by_fee_and_nonce, Transaction, TransactionDigest, TransactionKind, TxTimestamp,
+};
 
-use super::error::MempoolError;
+use super::error::{MempoolError, RefusalReason};
+use crate::status::{TxnStatus, TxnStatusTracker};
 
 pub type Result<T> = StdResult<T, MempoolError>;
 
@@ -50,22 +57,93 @@ impl TxnRecord {
 
 pub type PoolType = IndexMap<TransactionDigest, TxnRecord, FxBuildHasher>;
 
-#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
-pub enum TxnStatus {
-    #[default]
-    Pending,
-    Validating,
-    Validated,
-    Rejected,
+/// Key of the fee-ordered index kept alongside `PoolType`. Keys sort by the
+/// fee paid, highest first, then by the time the transaction was added to
+/// the pool and finally by digest, so the order is deterministic.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FeeIndexKey {
+    fee: Reverse<u128>,
+    added_timestamp: TxTimestamp,
+    txn_id: TransactionDigest,
+}
+
+impl From<&TxnRecord> for FeeIndexKey {
+    fn from(record: &TxnRecord) -> Self {
+        FeeIndexKey {
+            fee: Reverse(record.txn.fee()),
+            added_timestamp: record.added_timestamp,
+            txn_id: record.txn_id.clone(),
+        }
+    }
 }
 
+pub type FeeIndex = BTreeSet<FeeIndexKey>;
+
+/// Index of the pending transactions ordered by the time they were added to
+/// the pool, oldest first, used to find expired transactions.
+pub type TtlIndex = BTreeSet<(TxTimestamp, TransactionDigest)>;
+
 /// Mempool stores unprocessed transactions
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Mempool {
     pool: PoolType,
+    fee_index: FeeIndex,
+    ttl_index: TtlIndex,
+    /// Number of pending transactions of every sender
+    sender_counts: HashMap<Address, usize>,
 }
 
 pub const DEFAULT_INITIAL_MEMPOOL_CAPACITY: usize = 10000;
+pub const DEFAULT_MAX_MEMPOOL_TXNS: usize = 100_000;
+pub const DEFAULT_MAX_MEMPOOL_SIZE_IN_KILOBYTES: usize = 256 * 1024;
+pub const DEFAULT_MAX_TXNS_PER_SENDER: usize = 256;
+pub const DEFAULT_TXN_TTL_SECS: TxTimestamp = 3 * 60 * 60;
+
+/// Limits enforced by `LeftRightMempool` when admitting transactions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MempoolConfig {
+    /// Maximum number of transactions held by the pool
+    pub max_txns: usize,
+
+    /// Maximum size of the pool, measured the same way as
+    /// `LeftRightMempool::size_in_kilobytes`
+    pub max_size_in_kilobytes: usize,
+
+    /// Maximum number of pending transactions a single address may have
+    pub max_txns_per_sender: usize,
+
+    /// Number of seconds a transaction may stay in the pool, counted from
+    /// `TxnRecord::added_timestamp`
+    pub txn_ttl: TxTimestamp,
+}
+
+impl Default for MempoolConfig {
+    fn default() -> Self {
+        MempoolConfig {
+            max_txns: DEFAULT_MAX_MEMPOOL_TXNS,
+            max_size_in_kilobytes: DEFAULT_MAX_MEMPOOL_SIZE_IN_KILOBYTES,
+            max_txns_per_sender: DEFAULT_MAX_TXNS_PER_SENDER,
+            txn_ttl: DEFAULT_TXN_TTL_SECS,
+        }
+    }
+}
+
+impl MempoolConfig {
+    /// Returns true if a pool holding `num_of_txns` transactions is within
+    /// the configured count and size limits.
+    pub fn fits(&self, num_of_txns: usize) -> bool {
+        num_of_txns <= self.max_txns && size_in_kilobytes(num_of_txns) <= self.max_size_in_kilobytes
+    }
+}
+
+/// Estimates the size in kilobytes of a pool holding `num_of_txns`
+/// transactions.
+pub fn size_in_kilobytes(num_of_txns: usize) -> usize {
+    let txn_size_factor = std::mem::size_of::<TransactionKind>();
+
+    (num_of_txns * txn_size_factor) / 1024
+}
 
 impl Default for Mempool {
     fn default() -> Self {
@@ -74,6 +152,9 @@ impl Default for Mempool {
                 DEFAULT_INITIAL_MEMPOOL_CAPACITY,
                 <_>::default(),
             ),
+            fee_index: FeeIndex::new(),
+            ttl_index: TtlIndex::new(),
+            sender_counts: HashMap::new(),
         }
     }
 }
@@ -86,6 +167,132 @@ impl Mempool {
     pub fn is_empty(&self) -> bool {
         self.pool.is_empty()
     }
+
+    /// Returns the pending transactions, highest fee first, with the
+    /// transactions of every sender kept in ascending nonce order.
+    pub fn fee_ordered(&self) -> Vec<&TxnRecord> {
+        let records = self
+            .fee_index
+            .iter()
+            .filter_map(|key| self.pool.get(&key.txn_id));
+
+        order_by_fee_and_nonce(records, |record| &record.txn)
+    }
+
+    /// Returns the digests of the records that outlived `ttl` at `now`,
+    /// oldest first.
+    pub fn expired(&self, now: TxTimestamp, ttl: TxTimestamp) -> Vec<TransactionDigest> {
+        self.ttl_index
+            .iter()
+            .take_while(|(added_timestamp, _)| now.saturating_sub(*added_timestamp) > ttl)
+            .map(|(_, txn_id)| txn_id.clone())
+            .collect()
+    }
+
+    /// Returns the number of pending transactions sent by `sender`.
+    pub fn pending_from_sender(&self, sender: &Address) -> usize {
+        self.sender_counts.get(sender).copied().unwrap_or_default()
+    }
+
+    /// Checks whether `record` can be admitted under `config` at `now`.
+    ///
+    /// Returns the digests of the records that must be removed first: the
+    /// expired ones, followed by the lowest paying ones evicted to make room
+    /// for `record`. A `record` that already expired is refused. Records are
+    /// only evicted if they pay strictly less than `record`, lowest fee first
+    /// and most recently added first among equal fees, so the outcome only
+    /// depends on the contents of the pool.
+    pub fn admit(
+        &self,
+        record: &TxnRecord,
+        config: &MempoolConfig,
+        now: TxTimestamp,
+    ) -> Result<Vec<TransactionDigest>> {
+        if now.saturating_sub(record.added_timestamp) > config.txn_ttl {
+            return Err(MempoolError::TransactionRefused(
+                record.txn_id.clone(),
+                RefusalReason::Expired,
+            ));
+        }
+
+        let mut evicted = self.expired(now, config.txn_ttl);
+        let mut evicted_ids: HashSet<TransactionDigest> = evicted.iter().cloned().collect();
+
+        // NOTE: replacing a pending record does not grow the pool
+        if self.pool.contains_key(&record.txn_id) && !evicted_ids.contains(&record.txn_id) {
+            return Ok(evicted);
+        }
+
+        let sender = record.txn.sender_address();
+        let expired_from_sender = evicted
+            .iter()
+            .filter_map(|id| self.pool.get(id))
+            .filter(|pending| pending.txn.sender_address() == sender)
+            .count();
+        let pending_from_sender = self
+            .pending_from_sender(&sender)
+            .saturating_sub(expired_from_sender);
+
+        if pending_from_sender >= config.max_txns_per_sender {
+            return Err(MempoolError::TransactionRefused(
+                record.txn_id.clone(),
+                RefusalReason::SenderLimitReached(sender, config.max_txns_per_sender),
+            ));
+        }
+
+        let fee = record.txn.fee();
+        let mut lowest_paying = self.fee_index.iter().rev();
+
+        while !config.fits(self.pool.len() - evicted.len() + 1) {
+            match lowest_paying.find(|key| !evicted_ids.contains(&key.txn_id)) {
+                Some(key) if key.fee.0 < fee => {
+                    evicted_ids.insert(key.txn_id.clone());
+                    evicted.push(key.txn_id.clone());
+                }
+                _ => {
+                    return Err(MempoolError::TransactionRefused(
+                        record.txn_id.clone(),
+                        RefusalReason::PoolFull,
+                    ))
+                }
+            }
+        }
+
+        Ok(evicted)
+    }
+
+    fn insert_record(&mut self, mut record: TxnRecord) {
+        // NOTE: re-inserting a pending transaction does not extend its lifetime
+        if let Some(pending) = self.remove_record(&record.txn_id) {
+            record.added_timestamp = pending.added_timestamp;
+        }
+
+        self.fee_index.insert(FeeIndexKey::from(&record));
+        self.ttl_index
+            .insert((record.added_timestamp, record.txn_id.clone()));
+        *self
+            .sender_counts
+            .entry(record.txn.sender_address())
+            .or_default() += 1;
+        self.pool.insert(record.txn_id.clone(), record);
+    }
+
+    fn remove_record(&mut self, id: &TransactionDigest) -> Option<TxnRecord> {
+        let record = self.pool.swap_remove(id)?;
+        self.fee_index.remove(&FeeIndexKey::from(&record));
+        self.ttl_index
+            .remove(&(record.added_timestamp, record.txn_id.clone()));
+
+        let sender = record.txn.sender_address();
+        if let Some(count) = self.sender_counts.get_mut(&sender) {
+            *count -= 1;
+            if *count == 0 {
+                self.sender_counts.remove(&sender);
+            }
+        }
+
+        Some(record)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -98,10 +305,10 @@ impl Absorb<MempoolOp> for Mempool {
     fn absorb_first(&mut self, op: &mut MempoolOp, _: &Self) {
         match op {
             MempoolOp::Add(record) => {
-                self.pool.insert(record.txn_id.clone(), *record.clone());
+                self.insert_record(*record.clone());
             }
             MempoolOp::Remove(id) => {
-                self.pool.remove(id);
+                self.remove_record(id);
             }
         }
     }
@@ -118,35 +325,46 @@ pub trait FetchFiltered {
 }
 
 impl FetchFiltered for ReadHandle<Mempool> {
-    fn fetch_filtered<F>(&self, amount: u32, f: F) -> Vec<TxnRecord>
+    /// Returns up to `amount` records matching `f`, highest fee first.
+    fn fetch_filtered<F>(&self, amount: u32, mut f: F) -> Vec<TxnRecord>
     where
         F: FnMut(&TransactionDigest, &mut TxnRecord) -> bool,
     {
-        if let Some(map) = self.enter().map(|guard| guard.clone()) {
-            let mut result = map.pool;
-            result.retain(f);
-            let mut returned = Vec::<TxnRecord>::new();
-            for (_, v) in &result {
-                returned.push(v.clone());
-            }
-            // TODO:  Error - length
-            return returned[0..amount as usize].to_vec();
+        if let Some(mempool) = self.enter() {
+            return mempool
+                .fee_ordered()
+                .into_iter()
+                .cloned()
+                .filter_map(|mut record| {
+                    let digest = record.txn_id.clone();
+                    f(&digest, &mut record).then_some(record)
+                })
+                .take(amount as usize)
+                .collect();
         };
         Vec::<TxnRecord>::new()
     }
 }
 
+/// Persists the operations applied to a `LeftRightMempool` so its pending
+/// transactions can be recovered after a restart. Operations are recorded
+/// before they are applied to the pool.
+pub trait MempoolJournal: Debug + Send + Sync {
+    fn record(&self, op: &MempoolOp) -> Result<()>;
+}
+
 #[derive(Debug)]
 pub struct LeftRightMempool {
     pub read: ReadHandle<Mempool>,
     pub write: WriteHandle<Mempool, MempoolOp>,
+    config: MempoolConfig,
+    journal: Option<Arc<dyn MempoolJournal>>,
+    status_tracker: TxnStatusTracker,
 }
 
 impl Default for LeftRightMempool {
     fn default() -> Self {
-        let (write, read) = left_right::new::<Mempool, MempoolOp>();
-
-        LeftRightMempool { read, write }
+        Self::with_config(MempoolConfig::default())
     }
 }
 
@@ -156,6 +374,41 @@ impl LeftRightMempool {
         Self::default()
     }
 
+    /// Creates new Mempool DB that enforces the limits set in `config`
+    pub fn with_config(config: MempoolConfig) -> Self {
+        let (write, read) = left_right::new::<Mempool, MempoolOp>();
+
+        LeftRightMempool {
+            read,
+            write,
+            config,
+            journal: None,
+            status_tracker: TxnStatusTracker::default(),
+        }
+    }
+
+    /// Records every subsequent operation in `journal` before applying it
+    pub fn with_journal(mut self, journal: Arc<dyn MempoolJournal>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// Returns the limits enforced by this mempool
+    pub fn config(&self) -> &MempoolConfig {
+        &self.config
+    }
+
+    /// Returns the tracker holding the lifecycle status of the transactions
+    /// that went through this mempool
+    pub fn status_tracker(&self) -> TxnStatusTracker {
+        self.status_tracker.clone()
+    }
+
+    /// Returns the lifecycle status of a transaction, if tracked
+    pub fn txn_status(&self, id: &TransactionDigest) -> Option<TxnStatus> {
+        self.status_tracker.get(id)
+    }
+
     /// Getter for Mempool DB
     pub fn pool(&self) -> PoolType {
         self.read
@@ -174,8 +427,12 @@ impl LeftRightMempool {
     /// Returns a new MempoolReadHandleFactory, to simplify multithread access.
     pub fn factory(&self) -> MempoolReadHandleFactory {
         let factory = self.read.factory();
+        let status_tracker = self.status_tracker.clone();
 
-        MempoolReadHandleFactory { factory }
+        MempoolReadHandleFactory {
+            factory,
+            status_tracker,
+        }
     }
 
     /// Return the number of key-value pairs in the map.
@@ -196,15 +453,74 @@ impl LeftRightMempool {
         Ok(())
     }
 
+    /// Adds a new transaction to the pool, expiring stale transactions and
+    /// evicting lower paying ones if the pool is full.
+    ///
+    /// Returns `MempoolError::TransactionRefused` if admitting the
+    /// transaction would break the limits set in `MempoolConfig`.
     pub fn insert(&mut self, txn: TransactionKind) -> Result<usize> {
-        let txn_record = TxnRecord::new(txn);
-        self.write
-            .append(MempoolOp::Add(Box::new(txn_record)))
-            .publish();
+        self.insert_record(TxnRecord::new(txn))
+    }
+
+    /// Adds an existing record to the pool, keeping its timestamps, subject to
+    /// the same limits as `Self::insert`.
+    pub fn insert_record(&mut self, txn_record: TxnRecord) -> Result<usize> {
+        let now = chrono::offset::Utc::now().timestamp();
+        let txn_id = txn_record.txn_id.clone();
+
+        let admitted = match self.read.enter() {
+            Some(mempool) => mempool.admit(&txn_record, &self.config, now),
+            None => Ok(Vec::new()),
+        };
+
+        let evicted = admitted.map_err(|err| {
+            self.status_tracker
+                .update(&txn_id, TxnStatus::Rejected(err.to_string()));
+            err
+        })?;
+
+        for id in evicted {
+            self.append(MempoolOp::Remove(id.clone()))?;
+            self.status_tracker.update(
+                &id,
+                TxnStatus::Rejected("evicted from the mempool".to_string()),
+            );
+        }
+
+        self.append(MempoolOp::Add(Box::new(txn_record)))?;
+        self.publish();
+        self.status_tracker.update(&txn_id, TxnStatus::Pending);
 
         Ok(self.size_in_kilobytes())
     }
 
+    /// Removes every transaction that has stayed in the pool longer than the
+    /// configured TTL. Returns the number of transactions removed.
+    pub fn prune_expired(&mut self) -> Result<usize> {
+        self.prune_expired_at(chrono::offset::Utc::now().timestamp())
+    }
+
+    /// Same as `Self::prune_expired`, measuring how long transactions stayed
+    /// in the pool up to `now`.
+    pub fn prune_expired_at(&mut self, now: TxTimestamp) -> Result<usize> {
+        let expired = self
+            .read
+            .enter()
+            .map(|mempool| mempool.expired(now, self.config.txn_ttl))
+            .unwrap_or_default();
+
+        let num_expired = expired.len();
+        for id in expired {
+            self.append(MempoolOp::Remove(id.clone()))?;
+            self.status_tracker
+                .update(&id, TxnStatus::Rejected("expired".to_string()));
+        }
+
+        self.publish();
+
+        Ok(num_expired)
+    }
+
     /// Retrieves a single transaction identified by id, makes sure it exists in
     /// db
     pub fn get_txn(&mut self, txn_hash: &TransactionDigest) -> Option<TransactionKind> {
@@ -223,7 +539,10 @@ impl LeftRightMempool {
         self.pool().get(txn_id).cloned()
     }
 
-    /// It fetches the transactions from the pool and returns them.
+    /// It fetches the transactions paying the highest fees from the pool and
+    /// returns them. Transactions paying the same fee are returned in the
+    /// order they were added to the pool, and the transactions of a sender
+    /// are always returned in ascending nonce order.
     ///
     /// Arguments:
     ///
@@ -233,16 +552,17 @@ impl LeftRightMempool {
     ///
     /// A vector of tuples of type (TxHashString, TxnRecord)
     pub fn fetch_txns(&mut self, num_of_txns: usize) -> Vec<(TransactionDigest, TxnRecord)> {
-        let mut txns_records = vec![];
-        for i in 0..num_of_txns {
-            if i == self.pool().len() {
-                break;
-            }
-            if let Some(txn_data) = self.pool().pop() {
-                txns_records.push(txn_data);
-            }
-        }
-        txns_records
+        self.read
+            .enter()
+            .map(|mempool| {
+                mempool
+                    .fee_ordered()
+                    .into_iter()
+                    .take(num_of_txns)
+                    .map(|record| (record.txn_id.clone(), record.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
     }
 
     /// Adds a batch of new transaction, makes sure that each is unique in db.
@@ -256,39 +576,50 @@ impl LeftRightMempool {
         self.extend(txn_batch.clone())
     }
 
+    /// Adds a batch of transactions, subject to the same limits as
+    /// `Self::insert`. Every transaction of the batch is attempted and the
+    /// first refusal, if any, is returned once the batch was processed.
     pub fn extend(&mut self, txn_batch: HashSet<TransactionKind>) -> Result<()> {
-        txn_batch.into_iter().for_each(|t| {
-            self.write
-                .append(MempoolOp::Add(Box::new(TxnRecord::new(t))));
-        });
+        let mut refused = None;
 
-        self.publish();
-        Ok(())
+        for txn in txn_batch {
+            if let Err(err) = self.insert(txn) {
+                refused = refused.or(Some(err));
+            }
+        }
+
+        refused.map_or(Ok(()), Err)
     }
 
+    /// Adds a batch of existing records, keeping their timestamps, subject to
+    /// the same limits as `Self::insert`. Every record of the batch is
+    /// attempted and the first refusal, if any, is returned once the batch
+    /// was processed.
     pub fn extend_with_records(&mut self, record_batch: HashSet<TxnRecord>) -> Result<()> {
-        record_batch.into_iter().for_each(|t| {
-            self.write.append(MempoolOp::Add(Box::new(t)));
-        });
+        let mut refused = None;
 
-        self.publish();
-        Ok(())
+        for record in record_batch {
+            if let Err(err) = self.insert_record(record) {
+                refused = refused.or(Some(err));
+            }
+        }
+
+        refused.map_or(Ok(()), Err)
     }
 
     /// Removes a single transaction by [`TransactionDigest`].
     pub fn remove(&mut self, id: &TransactionDigest) -> Result<()> {
-        self.write
-            .append(MempoolOp::Remove(id.to_owned()))
-            .publish();
+        self.append(MempoolOp::Remove(id.to_owned()))?;
+        self.publish();
         Ok(())
     }
 
     /// Removes a batch of transactions, makes sure that each is unique in db.
     /// Pushes to ReadHandle after processing of the entire batch.
     pub fn remove_txns(&mut self, txn_batch: &HashSet<TransactionDigest>) -> Result<()> {
-        txn_batch.iter().for_each(|t| {
-            self.write.append(MempoolOp::Remove(t.to_owned()));
-        });
+        for id in txn_batch {
+            self.append(MempoolOp::Remove(id.to_owned()))?;
+        }
 
         self.publish();
 
@@ -296,12 +627,9 @@ impl LeftRightMempool {
     }
 
     /// Was the Txn validated ? And when ?
-    // TODO: rethink validated txn storage
     pub fn is_txn_validated(&mut self, txn: &TransactionKind) -> Result<TxTimestamp> {
-        match self.get(&txn.id()) {
-            Some(found) if matches!(found.status, TxnStatus::Validated) => {
-                Ok(found.validated_timestamp)
-            }
+        match self.status_tracker.get_record(&txn.id()) {
+            Some(found) if found.status.is_certified() => Ok(found.updated_timestamp),
             _ => Err(MempoolError::TransactionNotFound(txn.id())),
         }
     }
@@ -313,10 +641,18 @@ impl LeftRightMempool {
 
     /// Retrieves actual size of the mempooldb in Kilobytes.
     pub fn size_in_kilobytes(&self) -> usize {
-        let txn_size_factor = std::mem::size_of::<TransactionKind>();
-        let mempool_items = self.size();
+        size_in_kilobytes(self.size())
+    }
+
+    /// Records `op` in the journal, if any, and queues it for the next
+    /// publish.
+    fn append(&mut self, op: MempoolOp) -> Result<()> {
+        if let Some(journal) = &self.journal {
+            journal.record(&op)?;
+        }
 
-        (mempool_items * txn_size_factor) / 1024
+        self.write.append(op);
+        Ok(())
     }
 
     /// Pushes changes to Reader.
@@ -327,8 +663,7 @@ impl LeftRightMempool {
 
 impl From<PoolType> for LeftRightMempool {
     fn from(pool: PoolType) -> Self {
-        let (write, read) = left_right::new::<Mempool, MempoolOp>();
-        let mut mempool_db = Self { read, write };
+        let mut mempool_db = Self::default();
 
         let records = pool.values().cloned().collect::<HashSet<TxnRecord>>();
 
@@ -340,13 +675,26 @@ impl From<PoolType> for LeftRightMempool {
 
 impl Clone for LeftRightMempool {
     fn clone(&self) -> Self {
-        Self::from(self.pool())
+        let mut mempool_db = Self::with_config(self.config.clone());
+
+        let records = self
+            .pool()
+            .values()
+            .cloned()
+            .collect::<HashSet<TxnRecord>>();
+        mempool_db.extend_with_records(records).unwrap_or_default();
+
+        mempool_db.journal = self.journal.clone();
+        mempool_db.status_tracker = self.status_tracker.clone();
+
+        mempool_db
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct MempoolReadHandleFactory {
     factory: ReadHandleFactory<Mempool>,
+    status_tracker: TxnStatusTracker,
 }
 
 impl MempoolReadHandleFactory {
@@ -383,4 +731,9 @@ impl MempoolReadHandleFactory {
         }
         None
     }
+
+    /// Returns the lifecycle status of a transaction, if tracked
+    pub fn txn_status(&self, digest: &TransactionDigest) -> Option<TxnStatus> {
+        self.status_tracker.get(digest)
+    }
 }
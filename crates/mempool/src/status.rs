@@ -0,0 +1,218 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, RwLock},
+};
+
+use serde::{Deserialize, Serialize};
+use vrrb_core::transactions::{TransactionDigest, TxTimestamp};
+
+pub const DEFAULT_MAX_TRACKED_TXNS: usize = 1_000_000;
+
+/// Lifecycle of a transaction as observed by a node, from the moment it
+/// enters the mempool until it is finalized or rejected.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TxnStatus {
+    /// Waiting in the mempool to be validated by a farmer quorum
+    #[default]
+    Pending,
+
+    /// Validated by farmers, waiting for enough votes to be certified
+    Voting,
+
+    /// Certified by a farmer quorum, waiting to be included in a block
+    Certified,
+
+    /// Included in the proposal block with the given hash
+    Included(String),
+
+    /// Included in a certified convergence block and applied to state
+    Finalized,
+
+    /// Refused by the mempool or found invalid, with the reason why
+    Rejected(String),
+}
+
+impl TxnStatus {
+    /// Position of the status within the lifecycle. `Rejected` sits outside
+    /// of it.
+    fn stage(&self) -> Option<u8> {
+        match self {
+            TxnStatus::Pending => Some(0),
+            TxnStatus::Voting => Some(1),
+            TxnStatus::Certified => Some(2),
+            TxnStatus::Included(_) => Some(3),
+            TxnStatus::Finalized => Some(4),
+            TxnStatus::Rejected(_) => None,
+        }
+    }
+
+    /// Returns true once the transaction has been certified by a farmer
+    /// quorum
+    pub fn is_certified(&self) -> bool {
+        matches!(
+            self,
+            TxnStatus::Certified | TxnStatus::Included(_) | TxnStatus::Finalized
+        )
+    }
+
+    /// Returns true if a transaction can move from this status to `next`.
+    ///
+    /// Statuses only move forward through the lifecycle, a finalized
+    /// transaction never changes again and a rejected transaction may start
+    /// over if it is submitted again.
+    pub fn can_transition_to(&self, next: &TxnStatus) -> bool {
+        match (self, next) {
+            (TxnStatus::Finalized, _) => false,
+            (TxnStatus::Included(_), TxnStatus::Rejected(_)) => false,
+            (_, TxnStatus::Rejected(_)) => true,
+            (TxnStatus::Rejected(_), _) => true,
+            (TxnStatus::Included(current), TxnStatus::Included(next)) => current != next,
+            (current, next) => next.stage() > current.stage(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxnStatusRecord {
+    pub status: TxnStatus,
+    pub updated_timestamp: TxTimestamp,
+}
+
+#[derive(Debug, Default)]
+struct TrackedStatuses {
+    statuses: HashMap<TransactionDigest, TxnStatusRecord>,
+    order: VecDeque<TransactionDigest>,
+}
+
+/// Keeps track of the `TxnStatus` of the transactions seen by a node. It is
+/// cheap to clone and every clone shares the same statuses, so the modules
+/// driving a transaction through its lifecycle and the ones serving it to
+/// clients can each hold one.
+///
+/// Only the latest `capacity` transactions are tracked, the ones seen first
+/// are forgotten once it is exceeded.
+#[derive(Debug, Clone)]
+pub struct TxnStatusTracker {
+    inner: Arc<RwLock<TrackedStatuses>>,
+    capacity: usize,
+}
+
+impl Default for TxnStatusTracker {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_MAX_TRACKED_TXNS)
+    }
+}
+
+impl TxnStatusTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(TrackedStatuses::default())),
+            capacity,
+        }
+    }
+
+    /// Returns the current status of a transaction, if tracked
+    pub fn get(&self, id: &TransactionDigest) -> Option<TxnStatus> {
+        self.get_record(id).map(|record| record.status)
+    }
+
+    /// Returns the current status of a transaction along with the time it
+    /// was set, if tracked
+    pub fn get_record(&self, id: &TransactionDigest) -> Option<TxnStatusRecord> {
+        let guard = self.inner.read().ok()?;
+        guard.statuses.get(id).cloned()
+    }
+
+    /// Moves a transaction to `status` if allowed by
+    /// `TxnStatus::can_transition_to`. Returns true if the status changed.
+    pub fn update(&self, id: &TransactionDigest, status: TxnStatus) -> bool {
+        let Ok(mut guard) = self.inner.write() else {
+            return false;
+        };
+
+        let record = TxnStatusRecord {
+            status,
+            updated_timestamp: chrono::offset::Utc::now().timestamp(),
+        };
+
+        match guard.statuses.get_mut(id) {
+            Some(current) if !current.status.can_transition_to(&record.status) => return false,
+            Some(current) => *current = record,
+            None => {
+                guard.statuses.insert(id.clone(), record);
+                guard.order.push_back(id.clone());
+            }
+        }
+
+        while guard.order.len() > self.capacity {
+            if let Some(oldest) = guard.order.pop_front() {
+                guard.statuses.remove(&oldest);
+            }
+        }
+
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner
+            .read()
+            .map(|guard| guard.statuses.len())
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statuses_only_move_forward() {
+        let tracker = TxnStatusTracker::new();
+        let id = TransactionDigest::from(vec![1u8; 32]);
+
+        assert!(tracker.update(&id, TxnStatus::Pending));
+        assert!(tracker.update(&id, TxnStatus::Certified));
+        assert!(!tracker.update(&id, TxnStatus::Voting));
+        assert!(tracker.update(&id, TxnStatus::Included("block".to_string())));
+        assert!(!tracker.update(&id, TxnStatus::Rejected("late".to_string())));
+        assert!(tracker.update(&id, TxnStatus::Finalized));
+        assert!(!tracker.update(&id, TxnStatus::Pending));
+
+        assert_eq!(tracker.get(&id), Some(TxnStatus::Finalized));
+    }
+
+    #[test]
+    fn rejected_txns_can_be_resubmitted() {
+        let tracker = TxnStatusTracker::new();
+        let id = TransactionDigest::from(vec![1u8; 32]);
+
+        tracker.update(&id, TxnStatus::Rejected("evicted".to_string()));
+
+        assert!(tracker.update(&id, TxnStatus::Pending));
+        assert_eq!(tracker.get(&id), Some(TxnStatus::Pending));
+    }
+
+    #[test]
+    fn forgets_oldest_txns_beyond_capacity() {
+        let tracker = TxnStatusTracker::with_capacity(2);
+        let ids: Vec<TransactionDigest> = (0..3u8)
+            .map(|n| TransactionDigest::from(vec![n; 32]))
+            .collect();
+
+        ids.iter().for_each(|id| {
+            tracker.update(id, TxnStatus::Pending);
+        });
+
+        assert_eq!(tracker.len(), 2);
+        assert_eq!(tracker.get(&ids[0]), None);
+        assert_eq!(tracker.get(&ids[2]), Some(TxnStatus::Pending));
+    }
+}
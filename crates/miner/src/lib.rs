@@ -112,6 +112,7 @@ mod tests {
                 0,
                 LinkedHashMap::new(),
                 LinkedHashMap::new(),
+                LinkedHashMap::new(),
                 other_miner.claim.clone(),
                 engine.clone(),
             );
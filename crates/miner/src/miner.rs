@@ -100,6 +100,7 @@ pub struct MinerConfig {
 ///     pub claim: Claim,
 ///     pub dag: Arc<RwLock<BullDag<Block, String>>>,
 ///     pub last_block: Option<Arc<dyn InnerBlock<Header = BlockHeader, RewardType = Reward>>>,
+///     pub state_root_hash: String,
 ///     pub status: MinerStatus,
 ///     pub next_epoch_adjustment: i128,
 /// }
@@ -112,6 +113,9 @@ pub struct Miner {
     pub claim: Claim,
     pub dag: Arc<RwLock<BullDag<Block, String>>>,
     pub last_block: Option<Arc<dyn InnerBlock<Header = BlockHeader, RewardType = Reward>>>,
+    /// Root hash of the state right after `last_block` was applied, committed
+    /// to by the header of the next block mined
+    pub state_root_hash: String,
     pub status: MinerStatus,
     pub next_epoch_adjustment: i128,
 }
@@ -187,6 +191,7 @@ impl Miner {
             claim,
             dag: config.dag,
             last_block: None,
+            state_root_hash: String::new(),
             status: MinerStatus::Waiting,
             next_epoch_adjustment: 0,
         })
@@ -414,6 +419,7 @@ impl Miner {
                 self.secret_key,
                 txns_hash,
                 claims_hash,
+                self.state_root_hash.clone(),
                 self.next_epoch_adjustment,
             );
         }
@@ -426,6 +432,7 @@ impl Miner {
                 self.secret_key,
                 txns_hash,
                 claims_hash,
+                self.state_root_hash.clone(),
                 self.next_epoch_adjustment,
             );
         }
@@ -450,22 +457,7 @@ impl Miner {
     /// Hashes the current `ConvergenceBlock` being mined using
     /// the fields from the `BlockHeader`
     pub(crate) fn hash_block(&self, header: &BlockHeader) -> String {
-        let block_hash = hash_data!(
-            header.ref_hashes,
-            header.round,
-            header.block_seed,
-            header.next_block_seed,
-            header.block_height,
-            header.timestamp,
-            header.txn_hash,
-            header.miner_claim,
-            header.claim_list_hash,
-            header.block_reward,
-            header.next_block_reward,
-            header.miner_signature
-        );
-
-        format!("{block_hash:x}")
+        header.convergence_block_hash()
     }
 
     /// Gets the current election `seed` from the
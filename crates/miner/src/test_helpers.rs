@@ -178,6 +178,8 @@ pub(crate) fn create_txns(n: usize) -> impl Iterator<Item = (TransactionDigest,
             txn.token(),
             txn.amount(),
             txn.nonce(),
+            None,
+            0,
         );
 
         let digest = TransactionDigest::from(txn_digest_vec);
@@ -247,7 +249,16 @@ pub fn build_single_proposal_block(
 ) -> ProposalBlock {
     let txns = create_txns(n_txns).collect();
     let claims = create_claims(n_claims).collect();
-    ProposalBlock::build(last_block_hash, round, epoch, txns, claims, from, sk)
+    ProposalBlock::build(
+        last_block_hash,
+        round,
+        epoch,
+        txns,
+        claims,
+        LinkedHashMap::new(),
+        from,
+        sk,
+    )
 }
 
 /// A helper function to build `n` number of porposal blocks
@@ -417,6 +428,7 @@ pub fn add_genesis_to_dag(dag: &mut MinerDag) -> Option<String> {
             0,
             LinkedHashMap::new(),
             LinkedHashMap::new(),
+            LinkedHashMap::new(),
             miner.claim,
             signer,
         );
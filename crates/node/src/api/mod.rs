@@ -1,12 +1,20 @@
 use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
 
-use events::{Event, EventPublisher, EventSubscriber};
+use block::Block;
+use bulldag::graph::BullDag;
+use events::{Event, EventPublisher, EventSubscriber, DEFAULT_BUFFER};
 use mempool::MempoolReadHandleFactory;
 use storage::vrrbdb::VrrbDbReadHandle;
-use telemetry::info;
+use telemetry::{info, warn};
+use tokio::sync::broadcast::{self, error::RecvError};
 use tokio::task::JoinHandle;
 use vrrb_config::NodeConfig;
-use vrrb_rpc::rpc::{JsonRpcServer, JsonRpcServerConfig};
+use vrrb_rpc::{
+    grpc::{GrpcServer, GrpcServerConfig},
+    http::{HttpApiServer, HttpApiServerConfigBuilder, HttpApiState},
+    rpc::{FaucetDripConfig, JsonRpcServer, JsonRpcServerConfig, DEFAULT_FAUCET_DRIP_INTERVAL},
+};
 
 use crate::result::{NodeError, Result};
 
@@ -15,14 +23,33 @@ pub async fn setup_rpc_api_server(
     events_tx: EventPublisher,
     vrrbdb_read_handle: VrrbDbReadHandle,
     mempool_read_handle_factory: MempoolReadHandleFactory,
+    dag: Arc<RwLock<BullDag<Block, String>>>,
     mut jsonrpc_events_rx: EventSubscriber,
+    subscription_events_rx: EventSubscriber,
 ) -> Result<(JoinHandle<Result<()>>, SocketAddr)> {
+    let (subscription_events, _) = broadcast::channel(DEFAULT_BUFFER);
+    tokio::spawn(forward_subscription_events(
+        subscription_events_rx,
+        subscription_events.clone(),
+    ));
+
+    let faucet = config
+        .faucet_drip_amount
+        .map(|drip_amount| FaucetDripConfig {
+            secret_key: *config.keypair.get_miner_secret_key(),
+            drip_amount,
+            drip_interval: DEFAULT_FAUCET_DRIP_INTERVAL,
+        });
+
     let jsonrpc_server_config = JsonRpcServerConfig {
         address: config.jsonrpc_server_address,
         node_type: config.node_type,
         events_tx,
         vrrbdb_read_handle,
         mempool_read_handle_factory,
+        dag,
+        subscription_events,
+        faucet,
     };
 
     let (jsonrpc_server_handle, resolved_jsonrpc_server_addr) =
@@ -52,3 +79,122 @@ pub async fn setup_rpc_api_server(
 
     Ok((jsonrpc_server_handle, resolved_jsonrpc_server_addr))
 }
+
+/// Starts the gRPC server if the node is configured to serve one
+pub async fn setup_grpc_api_server(
+    config: &NodeConfig,
+    events_tx: EventPublisher,
+    vrrbdb_read_handle: VrrbDbReadHandle,
+    mempool_read_handle_factory: MempoolReadHandleFactory,
+    mut grpc_events_rx: EventSubscriber,
+) -> Result<Option<(JoinHandle<Result<()>>, SocketAddr)>> {
+    let Some(address) = config.grpc_server_address else {
+        return Ok(None);
+    };
+
+    let grpc_server_config = GrpcServerConfig {
+        address,
+        node_id: config.id.clone(),
+        node_type: config.node_type,
+        vrrbdb_read_handle,
+        mempool_read_handle_factory,
+        events_tx,
+    };
+
+    let (grpc_server_handle, resolved_grpc_server_addr) = GrpcServer::run(&grpc_server_config)
+        .await
+        .map_err(|err| NodeError::Other(format!("unable to start gRPC server: {err}")))?;
+
+    let grpc_server_handle = tokio::spawn(async move {
+        loop {
+            match grpc_events_rx.recv().await {
+                Ok(message) => {
+                    if let Event::Stop = message.into() {
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            }
+        }
+
+        grpc_server_handle
+            .stop()
+            .await
+            .map_err(|err| NodeError::Other(format!("gRPC server has stopped: {err}")))
+    });
+
+    info!("gRPC server started at {}", resolved_grpc_server_addr);
+
+    Ok(Some((grpc_server_handle, resolved_grpc_server_addr)))
+}
+
+pub async fn setup_http_api_server(
+    config: &NodeConfig,
+    state: HttpApiState,
+    mut http_api_events_rx: EventSubscriber,
+) -> Result<(JoinHandle<Result<()>>, SocketAddr)> {
+    let http_api_server_config = HttpApiServerConfigBuilder::default()
+        .address(&config.http_api_address.to_string())
+        .api_title(&config.http_api_title)
+        .api_version(&config.http_api_version)
+        .server_timeout(config.http_api_shutdown_timeout)
+        .tls_config(None)
+        .state(state)
+        .build();
+
+    let http_api_server = HttpApiServer::new(http_api_server_config)?;
+    let resolved_http_api_addr = http_api_server.address()?;
+
+    let (ctrl_tx, mut ctrl_rx) = broadcast::channel(1);
+
+    tokio::spawn(async move {
+        loop {
+            match http_api_events_rx.recv().await {
+                Ok(message) => {
+                    if let Event::Stop = message.into() {
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            }
+        }
+
+        let _ = ctrl_tx.send(Event::Stop);
+    });
+
+    let http_api_server_handle = tokio::spawn(async move {
+        http_api_server.start(&mut ctrl_rx).await?;
+
+        Ok(())
+    });
+
+    info!("HTTP API server started at {}", resolved_http_api_addr);
+
+    Ok((http_api_server_handle, resolved_http_api_addr))
+}
+
+/// Feeds the node's events to the JSON-RPC subscriptions until the node stops
+async fn forward_subscription_events(
+    mut events_rx: EventSubscriber,
+    subscription_events: broadcast::Sender<Event>,
+) {
+    loop {
+        let event: Event = match events_rx.recv().await {
+            Ok(message) => message.into(),
+            Err(RecvError::Lagged(skipped)) => {
+                warn!("JSON-RPC subscriptions skipped {skipped} events");
+                continue;
+            }
+            Err(RecvError::Closed) => break,
+        };
+
+        if let Event::Stop = event {
+            break;
+        }
+
+        // NOTE: sending only fails when no client is subscribed
+        let _ = subscription_events.send(event);
+    }
+}
@@ -1,12 +1,13 @@
 use super::{QuorumModule, QuorumModuleConfig};
 use crate::{NodeError, Result};
 use block::{
-    header::BlockHeader, Block, Certificate, ConvergenceBlock, GenesisBlock, ProposalBlock,
+    header::BlockHeader, AbandonedClaims, Block, Certificate, ConvergenceBlock, GenesisBlock,
+    ProposalBlock,
 };
 use bulldag::graph::BullDag;
 use ethereum_types::U256;
 use events::{SyncPeerData, Vote};
-use mempool::MempoolReadHandleFactory;
+use mempool::{MempoolReadHandleFactory, TxnStatus, TxnStatusTracker};
 use miner::conflict_resolver::Resolver;
 use primitives::{
     NodeId, NodeType, NodeTypeBytes, PKShareBytes, PayloadBytes, PublicKey, QuorumId, QuorumKind,
@@ -34,6 +35,7 @@ pub struct ConsensusModuleConfig {
     pub node_config: NodeConfig,
     // pub dkg_generator: DkgEngine,
     pub validator_public_key: PublicKey,
+    pub txn_status_tracker: TxnStatusTracker,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -78,6 +80,9 @@ pub struct ConsensusModule {
     pub(crate) quorum_certified_txns:
         HashMap<TransactionDigest, (TransactionKind, TransactionKindCertificate)>,
     pub(crate) quorum_certified_claims: HashMap<String, Claim>,
+    /// Claims abandoned by their owners, waiting to be included in a
+    /// proposal block
+    pub(crate) abandoned_claims: AbandonedClaims,
     pub(crate) keypair: Keypair,
     pub(crate) quorum_driver: QuorumModule,
     pub(crate) sig_engine: SignerEngine,
@@ -88,6 +93,7 @@ pub struct ConsensusModule {
     pub(crate) validator_core_manager: ValidatorCoreManager,
     pub miner_election_results: Option<BTreeMap<U256, Claim>>,
     pub certified_pending_transactions: IntGauge,
+    pub(crate) txn_status_tracker: TxnStatusTracker,
 }
 
 impl ConsensusModule {
@@ -117,6 +123,7 @@ impl ConsensusModule {
         Ok(Self {
             quorum_certified_txns: HashMap::new(),
             quorum_certified_claims: HashMap::new(),
+            abandoned_claims: AbandonedClaims::new(),
             keypair: cfg.keypair,
             quorum_driver: QuorumModule::new(quorum_module_config),
             sig_engine,
@@ -127,6 +134,7 @@ impl ConsensusModule {
             votes_pool: Default::default(),
             miner_election_results: None,
             certified_pending_transactions,
+            txn_status_tracker: cfg.txn_status_tracker,
         })
     }
 
@@ -158,6 +166,9 @@ impl ConsensusModule {
             inauguration: None,
             root_hash: prev_txn_root_hash,
             block_hash,
+            // NOTE: harvesters don't hold key shares from a DKG yet, so there are
+            // no signature shares to combine
+            threshold_signature: None,
         };
 
         Ok(certificate)
@@ -257,6 +268,11 @@ impl ConsensusModule {
         // let farmer_quorum_threshold = self.quorum_public_keyset()?.threshold();
         self.is_farmer()?;
 
+        // NOTE: a single farmer's verdict is only a vote, the transaction is rejected once
+        // enough of its quorum agree on it
+        self.txn_status_tracker
+            .update(&transaction.id(), TxnStatus::Voting);
+
         if let Some(vote) = self.form_vote(transaction.clone(), valid) {
             return Ok(vote);
         }
@@ -296,6 +312,8 @@ impl ConsensusModule {
             )))?
             .0;
         self.check_vote_is_valid(&quorum_id, &vote).await?;
+        self.txn_status_tracker
+            .update(&vote.txn.id(), TxnStatus::Voting);
         match self.votes_pool.entry(quorum_id.clone()) {
             Entry::Occupied(mut entry) => {
                 let map = entry.get_mut();
@@ -323,6 +341,32 @@ impl ConsensusModule {
             .await
             .map_err(|err| NodeError::Other(format!("threhold net yet reached, err: {}", err)))?;
 
+        let votes = self.get_quorum_pending_votes_for_transaction(&quorum_id, &vote)?;
+        let quorum_members = self.get_quorum_members(&quorum_id)?;
+        let rejections: HashSet<Vote> = votes
+            .iter()
+            .filter(|vote| !vote.is_txn_valid)
+            .cloned()
+            .collect();
+
+        if self.double_check_vote_threshold_reached(&rejections, quorum_members.clone()) {
+            self.txn_status_tracker.update(
+                &vote.txn.id(),
+                TxnStatus::Rejected(format!("rejected by farmer quorum {:?}", quorum_id)),
+            );
+            return Ok(());
+        }
+
+        let approvals: HashSet<Vote> = votes.into_iter().filter(|vote| vote.is_txn_valid).collect();
+
+        if !self.double_check_vote_threshold_reached(&approvals, quorum_members) {
+            return Err(NodeError::Other(format!(
+                "quorum {:?} has not yet agreed on the validity of txn: {}",
+                quorum_id,
+                vote.txn.id()
+            )));
+        }
+
         self.certify_transaction(&vote, &quorum_id).await
     }
 
@@ -335,7 +379,11 @@ impl ConsensusModule {
             if let Some(set) = map.get(&vote.txn.id().clone()) {
                 let cert = TransactionKindCertificate {
                     quorum_id: voting_quorum.clone(),
-                    votes: set.clone(),
+                    votes: set
+                        .iter()
+                        .filter(|vote| vote.is_txn_valid)
+                        .cloned()
+                        .collect(),
                     is_txn_valid: true,
                 };
                 self.quorum_certified_txns
@@ -343,6 +391,8 @@ impl ConsensusModule {
                     .or_insert((vote.txn.clone(), cert));
                 self.certified_pending_transactions
                     .set(self.quorum_certified_txns.len() as i64);
+                self.txn_status_tracker
+                    .update(&vote.txn.id(), TxnStatus::Certified);
                 return Ok(());
             }
         }
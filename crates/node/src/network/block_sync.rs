@@ -0,0 +1,343 @@
+//! Helpers used by nodes that joined late or fell behind to fetch the blocks
+//! they're missing from their peers.
+//!
+//! A node asks a peer for its chain tip, requests the confirmed blocks between
+//! its own tip and the peer's, and appends what it receives in causal order.
+
+use std::collections::HashMap;
+
+use block::{Block, BlockHash};
+use events::{BlockRequest, ChainTip};
+use storage::vrrbdb::BlockStore;
+
+use crate::Result;
+
+/// Maximum number of confirmed blocks served for a single request
+pub const MAX_SYNC_BLOCKS_PER_REQUEST: u128 = 32;
+
+/// Returns the chain tip a confirmed block represents. Proposal blocks are
+/// never confirmed on their own so they don't make a tip.
+pub fn chain_tip(block: &Block) -> Option<ChainTip> {
+    match block {
+        Block::Genesis { block } => Some(ChainTip {
+            block_height: block.header.block_height,
+            block_hash: block.hash.clone(),
+        }),
+        Block::Convergence { block } => Some(ChainTip {
+            block_height: block.header.block_height,
+            block_hash: block.hash.clone(),
+        }),
+        Block::Proposal { .. } => None,
+    }
+}
+
+/// Builds the request a node at `local` should send to a peer at `remote` to
+/// catch up with it, or `None` if it isn't behind.
+pub fn next_request(local: Option<&ChainTip>, remote: Option<&ChainTip>) -> Option<BlockRequest> {
+    let remote = remote?;
+    let start = match local {
+        Some(local) => local.block_height.checked_add(1)?,
+        None => 0,
+    };
+
+    if remote.block_height < start {
+        return None;
+    }
+
+    let end = remote
+        .block_height
+        .min(start.saturating_add(MAX_SYNC_BLOCKS_PER_REQUEST - 1));
+
+    Some(BlockRequest::ByHeight { start, end })
+}
+
+/// Collects the blocks persisted in `block_store` that answer `request`, in
+/// causal order. Range requests are capped to `MAX_SYNC_BLOCKS_PER_REQUEST`
+/// confirmed blocks.
+pub fn blocks_for_request(block_store: &BlockStore, request: &BlockRequest) -> Result<Vec<Block>> {
+    let blocks = match request {
+        BlockRequest::ByHash(hashes) => hashes
+            .iter()
+            .take(MAX_SYNC_BLOCKS_PER_REQUEST as usize)
+            .filter_map(|hash| block_store.get(hash).transpose())
+            .collect::<std::result::Result<Vec<_>, _>>()?,
+        BlockRequest::ByHeight { start, end } => {
+            let end = (*end).min(start.saturating_add(MAX_SYNC_BLOCKS_PER_REQUEST - 1));
+
+            confirmed_blocks_in_range(block_store, *start, end)?
+        }
+    };
+
+    Ok(causal_order(blocks))
+}
+
+/// Orders blocks so every block comes after the blocks it references that are
+/// part of the same batch. Duplicates are dropped.
+pub fn causal_order(blocks: Vec<Block>) -> Vec<Block> {
+    let mut pending: HashMap<BlockHash, Block> = blocks
+        .into_iter()
+        .map(|block| (block.hash(), block))
+        .collect();
+
+    // NOTE: visit blocks in a fixed order so the output doesn't depend on the
+    // order the blocks were received in
+    let mut hashes: Vec<BlockHash> = pending.keys().cloned().collect();
+    hashes.sort();
+
+    let mut ordered = Vec::with_capacity(pending.len());
+    for hash in hashes {
+        visit(&hash, &mut pending, &mut ordered);
+    }
+
+    ordered
+}
+
+/// Appends the block with the given hash to `ordered` after the pending
+/// blocks it references. Walks references with an explicit stack rather than
+/// recursion since chains restored from the block store can be arbitrarily
+/// long.
+fn visit(hash: &BlockHash, pending: &mut HashMap<BlockHash, Block>, ordered: &mut Vec<Block>) {
+    let Some(block) = pending.remove(hash) else {
+        return;
+    };
+
+    // NOTE: references are popped off the back, reversing them keeps visiting
+    // them in the order the block lists them
+    let unvisited = |block: &Block| references(block).into_iter().rev().collect::<Vec<_>>();
+    let mut stack = vec![(unvisited(&block), block)];
+
+    while let Some((remaining, _)) = stack.last_mut() {
+        match remaining.pop() {
+            Some(reference) => {
+                if let Some(block) = pending.remove(&reference) {
+                    stack.push((unvisited(&block), block));
+                }
+            }
+            None => {
+                if let Some((_, block)) = stack.pop() {
+                    ordered.push(block);
+                }
+            }
+        }
+    }
+}
+
+/// Hashes of the blocks a block builds upon
+fn references(block: &Block) -> Vec<BlockHash> {
+    match block {
+        Block::Genesis { .. } => vec![],
+        Block::Proposal { block } => vec![block.ref_block.clone()],
+        Block::Convergence { block } => block.header.ref_hashes.clone(),
+    }
+}
+
+/// Reads the confirmed blocks between heights `start` and `end` off the
+/// height index, along with the proposal blocks they are linked from.
+fn confirmed_blocks_in_range(
+    block_store: &BlockStore,
+    start: u128,
+    end: u128,
+) -> Result<Vec<Block>> {
+    let Some(count) = end.checked_sub(start) else {
+        return Ok(vec![]);
+    };
+
+    let mut blocks = Vec::new();
+
+    for block in block_store.range_by_height(start, count as usize + 1)? {
+        let in_range = chain_tip(&block)
+            .map(|tip| tip.block_height <= end)
+            .unwrap_or(false);
+
+        if !in_range {
+            break;
+        }
+
+        if block.is_convergence() {
+            for source in block_store.sources(&block.hash())? {
+                if let Some(source) = block_store.get(&source)? {
+                    if source.is_proposal() {
+                        blocks.push(source);
+                    }
+                }
+            }
+        }
+
+        blocks.push(block);
+    }
+
+    Ok(blocks)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, RwLock};
+
+    use bulldag::{graph::BullDag, vertex::Vertex};
+    use signer::engine::SignerEngine;
+    use vrrb_core::keypair::KeyPair;
+
+    use super::*;
+    use crate::test_utils::{
+        produce_accounts, produce_convergence_block, produce_genesis_block, produce_proposal_blocks,
+    };
+
+    fn tip(block_height: u128) -> ChainTip {
+        ChainTip {
+            block_height,
+            block_hash: format!("block-{block_height}"),
+        }
+    }
+
+    /// Builds a block store holding a genesis block, 3 proposal blocks and
+    /// the convergence block confirming them, linked the way the DAG links
+    /// them
+    fn produce_block_store() -> (BlockStore, BlockHash) {
+        let dag = Arc::new(RwLock::new(BullDag::new()));
+        let block_store = BlockStore::in_memory();
+        let keypair = KeyPair::random();
+        let sig_engine = SignerEngine::new(
+            *keypair.get_miner_public_key(),
+            *keypair.get_miner_secret_key(),
+        );
+
+        let genesis = produce_genesis_block();
+        let gblock: Block = genesis.clone().into();
+        block_store.insert(&gblock, &[]).unwrap();
+        let gvtx: Vertex<Block, BlockHash> = gblock.into();
+
+        let proposals =
+            produce_proposal_blocks(genesis.hash.clone(), produce_accounts(3), 3, 3, sig_engine);
+
+        if let Ok(mut guard) = dag.write() {
+            guard.add_vertex(&gvtx);
+            for proposal in proposals {
+                let pblock: Block = proposal.into();
+                block_store
+                    .insert(&pblock, &[genesis.hash.clone()])
+                    .unwrap();
+                let pvtx: Vertex<Block, BlockHash> = pblock.into();
+                guard.add_edge(&(&gvtx, &pvtx));
+            }
+        }
+
+        let convergence_hash = produce_convergence_block(dag.clone()).unwrap();
+        let guard = dag.read().unwrap();
+        let cvtx = guard.get_vertex(convergence_hash.clone()).unwrap();
+        let sources: Vec<BlockHash> = cvtx
+            .get_sources()
+            .iter()
+            .map(|src| src.to_string())
+            .collect();
+        block_store.insert(&cvtx.get_data(), &sources).unwrap();
+
+        (block_store, convergence_hash)
+    }
+
+    #[test]
+    fn nodes_behind_request_the_missing_range() {
+        assert_eq!(
+            next_request(None, Some(&tip(3))),
+            Some(BlockRequest::ByHeight { start: 0, end: 3 })
+        );
+        assert_eq!(
+            next_request(Some(&tip(2)), Some(&tip(5))),
+            Some(BlockRequest::ByHeight { start: 3, end: 5 })
+        );
+        assert_eq!(
+            next_request(Some(&tip(0)), Some(&tip(1000))),
+            Some(BlockRequest::ByHeight {
+                start: 1,
+                end: MAX_SYNC_BLOCKS_PER_REQUEST,
+            })
+        );
+    }
+
+    #[test]
+    fn nodes_caught_up_request_nothing() {
+        assert_eq!(next_request(Some(&tip(5)), Some(&tip(5))), None);
+        assert_eq!(next_request(Some(&tip(6)), Some(&tip(5))), None);
+        assert_eq!(next_request(Some(&tip(5)), None), None);
+        assert_eq!(next_request(None, None), None);
+    }
+
+    #[test]
+    fn range_requests_include_referenced_proposals_in_causal_order() {
+        let (block_store, convergence_hash) = produce_block_store();
+
+        let blocks =
+            blocks_for_request(&block_store, &BlockRequest::ByHeight { start: 0, end: 10 })
+                .unwrap();
+
+        assert_eq!(blocks.len(), 5);
+        assert!(matches!(blocks.first(), Some(Block::Genesis { .. })));
+        assert!(blocks[1..4].iter().all(|block| block.is_proposal()));
+        assert_eq!(
+            blocks.last().map(|block| block.hash()),
+            Some(convergence_hash)
+        );
+    }
+
+    #[test]
+    fn hash_requests_skip_unknown_blocks() {
+        let (block_store, convergence_hash) = produce_block_store();
+
+        let blocks = blocks_for_request(
+            &block_store,
+            &BlockRequest::ByHash(vec![convergence_hash.clone(), "unknown".to_string()]),
+        )
+        .unwrap();
+
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].hash(), convergence_hash);
+    }
+
+    #[test]
+    fn causal_order_handles_long_chains() {
+        let keypair = KeyPair::random();
+        let sig_engine = SignerEngine::new(
+            *keypair.get_miner_public_key(),
+            *keypair.get_miner_secret_key(),
+        );
+        let proposal = produce_proposal_blocks(
+            produce_genesis_block().hash,
+            produce_accounts(1),
+            1,
+            1,
+            sig_engine,
+        )
+        .remove(0);
+
+        // NOTE: the newest block sorts first, so ordering starts from the tip
+        // and has to walk the whole chain back before appending anything
+        let length = 100_000;
+        let chain: Vec<Block> = (0..length)
+            .map(|height| {
+                let mut block = proposal.clone();
+                block.ref_block = format!("block-{:06}", length - height + 1);
+                block.hash = format!("block-{:06}", length - height);
+                Block::Proposal { block }
+            })
+            .collect();
+
+        let mut reversed = chain.clone();
+        reversed.reverse();
+
+        assert_eq!(causal_order(reversed), chain);
+    }
+
+    #[test]
+    fn causal_order_does_not_depend_on_arrival_order() {
+        let (block_store, _) = produce_block_store();
+
+        let ordered =
+            blocks_for_request(&block_store, &BlockRequest::ByHeight { start: 0, end: 10 })
+                .unwrap();
+
+        let mut reversed = ordered.clone();
+        reversed.reverse();
+        reversed.extend(ordered.clone());
+
+        assert_eq!(causal_order(reversed), ordered);
+    }
+}
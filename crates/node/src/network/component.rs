@@ -12,7 +12,7 @@ use telemetry::info;
 use theater::{Actor, ActorImpl, Handler};
 use vrrb_config::{NodeConfig, QuorumMembershipConfig};
 
-use crate::network::module::*;
+use crate::network::{module::*, peer_scoring::PeerScoringMetrics};
 
 #[derive(Debug)]
 pub struct NetworkModuleComponentConfig {
@@ -46,6 +46,13 @@ impl RuntimeComponent<NetworkModuleComponentConfig, NetworkModuleComponentResolv
     ) -> crate::Result<RuntimeComponentHandle<NetworkModuleComponentResolvedData>> {
         let mut network_events_rx = args.network_events_rx;
         let node_config = args.config.clone();
+        let bootstrap_gossip_addr = args
+            .config
+            .bootstrap_peer_data
+            .as_ref()
+            .map(|peer_data| peer_data.udp_gossip_addr);
+
+        let peer_scoring_metrics = PeerScoringMetrics::new(&factory, labels.clone())?;
 
         let network_module_config = NetworkModuleConfig {
             node_id: args.node_id.clone(),
@@ -60,6 +67,8 @@ impl RuntimeComponent<NetworkModuleComponentConfig, NetworkModuleComponentResolv
             validator_public_key: args.validator_public_key,
             node_config,
             bootstrap_peer_data: args.config.bootstrap_peer_data,
+            peer_scoring_metrics: Some(peer_scoring_metrics),
+            vrrbdb_read_handle: Some(args.vrrbdb_read_handle),
         };
 
         let mut network_module = NetworkModule::new(network_module_config).await?;
@@ -75,6 +84,13 @@ impl RuntimeComponent<NetworkModuleComponentConfig, NetworkModuleComponentResolv
         let is_not_bootstrap = !network_module.is_bootstrap();
         if is_not_bootstrap {
             network_module.broadcast_join_intent().await?;
+
+            // NOTE: catch up with the blocks the network confirmed before this node joined
+            if let Some(peer) = bootstrap_gossip_addr {
+                if let Err(err) = network_module.request_chain_tip(peer).await {
+                    telemetry::warn!("Failed to request chain tip from {peer}: {err}");
+                }
+            }
         } else {
             is_bootstrap_node.inc();
         }
@@ -27,6 +27,11 @@ impl Handler<EventMessage> for NetworkModule {
     async fn handle(&mut self, event: EventMessage) -> theater::Result<ActorState> {
         match event.into() {
             Event::PeerJoined(peer_data) => {
+                if self.is_peer_banned(&peer_data.node_id) {
+                    telemetry::warn!("Ignoring banned peer {}", peer_data.node_id);
+                    return Ok(ActorState::Running);
+                }
+
                 info!("Storing peer information from {} in DHT", peer_data.node_id);
 
                 // TODO: revisit this insert method
@@ -35,6 +40,21 @@ impl Handler<EventMessage> for NetworkModule {
                     &peer_data.kademlia_liveness_addr.to_string(),
                 );
 
+                // NOTE: peers only join through announcements signed with the
+                // key already known for them
+                if let Err(err) = self.register_peer(&peer_data) {
+                    telemetry::error!("Failed to register peer {}: {err}", peer_data.node_id);
+                }
+
+                if !self.has_session_with(&peer_data.node_id) {
+                    if let Err(err) = self.handshake(peer_data.node_id.clone()).await {
+                        telemetry::warn!(
+                            "Failed to start handshake with {}: {err}",
+                            peer_data.node_id
+                        );
+                    }
+                }
+
                 let evt = Event::NodeAddedToPeerList(peer_data.clone());
                 let em = EventMessage::new(Some(RUNTIME_TOPIC_STR.into()), evt);
 
@@ -44,6 +64,15 @@ impl Handler<EventMessage> for NetworkModule {
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             }
             Event::QuorumMembershipAssigmentsCreated(assigments) => {
+                for peer_data in assigments
+                    .iter()
+                    .flat_map(|assigment| assigment.peers.iter())
+                {
+                    if let Err(err) = self.register_peer(peer_data) {
+                        telemetry::error!("Failed to register peer {}: {err}", peer_data.node_id);
+                    }
+                }
+
                 self.notify_quorum_membership_assignments(assigments)
                     .await?;
             }
@@ -97,6 +126,31 @@ impl Handler<EventMessage> for NetworkModule {
                 self.broadcast_block(block).await?;
             }
 
+            Event::SyncRequested(peer) => {
+                info!("Requesting chain tip from {peer}");
+                self.request_chain_tip(peer).await?;
+            }
+            Event::ChainTipResolved { peer, tip } => {
+                self.send_chain_tip(peer, tip).await?;
+            }
+            Event::BlocksRequested { peer, request } => {
+                info!("Requesting missing blocks from {peer}");
+                self.request_blocks(peer, request).await?;
+            }
+            Event::BlocksResolved { peer, blocks } => {
+                info!("Sending {} blocks to {peer}", blocks.len());
+                self.send_blocks(peer, blocks).await?;
+            }
+            Event::HandshakeRequested {
+                node_id,
+                challenge,
+                ephemeral_key,
+            } => {
+                info!("Answering handshake from {node_id}");
+                self.answer_handshake(node_id, challenge, ephemeral_key)
+                    .await?;
+            }
+
             _ => {}
         }
 
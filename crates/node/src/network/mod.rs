@@ -1,8 +1,11 @@
+pub mod block_sync;
 mod component;
 mod handler;
 mod module;
 mod network_event;
 mod network_event_handler;
+pub mod peer_scoring;
+pub mod transport;
 
 pub use component::*;
 
@@ -1,20 +1,28 @@
-use std::net::SocketAddr;
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex, MutexGuard},
+};
 
 use block::{Block, Certificate, ConvergenceBlock};
 use dyswarm::{
     client::{BroadcastArgs, BroadcastConfig},
     server::ServerConfig,
 };
-use events::{AssignedQuorumMembership, EventPublisher, Vote};
+use events::{AssignedQuorumMembership, BlockRequest, ChainTip, EventPublisher, PeerData, Vote};
 use hbbft::sync_key_gen::{Ack, Part};
 use kademlia_dht::{Node as KademliaNode, NodeData};
 use primitives::{ConvergencePartialSig, KademliaPeerId, NodeId, NodeType, PublicKey};
+use storage::vrrbdb::VrrbDbReadHandle;
 use telemetry::info;
 use theater::{ActorId, ActorState};
-use vrrb_config::{NodeConfig, QuorumMembershipConfig};
+use vrrb_config::{BootstrapPeerData, NodeConfig, QuorumMember, QuorumMembershipConfig};
 use vrrb_core::claim::Claim;
 
-use super::NetworkEvent;
+use super::{
+    peer_scoring::{PeerScoreboard, PeerScoringMetrics},
+    transport::SecureTransport,
+    NetworkEvent,
+};
 use crate::{network::DyswarmHandler, result::Result, NodeError, DEFAULT_ERASURE_COUNT};
 
 // TODO: change these magic numbers when retrieving the closest peers to a dynamically sized
@@ -37,6 +45,8 @@ pub struct NetworkModule {
     pub(crate) dyswarm_client: dyswarm::client::Client,
     pub(crate) _membership_config: Option<QuorumMembershipConfig>,
     pub(crate) validator_public_key: PublicKey,
+    pub(crate) peer_scores: Arc<Mutex<PeerScoreboard>>,
+    pub(crate) transport: Arc<Mutex<SecureTransport>>,
 }
 
 #[derive(Debug, Clone)]
@@ -68,6 +78,13 @@ pub struct NetworkModuleConfig {
     pub validator_public_key: PublicKey,
 
     pub node_config: NodeConfig,
+
+    /// Counters for the offenses and bans of peers, not exported when unset
+    pub peer_scoring_metrics: Option<PeerScoringMetrics>,
+
+    /// Where the claims peers are recognized by are looked up. Only
+    /// configured quorum members are recognized when unset
+    pub vrrbdb_read_handle: Option<VrrbDbReadHandle>,
 }
 
 impl NetworkModule {
@@ -94,7 +111,26 @@ impl NetworkModule {
 
         let events_tx = config.events_tx.clone();
 
-        let handler = DyswarmHandler::new(config.node_id.clone(), events_tx.clone());
+        config
+            .node_config
+            .peer_scoring
+            .validate()
+            .map_err(|err| NodeError::ConfigError(err.to_string()))?;
+
+        let mut peer_scores = PeerScoreboard::new(config.node_config.peer_scoring.clone());
+        if let Some(metrics) = config.peer_scoring_metrics.clone() {
+            peer_scores = peer_scores.with_metrics(metrics);
+        }
+        let peer_scores = Arc::new(Mutex::new(peer_scores));
+
+        let transport = Arc::new(Mutex::new(Self::setup_transport(&config)));
+
+        let handler = DyswarmHandler::new(
+            config.node_id.clone(),
+            events_tx.clone(),
+            peer_scores.clone(),
+            transport.clone(),
+        );
 
         let dyswarm_server_handle = dyswarm_server.run(handler).await?;
 
@@ -116,11 +152,68 @@ impl NetworkModule {
             dyswarm_client,
             _membership_config: config.membership_config.clone(),
             validator_public_key: config.validator_public_key,
+            peer_scores,
+            transport,
         };
 
         Ok(network_component)
     }
 
+    /// Sets up the transport with the quorum members and bootstrap node this
+    /// node is configured with, looking up everyone else's claim
+    fn setup_transport(config: &NetworkModuleConfig) -> SecureTransport {
+        let node_config = &config.node_config;
+
+        let mut transport = SecureTransport::new(
+            config.node_id.clone(),
+            node_config.keypair.get_validator_secret_key_owned(),
+            node_config.encrypt_direct_messages,
+        );
+
+        if let Some(claims) = config.vrrbdb_read_handle.clone() {
+            transport = transport.with_claims(claims);
+        }
+
+        let bootstrap_quorum_members = node_config
+            .bootstrap_config
+            .iter()
+            .flat_map(|bootstrap| bootstrap.bootstrap_quorum_config.quorum_members.values())
+            .cloned()
+            .map(QuorumMember::from);
+
+        let quorum_members = node_config
+            .quorum_config
+            .iter()
+            .flat_map(|quorum| quorum.quorum_members.values())
+            .cloned();
+
+        for member in node_config
+            .whitelisted_nodes
+            .iter()
+            .cloned()
+            .chain(quorum_members)
+            .chain(bootstrap_quorum_members)
+        {
+            transport.register_peer(
+                member.node_id,
+                member.validator_public_key,
+                member.udp_gossip_address,
+            );
+        }
+
+        if let Some(BootstrapPeerData {
+            udp_gossip_addr,
+            node_id: Some(node_id),
+            validator_public_key: Some(validator_public_key),
+            ..
+        }) = config.bootstrap_peer_data.clone()
+        {
+            transport.register_bootstrap(node_id, validator_public_key, udp_gossip_addr);
+        }
+
+        transport
+    }
+
     fn setup_kademlia_node(config: NetworkModuleConfig) -> Result<KademliaNode> {
         // TODO: inspect that nodes are being created with the correct config when a
         // bootstrap is provided
@@ -206,8 +299,106 @@ impl NetworkModule {
         self.validator_public_key
     }
 
+    /// Records the validator key and address a peer announced. Only called
+    /// for announcements authenticated by the key already known for the peer
+    pub(crate) fn register_peer(&self, peer_data: &PeerData) -> Result<()> {
+        self.transport()?.register_peer(
+            peer_data.node_id.clone(),
+            peer_data.validator_public_key,
+            peer_data.udp_gossip_addr,
+        );
+
+        Ok(())
+    }
+
+    /// Returns true if the peer with the given ID is currently banned
+    pub fn is_peer_banned(&self, node_id: &NodeId) -> bool {
+        match self.peer_scores.lock() {
+            Ok(mut peer_scores) => peer_scores.is_banned(node_id),
+            Err(err) => {
+                telemetry::error!("Failed to lock peer scores: {err}");
+                false
+            }
+        }
+    }
+
+    fn transport(&self) -> Result<MutexGuard<SecureTransport>> {
+        self.transport
+            .lock()
+            .map_err(|err| NodeError::Other(format!("Failed to lock transport: {err}")))
+    }
+
+    /// Signs an event to be broadcast to peers
+    fn seal(&self, event: NetworkEvent) -> Result<dyswarm::types::Message<NetworkEvent>> {
+        let envelope = self.transport()?.seal(&event)?;
+
+        Ok(dyswarm::types::Message::new(NetworkEvent::Envelope(
+            Box::new(envelope),
+        )))
+    }
+
+    /// Signs an event sent to the peer listening at `peer`, encrypting it
+    /// once a handshake with the peer completed, if enabled
+    fn seal_for(
+        &self,
+        peer: SocketAddr,
+        event: NetworkEvent,
+    ) -> Result<dyswarm::types::Message<NetworkEvent>> {
+        let envelope = self.transport()?.seal_for(peer, &event)?;
+
+        Ok(dyswarm::types::Message::new(NetworkEvent::Envelope(
+            Box::new(envelope),
+        )))
+    }
+
+    /// Returns true if a handshake with `node_id` completed
+    pub fn has_session_with(&self, node_id: &NodeId) -> bool {
+        match self.transport() {
+            Ok(transport) => transport.has_session(node_id),
+            Err(err) => {
+                telemetry::error!("{err}");
+                false
+            }
+        }
+    }
+
+    /// Starts a handshake with `node_id` at the address known for it,
+    /// agreeing on a session key with it
+    pub(crate) async fn handshake(&mut self, node_id: NodeId) -> Result<()> {
+        let (peer, event) = self.transport()?.initiate_handshake(node_id)?;
+        let message = self.seal(event)?;
+
+        self.dyswarm_client
+            .send_data_via_quic(message, peer)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Answers a handshake started by `node_id` at the address known for it
+    pub(crate) async fn answer_handshake(
+        &mut self,
+        node_id: NodeId,
+        challenge: Vec<u8>,
+        ephemeral_key: PublicKey,
+    ) -> Result<()> {
+        let (peer, event) =
+            self.transport()?
+                .accept_handshake(node_id, challenge, ephemeral_key)?;
+
+        // NOTE: the answer can't be encrypted, the peer needs it to derive the
+        // session key
+        let message = self.seal(event)?;
+
+        self.dyswarm_client
+            .send_data_via_quic(message, peer)
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn broadcast_join_intent(&mut self) -> Result<()> {
-        let msg = dyswarm::types::Message::new(NetworkEvent::PeerJoined {
+        let msg = self.seal(NetworkEvent::PeerJoined {
             node_id: self.node_id.clone(),
             node_type: self.node_type(),
             kademlia_peer_id: self.kademlia_peer_id(),
@@ -215,7 +406,7 @@ impl NetworkModule {
             raptorq_gossip_addr: self.raptorq_gossip_addr(),
             kademlia_liveness_addr: self.kademlia_liveness_addr(),
             validator_public_key: self.validator_public_key(),
-        });
+        })?;
 
         let nid = self.kademlia_node.node_data().id;
         let rt = self.kademlia_node.get_routing_table();
@@ -261,9 +452,7 @@ impl NetworkModule {
 
         self.dyswarm_client.add_peers(socket_address).await?;
 
-        let message = dyswarm::types::Message::new(
-            NetworkEvent::QuorumMembershipAssigmentsCreated(assignments),
-        );
+        let message = self.seal(NetworkEvent::QuorumMembershipAssigmentsCreated(assignments))?;
 
         self.dyswarm_client
             .broadcast(BroadcastArgs {
@@ -291,7 +480,7 @@ impl NetworkModule {
 
         let node_id = self.node_id.clone();
 
-        let message = dyswarm::types::Message::new(NetworkEvent::ClaimCreated { node_id, claim });
+        let message = self.seal(NetworkEvent::ClaimCreated { node_id, claim })?;
 
         self.dyswarm_client
             .broadcast(BroadcastArgs {
@@ -317,8 +506,7 @@ impl NetworkModule {
 
         self.dyswarm_client.add_peers(socket_addresses).await?;
 
-        let message =
-            dyswarm::types::Message::new(NetworkEvent::PartCommitmentCreated(node_id, part));
+        let message = self.seal(NetworkEvent::PartCommitmentCreated(node_id, part))?;
 
         self.dyswarm_client
             .broadcast(BroadcastArgs {
@@ -351,11 +539,14 @@ impl NetworkModule {
 
         let addr = found_peer.udp_gossip_addr;
 
-        let message = dyswarm::types::Message::new(NetworkEvent::PartCommitmentAcknowledged {
-            node_id,
-            sender_id,
-            ack,
-        });
+        let message = self.seal_for(
+            addr,
+            NetworkEvent::PartCommitmentAcknowledged {
+                node_id,
+                sender_id,
+                ack,
+            },
+        )?;
 
         self.dyswarm_client
             .send_data_via_quic(message, addr)
@@ -368,7 +559,7 @@ impl NetworkModule {
         &mut self,
         block: ConvergenceBlock,
     ) -> Result<()> {
-        let message = dyswarm::types::Message::new(NetworkEvent::ConvergenceBlockCertified(block));
+        let message = self.seal(NetworkEvent::ConvergenceBlockCertified(block))?;
 
         self.dyswarm_client
             .broadcast(BroadcastArgs {
@@ -385,8 +576,7 @@ impl NetworkModule {
         &mut self,
         sig: ConvergencePartialSig,
     ) -> Result<()> {
-        let message =
-            dyswarm::types::Message::new(NetworkEvent::ConvergenceBlockPartialSignComplete(sig));
+        let message = self.seal(NetworkEvent::ConvergenceBlockPartialSignComplete(sig))?;
 
         self.dyswarm_client
             .broadcast(BroadcastArgs {
@@ -400,7 +590,7 @@ impl NetworkModule {
     }
 
     pub async fn broadcast_certificate(&mut self, cert: Certificate) -> Result<()> {
-        let message = dyswarm::types::Message::new(NetworkEvent::BroadcastCertificate(cert));
+        let message = self.seal(NetworkEvent::BroadcastCertificate(cert))?;
 
         self.dyswarm_client
             .broadcast(BroadcastArgs {
@@ -415,8 +605,7 @@ impl NetworkModule {
 
     pub async fn broadcast_transaction_vote(&mut self, vote: Vote) -> Result<()> {
         telemetry::info!("Broadcasting transaction vote to network");
-        let message =
-            dyswarm::types::Message::new(NetworkEvent::BroadcastTransactionVote(Box::new(vote)));
+        let message = self.seal(NetworkEvent::BroadcastTransactionVote(Box::new(vote)))?;
         self.dyswarm_client
             .broadcast(BroadcastArgs {
                 config: Default::default(),
@@ -441,7 +630,7 @@ impl NetworkModule {
 
         self.dyswarm_client.add_peers(socket_address).await?;
 
-        let message = dyswarm::types::Message::new(NetworkEvent::BlockCreated(block));
+        let message = self.seal(NetworkEvent::BlockCreated(block))?;
 
         self.dyswarm_client
             .broadcast(BroadcastArgs {
@@ -453,4 +642,77 @@ impl NetworkModule {
 
         Ok(())
     }
+
+    /// Asks the peer listening at `peer` for its chain tip, which starts
+    /// synchronizing blocks with it
+    pub(crate) async fn request_chain_tip(&mut self, peer: SocketAddr) -> Result<()> {
+        let message = self.seal_for(
+            peer,
+            NetworkEvent::ChainTipRequested {
+                sender_id: self.node_id.clone(),
+            },
+        )?;
+
+        self.dyswarm_client
+            .send_data_via_quic(message, peer)
+            .await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn send_chain_tip(
+        &mut self,
+        peer: SocketAddr,
+        tip: Option<ChainTip>,
+    ) -> Result<()> {
+        let message = self.seal_for(
+            peer,
+            NetworkEvent::ChainTipReported {
+                sender_id: self.node_id.clone(),
+                tip,
+            },
+        )?;
+
+        self.dyswarm_client
+            .send_data_via_quic(message, peer)
+            .await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn request_blocks(
+        &mut self,
+        peer: SocketAddr,
+        request: BlockRequest,
+    ) -> Result<()> {
+        let message = self.seal_for(
+            peer,
+            NetworkEvent::BlocksRequested {
+                sender_id: self.node_id.clone(),
+                request,
+            },
+        )?;
+
+        self.dyswarm_client
+            .send_data_via_quic(message, peer)
+            .await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn send_blocks(&mut self, peer: SocketAddr, blocks: Vec<Block>) -> Result<()> {
+        let message = self.seal_for(
+            peer,
+            NetworkEvent::BlocksProvided {
+                sender_id: self.node_id.clone(),
+                blocks,
+            },
+        )?;
+
+        self.dyswarm_client
+            .send_data_via_quic(message, peer)
+            .await?;
+
+        Ok(())
+    }
 }
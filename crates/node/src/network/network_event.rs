@@ -1,13 +1,15 @@
 use std::net::SocketAddr;
 
 use block::{Block, Certificate, ConvergenceBlock};
-use events::{AssignedQuorumMembership, Vote};
+use events::{AssignedQuorumMembership, BlockRequest, ChainTip, Vote};
 use hbbft::sync_key_gen::{Ack, Part};
 use mempool::TxnRecord;
 use primitives::{ConvergencePartialSig, KademliaPeerId, NodeId, NodeType, PeerId, PublicKey};
 use serde::{Deserialize, Serialize};
 use vrrb_core::claim::Claim;
 
+use super::transport::NetworkEnvelope;
+
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
 /// Represents data trasmitted over the VRRB network by nodes that participate
 /// in it
@@ -17,8 +19,11 @@ pub enum NetworkEvent {
         claim: Claim,
     },
 
+    /// A node gave up its claim. `signature` is the claim key's signature
+    /// over the abandonment.
     ClaimAbandoned {
         claim: Vec<u8>,
+        signature: String,
         sender_id: String,
     },
 
@@ -66,6 +71,81 @@ pub enum NetworkEvent {
     BroadcastTransactionVote(Box<Vote>),
     Ping(NodeId),
 
+    /// Asks a peer for the latest block it confirmed. The answer is sent back
+    /// to the address known for the sender
+    ChainTipRequested {
+        sender_id: NodeId,
+    },
+
+    /// Latest block confirmed by the sender, if any
+    ChainTipReported {
+        sender_id: NodeId,
+        tip: Option<ChainTip>,
+    },
+
+    /// Asks a peer for blocks the sender is missing. The answer is sent back
+    /// to the address known for the sender
+    BlocksRequested {
+        sender_id: NodeId,
+        request: BlockRequest,
+    },
+
+    /// Blocks sent in answer to a `BlocksRequested`
+    BlocksProvided {
+        sender_id: NodeId,
+        blocks: Vec<Block>,
+    },
+
+    /// Starts a handshake with a peer. The sender signs an ephemeral key
+    /// along with `challenge`, the answer is sent back to the address known
+    /// for the sender
+    HandshakeInitiated {
+        challenge: Vec<u8>,
+        ephemeral_key: PublicKey,
+    },
+
+    /// Answer to a `HandshakeInitiated`, echoing its challenge
+    HandshakeAccepted {
+        challenge: Vec<u8>,
+        ephemeral_key: PublicKey,
+    },
+
+    /// Any of the other events, signed by the node that sent it. Events that
+    /// don't come sealed in an envelope are dropped
+    Envelope(Box<NetworkEnvelope>),
+
     #[default]
     Empty,
 }
+
+impl NetworkEvent {
+    /// ID of the node that sent the event, for the events that carry it
+    pub fn sender_id(&self) -> Option<&NodeId> {
+        match self {
+            NetworkEvent::ClaimCreated { node_id, .. }
+            | NetworkEvent::PeerJoined { node_id, .. }
+            | NetworkEvent::PartCommitmentCreated(node_id, _)
+            | NetworkEvent::Ping(node_id) => Some(node_id),
+            NetworkEvent::ClaimAbandoned { sender_id, .. }
+            | NetworkEvent::PartCommitmentAcknowledged { sender_id, .. }
+            | NetworkEvent::ChainTipRequested { sender_id, .. }
+            | NetworkEvent::ChainTipReported { sender_id, .. }
+            | NetworkEvent::BlocksRequested { sender_id, .. }
+            | NetworkEvent::BlocksProvided { sender_id, .. } => Some(sender_id),
+            _ => None,
+        }
+    }
+
+    /// Whether receiving the same event twice is a duplicate. Pings and block
+    /// sync requests and answers are legitimately repeated.
+    pub fn is_deduplicated(&self) -> bool {
+        !matches!(
+            self,
+            NetworkEvent::Ping(_)
+                | NetworkEvent::ChainTipRequested { .. }
+                | NetworkEvent::ChainTipReported { .. }
+                | NetworkEvent::BlocksRequested { .. }
+                | NetworkEvent::BlocksProvided { .. }
+        )
+    }
+}
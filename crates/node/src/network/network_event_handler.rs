@@ -1,19 +1,139 @@
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
 use async_trait::async_trait;
 use dyswarm::types::Message as DyswarmMessage;
-use events::{Event, EventMessage, EventPublisher, PeerData};
-use primitives::{NodeId, NETWORK_TOPIC_STR, RUNTIME_TOPIC_STR};
+use events::{AssignedQuorumMembership, Event, EventMessage, EventPublisher, PeerData};
+use primitives::{NodeId, PublicKey, NETWORK_TOPIC_STR, RUNTIME_TOPIC_STR};
+use vrrb_core::claim::Claim;
 
-use crate::{network::NetworkEvent, NodeError, Result};
+use crate::{
+    network::{
+        peer_scoring::{Admission, Offense, PeerScoreboard},
+        transport::{NetworkEnvelope, Opened, SecureTransport},
+        NetworkEvent,
+    },
+    NodeError, Result,
+};
 
 #[derive(Debug, Clone)]
 pub struct DyswarmHandler {
     pub node_id: NodeId,
     pub events_tx: EventPublisher,
+    pub peer_scores: Arc<Mutex<PeerScoreboard>>,
+    pub transport: Arc<Mutex<SecureTransport>>,
 }
 
 impl DyswarmHandler {
-    pub fn new(node_id: NodeId, events_tx: EventPublisher) -> Self {
-        Self { node_id, events_tx }
+    pub fn new(
+        node_id: NodeId,
+        events_tx: EventPublisher,
+        peer_scores: Arc<Mutex<PeerScoreboard>>,
+        transport: Arc<Mutex<SecureTransport>>,
+    ) -> Self {
+        Self {
+            node_id,
+            events_tx,
+            peer_scores,
+            transport,
+        }
+    }
+
+    fn admit(&self, sender: &NodeId, event: &NetworkEvent) -> Admission {
+        match self.peer_scores.lock() {
+            Ok(mut peer_scores) => peer_scores.admit(sender, event),
+            Err(err) => {
+                telemetry::error!("Failed to lock peer scores: {err}");
+                Admission::Accepted
+            }
+        }
+    }
+
+    fn admit_introduction(&self, event: &NetworkEvent) -> Admission {
+        match self.peer_scores.lock() {
+            Ok(mut peer_scores) => peer_scores.admit_introduction(event),
+            Err(err) => {
+                telemetry::error!("Failed to lock peer scores: {err}");
+                Admission::Accepted
+            }
+        }
+    }
+
+    fn penalize(&self, peer: &NodeId, offense: Offense) {
+        match self.peer_scores.lock() {
+            Ok(mut peer_scores) => peer_scores.penalize(peer, offense),
+            Err(err) => telemetry::error!("Failed to lock peer scores: {err}"),
+        }
+    }
+
+    fn open(&self, envelope: NetworkEnvelope) -> Result<Opened> {
+        let mut transport = self
+            .transport
+            .lock()
+            .map_err(|err| NodeError::Other(format!("Failed to lock transport: {err}")))?;
+
+        Ok(transport.open(envelope)?)
+    }
+
+    fn complete_handshake(
+        &self,
+        node_id: NodeId,
+        challenge: &[u8],
+        ephemeral_key: PublicKey,
+    ) -> Result<()> {
+        let mut transport = self
+            .transport
+            .lock()
+            .map_err(|err| NodeError::Other(format!("Failed to lock transport: {err}")))?;
+
+        Ok(transport.complete_handshake(node_id, challenge, ephemeral_key)?)
+    }
+
+    /// Returns the address known for `node_id`, which sync answers are sent
+    /// to. Addresses carried in payloads aren't trusted, so requests from
+    /// peers without one are dropped.
+    fn peer_addr(&self, node_id: &NodeId) -> Result<Option<SocketAddr>> {
+        let transport = self
+            .transport
+            .lock()
+            .map_err(|err| NodeError::Other(format!("Failed to lock transport: {err}")))?;
+
+        let peer_addr = transport.peer_addr(node_id);
+        if peer_addr.is_none() {
+            telemetry::warn!("Dropped sync event from node {node_id} with no known address");
+        }
+
+        Ok(peer_addr)
+    }
+
+    /// Learns the validator keys and addresses of the peers in quorum
+    /// assignments made by the bootstrap node. Assignments sent by anyone
+    /// else are only passed along.
+    fn register_assigned_peers(&self, sender: &NodeId, assignments: &[AssignedQuorumMembership]) {
+        let mut transport = match self.transport.lock() {
+            Ok(transport) => transport,
+            Err(err) => {
+                telemetry::error!("Failed to lock transport: {err}");
+                return;
+            }
+        };
+
+        if !transport.is_bootstrap(sender) {
+            return;
+        }
+
+        for peer in assignments
+            .iter()
+            .flat_map(|assignment| assignment.peers.iter())
+        {
+            transport.register_peer(
+                peer.node_id.clone(),
+                peer.validator_public_key,
+                peer.udp_gossip_addr,
+            );
+        }
     }
 
     async fn send_event(&self, topic: &str, evt: Event) -> Result<()> {
@@ -33,7 +153,53 @@ impl DyswarmHandler {
 #[async_trait]
 impl dyswarm::server::Handler<NetworkEvent> for DyswarmHandler {
     async fn handle(&self, msg: DyswarmMessage<NetworkEvent>) -> dyswarm::types::Result<()> {
-        match msg.data {
+        let NetworkEvent::Envelope(envelope) = msg.data else {
+            telemetry::warn!("Dropped network event that wasn't sealed in an envelope");
+            return Ok(());
+        };
+
+        // NOTE: peers are scored by the node the envelope was authenticated as,
+        // IDs carried in the payload are only checked against it
+        let sender_id = envelope.sender_id.clone();
+        let sender_key = envelope.sender_key;
+
+        let (event, is_introduction) = match self.open(*envelope) {
+            Ok(Opened::Authenticated(event)) => (event, false),
+            Ok(Opened::Introduction(event)) => (event, true),
+            Err(err) => {
+                telemetry::warn!("Dropped network event from {sender_id}: {err}");
+                if let NodeError::Transport(err) = err {
+                    if let Some(offense) = err.offense() {
+                        self.penalize(&sender_id, offense);
+                    }
+                }
+                return Ok(());
+            }
+        };
+
+        if event
+            .sender_id()
+            .is_some_and(|claimed_id| claimed_id != &sender_id)
+        {
+            telemetry::warn!("Node {sender_id} sent an event on behalf of another node");
+            self.penalize(&sender_id, Offense::InvalidSignature);
+            return Ok(());
+        }
+
+        // NOTE: introductions don't authenticate their sender, so they aren't
+        // scored against it
+        let admission = if is_introduction {
+            self.admit_introduction(&event)
+        } else {
+            self.admit(&sender_id, &event)
+        };
+        if admission != Admission::Accepted {
+            telemetry::debug!("Dropped network event from {sender_id}: {admission:?}");
+
+            return Ok(());
+        }
+
+        match event {
             NetworkEvent::PeerJoined {
                 node_id,
                 node_type,
@@ -43,6 +209,14 @@ impl dyswarm::server::Handler<NetworkEvent> for DyswarmHandler {
                 kademlia_liveness_addr,
                 validator_public_key,
             } => {
+                if validator_public_key != sender_key {
+                    telemetry::warn!(
+                        "Node {node_id} announced a validator key it didn't sign with"
+                    );
+                    self.penalize(&sender_id, Offense::InvalidSignature);
+                    return Ok(());
+                }
+
                 telemetry::info!("Node {} joined network", node_id);
 
                 let evt = Event::PeerJoined(PeerData {
@@ -58,6 +232,16 @@ impl dyswarm::server::Handler<NetworkEvent> for DyswarmHandler {
                 self.send_event_to_network(evt).await?;
             }
             NetworkEvent::ClaimCreated { node_id, claim } => {
+                if let Err(err) = claim.verify_signature() {
+                    telemetry::warn!(
+                        "Received claim with an invalid signature from {sender_id}: {err}"
+                    );
+                    if !is_introduction {
+                        self.penalize(&sender_id, Offense::InvalidSignature);
+                    }
+                    return Ok(());
+                }
+
                 telemetry::info!(
                     "Node ID {} received claim from {}: {}",
                     self.node_id,
@@ -70,7 +254,42 @@ impl dyswarm::server::Handler<NetworkEvent> for DyswarmHandler {
                 self.send_event_to_network(evt).await?;
             }
 
+            NetworkEvent::ClaimAbandoned {
+                claim, signature, ..
+            } => {
+                let claim = match serde_json::from_slice::<Claim>(&claim) {
+                    Ok(claim) => claim,
+                    Err(err) => {
+                        telemetry::error!(
+                            "Received malformed abandoned claim from {sender_id}: {err}"
+                        );
+                        self.penalize(&sender_id, Offense::MalformedEvent);
+                        return Ok(());
+                    }
+                };
+
+                if let Err(err) = claim.verify_abandonment(signature.clone()) {
+                    telemetry::warn!(
+                        "Received claim abandonment with an invalid signature from {sender_id}: {err}"
+                    );
+                    self.penalize(&sender_id, Offense::InvalidSignature);
+                    return Ok(());
+                }
+
+                telemetry::info!(
+                    "Node ID {} received abandoned claim from {}",
+                    self.node_id,
+                    sender_id
+                );
+
+                let evt = Event::ClaimAbandoned { claim, signature };
+
+                self.send_event_to_runtime(evt).await?;
+            }
+
             NetworkEvent::QuorumMembershipAssigmentsCreated(assignments) => {
+                self.register_assigned_peers(&sender_id, &assignments);
+
                 telemetry::info!(
                     "Node ID {} received {} assignments",
                     self.node_id,
@@ -85,6 +304,11 @@ impl dyswarm::server::Handler<NetworkEvent> for DyswarmHandler {
             NetworkEvent::AssignmentToQuorumCreated {
                 assigned_membership,
             } => {
+                self.register_assigned_peers(
+                    &sender_id,
+                    std::slice::from_ref(&assigned_membership),
+                );
+
                 telemetry::info!(
                     "Node ID {} received assignment to quorum: {:?}",
                     self.node_id,
@@ -123,6 +347,86 @@ impl dyswarm::server::Handler<NetworkEvent> for DyswarmHandler {
                 self.send_event_to_runtime(evt).await?;
             }
 
+            NetworkEvent::ChainTipRequested { .. } => {
+                telemetry::info!("Node {sender_id} requested chain tip");
+
+                let Some(peer) = self.peer_addr(&sender_id)? else {
+                    return Ok(());
+                };
+
+                let evt = Event::ChainTipRequested(peer);
+
+                self.send_event_to_runtime(evt).await?;
+            }
+
+            NetworkEvent::ChainTipReported { tip, .. } => {
+                telemetry::info!("Node {sender_id} reported chain tip: {tip:?}");
+
+                let Some(peer) = self.peer_addr(&sender_id)? else {
+                    return Ok(());
+                };
+
+                let evt = Event::PeerChainTipReceived { peer, tip };
+
+                self.send_event_to_runtime(evt).await?;
+            }
+
+            NetworkEvent::BlocksRequested { request, .. } => {
+                telemetry::info!("Node {sender_id} requested blocks: {request:?}");
+
+                let Some(peer) = self.peer_addr(&sender_id)? else {
+                    return Ok(());
+                };
+
+                let evt = Event::BlocksRequestReceived { peer, request };
+
+                self.send_event_to_runtime(evt).await?;
+            }
+
+            NetworkEvent::BlocksProvided { blocks, .. } => {
+                telemetry::info!("Received {} blocks from node {sender_id}", blocks.len());
+
+                let Some(peer) = self.peer_addr(&sender_id)? else {
+                    return Ok(());
+                };
+
+                let evt = Event::SyncBlocksReceived { peer, blocks };
+
+                self.send_event_to_runtime(evt).await?;
+            }
+
+            NetworkEvent::HandshakeInitiated {
+                challenge,
+                ephemeral_key,
+            } => {
+                telemetry::info!("Node {sender_id} started a handshake");
+
+                let evt = Event::HandshakeRequested {
+                    node_id: sender_id,
+                    challenge,
+                    ephemeral_key,
+                };
+
+                self.send_event_to_network(evt).await?;
+            }
+
+            NetworkEvent::HandshakeAccepted {
+                challenge,
+                ephemeral_key,
+            } => match self.complete_handshake(sender_id.clone(), &challenge, ephemeral_key) {
+                Ok(()) => telemetry::info!("Completed handshake with node {sender_id}"),
+                Err(err) => telemetry::warn!("Failed to complete handshake: {err}"),
+            },
+
+            NetworkEvent::Envelope(_) => {
+                telemetry::warn!("Node {sender_id} sent an envelope within an envelope");
+                self.penalize(&sender_id, Offense::MalformedEvent);
+            }
+
+            NetworkEvent::Empty => {
+                telemetry::warn!("Received an empty network event");
+            }
+
             _ => {}
         }
 
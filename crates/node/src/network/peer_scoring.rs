@@ -0,0 +1,550 @@
+//! Accounting of the network events received from each peer.
+//!
+//! Every peer starts with a score of 0 and loses points when it misbehaves.
+//! Peers whose score drops to the configured throttle score get a lower rate
+//! limit, and peers whose score drops to the ban score have everything they
+//! send dropped until the ban is lifted.
+//!
+//! NOTE: peers are identified by the node ID their envelopes are
+//! authenticated as, see [`super::transport`]. Nodes that aren't known yet
+//! can pick any node ID, their introductions share a single rate limit.
+
+use std::{
+    collections::HashMap,
+    hash::{Hash, Hasher},
+    time::{Duration, Instant},
+};
+
+use metric_exporter::metric_factory::PrometheusFactory;
+use primitives::NodeId;
+use prometheus::IntCounter;
+use vrrb_config::PeerScoringConfig;
+use vrrb_core::cache::Cache;
+
+use super::NetworkEvent;
+use crate::{NodeError, Result};
+
+/// Misbehaviour a peer loses points for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Offense {
+    InvalidSignature,
+    MalformedEvent,
+    DuplicateMessage,
+    RateLimited,
+}
+
+/// Whether a message received from a peer should be processed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    Accepted,
+    Duplicate,
+    RateLimited,
+    Banned,
+}
+
+/// Counters exported for every offense and ban
+#[derive(Debug, Clone)]
+pub struct PeerScoringMetrics {
+    invalid_signatures: IntCounter,
+    malformed_events: IntCounter,
+    duplicate_messages: IntCounter,
+    rate_limited_messages: IntCounter,
+    peers_banned: IntCounter,
+    banned_peer_messages: IntCounter,
+}
+
+impl PeerScoringMetrics {
+    pub fn new(factory: &PrometheusFactory, labels: HashMap<String, String>) -> Result<Self> {
+        let counter = |name: &str, help: &str| {
+            factory
+                .build_int_counter(name, help, labels.clone())
+                .map_err(|e| {
+                    NodeError::Other(format!("Failed to build prometheus metric :{:?}", e))
+                })
+        };
+
+        Ok(Self {
+            invalid_signatures: counter(
+                "peer_invalid_signatures",
+                "No of events received from peers with an invalid signature",
+            )?,
+            malformed_events: counter(
+                "peer_malformed_events",
+                "No of malformed events received from peers",
+            )?,
+            duplicate_messages: counter(
+                "peer_duplicate_messages",
+                "No of duplicate messages received from peers",
+            )?,
+            rate_limited_messages: counter(
+                "peer_rate_limited_messages",
+                "No of messages dropped because a peer exceeded its rate limit",
+            )?,
+            peers_banned: counter("peers_banned", "No of peers banned")?,
+            banned_peer_messages: counter(
+                "banned_peer_messages",
+                "No of messages dropped because their sender is banned",
+            )?,
+        })
+    }
+
+    fn record(&self, offense: Offense) {
+        match offense {
+            Offense::InvalidSignature => self.invalid_signatures.inc(),
+            Offense::MalformedEvent => self.malformed_events.inc(),
+            Offense::DuplicateMessage => self.duplicate_messages.inc(),
+            Offense::RateLimited => self.rate_limited_messages.inc(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PeerRecord {
+    score: i64,
+    window_start: Instant,
+    messages_in_window: u32,
+    banned_until: Option<Instant>,
+}
+
+impl PeerRecord {
+    fn new(now: Instant) -> Self {
+        Self {
+            score: 0,
+            window_start: now,
+            messages_in_window: 0,
+            banned_until: None,
+        }
+    }
+
+    /// Returns true while the peer is banned. Peers start over once their ban
+    /// is lifted.
+    fn is_banned(&mut self, now: Instant) -> bool {
+        match self.banned_until {
+            Some(until) if now < until => true,
+            Some(_) => {
+                *self = PeerRecord::new(now);
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Starts a new window once the current one is over, giving back points
+    /// for every window that went by
+    fn roll_window(&mut self, config: &PeerScoringConfig, now: Instant) {
+        let interval = Duration::from_millis(config.rate_limit_interval_ms);
+        let elapsed = now.saturating_duration_since(self.window_start);
+
+        if elapsed < interval {
+            return;
+        }
+
+        let windows = (elapsed.as_millis() / interval.as_millis()).min(i64::MAX as u128) as i64;
+        self.score = self
+            .score
+            .saturating_add(windows.saturating_mul(config.score_recovery_per_interval))
+            .min(0);
+        self.window_start = now;
+        self.messages_in_window = 0;
+    }
+}
+
+/// Keeps the score of every peer a node hears from, along with the messages
+/// it has already seen
+#[derive(Debug, Clone)]
+pub struct PeerScoreboard {
+    config: PeerScoringConfig,
+    peers: Cache<NodeId, PeerRecord>,
+    /// Shared by every node introducing itself, they can pick any node ID
+    introductions: PeerRecord,
+    /// Digests of the messages already received, along with the peer that
+    /// delivered them first. Introductions have no known sender.
+    seen_messages: Cache<u64, Option<NodeId>>,
+    metrics: Option<PeerScoringMetrics>,
+}
+
+impl PeerScoreboard {
+    pub fn new(config: PeerScoringConfig) -> Self {
+        // NOTE: forgetting peers sooner would lift their bans early
+        let peer_ttl = config.ban_duration_secs.saturating_mul(1_000).max(60_000);
+
+        Self {
+            peers: Cache::new(config.max_tracked_peers, peer_ttl),
+            introductions: PeerRecord::new(Instant::now()),
+            seen_messages: Cache::new(config.seen_message_capacity, config.seen_message_ttl_ms),
+            metrics: None,
+            config,
+        }
+    }
+
+    pub fn with_metrics(self, metrics: PeerScoringMetrics) -> Self {
+        Self {
+            metrics: Some(metrics),
+            ..self
+        }
+    }
+
+    /// Decides whether an event received from `sender` should be processed,
+    /// penalizing the sender for spam and for sending the same message again.
+    /// Copies of a message relayed by other peers are dropped without
+    /// penalty. `sender` must be the
+    /// node the event was authenticated as, never an ID taken from its payload
+    pub fn admit(&mut self, sender: &NodeId, event: &NetworkEvent) -> Admission {
+        let digest = event.is_deduplicated().then(|| digest(event));
+
+        self.admit_at(sender, digest, Instant::now())
+    }
+
+    /// Decides whether an introduction from a node that isn't known yet
+    /// should be processed. Introductions share the throttled rate limit
+    /// instead of being scored against their sender.
+    pub fn admit_introduction(&mut self, event: &NetworkEvent) -> Admission {
+        self.admit_introduction_at(digest(event), Instant::now())
+    }
+
+    /// Lowers the score of a peer, banning it once it drops to the ban score
+    pub fn penalize(&mut self, peer: &NodeId, offense: Offense) {
+        self.penalize_at(peer, offense, Instant::now())
+    }
+
+    pub fn is_banned(&mut self, peer: &NodeId) -> bool {
+        let now = Instant::now();
+
+        self.peers
+            .get_mut(peer)
+            .is_some_and(|record| record.is_banned(now))
+    }
+
+    /// Current score of a peer, if it's being tracked
+    pub fn score(&mut self, peer: &NodeId) -> Option<i64> {
+        self.peers.get(peer).map(|record| record.score)
+    }
+
+    fn admit_at(&mut self, peer: &NodeId, digest: Option<u64>, now: Instant) -> Admission {
+        if !self.peers.contains(peer) {
+            self.peers.push(peer.clone(), PeerRecord::new(now));
+        }
+
+        let Some(record) = self.peers.get_mut(peer) else {
+            return Admission::Accepted;
+        };
+
+        if record.is_banned(now) {
+            if let Some(metrics) = &self.metrics {
+                metrics.banned_peer_messages.inc();
+            }
+
+            return Admission::Banned;
+        }
+
+        record.roll_window(&self.config, now);
+        record.messages_in_window = record.messages_in_window.saturating_add(1);
+
+        let limit = if record.score <= self.config.throttle_score {
+            self.config.throttled_messages_per_interval
+        } else {
+            self.config.max_messages_per_interval
+        };
+
+        if record.messages_in_window > limit {
+            self.penalize_at(peer, Offense::RateLimited, now);
+            return Admission::RateLimited;
+        }
+
+        if let Some(digest) = digest {
+            if let Some(first_sender) = self.seen_messages.get(&digest).cloned() {
+                // NOTE: gossip reaches a node through every peer relaying it,
+                // only the peer that delivered it first sending it again is
+                // at fault
+                if first_sender.as_ref() == Some(peer) {
+                    self.penalize_at(peer, Offense::DuplicateMessage, now);
+                } else {
+                    self.record(Offense::DuplicateMessage);
+                }
+
+                return Admission::Duplicate;
+            }
+
+            self.seen_messages.push(digest, Some(peer.clone()));
+        }
+
+        Admission::Accepted
+    }
+
+    fn admit_introduction_at(&mut self, digest: u64, now: Instant) -> Admission {
+        self.introductions.roll_window(&self.config, now);
+        self.introductions.messages_in_window =
+            self.introductions.messages_in_window.saturating_add(1);
+
+        if self.introductions.messages_in_window > self.config.throttled_messages_per_interval {
+            self.record(Offense::RateLimited);
+            return Admission::RateLimited;
+        }
+
+        if self.seen_messages.contains(&digest) {
+            self.record(Offense::DuplicateMessage);
+            return Admission::Duplicate;
+        }
+
+        self.seen_messages.push(digest, None);
+
+        Admission::Accepted
+    }
+
+    fn penalize_at(&mut self, peer: &NodeId, offense: Offense, now: Instant) {
+        let penalty = match offense {
+            Offense::InvalidSignature => self.config.invalid_signature_penalty,
+            Offense::MalformedEvent => self.config.malformed_event_penalty,
+            Offense::DuplicateMessage => self.config.duplicate_message_penalty,
+            Offense::RateLimited => self.config.rate_limit_penalty,
+        };
+
+        self.record(offense);
+
+        if !self.peers.contains(peer) {
+            self.peers.push(peer.clone(), PeerRecord::new(now));
+        }
+
+        let Some(record) = self.peers.get_mut(peer) else {
+            return;
+        };
+
+        if record.banned_until.is_some() {
+            return;
+        }
+
+        record.score = record.score.saturating_sub(penalty);
+
+        if record.score <= self.config.ban_score {
+            record.banned_until = Some(now + Duration::from_secs(self.config.ban_duration_secs));
+
+            telemetry::warn!("Banned peer {peer} with score {}", record.score);
+
+            if let Some(metrics) = &self.metrics {
+                metrics.peers_banned.inc();
+            }
+        }
+    }
+
+    fn record(&self, offense: Offense) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record(offense);
+        }
+    }
+}
+
+/// Digest used to recognize events that were already received
+fn digest(event: &NetworkEvent) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    serde_json::to_vec(event)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> PeerScoringConfig {
+        PeerScoringConfig {
+            max_messages_per_interval: 4,
+            throttled_messages_per_interval: 2,
+            ..Default::default()
+        }
+    }
+
+    fn peer() -> NodeId {
+        "peer".to_string()
+    }
+
+    fn another_peer() -> NodeId {
+        "another peer".to_string()
+    }
+
+    #[test]
+    fn duplicate_messages_are_dropped_and_only_repeats_are_penalized() {
+        let mut scoreboard = PeerScoreboard::new(config());
+        let now = Instant::now();
+
+        assert_eq!(
+            scoreboard.admit_at(&peer(), Some(1), now),
+            Admission::Accepted
+        );
+        assert_eq!(
+            scoreboard.admit_at(&peer(), Some(1), now),
+            Admission::Duplicate
+        );
+        assert_eq!(
+            scoreboard.admit_at(&another_peer(), Some(1), now),
+            Admission::Duplicate
+        );
+        assert_eq!(
+            scoreboard.admit_at(&another_peer(), None, now),
+            Admission::Accepted
+        );
+
+        assert_eq!(
+            scoreboard.score(&peer()),
+            Some(-config().duplicate_message_penalty)
+        );
+        // NOTE: relaying a message another peer delivered first is not an
+        // offense
+        assert_eq!(scoreboard.score(&another_peer()), Some(0));
+    }
+
+    #[test]
+    fn peers_over_their_rate_limit_are_throttled() {
+        let mut scoreboard = PeerScoreboard::new(config());
+        let now = Instant::now();
+
+        for _ in 0..4 {
+            assert_eq!(scoreboard.admit_at(&peer(), None, now), Admission::Accepted);
+        }
+        assert_eq!(
+            scoreboard.admit_at(&peer(), None, now),
+            Admission::RateLimited
+        );
+
+        // NOTE: a new window starts once the interval is over
+        let later = now + Duration::from_millis(config().rate_limit_interval_ms);
+        assert_eq!(
+            scoreboard.admit_at(&peer(), None, later),
+            Admission::Accepted
+        );
+    }
+
+    #[test]
+    fn peers_with_a_low_score_get_a_lower_rate_limit() {
+        let mut scoreboard = PeerScoreboard::new(config());
+        let now = Instant::now();
+
+        scoreboard.penalize_at(&peer(), Offense::InvalidSignature, now);
+        scoreboard.penalize_at(&peer(), Offense::MalformedEvent, now);
+        assert!(scoreboard.score(&peer()).unwrap() <= config().throttle_score);
+
+        for _ in 0..2 {
+            assert_eq!(scoreboard.admit_at(&peer(), None, now), Admission::Accepted);
+        }
+        assert_eq!(
+            scoreboard.admit_at(&peer(), None, now),
+            Admission::RateLimited
+        );
+    }
+
+    #[test]
+    fn misbehaving_peers_are_banned_until_the_ban_is_lifted() {
+        let mut scoreboard = PeerScoreboard::new(config());
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            scoreboard.penalize_at(&peer(), Offense::InvalidSignature, now);
+        }
+
+        assert_eq!(
+            scoreboard.admit_at(&peer(), Some(1), now),
+            Admission::Banned
+        );
+        assert_eq!(
+            scoreboard.admit_at(&another_peer(), Some(2), now),
+            Admission::Accepted
+        );
+
+        let lifted = now + Duration::from_secs(config().ban_duration_secs);
+        assert_eq!(
+            scoreboard.admit_at(&peer(), Some(3), lifted),
+            Admission::Accepted
+        );
+        assert_eq!(scoreboard.score(&peer()), Some(0));
+    }
+
+    #[test]
+    fn scores_recover_over_time() {
+        let mut scoreboard = PeerScoreboard::new(config());
+        let now = Instant::now();
+
+        scoreboard.penalize_at(&peer(), Offense::MalformedEvent, now);
+
+        let later = now + Duration::from_millis(config().rate_limit_interval_ms * 5);
+        scoreboard.admit_at(&peer(), None, later);
+
+        assert_eq!(
+            scoreboard.score(&peer()),
+            Some(-config().malformed_event_penalty + 5)
+        );
+    }
+
+    #[test]
+    fn events_without_a_sender_id_count_against_their_authenticated_sender() {
+        let mut scoreboard = PeerScoreboard::new(config());
+        let secret_key = primitives::SecretKey::new(&mut rand::thread_rng());
+        let ephemeral_key =
+            primitives::PublicKey::from_secret_key(secp256k1::SECP256K1, &secret_key);
+        let answer = |challenge: u8| NetworkEvent::HandshakeAccepted {
+            challenge: vec![challenge],
+            ephemeral_key,
+        };
+
+        for challenge in 0..4 {
+            assert_eq!(
+                scoreboard.admit(&peer(), &answer(challenge)),
+                Admission::Accepted
+            );
+        }
+        assert_eq!(
+            scoreboard.admit(&peer(), &answer(4)),
+            Admission::RateLimited
+        );
+        assert_eq!(
+            scoreboard.score(&peer()),
+            Some(-config().rate_limit_penalty)
+        );
+    }
+
+    #[test]
+    fn introductions_share_a_rate_limit_whatever_node_id_they_use() {
+        let mut scoreboard = PeerScoreboard::new(config());
+        let now = Instant::now();
+
+        assert_eq!(
+            scoreboard.admit_introduction_at(1, now),
+            Admission::Accepted
+        );
+        assert_eq!(
+            scoreboard.admit_introduction_at(1, now),
+            Admission::Duplicate
+        );
+        assert_eq!(
+            scoreboard.admit_introduction_at(2, now),
+            Admission::RateLimited
+        );
+
+        // NOTE: introductions don't start tracking their sender
+        assert_eq!(scoreboard.score(&peer()), None);
+
+        let later = now + Duration::from_millis(config().rate_limit_interval_ms);
+        assert_eq!(
+            scoreboard.admit_introduction_at(3, later),
+            Admission::Accepted
+        );
+    }
+
+    #[test]
+    fn sync_requests_are_not_deduplicated() {
+        let request = NetworkEvent::ChainTipRequested { sender_id: peer() };
+        let mut scoreboard = PeerScoreboard::new(config());
+
+        assert_eq!(scoreboard.admit(&peer(), &request), Admission::Accepted);
+        assert_eq!(scoreboard.admit(&peer(), &request), Admission::Accepted);
+
+        let abandoned = NetworkEvent::ClaimAbandoned {
+            claim: vec![],
+            signature: String::new(),
+            sender_id: peer(),
+        };
+
+        assert_eq!(scoreboard.admit(&peer(), &abandoned), Admission::Accepted);
+        assert_eq!(scoreboard.admit(&peer(), &abandoned), Admission::Duplicate);
+    }
+}
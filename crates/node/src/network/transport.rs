@@ -0,0 +1,850 @@
+//! Authentication and encryption of the events nodes send each other.
+//!
+//! Every event leaves a node sealed in a [`NetworkEnvelope`] signed with its
+//! validator key. Envelopes are only opened when signed with the key known for
+//! their sender: the one of its quorum membership, or the one bound to its
+//! claim. Nodes that aren't known yet can only introduce themselves with a
+//! claim binding the key they signed with.
+//!
+//! Nodes handshake with the peers they learn about: both sides sign a fresh
+//! ephemeral key, along with a challenge picked by the initiator, and derive a
+//! session key from them. Sessions belong to the node the handshake
+//! authenticated and are reached at the address known for it, never one
+//! picked by the peer. Events sent to a single peer are encrypted with that
+//! key when `encrypt_direct_messages` is enabled, broadcasts are only signed.
+
+use std::{collections::HashMap, net::SocketAddr};
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use primitives::{NodeId, PublicKey, SecretKey, Signature};
+use secp256k1::{ecdh::SharedSecret, hashes::sha256, Message, SECP256K1};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use storage::vrrbdb::VrrbDbReadHandle;
+use thiserror::Error;
+use vrrb_core::{cache::Cache, claim::Claim};
+
+use super::{peer_scoring::Offense, NetworkEvent};
+
+/// How far the timestamp of an envelope can be from this node's clock, in
+/// milliseconds
+pub const MAX_ENVELOPE_AGE_MS: i64 = 60_000;
+
+const CHALLENGE_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+/// Number of peers whose sessions are remembered at once
+const MAX_TRACKED_PEERS: usize = 10_000;
+const PEER_TTL_MS: u64 = 24 * 60 * 60 * 1_000;
+
+/// Handshakes started by this node that haven't been answered yet
+const MAX_PENDING_HANDSHAKES: usize = 1_024;
+const HANDSHAKE_TTL_MS: u64 = 30_000;
+
+type SessionKey = [u8; 32];
+
+pub type TransportResult<T> = std::result::Result<T, TransportError>;
+
+#[derive(Debug, Error)]
+pub enum TransportError {
+    #[error("invalid envelope signature: {0}")]
+    InvalidSignature(String),
+
+    #[error("envelope from {0} is signed with a key other than its validator key")]
+    KeyMismatch(NodeId),
+
+    #[error("{0} is neither a known quorum member nor has a claim binding its key")]
+    UnknownSender(NodeId),
+
+    #[error("no address is known for {0}")]
+    UnknownAddress(NodeId),
+
+    #[error("envelope from {sender} was sealed {age_ms}ms away from this node's clock")]
+    Stale { sender: NodeId, age_ms: i64 },
+
+    #[error("no session with {0} to decrypt its envelope with")]
+    NoSession(NodeId),
+
+    #[error("failed to decrypt envelope from {0}")]
+    Decryption(NodeId),
+
+    #[error("{0} answered a handshake this node didn't start")]
+    UnknownHandshake(NodeId),
+
+    #[error("malformed envelope: {0}")]
+    Malformed(String),
+}
+
+impl TransportError {
+    /// Offense the sender of a rejected envelope is penalized for. Envelopes
+    /// are only attributed to their sender once they check out against the
+    /// key known for it, anyone could have forged or replayed the others.
+    pub fn offense(&self) -> Option<Offense> {
+        match self {
+            TransportError::Decryption(_) | TransportError::Malformed(_) => {
+                Some(Offense::MalformedEvent)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A network event signed by the node that sent it, encrypted if it's meant
+/// for a single peer that completed a handshake with the sender
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkEnvelope {
+    pub sender_id: NodeId,
+
+    /// Validator public key of the sender
+    pub sender_key: PublicKey,
+
+    /// Milliseconds since the UNIX epoch when the envelope was sealed
+    pub timestamp: i64,
+
+    /// Nonce the payload was encrypted with, unset for plain payloads
+    pub nonce: Option<[u8; NONCE_LEN]>,
+
+    /// Serialized event, signed as is so it doesn't have to serialize the
+    /// same way twice
+    pub payload: Vec<u8>,
+
+    pub signature: Signature,
+}
+
+impl NetworkEnvelope {
+    fn signed_message(&self) -> TransportResult<Message> {
+        signed_message(
+            &self.sender_id,
+            &self.sender_key,
+            self.timestamp,
+            &self.nonce,
+            &self.payload,
+        )
+    }
+}
+
+/// Event carried by an envelope that was opened
+#[derive(Debug, Clone)]
+pub enum Opened {
+    /// Sent by a node signing with the key known for it
+    Authenticated(NetworkEvent),
+
+    /// Claim a node that isn't known yet introduced itself with, signed with
+    /// the key the claim binds
+    Introduction(NetworkEvent),
+}
+
+/// Validator key and address of a quorum member
+#[derive(Debug, Clone)]
+struct KnownPeer {
+    validator_public_key: PublicKey,
+    udp_gossip_addr: SocketAddr,
+}
+
+#[derive(Debug, Clone)]
+struct PendingHandshake {
+    node_id: NodeId,
+    peer: SocketAddr,
+    ephemeral_secret: SecretKey,
+}
+
+/// Seals the events a node sends and opens the ones it receives, keeping
+/// track of the keys known for its peers and the sessions it shares with
+/// them
+#[derive(Debug)]
+pub struct SecureTransport {
+    node_id: NodeId,
+    secret_key: SecretKey,
+    public_key: PublicKey,
+    encrypt_direct_messages: bool,
+    known_peers: HashMap<NodeId, KnownPeer>,
+    bootstrap_id: Option<NodeId>,
+    claims: Option<VrrbDbReadHandle>,
+    pending_handshakes: Cache<Vec<u8>, PendingHandshake>,
+    sessions: Cache<NodeId, SessionKey>,
+    peer_sessions: Cache<SocketAddr, NodeId>,
+}
+
+impl SecureTransport {
+    pub fn new(node_id: NodeId, secret_key: SecretKey, encrypt_direct_messages: bool) -> Self {
+        Self {
+            node_id,
+            public_key: PublicKey::from_secret_key(SECP256K1, &secret_key),
+            secret_key,
+            encrypt_direct_messages,
+            known_peers: HashMap::new(),
+            bootstrap_id: None,
+            claims: None,
+            pending_handshakes: Cache::new(MAX_PENDING_HANDSHAKES, HANDSHAKE_TTL_MS),
+            sessions: Cache::new(MAX_TRACKED_PEERS, PEER_TTL_MS),
+            peer_sessions: Cache::new(MAX_TRACKED_PEERS, PEER_TTL_MS),
+        }
+    }
+
+    /// Looks up the validator keys and addresses of nodes that aren't known
+    /// quorum members in their stored claims
+    pub fn with_claims(mut self, claims: VrrbDbReadHandle) -> Self {
+        self.claims = Some(claims);
+        self
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    /// Records the validator key and address of a quorum member
+    pub fn register_peer(
+        &mut self,
+        node_id: NodeId,
+        validator_public_key: PublicKey,
+        udp_gossip_addr: SocketAddr,
+    ) {
+        self.known_peers.insert(
+            node_id,
+            KnownPeer {
+                validator_public_key,
+                udp_gossip_addr,
+            },
+        );
+    }
+
+    /// Records the bootstrap node, whose quorum assignments introduce the
+    /// other members
+    pub fn register_bootstrap(
+        &mut self,
+        node_id: NodeId,
+        validator_public_key: PublicKey,
+        udp_gossip_addr: SocketAddr,
+    ) {
+        self.register_peer(node_id.clone(), validator_public_key, udp_gossip_addr);
+        self.bootstrap_id = Some(node_id);
+    }
+
+    /// Returns true if `node_id` is the bootstrap node
+    pub fn is_bootstrap(&self, node_id: &NodeId) -> bool {
+        self.bootstrap_id.as_ref() == Some(node_id)
+    }
+
+    /// Validator key known for a node, either as a quorum member or through
+    /// the key bound to its stored claim
+    pub fn validator_key(&self, node_id: &NodeId) -> Option<PublicKey> {
+        match self.known_peers.get(node_id) {
+            Some(peer) => Some(peer.validator_public_key),
+            None => self.stored_claim(node_id)?.validator_key(),
+        }
+    }
+
+    /// Address a node is reached at, either as a quorum member or the one of
+    /// its stored claim
+    pub fn peer_addr(&self, node_id: &NodeId) -> Option<SocketAddr> {
+        match self.known_peers.get(node_id) {
+            Some(peer) => Some(peer.udp_gossip_addr),
+            None => Some(self.stored_claim(node_id)?.ip_address),
+        }
+    }
+
+    /// Returns true if a handshake with `node_id` completed
+    pub fn has_session(&self, node_id: &NodeId) -> bool {
+        self.sessions.contains(node_id)
+    }
+
+    /// Signs an event to be broadcast
+    pub fn seal(&self, event: &NetworkEvent) -> TransportResult<NetworkEnvelope> {
+        self.seal_with(event, None)
+    }
+
+    /// Signs an event sent to the peer listening at `peer`, encrypting it if
+    /// enabled and a handshake with the peer completed
+    pub fn seal_for(
+        &mut self,
+        peer: SocketAddr,
+        event: &NetworkEvent,
+    ) -> TransportResult<NetworkEnvelope> {
+        let session = if self.encrypt_direct_messages {
+            self.session_for(&peer)
+        } else {
+            None
+        };
+
+        self.seal_with(event, session)
+    }
+
+    /// Checks an envelope is signed by the key known for its sender and
+    /// returns the event it carries. Envelopes from unknown nodes are only
+    /// opened if they carry a claim introducing their sender
+    pub fn open(&mut self, envelope: NetworkEnvelope) -> TransportResult<Opened> {
+        self.open_at(envelope, chrono::Utc::now().timestamp_millis())
+    }
+
+    /// Starts a handshake with `node_id`, returning the address known for it
+    /// and the event to send there
+    pub fn initiate_handshake(
+        &mut self,
+        node_id: NodeId,
+    ) -> TransportResult<(SocketAddr, NetworkEvent)> {
+        let peer = self
+            .peer_addr(&node_id)
+            .ok_or_else(|| TransportError::UnknownAddress(node_id.clone()))?;
+
+        let challenge = rand::random::<[u8; CHALLENGE_LEN]>().to_vec();
+        let (ephemeral_secret, ephemeral_key) = SECP256K1.generate_keypair(&mut rand::thread_rng());
+
+        self.pending_handshakes.push(
+            challenge.clone(),
+            PendingHandshake {
+                node_id,
+                peer,
+                ephemeral_secret,
+            },
+        );
+
+        Ok((
+            peer,
+            NetworkEvent::HandshakeInitiated {
+                challenge,
+                ephemeral_key,
+            },
+        ))
+    }
+
+    /// Answers a handshake started by `node_id`, returning the address known
+    /// for it and the event to send there. The event must be sent in a plain
+    /// envelope, the peer can't decrypt anything until it gets it
+    pub fn accept_handshake(
+        &mut self,
+        node_id: NodeId,
+        challenge: Vec<u8>,
+        ephemeral_key: PublicKey,
+    ) -> TransportResult<(SocketAddr, NetworkEvent)> {
+        if challenge.len() != CHALLENGE_LEN {
+            return Err(TransportError::Malformed(format!(
+                "handshake challenge from {node_id} is {} bytes long",
+                challenge.len()
+            )));
+        }
+
+        let peer = self
+            .peer_addr(&node_id)
+            .ok_or_else(|| TransportError::UnknownAddress(node_id.clone()))?;
+
+        let (ephemeral_secret, local_ephemeral_key) =
+            SECP256K1.generate_keypair(&mut rand::thread_rng());
+
+        let session = session_key(&ephemeral_key, &ephemeral_secret, &challenge);
+        self.start_session(node_id, peer, session);
+
+        Ok((
+            peer,
+            NetworkEvent::HandshakeAccepted {
+                challenge,
+                ephemeral_key: local_ephemeral_key,
+            },
+        ))
+    }
+
+    /// Completes a handshake this node started once `node_id` answered it
+    pub fn complete_handshake(
+        &mut self,
+        node_id: NodeId,
+        challenge: &[u8],
+        ephemeral_key: PublicKey,
+    ) -> TransportResult<()> {
+        let challenge = challenge.to_vec();
+        let pending = self
+            .pending_handshakes
+            .get(&challenge)
+            .filter(|pending| pending.node_id == node_id)
+            .cloned()
+            .ok_or_else(|| TransportError::UnknownHandshake(node_id.clone()))?;
+
+        self.pending_handshakes.remove(&challenge);
+
+        let session = session_key(&ephemeral_key, &pending.ephemeral_secret, &challenge);
+        self.start_session(node_id, pending.peer, session);
+
+        Ok(())
+    }
+
+    fn stored_claim(&self, node_id: &NodeId) -> Option<Claim> {
+        self.claims.as_ref()?.get_claim_by_node_id(node_id).ok()
+    }
+
+    fn session_for(&mut self, peer: &SocketAddr) -> Option<SessionKey> {
+        let node_id = self.peer_sessions.get(peer)?.clone();
+
+        self.sessions.get(&node_id).copied()
+    }
+
+    fn start_session(&mut self, node_id: NodeId, peer: SocketAddr, session: SessionKey) {
+        self.peer_sessions.push(peer, node_id.clone());
+        self.sessions.push(node_id, session);
+    }
+
+    fn seal_with(
+        &self,
+        event: &NetworkEvent,
+        session: Option<SessionKey>,
+    ) -> TransportResult<NetworkEnvelope> {
+        let mut payload =
+            serde_json::to_vec(event).map_err(|err| TransportError::Malformed(err.to_string()))?;
+
+        let mut nonce = None;
+        if let Some(session) = session {
+            let bytes = rand::random::<[u8; NONCE_LEN]>();
+            payload = cipher(&session)
+                .encrypt(
+                    Nonce::from_slice(&bytes),
+                    Payload {
+                        msg: &payload,
+                        aad: self.node_id.as_bytes(),
+                    },
+                )
+                .map_err(|err| TransportError::Malformed(err.to_string()))?;
+            nonce = Some(bytes);
+        }
+
+        let timestamp = chrono::Utc::now().timestamp_millis();
+        let message = signed_message(&self.node_id, &self.public_key, timestamp, &nonce, &payload)?;
+
+        Ok(NetworkEnvelope {
+            sender_id: self.node_id.clone(),
+            sender_key: self.public_key,
+            timestamp,
+            nonce,
+            payload,
+            signature: self.secret_key.sign_ecdsa(message),
+        })
+    }
+
+    fn open_at(&mut self, envelope: NetworkEnvelope, now: i64) -> TransportResult<Opened> {
+        envelope
+            .signature
+            .verify(&envelope.signed_message()?, &envelope.sender_key)
+            .map_err(|err| TransportError::InvalidSignature(err.to_string()))?;
+
+        let age_ms = now.saturating_sub(envelope.timestamp);
+        if age_ms.saturating_abs() > MAX_ENVELOPE_AGE_MS {
+            return Err(TransportError::Stale {
+                sender: envelope.sender_id,
+                age_ms,
+            });
+        }
+
+        match self.validator_key(&envelope.sender_id) {
+            Some(key) if key == envelope.sender_key => {}
+            Some(_) => return Err(TransportError::KeyMismatch(envelope.sender_id)),
+            None => return self.open_introduction(envelope),
+        }
+
+        let payload = match envelope.nonce {
+            Some(nonce) => {
+                let session = self
+                    .sessions
+                    .get(&envelope.sender_id)
+                    .copied()
+                    .ok_or_else(|| TransportError::NoSession(envelope.sender_id.clone()))?;
+
+                cipher(&session)
+                    .decrypt(
+                        Nonce::from_slice(&nonce),
+                        Payload {
+                            msg: &envelope.payload,
+                            aad: envelope.sender_id.as_bytes(),
+                        },
+                    )
+                    .map_err(|_| TransportError::Decryption(envelope.sender_id.clone()))?
+            }
+            None => envelope.payload,
+        };
+
+        serde_json::from_slice(&payload)
+            .map(Opened::Authenticated)
+            .map_err(|err| TransportError::Malformed(err.to_string()))
+    }
+
+    /// Opens the envelope of a node no key is known for, which can only carry
+    /// its own claim binding the key the envelope is signed with. Nothing in
+    /// it is attributed to the sender otherwise, anyone can pick a node ID.
+    fn open_introduction(&self, envelope: NetworkEnvelope) -> TransportResult<Opened> {
+        let sender_id = envelope.sender_id;
+        if envelope.nonce.is_some() {
+            return Err(TransportError::UnknownSender(sender_id));
+        }
+
+        match serde_json::from_slice(&envelope.payload) {
+            Ok(NetworkEvent::ClaimCreated { node_id, claim })
+                if node_id == sender_id
+                    && claim.node_id == sender_id
+                    && claim.validator_key() == Some(envelope.sender_key) =>
+            {
+                Ok(Opened::Introduction(NetworkEvent::ClaimCreated {
+                    node_id,
+                    claim,
+                }))
+            }
+            _ => Err(TransportError::UnknownSender(sender_id)),
+        }
+    }
+}
+
+fn signed_message(
+    sender_id: &NodeId,
+    sender_key: &PublicKey,
+    timestamp: i64,
+    nonce: &Option<[u8; NONCE_LEN]>,
+    payload: &[u8],
+) -> TransportResult<Message> {
+    let bytes = bincode::serialize(&(sender_id, sender_key, timestamp, nonce, payload))
+        .map_err(|err| TransportError::Malformed(err.to_string()))?;
+
+    Ok(Message::from_hashed_data::<sha256::Hash>(&bytes))
+}
+
+/// Both sides of a handshake derive the same key from their own ephemeral
+/// secret and the other side's ephemeral key
+fn session_key(
+    peer_ephemeral_key: &PublicKey,
+    ephemeral_secret: &SecretKey,
+    challenge: &[u8],
+) -> SessionKey {
+    let shared_secret = SharedSecret::new(peer_ephemeral_key, ephemeral_secret);
+
+    let mut hasher = Sha256::new();
+    hasher.update(shared_secret.secret_bytes());
+    hasher.update(challenge);
+    hasher.finalize().into()
+}
+
+fn cipher(session: &SessionKey) -> ChaCha20Poly1305 {
+    ChaCha20Poly1305::new(Key::from_slice(session))
+}
+
+#[cfg(test)]
+mod tests {
+    use primitives::Address;
+    use storage::vrrbdb::{StorageBackendType, VrrbDb, VrrbDbConfig};
+    use vrrb_core::keypair::KeyPair;
+
+    use super::*;
+
+    fn transport(node_id: &str, encrypt_direct_messages: bool) -> SecureTransport {
+        let secret_key = SecretKey::new(&mut rand::thread_rng());
+
+        SecureTransport::new(node_id.to_string(), secret_key, encrypt_direct_messages)
+    }
+
+    fn addr(port: u16) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    /// Makes `sender`, listening at `addr`, a quorum member `receiver` knows
+    fn know(receiver: &mut SecureTransport, sender: &SecureTransport, addr: SocketAddr) {
+        receiver.register_peer(sender.node_id.clone(), sender.public_key(), addr);
+    }
+
+    fn ping(node_id: &str) -> NetworkEvent {
+        NetworkEvent::Ping(node_id.to_string())
+    }
+
+    fn is_ping_from(opened: &Opened, node_id: &str) -> bool {
+        matches!(opened, Opened::Authenticated(NetworkEvent::Ping(id)) if id == node_id)
+    }
+
+    /// Claim of `node_id`, listening at port 9100, binding `validator_public_key`
+    fn claim(node_id: &str, validator_public_key: PublicKey) -> Claim {
+        let keypair = KeyPair::random();
+        let public_key = keypair.miner_public_key_owned();
+        let secret_key = keypair.get_miner_secret_key().secret_bytes().to_vec();
+
+        let signature =
+            Claim::signature_for_valid_claim(public_key, addr(9100), secret_key.clone()).unwrap();
+        let mut claim = Claim::new(
+            public_key,
+            Address::new(public_key),
+            addr(9100),
+            signature,
+            node_id.to_string(),
+        )
+        .unwrap();
+        claim
+            .bind_validator_key(validator_public_key, secret_key)
+            .unwrap();
+
+        claim
+    }
+
+    /// Runs a handshake started by `initiator`, listening at `initiator_addr`,
+    /// with `responder`, listening at `responder_addr`. Both must know each
+    /// other
+    fn handshake(
+        initiator: &mut SecureTransport,
+        initiator_addr: SocketAddr,
+        responder: &mut SecureTransport,
+        responder_addr: SocketAddr,
+    ) {
+        let (peer, request) = initiator
+            .initiate_handshake(responder.node_id.clone())
+            .unwrap();
+        assert_eq!(peer, responder_addr);
+        let envelope = initiator.seal(&request).unwrap();
+
+        let Opened::Authenticated(NetworkEvent::HandshakeInitiated {
+            challenge,
+            ephemeral_key,
+        }) = responder.open(envelope).unwrap()
+        else {
+            panic!("expected a handshake request");
+        };
+
+        let (peer, answer) = responder
+            .accept_handshake(initiator.node_id.clone(), challenge, ephemeral_key)
+            .unwrap();
+        assert_eq!(peer, initiator_addr);
+        let envelope = responder.seal(&answer).unwrap();
+
+        let Opened::Authenticated(NetworkEvent::HandshakeAccepted {
+            challenge,
+            ephemeral_key,
+        }) = initiator.open(envelope).unwrap()
+        else {
+            panic!("expected a handshake answer");
+        };
+
+        initiator
+            .complete_handshake(responder.node_id.clone(), &challenge, ephemeral_key)
+            .unwrap();
+    }
+
+    #[test]
+    fn sealed_events_from_known_peers_are_opened() {
+        let sender = transport("node-a", false);
+        let mut receiver = transport("node-b", false);
+        know(&mut receiver, &sender, addr(9001));
+
+        let envelope = sender.seal(&ping("node-a")).unwrap();
+
+        assert!(is_ping_from(&receiver.open(envelope).unwrap(), "node-a"));
+    }
+
+    #[test]
+    fn envelopes_from_unknown_nodes_are_rejected() {
+        let sender = transport("node-a", false);
+        let mut receiver = transport("node-b", false);
+
+        let envelope = sender.seal(&ping("node-a")).unwrap();
+
+        assert!(matches!(
+            receiver.open(envelope),
+            Err(TransportError::UnknownSender(_))
+        ));
+    }
+
+    #[test]
+    fn tampered_envelopes_are_rejected() {
+        let sender = transport("node-a", false);
+        let mut receiver = transport("node-b", false);
+        know(&mut receiver, &sender, addr(9001));
+
+        let mut envelope = sender.seal(&ping("node-a")).unwrap();
+        envelope.payload = serde_json::to_vec(&ping("node-c")).unwrap();
+
+        assert!(matches!(
+            receiver.open(envelope),
+            Err(TransportError::InvalidSignature(_))
+        ));
+
+        let mut envelope = sender.seal(&ping("node-a")).unwrap();
+        envelope.sender_id = "node-c".to_string();
+
+        assert!(matches!(
+            receiver.open(envelope),
+            Err(TransportError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn envelopes_signed_with_another_key_than_the_known_one_are_rejected() {
+        let sender = transport("node-a", false);
+        let impostor = transport("node-a", false);
+        let mut receiver = transport("node-b", false);
+        know(&mut receiver, &sender, addr(9001));
+
+        assert!(matches!(
+            receiver.open(impostor.seal(&ping("node-a")).unwrap()),
+            Err(TransportError::KeyMismatch(_))
+        ));
+        assert!(receiver.open(sender.seal(&ping("node-a")).unwrap()).is_ok());
+    }
+
+    #[test]
+    fn nodes_are_recognized_by_the_validator_key_bound_to_their_stored_claim() {
+        let sender = transport("node-a", false);
+        let impostor = transport("node-a", false);
+
+        let mut config = VrrbDbConfig::default();
+        config.backend = StorageBackendType::InMemory;
+        let mut db = VrrbDb::new(config).unwrap();
+        db.insert_claim(claim("node-a", sender.public_key()))
+            .unwrap();
+
+        let mut receiver = transport("node-b", false).with_claims(db.read_handle());
+
+        assert!(is_ping_from(
+            &receiver
+                .open(sender.seal(&ping("node-a")).unwrap())
+                .unwrap(),
+            "node-a"
+        ));
+        assert!(matches!(
+            receiver.open(impostor.seal(&ping("node-a")).unwrap()),
+            Err(TransportError::KeyMismatch(_))
+        ));
+        assert_eq!(receiver.peer_addr(&"node-a".to_string()), Some(addr(9100)));
+    }
+
+    #[test]
+    fn unknown_nodes_can_only_introduce_themselves_with_a_claim_binding_their_key() {
+        let sender = transport("node-a", false);
+        let mut receiver = transport("node-b", false);
+
+        let introduction = NetworkEvent::ClaimCreated {
+            node_id: "node-a".to_string(),
+            claim: claim("node-a", sender.public_key()),
+        };
+        assert!(matches!(
+            receiver.open(sender.seal(&introduction).unwrap()),
+            Ok(Opened::Introduction(NetworkEvent::ClaimCreated { .. }))
+        ));
+
+        // NOTE: a claim binding someone else's key doesn't introduce the sender
+        let unbound = NetworkEvent::ClaimCreated {
+            node_id: "node-a".to_string(),
+            claim: claim("node-a", transport("node-c", false).public_key()),
+        };
+        assert!(matches!(
+            receiver.open(sender.seal(&unbound).unwrap()),
+            Err(TransportError::UnknownSender(_))
+        ));
+
+        let other_node = NetworkEvent::ClaimCreated {
+            node_id: "node-c".to_string(),
+            claim: claim("node-c", sender.public_key()),
+        };
+        assert!(matches!(
+            receiver.open(sender.seal(&other_node).unwrap()),
+            Err(TransportError::UnknownSender(_))
+        ));
+    }
+
+    #[test]
+    fn stale_envelopes_are_rejected() {
+        let sender = transport("node-a", false);
+        let mut receiver = transport("node-b", false);
+        know(&mut receiver, &sender, addr(9001));
+
+        let envelope = sender.seal(&ping("node-a")).unwrap();
+        let now = envelope.timestamp + MAX_ENVELOPE_AGE_MS + 1;
+
+        assert!(matches!(
+            receiver.open_at(envelope, now),
+            Err(TransportError::Stale { .. })
+        ));
+    }
+
+    #[test]
+    fn direct_messages_are_encrypted_once_a_handshake_completes() {
+        let (addr_a, addr_b) = (addr(9001), addr(9002));
+        let mut node_a = transport("node-a", true);
+        let mut node_b = transport("node-b", true);
+        know(&mut node_a, &node_b, addr_b);
+        know(&mut node_b, &node_a, addr_a);
+
+        let envelope = node_a.seal_for(addr_b, &ping("node-a")).unwrap();
+        assert!(envelope.nonce.is_none());
+
+        handshake(&mut node_a, addr_a, &mut node_b, addr_b);
+        assert!(node_a.has_session(&"node-b".to_string()));
+        assert!(node_b.has_session(&"node-a".to_string()));
+
+        let envelope = node_a.seal_for(addr_b, &ping("node-a")).unwrap();
+        assert!(envelope.nonce.is_some());
+        assert!(serde_json::from_slice::<NetworkEvent>(&envelope.payload).is_err());
+        assert!(is_ping_from(&node_b.open(envelope).unwrap(), "node-a"));
+
+        let envelope = node_b.seal_for(addr_a, &ping("node-b")).unwrap();
+        assert!(envelope.nonce.is_some());
+        assert!(is_ping_from(&node_a.open(envelope).unwrap(), "node-b"));
+
+        let mut outsider = transport("node-c", true);
+        know(&mut outsider, &node_a, addr_a);
+        let envelope = node_a.seal_for(addr_b, &ping("node-a")).unwrap();
+        assert!(matches!(
+            outsider.open(envelope),
+            Err(TransportError::NoSession(_))
+        ));
+    }
+
+    #[test]
+    fn direct_messages_stay_plain_when_encryption_is_disabled() {
+        let (addr_a, addr_b) = (addr(9001), addr(9002));
+        let mut node_a = transport("node-a", false);
+        let mut node_b = transport("node-b", false);
+        know(&mut node_a, &node_b, addr_b);
+        know(&mut node_b, &node_a, addr_a);
+
+        handshake(&mut node_a, addr_a, &mut node_b, addr_b);
+
+        let envelope = node_a.seal_for(addr_b, &ping("node-a")).unwrap();
+        assert!(envelope.nonce.is_none());
+        assert!(is_ping_from(&node_b.open(envelope).unwrap(), "node-a"));
+    }
+
+    #[test]
+    fn handshakes_are_bound_to_the_node_they_were_started_with() {
+        let mut node_a = transport("node-a", true);
+        let node_b = transport("node-b", true);
+        know(&mut node_a, &node_b, addr(9002));
+
+        let (_, request) = node_a.initiate_handshake("node-b".to_string()).unwrap();
+        let NetworkEvent::HandshakeInitiated { challenge, .. } = request else {
+            panic!("expected a handshake request");
+        };
+        let (_, ephemeral_key) = SECP256K1.generate_keypair(&mut rand::thread_rng());
+
+        assert!(matches!(
+            node_a.complete_handshake("node-c".to_string(), &challenge, ephemeral_key),
+            Err(TransportError::UnknownHandshake(_))
+        ));
+        assert!(!node_a.has_session(&"node-c".to_string()));
+    }
+
+    #[test]
+    fn handshakes_with_nodes_without_a_known_address_are_refused() {
+        let mut node_a = transport("node-a", true);
+        let (_, ephemeral_key) = SECP256K1.generate_keypair(&mut rand::thread_rng());
+
+        assert!(matches!(
+            node_a.initiate_handshake("node-b".to_string()),
+            Err(TransportError::UnknownAddress(_))
+        ));
+        assert!(matches!(
+            node_a.accept_handshake("node-b".to_string(), vec![0; CHALLENGE_LEN], ephemeral_key),
+            Err(TransportError::UnknownAddress(_))
+        ));
+        assert!(!node_a.has_session(&"node-b".to_string()));
+    }
+
+    #[test]
+    fn answers_to_unknown_handshakes_are_rejected() {
+        let mut node_a = transport("node-a", true);
+        let (_, ephemeral_key) = SECP256K1.generate_keypair(&mut rand::thread_rng());
+
+        assert!(matches!(
+            node_a.complete_handshake("node-b".to_string(), &[0; CHALLENGE_LEN], ephemeral_key),
+            Err(TransportError::UnknownHandshake(_))
+        ));
+    }
+}
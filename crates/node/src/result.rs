@@ -61,6 +61,12 @@ pub enum NodeError {
     #[error("{0}")]
     Core(#[from] vrrb_core::Error),
 
+    #[error("invalid block: {0}")]
+    Dag(#[from] crate::state_manager::DagError),
+
+    #[error("network transport error: {0}")]
+    Transport(#[from] crate::network::transport::TransportError),
+
     #[error("{0}")]
     Other(String),
 }
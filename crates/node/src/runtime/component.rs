@@ -1,9 +1,11 @@
 use crate::{node_runtime::NodeRuntime, NodeError, RuntimeComponent, RuntimeComponentHandle};
+use block::Block;
+use bulldag::graph::BullDag;
 use events::{EventPublisher, EventSubscriber};
 use mempool::MempoolReadHandleFactory;
 use metric_exporter::metric_factory::PrometheusFactory;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use storage::vrrbdb::VrrbDbReadHandle;
 use theater::{Actor, ActorImpl};
@@ -22,6 +24,7 @@ pub struct NodeRuntimeComponentResolvedData {
     pub node_config: NodeConfig,
     pub state_read_handle: VrrbDbReadHandle,
     pub mempool_read_handle_factory: MempoolReadHandleFactory,
+    pub dag: Arc<RwLock<BullDag<Block, String>>>,
 }
 
 #[async_trait::async_trait]
@@ -45,6 +48,7 @@ impl RuntimeComponent<NodeRuntimeComponentConfig, NodeRuntimeComponentResolvedDa
 
         let state_read_handle = node_runtime.state_read_handle();
         let mempool_read_handle_factory = node_runtime.mempool_read_handle_factory();
+        let dag = node_runtime.state_driver.dag();
         let unvoted_pending_transactions = factory
             .build_int_gauge(
                 "unvoted_pending_transactions",
@@ -76,6 +80,7 @@ impl RuntimeComponent<NodeRuntimeComponentConfig, NodeRuntimeComponentResolvedDa
             node_config: args.config,
             state_read_handle,
             mempool_read_handle_factory,
+            dag,
         };
 
         let component_handle = RuntimeComponentHandle::new(
@@ -1,15 +1,18 @@
 use block::{
     header::BlockHeader, Block, Certificate, ConvergenceBlock, GenesisBlock, ProposalBlock,
 };
-use events::{AccountBytes, AssignedQuorumMembership, Event, PeerData, Vote};
+use events::{
+    AccountBytes, AssignedQuorumMembership, BlockRequest, ChainTip, Event, PeerData, Vote,
+};
 use miner::conflict_resolver::Resolver;
 use primitives::{Address, NodeId, PublicKey, QuorumId, QuorumKind, Signature};
 use signer::engine::{QuorumData, QuorumMembers as InaugaratedMembers};
 use std::collections::HashMap;
 use storage::vrrbdb::ApplyBlockResult;
-use vrrb_core::transactions::TransactionDigest;
+use vrrb_core::{claim::Claim, transactions::TransactionDigest};
 
 use crate::{
+    network::block_sync,
     node_runtime::NodeRuntime,
     result::{NodeError, Result},
 };
@@ -33,19 +36,12 @@ impl NodeRuntime {
         Ok(apply_result)
     }
 
-    // TODO:
-    // check if from valid harvester
-    // whoever sent the proposal block must be a valid harvester
-    // sig_engine.quorum_members().is_harvester()
     fn handle_proposal_block_received(&mut self, block: ProposalBlock) -> Result<ApplyBlockResult> {
-        if let Err(e) = self
-            .state_driver
+        self.state_driver
             .dag
-            .append_proposal(&block, self.consensus_driver.sig_engine.clone())
-        {
-            let err_note = format!("Failed to append proposal block to DAG: {e:?}");
-            return Err(NodeError::Other(err_note));
-        }
+            .append_proposal(&block, self.consensus_driver.sig_engine.clone())?;
+
+        self.state_driver.mark_txns_included(&block);
 
         let apply_result = self.state_driver.apply_block(Block::Proposal { block })?;
 
@@ -62,12 +58,7 @@ impl NodeRuntime {
         self.consensus_driver.is_harvester()?;
         let apply_result = self
             .state_driver
-            .append_convergence(&block)
-            .map_err(|err| {
-                NodeError::Other(format!(
-                    "Could not append convergence block to DAG: {err:?}"
-                ))
-            })?;
+            .append_convergence(&block, &self.consensus_driver.sig_engine)?;
 
         Ok(apply_result)
     }
@@ -130,6 +121,9 @@ impl NodeRuntime {
                 inauguration: inauguration.clone(),
                 root_hash,
                 block_hash: block_hash.clone(),
+                // NOTE: harvesters don't hold key shares from a DKG yet, so there are
+                // no signature shares to combine
+                threshold_signature: None,
             };
             //            if let Some(quorum_members) = inauguration {
             //                self.consensus_driver.sig_engine.set_quorum_members(
@@ -201,31 +195,93 @@ impl NodeRuntime {
     }
 
     pub fn verify_certificate(&mut self, certificate: &Certificate) -> Result<()> {
-        let cert_sigs = certificate.signatures.clone();
-        if cert_sigs.len()
-            < self
-                .consensus_driver
-                .sig_engine
-                .quorum_members()
-                .get_harvester_threshold()
-        {
-            return Err(NodeError::Other("threshold not reached".to_string()));
-        }
-        self.consensus_driver
-            .sig_engine
-            .verify_batch(&certificate.signatures, &certificate.block_hash)
-            .map_err(|err| NodeError::Other(err.to_string()))?;
+        self.state_driver
+            .dag
+            .verify_certificate(certificate, &self.consensus_driver.sig_engine)?;
 
         Ok(())
     }
 
+    /// Returns the latest block this node confirmed, as reported to peers
+    /// syncing with it
+    pub fn chain_tip(&self) -> Option<ChainTip> {
+        self.state_driver
+            .dag
+            .last_confirmed_block()
+            .as_ref()
+            .and_then(block_sync::chain_tip)
+    }
+
+    pub fn handle_blocks_request_received(&self, request: &BlockRequest) -> Result<Vec<Block>> {
+        block_sync::blocks_for_request(self.state_driver.dag.block_store(), request)
+    }
+
+    /// Appends the blocks a peer sent while this node catches up with the
+    /// network, in causal order. Blocks already known are skipped. Returns the
+    /// number of blocks that were appended.
+    pub fn handle_sync_blocks_received(&mut self, blocks: Vec<Block>) -> Result<usize> {
+        let mut appended = 0;
+
+        for block in block_sync::causal_order(blocks) {
+            if self.state_driver.dag.contains_block(&block.hash()) {
+                continue;
+            }
+
+            match &block {
+                Block::Genesis { block } => {
+                    self.verify_genesis_block_origin(block.clone())?;
+                    self.verify_synced_certificate(&block.hash, block.certificate.as_ref())?;
+                }
+                // NOTE: the DAG verifies certificates, but would keep an uncertified
+                // block pending instead of rejecting it
+                Block::Convergence { block } if block.certificate.is_none() => {
+                    return Err(NodeError::Other(format!(
+                        "convergence block {} is not certified",
+                        block.hash
+                    )));
+                }
+                Block::Convergence { .. } | Block::Proposal { .. } => {}
+            }
+
+            self.state_driver
+                .append_synced_block(block, self.consensus_driver.sig_engine.clone())?;
+
+            appended += 1;
+        }
+
+        Ok(appended)
+    }
+
+    /// Verifies the certificate of a block fetched while syncing, which must
+    /// be present and certify that very block
+    fn verify_synced_certificate(
+        &mut self,
+        block_hash: &str,
+        certificate: Option<&Certificate>,
+    ) -> Result<()> {
+        let certificate = certificate
+            .ok_or_else(|| NodeError::Other(format!("block {block_hash} is not certified")))?;
+
+        if certificate.block_hash != block_hash {
+            return Err(NodeError::Other(format!(
+                "certificate for block {} does not certify block {block_hash}",
+                certificate.block_hash
+            )));
+        }
+
+        self.verify_certificate(certificate)
+    }
+
     pub fn append_certificate_to_convergence_block(
         &mut self,
         certificate: &Certificate,
     ) -> Result<Option<ConvergenceBlock>> {
-        self.state_driver
-            .append_certificate_to_convergence_block(certificate)
-            .map_err(|err| NodeError::Other(format!("{:?}", err)))
+        let block = self.state_driver.append_certificate_to_convergence_block(
+            certificate,
+            &self.consensus_driver.sig_engine,
+        )?;
+
+        Ok(block)
     }
 
     pub fn append_certificate_to_genesis_block(
@@ -234,7 +290,11 @@ impl NodeRuntime {
         certificate: &Certificate,
     ) -> Result<Option<GenesisBlock>> {
         self.state_driver
-            .append_certificate_to_genesis_block(block_hash, certificate)
+            .append_certificate_to_genesis_block(
+                block_hash,
+                certificate,
+                &self.consensus_driver.sig_engine,
+            )
             .map_err(|err| NodeError::Other(format!("{:?}", err)))
     }
 
@@ -258,6 +318,30 @@ impl NodeRuntime {
         )
     }
 
+    /// Queues the abandonment of a stored claim for inclusion in the next
+    /// proposal block. The claim is only removed from state once a block
+    /// carrying the abandonment is confirmed.
+    pub fn handle_claim_abandoned(&mut self, claim: Claim, signature: String) -> Result<()> {
+        let stored = self.state_driver.get_claim(&claim.hash)?;
+
+        if stored.public_key != claim.public_key {
+            return Err(NodeError::Other(format!(
+                "claim {} is held by a different key",
+                claim.hash
+            )));
+        }
+
+        stored
+            .verify_abandonment(signature.clone())
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        self.consensus_driver
+            .abandoned_claims
+            .insert(stored.hash, signature);
+
+        Ok(())
+    }
+
     pub async fn handle_vote_received(&mut self, vote: Vote) -> Result<()> {
         self.consensus_driver.handle_vote_received(vote).await
     }
@@ -15,7 +15,7 @@ mod tests {
         create_node_runtime_network, create_quorum_assigned_node_runtime_network,
         create_sender_receiver_addresses, create_txn_from_accounts,
         create_txn_from_accounts_invalid_signature, create_txn_from_accounts_invalid_timestamp,
-        setup_network, setup_whitelisted_nodes,
+        setup_network, setup_whitelisted_nodes, INITIAL_ACCOUNT_CREDITS,
     };
     use crate::NodeError;
     use block::{Block, GenesisReceiver};
@@ -556,7 +556,7 @@ mod tests {
 
         let (_, sender_public_key) = generate_account_keypair();
         let mut sender_account = Account::new(sender_public_key.into());
-        let update_field = AccountField::Credits(100000);
+        let update_field = AccountField::Credits(INITIAL_ACCOUNT_CREDITS);
         let _ = sender_account.update_field(update_field);
         let sender_address = node_0.create_account(sender_public_key).unwrap();
 
@@ -804,7 +804,7 @@ mod tests {
 
         let (_, sender_public_key) = generate_account_keypair();
         let mut sender_account = Account::new(sender_public_key.into());
-        let update_field = AccountField::Credits(100000);
+        let update_field = AccountField::Credits(INITIAL_ACCOUNT_CREDITS);
         let _ = sender_account.update_field(update_field);
         let sender_address = node_0.create_account(sender_public_key).unwrap();
 
@@ -925,7 +925,7 @@ mod tests {
 
         let (_, sender_public_key) = generate_account_keypair();
         let mut sender_account = Account::new(sender_public_key.into());
-        let update_field = AccountField::Credits(100000);
+        let update_field = AccountField::Credits(INITIAL_ACCOUNT_CREDITS);
         let _ = sender_account.update_field(update_field);
         let sender_address = node_0.create_account(sender_public_key).unwrap();
 
@@ -1046,7 +1046,7 @@ mod tests {
 
         let (_, sender_public_key) = generate_account_keypair();
         let mut sender_account = Account::new(sender_public_key.into());
-        let update_field = AccountField::Credits(100000);
+        let update_field = AccountField::Credits(INITIAL_ACCOUNT_CREDITS);
         let _ = sender_account.update_field(update_field);
         let sender_address = node_0.create_account(sender_public_key).unwrap();
 
@@ -1118,7 +1118,7 @@ mod tests {
         let ((mut sender_account, sender_address), receiver_address) =
             create_sender_receiver_addresses();
 
-        let update_field = AccountField::Credits(100000);
+        let update_field = AccountField::Credits(INITIAL_ACCOUNT_CREDITS);
         let _ = sender_account.update_field(update_field);
         let account_bytes = bincode::serialize(&sender_account.clone()).unwrap();
 
@@ -24,7 +24,10 @@ use std::{
     collections::HashMap,
     sync::{Arc, RwLock},
 };
-use storage::vrrbdb::{StateStoreReadHandleFactory, VrrbDbConfig, VrrbDbReadHandle};
+use storage::vrrbdb::{
+    MempoolJournalStore, PruningMode, StateStoreReadHandleFactory, StorageBackendType,
+    VrrbDbConfig, VrrbDbReadHandle, DEFAULT_PRUNING_BATCH_SIZE,
+};
 use theater::{ActorId, ActorState};
 use tokio::task::JoinHandle;
 use utils::payload::digest_data_to_bytes;
@@ -32,7 +35,7 @@ use vrrb_config::{NodeConfig, QuorumMembershipConfig};
 use vrrb_core::{
     account::{Account, UpdateArgs},
     claim::Claim,
-    transactions::{TransactionDigest, TransactionKind},
+    transactions::{order_by_fee_and_nonce, TransactionDigest, TransactionKind},
 };
 
 pub const PULL_TXN_BATCH_SIZE: usize = 100;
@@ -73,7 +76,7 @@ impl NodeRuntime {
                 .to_vec(),
         )?;
 
-        let claim = Claim::new(
+        let mut claim = Claim::new(
             miner_public_key,
             Address::new(miner_public_key),
             config.public_ip_address,
@@ -82,22 +85,70 @@ impl NodeRuntime {
         )
         .map_err(NodeError::from)?;
 
+        // NOTE: peers recognize the envelopes this node signs by the validator
+        // key its claim binds
+        claim
+            .bind_validator_key(
+                config.keypair.validator_public_key_owned(),
+                config
+                    .keypair
+                    .get_miner_secret_key()
+                    .secret_bytes()
+                    .to_vec(),
+            )
+            .map_err(NodeError::from)?;
+
         let mut vrrbdb_config = VrrbDbConfig::default();
 
         if config.db_path() != &vrrbdb_config.path {
             vrrbdb_config.with_path(config.db_path().to_path_buf());
         }
 
-        let database = storage::vrrbdb::VrrbDb::new(vrrbdb_config);
-        let mempool = LeftRightMempool::new();
+        if config.in_memory_storage {
+            vrrbdb_config.backend = StorageBackendType::InMemory;
+        }
+
+        if let Some(window) = config.state_history_window {
+            vrrbdb_config.history_window = window;
+        }
+
+        if config.archive_mode {
+            vrrbdb_config.pruning = PruningMode::Archive;
+        } else if let Some(window) = config.state_pruning_window {
+            vrrbdb_config.pruning = PruningMode::Prune {
+                window,
+                batch_size: DEFAULT_PRUNING_BATCH_SIZE,
+            };
+        }
+
+        let database = storage::vrrbdb::VrrbDb::new(vrrbdb_config)?;
+        let mempool = LeftRightMempool::with_config(config.mempool.clone());
+
+        let mempool_journal = if config.enable_mempool_journal {
+            let journal_path = config.db_path().join("mempool");
+            Some(MempoolJournalStore::new(journal_path).map_err(NodeError::from)?)
+        } else {
+            None
+        };
 
-        let state_driver = StateManager::new(StateManagerConfig {
+        let mut state_driver = StateManager::new(StateManagerConfig {
             database: database.clone(),
             mempool,
             dag: dag.clone(),
             claim: claim.clone(),
+            mempool_journal,
         });
 
+        let restored_txns = state_driver.restore_mempool()?;
+        if restored_txns > 0 {
+            telemetry::info!("restored {restored_txns} pending transactions from mempool journal");
+        }
+
+        let restored_blocks = state_driver.restore_dag()?;
+        if restored_blocks > 0 {
+            telemetry::info!("restored {restored_blocks} blocks from the database");
+        }
+
         let (_, miner_secret_key) = config.keypair.get_secret_keys();
         let (_, miner_public_key) = config.keypair.get_public_keys();
 
@@ -123,6 +174,7 @@ impl NodeRuntime {
                 keypair: config.keypair.clone(),
                 node_config: config.clone(),
                 validator_public_key: config.keypair.validator_public_key_owned(),
+                txn_status_tracker: state_driver.txn_status_tracker(),
             },
             state_driver.mempool_read_handle_factory(),
             database.state_store_factory(),
@@ -395,10 +447,13 @@ impl NodeRuntime {
         sig_engine: SignerEngine,
     ) -> Result<ProposalBlock> {
         self.consensus_driver.is_harvester()?;
-        let txns = self
-            .consensus_driver
-            .quorum_certified_txns
-            .iter()
+
+        // NOTE: certified transactions paying the highest fees are included first,
+        // without reordering the transactions of a sender
+        let mut txns: Vec<_> = self.consensus_driver.quorum_certified_txns.iter().collect();
+        txns.sort_by(|(a_digest, _), (b_digest, _)| a_digest.cmp(b_digest));
+        let txns = order_by_fee_and_nonce(txns, |(_, (txn, _))| txn)
+            .into_iter()
             .take(PULL_TXN_BATCH_SIZE);
 
         // NOTE: Read updated claims
@@ -426,13 +481,28 @@ impl NodeRuntime {
         self.consensus_driver
             .certified_pending_transactions
             .set(self.consensus_driver.quorum_certified_txns.len() as i64);
+        // NOTE: abandonments already applied by a confirmed block are dropped
+        let state_driver = &self.state_driver;
+        self.consensus_driver
+            .abandoned_claims
+            .retain(|hash, _| state_driver.get_claim(hash).is_ok());
+        let abandoned_claims = self.consensus_driver.abandoned_claims.clone();
+
         Ok(ProposalBlock::build(
-            ref_hash, round, epoch, txns_list, claim_list, from, sig_engine,
+            ref_hash,
+            round,
+            epoch,
+            txns_list,
+            claim_list,
+            abandoned_claims,
+            from,
+            sig_engine,
         ))
     }
 
     pub fn mine_convergence_block(&mut self) -> Result<ConvergenceBlock> {
         self.has_required_node_type(NodeType::Miner, "mine convergence block")?;
+        self.mining_driver.state_root_hash = self.state_driver.state_root_hash()?;
         self.mining_driver
             .mine_convergence_block()
             .ok_or(NodeError::Other(
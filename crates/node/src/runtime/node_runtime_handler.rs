@@ -1,4 +1,4 @@
-use crate::node_runtime::NodeRuntime;
+use crate::{network::block_sync, node_runtime::NodeRuntime};
 use async_trait::async_trait;
 use block::{Block, Certificate, GenesisReceiver};
 use events::{AssignedQuorumMembership, Event, EventMessage};
@@ -185,7 +185,7 @@ impl Handler<EventMessage> for NodeRuntime {
                 // This can occur as a result of block application
             }
             Event::UpdateState(block) => {
-                if let Err(err) = self.state_driver.update_state(block.hash.clone()) {
+                if let Err(err) = self.state_driver.apply_confirmed_block(&block) {
                     telemetry::error!("error updating state: {}", err);
                 } else {
                     self.events_tx
@@ -229,6 +229,12 @@ impl Handler<EventMessage> for NodeRuntime {
                 // Claim should be added to pending claims
                 // Event to validate claim should be created
             }
+            Event::ClaimAbandoned { claim, signature } => {
+                let claim_hash = claim.hash;
+                if let Err(err) = self.handle_claim_abandoned(claim, signature) {
+                    telemetry::error!("error abandoning claim {claim_hash}: {err}");
+                }
+            }
             Event::BlockCreated(mut block) => {
                 let node_id = self.config_ref().id.clone();
                 telemetry::info!(
@@ -256,6 +262,75 @@ impl Handler<EventMessage> for NodeRuntime {
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             }
+            Event::ChainTipRequested(peer) => {
+                let tip = self.chain_tip();
+                let em = EventMessage::new(
+                    Some(NETWORK_TOPIC_STR.into()),
+                    Event::ChainTipResolved { peer, tip },
+                );
+
+                self.events_tx
+                    .send(em)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            }
+            Event::PeerChainTipReceived { peer, tip } => {
+                let local_tip = self.chain_tip();
+
+                if let Some(request) = block_sync::next_request(local_tip.as_ref(), tip.as_ref()) {
+                    info!("Node is behind {peer}, requesting blocks: {request:?}");
+
+                    let em = EventMessage::new(
+                        Some(NETWORK_TOPIC_STR.into()),
+                        Event::BlocksRequested { peer, request },
+                    );
+
+                    self.events_tx
+                        .send(em)
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
+            }
+            Event::BlocksRequestReceived { peer, request } => {
+                let blocks = self
+                    .handle_blocks_request_received(&request)
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                let em = EventMessage::new(
+                    Some(NETWORK_TOPIC_STR.into()),
+                    Event::BlocksResolved { peer, blocks },
+                );
+
+                self.events_tx
+                    .send(em)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            }
+            Event::SyncBlocksReceived { peer, blocks } => {
+                // NOTE: blocks come from an untrusted peer, so a bad batch is
+                // dropped instead of stopping the runtime
+                match self.handle_sync_blocks_received(blocks) {
+                    Ok(0) => info!("Node is in sync with {peer}"),
+                    Ok(appended) => {
+                        info!("Appended {appended} blocks from {peer}");
+
+                        // NOTE: ask for the tip again, the peer may have more
+                        // blocks than fit in a single response
+                        let em = EventMessage::new(
+                            Some(NETWORK_TOPIC_STR.into()),
+                            Event::SyncRequested(peer),
+                        );
+
+                        self.events_tx
+                            .send(em)
+                            .await
+                            .map_err(|err| TheaterError::Other(err.to_string()))?;
+                    }
+                    Err(err) => {
+                        telemetry::error!("Failed to append blocks from {peer}: {err}");
+                    }
+                }
+            }
             Event::HarvesterSignatureReceived(block_hash, node_id, sig) => {
                 self.handle_harvester_signature_received(block_hash, node_id, sig)
                     .await
@@ -272,6 +347,11 @@ impl Handler<EventMessage> for NodeRuntime {
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             }
+            Event::HarvesterPublicKeyReceived(public_key_set) => {
+                self.state_driver
+                    .dag
+                    .set_harvester_public_key_set(public_key_set);
+            }
             Event::BlockConfirmed(cert_bytes) => {
                 let certificate: Certificate = bincode::deserialize(&cert_bytes)
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
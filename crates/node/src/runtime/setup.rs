@@ -7,9 +7,10 @@ use std::sync::Arc;
 use storage::vrrbdb::VrrbDbReadHandle;
 use telemetry::info;
 use vrrb_config::NodeConfig;
+use vrrb_rpc::http::HttpApiState;
 
 use crate::{
-    api::setup_rpc_api_server,
+    api::{setup_grpc_api_server, setup_http_api_server, setup_rpc_api_server},
     component::NodeRuntimeComponentConfig,
     indexer_module::setup_indexer_module,
     network::{NetworkModule, NetworkModuleComponentConfig},
@@ -36,6 +37,9 @@ pub async fn setup_runtime_components(
     let runtime_events_rx = router.subscribe(Some(RUNTIME_TOPIC_STR.into()))?;
     let network_events_rx = router.subscribe(Some(NETWORK_TOPIC_STR.into()))?;
     let jsonrpc_events_rx = router.subscribe(Some(JSON_RPC_API_TOPIC_STR.into()))?;
+    let jsonrpc_subscription_events_rx = router.subscribe(None)?;
+    let grpc_events_rx = router.subscribe(None)?;
+    let http_api_events_rx = router.subscribe(None)?;
     let indexer_events_rx = router.subscribe(None)?;
 
     let mut runtime_manager = RuntimeComponentManager::new();
@@ -59,6 +63,7 @@ pub async fn setup_runtime_components(
 
     let mempool_read_handle_factory = handle_data.mempool_read_handle_factory;
     let state_read_handle = handle_data.state_read_handle;
+    let dag = handle_data.dag;
 
     runtime_manager.register_component(
         node_runtime_component_handle.label(),
@@ -98,7 +103,9 @@ pub async fn setup_runtime_components(
         events_tx.clone(),
         state_read_handle.clone(),
         mempool_read_handle_factory.clone(),
+        dag,
         jsonrpc_events_rx,
+        jsonrpc_subscription_events_rx,
     )
     .await?;
 
@@ -108,6 +115,39 @@ pub async fn setup_runtime_components(
 
     runtime_manager.register_component("API".to_string(), jsonrpc_server_handle);
 
+    if let Some((grpc_server_handle, resolved_grpc_server_addr)) = setup_grpc_api_server(
+        &config,
+        events_tx.clone(),
+        state_read_handle.clone(),
+        mempool_read_handle_factory.clone(),
+        grpc_events_rx,
+    )
+    .await?
+    {
+        config.grpc_server_address = Some(resolved_grpc_server_addr);
+
+        info!("gRPC server address: {}", resolved_grpc_server_addr);
+
+        runtime_manager.register_component("gRPC API".to_string(), grpc_server_handle);
+    }
+
+    let (http_api_server_handle, resolved_http_api_addr) = setup_http_api_server(
+        &config,
+        HttpApiState {
+            vrrbdb_read_handle: state_read_handle.clone(),
+            mempool_read_handle_factory: mempool_read_handle_factory.clone(),
+            events_tx: events_tx.clone(),
+        },
+        http_api_events_rx,
+    )
+    .await?;
+
+    config.http_api_address = resolved_http_api_addr;
+
+    info!("HTTP API server address: {}", config.http_api_address);
+
+    runtime_manager.register_component("HTTP API".to_string(), http_api_server_handle);
+
     if config.enable_block_indexing {
         let _handle = setup_indexer_module(
             &config,
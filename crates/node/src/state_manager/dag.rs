@@ -1,28 +1,82 @@
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     sync::{Arc, RwLock, RwLockReadGuard},
 };
 
 use block::{
-    header::BlockHeader,
-    valid::{BlockValidationData, Valid},
-    Block, Certificate, ConvergenceBlock, GenesisBlock, InnerBlock, ProposalBlock,
+    header::BlockHeader, valid::Valid, Block, Certificate, ConvergenceBlock, GenesisBlock,
+    InnerBlock, ProposalBlock,
 };
 use bulldag::{
     graph::{BullDag, GraphError},
     vertex::Vertex,
 };
+use hbbft::crypto::{PublicKeySet, Signature as ThresholdSignature, SIG_SIZE};
 use indexmap::IndexMap;
-use primitives::{HarvesterQuorumThreshold, NodeId, PublicKey, Signature, SignatureType};
-use signer::engine::{QuorumMembers, SignerEngine};
-use signer::types::{SignerError, SignerResult};
+use primitives::{NodeId, QuorumKind, Signature};
+use signer::engine::SignerEngine;
+use storage::vrrbdb::BlockStore;
+use thiserror::Error;
 use vrrb_core::claim::Claim;
 
-use crate::{NodeError, Result};
+use crate::{network::block_sync::causal_order, NodeError, Result};
 
 pub type Edge = (Vertex<Block, String>, Vertex<Block, String>);
 pub type Edges = Vec<Edge>;
 pub type GraphResult<T> = std::result::Result<T, GraphError>;
+pub type DagResult<T> = std::result::Result<T, DagError>;
+
+/// Reasons a block is refused by the DAG
+#[derive(Debug, Error)]
+pub enum DagError {
+    #[error("{0:?}")]
+    Graph(GraphError),
+
+    #[error("block {0} is not signed")]
+    MissingSignature(String),
+
+    #[error("unable to find pending convergence block {0}")]
+    MissingPendingBlock(String),
+
+    #[error("certificate for block {certificate} was attached to block {block}")]
+    CertificateMismatch { block: String, certificate: String },
+
+    #[error("harvester quorum members are unknown")]
+    UnknownHarvesterQuorum,
+
+    #[error("certificate signer {0} is not a harvester quorum member")]
+    NotAHarvester(NodeId),
+
+    #[error("certificate signer {0} signed more than once")]
+    DuplicateSigner(NodeId),
+
+    #[error("certificate has {signatures} signatures, {threshold} are required")]
+    ThresholdNotReached { signatures: usize, threshold: usize },
+
+    #[error("invalid signature from {signer}: {reason}")]
+    InvalidSignature { signer: NodeId, reason: String },
+
+    #[error("certificate for block {0} carries no threshold signature")]
+    MissingThresholdSignature(String),
+
+    #[error("invalid threshold signature: {0}")]
+    InvalidThresholdSignature(String),
+
+    #[error("proposer {0} is not a farmer quorum member")]
+    UnknownProposer(NodeId),
+
+    #[error("failed to persist block {block}: {reason}")]
+    Persistence { block: String, reason: String },
+
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<GraphError> for DagError {
+    fn from(err: GraphError) -> Self {
+        DagError::Graph(err)
+    }
+}
 
 ///
 /// The runtime module that manages the DAG, both exposing
@@ -49,14 +103,19 @@ pub type GraphResult<T> = std::result::Result<T, GraphError>;
 #[derive(Clone, Debug)]
 pub struct DagModule {
     dag: Arc<RwLock<BullDag<Block, String>>>,
-    quorum_members: Option<QuorumMembers>,
-    _harvester_quorum_threshold: Option<HarvesterQuorumThreshold>,
     last_confirmed_block_header: Option<BlockHeader>,
     last_confirmed_block: Option<Block>,
+    /// Genesis block received before its certificate
+    pending_genesis_block: Option<GenesisBlock>,
     // String in next 2 fields represent the block hash
     pending_convergence_blocks: IndexMap<String, ConvergenceBlock>,
     _pending_certificates: IndexMap<String, Certificate>,
     partial_certificate_signatures: IndexMap<String, HashSet<(NodeId, Signature)>>,
+    /// Public key set of the harvester quorum, which threshold signatures of
+    /// certificates are verified against
+    harvester_public_key_set: Option<PublicKeySet>,
+    /// Where appended blocks are persisted so the DAG survives a restart
+    block_store: BlockStore,
     // TODO: Why is the Claim here?
     // TODO: Move this elsewhere, should not be in the DAG
     claim: Claim,
@@ -66,17 +125,105 @@ impl DagModule {
     pub fn new(dag: Arc<RwLock<BullDag<Block, String>>>, claim: Claim) -> Self {
         Self {
             dag,
-            quorum_members: None,
-            _harvester_quorum_threshold: None,
             last_confirmed_block_header: None,
             last_confirmed_block: None,
+            pending_genesis_block: None,
             pending_convergence_blocks: IndexMap::new(),
             _pending_certificates: IndexMap::new(),
             partial_certificate_signatures: IndexMap::new(),
+            harvester_public_key_set: None,
+            block_store: BlockStore::in_memory(),
             claim,
         }
     }
 
+    /// Persists appended blocks to `block_store` instead of keeping them in
+    /// memory only
+    pub fn with_block_store(self, block_store: BlockStore) -> Self {
+        Self {
+            block_store,
+            ..self
+        }
+    }
+
+    /// Rebuilds the DAG from the blocks persisted in the block store and
+    /// restores the last confirmed block. Returns the number of blocks
+    /// restored.
+    pub fn restore(&mut self) -> Result<usize> {
+        let stored_blocks = self.block_store.entries()?;
+        let mut sources: HashMap<String, Vec<String>> = HashMap::new();
+        let mut blocks = Vec::with_capacity(stored_blocks.len());
+
+        for stored in stored_blocks {
+            sources.insert(stored.block.hash(), stored.sources);
+            blocks.push(stored.block);
+        }
+
+        let blocks = causal_order(blocks);
+        let restored = blocks.len();
+
+        {
+            let mut guard = self
+                .dag
+                .write()
+                .map_err(|err| NodeError::Other(err.to_string()))?;
+
+            for block in blocks {
+                let mut source_vertices = Vec::new();
+                for source in sources.get(&block.hash()).into_iter().flatten() {
+                    if let Some(vertex) = guard.get_vertex(source.clone()) {
+                        source_vertices.push(vertex.clone());
+                    }
+                }
+
+                let vtx: Vertex<Block, String> = block.into();
+
+                if source_vertices.is_empty() {
+                    guard.add_vertex(&vtx);
+                }
+
+                for source in source_vertices.iter() {
+                    guard.add_edge(&(source, &vtx));
+                }
+            }
+        }
+
+        match self.block_store.last_confirmed_block()? {
+            Some(Block::Genesis { block }) => {
+                self.last_confirmed_block_header = Some(block.header.clone());
+                self.last_confirmed_block = Some(Block::Genesis { block });
+            }
+            Some(Block::Convergence { block }) => {
+                self.last_confirmed_block_header = Some(block.header.clone());
+                self.last_confirmed_block = Some(Block::Convergence { block });
+            }
+            _ => {}
+        }
+
+        Ok(restored)
+    }
+
+    /// Persists a block about to be appended to the DAG. Blocks are persisted
+    /// first so one that can't be is rejected instead of only living in
+    /// memory until the node restarts.
+    fn persist_block(&self, block: &Block, sources: &[String]) -> DagResult<()> {
+        self.block_store
+            .insert(block, sources)
+            .map_err(|err| DagError::Persistence {
+                block: block.hash(),
+                reason: err.to_string(),
+            })
+    }
+
+    /// Store the blocks appended to the DAG are persisted to
+    pub fn block_store(&self) -> &BlockStore {
+        &self.block_store
+    }
+
+    pub fn set_harvester_public_key_set(&mut self, public_key_set: PublicKeySet) {
+        self.harvester_public_key_set = Some(public_key_set);
+    }
+
     pub fn claim(&self) -> Claim {
         self.claim.clone()
     }
@@ -95,8 +242,13 @@ impl DagModule {
         self.last_confirmed_block_header.clone()
     }
 
-    pub fn set_quorum_members(&mut self, quorum_members: QuorumMembers) {
-        self.quorum_members = Some(quorum_members);
+    pub fn last_confirmed_block(&self) -> Option<Block> {
+        self.last_confirmed_block.clone()
+    }
+
+    /// Returns true if a block with the given hash was appended to the DAG
+    pub fn contains_block(&self, block_hash: &str) -> bool {
+        self.get_reference_block(block_hash).is_ok()
     }
 
     pub fn get_pending_convergence_block_mut(
@@ -109,23 +261,29 @@ impl DagModule {
     pub fn append_certificate_to_convergence_block(
         &mut self,
         certificate: &Certificate,
-    ) -> GraphResult<Option<ConvergenceBlock>> {
+        sig_engine: &SignerEngine,
+    ) -> DagResult<Option<ConvergenceBlock>> {
         let mut block = self
             .get_pending_convergence_block_mut(&certificate.block_hash)
-            .ok_or(GraphError::Other(
-                "unable to find pending convergence block".to_string(),
-            ))?
+            .ok_or_else(|| DagError::MissingPendingBlock(certificate.block_hash.clone()))?
             .clone();
 
         block
             .append_certificate(certificate)
-            .map_err(|err| GraphError::Other(err.to_string()))?;
+            .map_err(|err| DagError::Other(err.to_string()))?;
 
-        self.append_convergence(&block)
-            .map_err(|err| GraphError::Other(format!("{:?}", err)))
+        self.append_convergence(&block, sig_engine)
     }
 
+    /// Returns the genesis block with the given hash, whether it is still
+    /// pending or was already appended to the DAG
     fn get_genesis_block(&self, block_hash: &str) -> GraphResult<GenesisBlock> {
+        if let Some(genesis) = &self.pending_genesis_block {
+            if genesis.hash == block_hash {
+                return Ok(genesis.clone());
+            }
+        }
+
         let guard = self
             .dag
             .read()
@@ -143,73 +301,94 @@ impl DagModule {
         &mut self,
         block_hash: &str,
         certificate: &Certificate,
-    ) -> GraphResult<Option<GenesisBlock>> {
+        sig_engine: &SignerEngine,
+    ) -> DagResult<Option<GenesisBlock>> {
         let mut genesis_block = self.get_genesis_block(block_hash)?;
         genesis_block
             .append_certificate(certificate)
-            .map_err(|err| GraphError::Other(format!("{err:?}")))?;
-        self.append_genesis(&genesis_block)
-            .map_err(|err| GraphError::Other(format!("{err:?}")))?;
-        Ok(Some(genesis_block))
+            .map_err(|err| DagError::Other(format!("{err:?}")))?;
+
+        self.append_genesis(&genesis_block, sig_engine)
     }
 
-    pub fn append_genesis(&mut self, genesis: &GenesisBlock) -> GraphResult<()> {
-        // TODO: re-enable checking genesis block certificates
-        //
-        // let valid = self.check_valid_genesis(genesis);
-        // if !valid {
-        //     return Err(GraphError::Other(format!(
-        //         "invalid genesis block: {}",
-        //         genesis.hash,
-        //     )));
-        // }
+    /// Appends a genesis block once its certificate verifies. A block without
+    /// a certificate is kept pending until one is appended to it, a block whose
+    /// certificate doesn't verify is rejected.
+    pub fn append_genesis(
+        &mut self,
+        genesis: &GenesisBlock,
+        sig_engine: &SignerEngine,
+    ) -> DagResult<Option<GenesisBlock>> {
+        let valid =
+            self.check_valid_certificate(&genesis.hash, genesis.certificate.as_ref(), sig_engine)?;
+
+        if !valid {
+            self.pending_genesis_block = Some(genesis.clone());
+
+            return Ok(None);
+        }
 
-        // if valid {
         let block: Block = genesis.clone().into();
         let vtx: Vertex<Block, String> = block.clone().into();
+        self.persist_block(&block, &[])?;
         self.write_genesis(&vtx)?;
 
         self.last_confirmed_block_header = Some(genesis.header.clone());
         self.last_confirmed_block = Some(block);
-        // }
+        self.pending_genesis_block = None;
 
-        Ok(())
+        Ok(Some(genesis.clone()))
     }
 
+    /// Appends a proposal block once its proposer's signature verifies
     pub fn append_proposal(
         &mut self,
         proposal: &ProposalBlock,
         sig_engine: SignerEngine,
-    ) -> GraphResult<()> {
-        let valid = self.check_valid_proposal(proposal, sig_engine);
+    ) -> DagResult<()> {
+        self.verify_proposal(proposal, &sig_engine)?;
 
-        if valid {
-            if let Ok(ref_block) = self.get_reference_block(&proposal.ref_block) {
-                let block: Block = proposal.clone().into();
-                let vtx: Vertex<Block, String> = block.into();
-                let edge = (&ref_block, &vtx);
-                self.write_edge(edge)?;
-            } else {
-                return Err(GraphError::NonExistentSource);
-            }
-        }
+        let ref_block = self
+            .get_reference_block(&proposal.ref_block)
+            .map_err(|_| GraphError::NonExistentSource)?;
+
+        let block: Block = proposal.clone().into();
+        let vtx: Vertex<Block, String> = block.clone().into();
+        self.persist_block(&block, &[proposal.ref_block.clone()])?;
+
+        let edge = (&ref_block, &vtx);
+        self.write_edge(edge)?;
 
         Ok(())
     }
 
+    /// Appends a convergence block once its certificate verifies. Blocks
+    /// without a certificate are kept pending until one is appended to them,
+    /// blocks whose certificate doesn't verify are rejected.
+    ///
+    /// Certified blocks are confirmed and persisted before their state is
+    /// applied, so `StateManager::restore_dag` applies them if the node stops
+    /// in between.
     pub fn append_convergence(
         &mut self,
         convergence: &ConvergenceBlock,
-    ) -> GraphResult<Option<ConvergenceBlock>> {
-        let valid = self.check_valid_convergence(convergence);
+        sig_engine: &SignerEngine,
+    ) -> DagResult<Option<ConvergenceBlock>> {
+        let valid = self.check_valid_convergence(convergence, sig_engine)?;
 
         if valid {
             let ref_blocks: Vec<Vertex<Block, String>> =
                 self.get_convergence_reference_blocks(convergence);
             //dbg!(&ref_blocks);
             let block: Block = convergence.clone().into();
-            let vtx: Vertex<Block, String> = block.into();
+            let vtx: Vertex<Block, String> = block.clone().into();
             //dbg!(&vtx);
+            let sources: Vec<String> = ref_blocks
+                .iter()
+                .map(|ref_block| ref_block.get_data().hash())
+                .collect();
+            self.persist_block(&block, &sources)?;
+
             let edges: Edges = ref_blocks
                 .iter()
                 .map(|ref_block| (ref_block.clone(), vtx.clone()))
@@ -221,11 +400,9 @@ impl DagModule {
                 block: convergence.clone(),
             });
 
-            self.pending_convergence_blocks
-                .remove(&convergence.hash)
-                .ok_or(GraphError::Other(
-                    "unable to find pending convergence block".to_string(),
-                ))?;
+            // NOTE: blocks fetched while syncing arrive already certified and were
+            // never pending on this node
+            self.pending_convergence_blocks.remove(&convergence.hash);
 
             return Ok(Some(convergence.clone()));
         } else {
@@ -237,6 +414,46 @@ impl DagModule {
         Ok(None)
     }
 
+    /// Appends the certified block a state snapshot was taken at, along with
+    /// the proposals it references, and confirms it. A node bootstrapping
+    /// from a snapshot knows none of the blocks those were built on, so they
+    /// become the roots of its DAG. Callers verify the blocks beforehand.
+    pub fn append_snapshot_block(
+        &mut self,
+        convergence: &ConvergenceBlock,
+        proposals: &[ProposalBlock],
+    ) -> DagResult<()> {
+        let block: Block = convergence.clone().into();
+        let vtx: Vertex<Block, String> = block.clone().into();
+
+        let mut edges: Edges = Vec::with_capacity(proposals.len());
+        for proposal in proposals {
+            let proposal_block: Block = proposal.clone().into();
+            self.persist_block(&proposal_block, &[proposal.ref_block.clone()])?;
+
+            let proposal_vtx: Vertex<Block, String> = proposal_block.into();
+            self.write_vertex(&proposal_vtx)?;
+            edges.push((proposal_vtx, vtx.clone()));
+        }
+
+        let sources: Vec<String> = proposals
+            .iter()
+            .map(|proposal| proposal.hash.clone())
+            .collect();
+        self.persist_block(&block, &sources)?;
+
+        if edges.is_empty() {
+            self.write_vertex(&vtx)?;
+        } else {
+            self.extend_edges(edges)?;
+        }
+
+        self.last_confirmed_block_header = Some(convergence.header.clone());
+        self.last_confirmed_block = Some(block);
+
+        Ok(())
+    }
+
     pub fn get_convergence_reference_blocks(
         &self,
         convergence: &ConvergenceBlock,
@@ -304,27 +521,126 @@ impl DagModule {
         Err(GraphError::Other("Error getting write guard".to_string()))
     }
 
-    fn check_valid_proposal(&self, block: &ProposalBlock, _sig_engine: SignerEngine) -> bool {
-        if let Ok(_validation_data) = block.get_validation_data() {
-            todo!();
-        } else {
-            false
+    /// Verifies a proposal block was signed by the farmer quorum member it
+    /// claims to come from
+    pub fn verify_proposal(
+        &self,
+        block: &ProposalBlock,
+        sig_engine: &SignerEngine,
+    ) -> DagResult<()> {
+        let validation_data = block
+            .get_validation_data()
+            .map_err(|_| DagError::MissingSignature(block.hash.clone()))?;
+
+        let proposer = block.from.node_id();
+        let is_farmer = sig_engine.quorum_members().0.values().any(|quorum| {
+            quorum.quorum_kind == QuorumKind::Farmer && quorum.members.contains_key(proposer)
+        });
+
+        if !is_farmer {
+            return Err(DagError::UnknownProposer(proposer.clone()));
         }
+
+        for (signer, signature) in validation_data.signatures.iter() {
+            sig_engine
+                .verify(signer, signature, &validation_data.payload_hash)
+                .map_err(|err| DagError::InvalidSignature {
+                    signer: signer.clone(),
+                    reason: err.to_string(),
+                })?;
+        }
+
+        Ok(())
     }
 
+    /// Returns whether a convergence block is certified. Blocks without a
+    /// certificate are still pending, blocks with a certificate that doesn't
+    /// verify are rejected.
     //TODO: Refactor to return ConvergenceBlockStatus Enum as Pending
     // or Confirmed variant
-    fn check_valid_convergence(&mut self, block: &ConvergenceBlock) -> bool {
-        if let Some(_certificate) = &block.certificate {
-            //TODO: Remove this as it is redundant...
-            //match self.verify_certificate(certificate) {
-            //Ok(true) => return true,
-            //Ok(false) => return false,
-            //Err(_) => return false,
-            //}
-            return true;
+    fn check_valid_convergence(
+        &self,
+        block: &ConvergenceBlock,
+        sig_engine: &SignerEngine,
+    ) -> DagResult<bool> {
+        self.check_valid_certificate(&block.hash, block.certificate.as_ref(), sig_engine)
+    }
+
+    /// Returns whether the block with the given hash is certified by
+    /// `certificate`, failing if it carries a certificate that doesn't verify
+    fn check_valid_certificate(
+        &self,
+        block_hash: &str,
+        certificate: Option<&Certificate>,
+        sig_engine: &SignerEngine,
+    ) -> DagResult<bool> {
+        let Some(certificate) = certificate else {
+            return Ok(false);
+        };
+
+        if certificate.block_hash != block_hash {
+            return Err(DagError::CertificateMismatch {
+                block: block_hash.to_string(),
+                certificate: certificate.block_hash.clone(),
+            });
         }
-        false
+
+        self.verify_certificate(certificate, sig_engine)?;
+
+        Ok(true)
+    }
+
+    /// Verifies a certificate carries a signature over the certified block
+    /// hash from at least a threshold of distinct harvester quorum members,
+    /// along with a threshold signature over it once the public key set of
+    /// the harvester quorum is known
+    pub fn verify_certificate(
+        &self,
+        certificate: &Certificate,
+        sig_engine: &SignerEngine,
+    ) -> DagResult<()> {
+        let harvesters = sig_engine
+            .quorum_members()
+            .get_harvester_data()
+            .ok_or(DagError::UnknownHarvesterQuorum)?;
+
+        let mut signers = HashSet::new();
+        for (signer, _) in certificate.signatures.iter() {
+            if !harvesters.members.contains_key(signer) {
+                return Err(DagError::NotAHarvester(signer.clone()));
+            }
+
+            if !signers.insert(signer) {
+                return Err(DagError::DuplicateSigner(signer.clone()));
+            }
+        }
+
+        // NOTE: an empty certificate never certifies a block, even for an empty
+        // quorum
+        let threshold = sig_engine.quorum_members().get_harvester_threshold().max(1);
+        if signers.len() < threshold {
+            return Err(DagError::ThresholdNotReached {
+                signatures: signers.len(),
+                threshold,
+            });
+        }
+
+        for (signer, signature) in certificate.signatures.iter() {
+            sig_engine
+                .verify(signer, signature, &certificate.block_hash)
+                .map_err(|err| DagError::InvalidSignature {
+                    signer: signer.clone(),
+                    reason: err.to_string(),
+                })?;
+        }
+
+        // NOTE: until the public key set of the harvester quorum is known,
+        // certificates are verified by the signatures of its members only
+        if let Some(public_key_set) = &self.harvester_public_key_set {
+            verify_threshold_signature(public_key_set, certificate)?;
+        }
+
+        Ok(())
     }
 
     pub fn add_signer_to_block(
@@ -363,163 +679,37 @@ impl DagModule {
 
         Err(NodeError::Other("threshold not reached".to_string()))
     }
+}
 
-    fn _verify_certificate_signature(
-        &self,
-        signature: &mut Vec<(NodeId, Signature)>,
-        sig_type: SignatureType,
-        payload_hash: Vec<u8>,
-        sig_engine: SignerEngine,
-    ) -> SignerResult<(bool, SignatureType)> {
-        match sig_type {
-            SignatureType::PartialSignature => {
-                if let Some((id, sig)) = signature.pop() {
-                    self._verify_certificate_partial_sig(sig, id, payload_hash, sig_engine)
-                } else {
-                    Err(SignerError::PartialSignatureError(
-                        "no signature provided".to_string(),
-                    ))
-                }
-            }
-            SignatureType::ThresholdSignature | SignatureType::ChainLockSignature => {
-                self._verify_certificate_threshold_sig(signature.clone(), payload_hash, sig_engine)
-            }
-        }
-    }
-
-    fn _verify_certificate_partial_sig(
-        &self,
-        sig: Signature,
-        node_idx: NodeId,
-        payload_hash: Vec<u8>,
-        sig_engine: SignerEngine,
-    ) -> SignerResult<(bool, SignatureType)> {
-        let public_keyshare = self._get_harvester_public_keyshare(node_idx)?;
-        self._verify_partial_sig_with_public_keyshare(
-            sig,
-            public_keyshare,
-            payload_hash,
-            sig_engine,
-        )
-    }
-
-    fn _verify_certificate_threshold_sig(
-        &self,
-        sigs: Vec<(NodeId, Signature)>,
-        payload_hash: Vec<u8>,
-        sig_engine: SignerEngine,
-    ) -> SignerResult<(bool, SignatureType)> {
-        self._verify_threshold_sig_with_public_keyset(sigs, payload_hash, sig_engine)
-    }
-
-    fn _verify_threshold_sig_with_public_keyset(
-        &self,
-        sigs: Vec<(NodeId, Signature)>,
-        payload_hash: Vec<u8>,
-        sig_engine: SignerEngine,
-    ) -> SignerResult<(bool, SignatureType)> {
-        sig_engine
-            .verify_batch(&sigs, &payload_hash)
-            .map_err(|err| SignerError::ThresholdSignatureError(err.to_string()))?;
-
-        Ok((true, SignatureType::ThresholdSignature))
-    }
-
-    fn _verify_partial_sig_with_public_keyshare(
-        &self,
-        sig: Signature,
-        public_keyshare: PublicKey,
-        payload_hash: Vec<u8>,
-        sig_engine: SignerEngine,
-    ) -> SignerResult<(bool, SignatureType)> {
-        if let Some(mut harvesters) = sig_engine.quorum_members().get_harvester_data() {
-            harvesters.members.retain(|_id, pk| *pk == public_keyshare);
-            if let Some((id, _pk)) = harvesters.members.iter().next() {
-                sig_engine.verify(id, &sig, &payload_hash).map_err(|_err| {
-                    SignerError::PartialSignatureError("unable to verify signature".to_string())
-                })?;
-                Ok((true, SignatureType::PartialSignature))
-            } else {
-                Err(SignerError::PartialSignatureError(
-                    "unable to find signer in sig engine".to_string(),
-                ))
-            }
-        } else {
-            Err(SignerError::PartialSignatureError(
-                "Error parsing signature into array".to_string(),
-            ))
-        }
-    }
-
-    #[deprecated]
-    fn _verify_threshold_sig(
-        &self,
-        validation_data: BlockValidationData,
-        sig_engine: SignerEngine,
-    ) -> SignerResult<bool> {
-        let sig_set = validation_data.signatures.clone();
-        if sig_set.len() <= sig_engine.quorum_members().get_harvester_threshold() {
-            return Err(SignerError::ThresholdSignatureError(
-                "not enough signatures received to meet threshold".to_string(),
-            ));
-        }
-
-        sig_engine
-            .verify_batch(&sig_set, &validation_data.payload_hash)
-            .map_err(|err| SignerError::ThresholdSignatureError(err.to_string()))?;
-
-        Ok(true)
-    }
-
-    fn _get_harvester_public_keyshare(&self, node_id: NodeId) -> SignerResult<PublicKey> {
-        let public_key_share = {
-            if let Some(quorum_members) = self.quorum_members.clone() {
-                if let Some(data) = quorum_members.get_harvester_data() {
-                    if let Some(key) = data.members.get(&node_id) {
-                        *key
-                    } else {
-                        return Err(SignerError::GroupPublicKeyMissing);
-                    }
-                } else {
-                    return Err(SignerError::GroupPublicKeyMissing);
-                }
-            } else {
-                return Err(SignerError::GroupPublicKeyMissing);
-            }
-        };
-
-        Ok(public_key_share)
-    }
-
-    fn _get_harvester_public_keyset(&self) -> SignerResult<Vec<PublicKey>> {
-        let public_keyset = {
-            if let Some(quorum_members) = self.quorum_members.clone() {
-                if let Some(data) = quorum_members.get_harvester_data() {
-                    data.members.values().cloned().collect()
-                } else {
-                    return Err(SignerError::GroupPublicKeyMissing);
-                }
-            } else {
-                return Err(SignerError::GroupPublicKeyMissing);
-            }
-        };
-
-        Ok(public_keyset)
-    }
-
-    fn _get_harvester_node_ids(&self) -> SignerResult<Vec<NodeId>> {
-        let node_ids = {
-            if let Some(quorum_members) = self.quorum_members.clone() {
-                if let Some(data) = quorum_members.get_harvester_data() {
-                    data.members.keys().cloned().collect()
-                } else {
-                    return Err(SignerError::GroupPublicKeyMissing);
-                }
-            } else {
-                return Err(SignerError::GroupPublicKeyMissing);
-            }
-        };
-
-        Ok(node_ids)
-    }
+/// Verifies the threshold signature of a certificate against the public key
+/// set of the harvester quorum
+fn verify_threshold_signature(
+    public_key_set: &PublicKeySet,
+    certificate: &Certificate,
+) -> DagResult<()> {
+    let signature = certificate
+        .threshold_signature
+        .as_ref()
+        .ok_or_else(|| DagError::MissingThresholdSignature(certificate.block_hash.clone()))?;
+
+    let bytes: [u8; SIG_SIZE] = signature.as_slice().try_into().map_err(|_| {
+        DagError::InvalidThresholdSignature(format!(
+            "expected {SIG_SIZE} bytes, got {}",
+            signature.len()
+        ))
+    })?;
+    let signature = ThresholdSignature::from_bytes(bytes)
+        .map_err(|err| DagError::InvalidThresholdSignature(err.to_string()))?;
+
+    if !public_key_set
+        .public_key()
+        .verify(&signature, &certificate.block_hash)
+    {
+        return Err(DagError::InvalidThresholdSignature(format!(
+            "signature over block {} doesn't verify against the harvester public key set",
+            certificate.block_hash
+        )));
+    }
+
+    Ok(())
 }
@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::HashMap,
     sync::{Arc, RwLock},
 };
 
@@ -10,18 +10,18 @@ use bulldag::{
     graph::{BullDag, GraphError},
     vertex::Vertex,
 };
-use ethereum_types::U256;
 use events::Event;
-use mempool::{LeftRightMempool, MempoolReadHandleFactory};
+use mempool::{LeftRightMempool, MempoolReadHandleFactory, TxnStatus, TxnStatusTracker};
 use primitives::{Address, NodeId, Round};
-use signer::engine::{QuorumMembers, SignerEngine};
-use storage::vrrbdb::{types::*, ApplyBlockResult};
+use signer::engine::SignerEngine;
+use storage::vrrbdb::{ApplyBlockResult, StateSnapshot};
 use storage::{
     storage_utils::StorageError,
-    vrrbdb::{Claims, VrrbDb, VrrbDbReadHandle},
+    vrrbdb::{Claims, MempoolJournalStore, VrrbDb, VrrbDbReadHandle},
 };
-use telemetry::info;
+use telemetry::{info, warn};
 use theater::{ActorId, ActorState};
+use validator::txn_validator::TxnValidator;
 use vrrb_core::{account::Account, claim::Claim};
 use vrrb_core::{
     account::UpdateArgs,
@@ -31,10 +31,7 @@ use vrrb_core::{
 use crate::{data_store::DataStore, state_reader::StateReader};
 use crate::{NodeError, Result};
 
-use super::{
-    utils::{consolidate_update_args, get_update_args},
-    DagModule, GraphResult,
-};
+use super::{DagError, DagModule, DagResult, GraphResult};
 
 /// Provides a convenient configuration struct for building a
 /// StateManager
@@ -44,6 +41,8 @@ pub struct StateManagerConfig {
     pub dag: Arc<RwLock<BullDag<Block, String>>>,
     pub mempool: LeftRightMempool,
     pub claim: Claim,
+    /// Journal the mempool is persisted to, if enabled in `NodeConfig`
+    pub mempool_journal: Option<MempoolJournalStore>,
 }
 
 #[derive(Debug, Clone)]
@@ -53,56 +52,76 @@ pub struct StateManager {
     pub(crate) dag: DagModule,
     pub(crate) database: VrrbDb,
     pub(crate) mempool: LeftRightMempool,
+    pub(crate) mempool_journal: Option<MempoolJournalStore>,
 }
 
 impl StateManager {
     pub fn new(config: StateManagerConfig) -> Self {
-        let dag_module = DagModule::new(config.dag.clone(), config.claim.clone());
+        let dag_module = DagModule::new(config.dag.clone(), config.claim.clone())
+            .with_block_store(config.database.block_store());
+
+        let mempool = match config.mempool_journal.clone() {
+            Some(journal) => config.mempool.with_journal(Arc::new(journal)),
+            None => config.mempool,
+        };
 
         Self {
             _actor_id: uuid::Uuid::new_v4().to_string(),
             database: config.database,
             _status: ActorState::Stopped,
             dag: dag_module,
-            mempool: config.mempool,
+            mempool,
+            mempool_journal: config.mempool_journal,
         }
     }
 
     pub fn append_genesis(
         &mut self,
         genesis_block: &GenesisBlock,
-    ) -> GraphResult<ApplyBlockResult> {
-        self.dag.append_genesis(genesis_block)?;
-        self.apply_block(Block::Genesis {
-            block: genesis_block.to_owned(),
-        })
-        .map_err(|err| GraphError::Other(format!("{err:?}")))
+        sig_engine: &SignerEngine,
+    ) -> DagResult<ApplyBlockResult> {
+        if let Some(block) = self.dag.append_genesis(genesis_block, sig_engine)? {
+            return self
+                .apply_block(Block::Genesis { block })
+                .map_err(|err| DagError::Other(format!("{err:?}")));
+        }
+
+        Err(DagError::Other(
+            "unable to append and apply genesis block".to_string(),
+        ))
     }
 
     pub fn append_convergence(
         &mut self,
         convergence: &ConvergenceBlock,
-    ) -> GraphResult<ApplyBlockResult> {
-        let opt = self.dag.append_convergence(convergence)?;
+        sig_engine: &SignerEngine,
+    ) -> DagResult<ApplyBlockResult> {
+        let opt = self.dag.append_convergence(convergence, sig_engine)?;
         if let Some(cblock) = opt {
-            let ref_blocks = self.dag.get_convergence_reference_blocks(convergence);
-            let proposals: Vec<ProposalBlock> = ref_blocks
-                .iter()
-                .filter_map(|vertex| match vertex.get_data() {
-                    Block::Proposal { block } => Some(block.clone()),
-                    _ => None,
-                })
-                .collect();
+            let proposals = self.get_convergence_proposals(convergence);
 
             let res = self.apply_convergence_block(&cblock, &proposals)?;
             return Ok(res);
         }
 
-        Err(GraphError::Other(
+        Err(DagError::Other(
             "unable to append and apply convergence block".to_string(),
         ))
     }
 
+    /// Returns the proposal blocks within the DAG a convergence block
+    /// references
+    fn get_convergence_proposals(&self, convergence: &ConvergenceBlock) -> Vec<ProposalBlock> {
+        self.dag
+            .get_convergence_reference_blocks(convergence)
+            .iter()
+            .filter_map(|vertex| match vertex.get_data() {
+                Block::Proposal { block } => Some(block.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn apply_convergence_block(
         &mut self,
         convergence: &ConvergenceBlock,
@@ -118,22 +137,58 @@ impl StateManager {
     pub fn append_certificate_to_convergence_block(
         &mut self,
         certificate: &Certificate,
-    ) -> GraphResult<Option<ConvergenceBlock>> {
+        sig_engine: &SignerEngine,
+    ) -> DagResult<Option<ConvergenceBlock>> {
         self.dag
-            .append_certificate_to_convergence_block(certificate)
+            .append_certificate_to_convergence_block(certificate, sig_engine)
     }
 
     pub fn append_certificate_to_genesis_block(
         &mut self,
         block_hash: &str,
         certificate: &Certificate,
-    ) -> GraphResult<Option<GenesisBlock>> {
+        sig_engine: &SignerEngine,
+    ) -> DagResult<Option<GenesisBlock>> {
         self.dag
-            .append_certificate_to_genesis_block(block_hash, certificate)
+            .append_certificate_to_genesis_block(block_hash, certificate, sig_engine)
     }
 
-    pub fn export_state(&self) {
-        self.database.export_state();
+    /// Takes a snapshot of the state the confirmed `ConvergenceBlock` at
+    /// `block_height` was built on, so new nodes can bootstrap from it.
+    pub fn export_state(&self, block_height: u128) -> Result<StateSnapshot> {
+        Ok(self.database.export_state(block_height)?)
+    }
+
+    /// Imports a snapshot exported by another node. The certificate of the
+    /// snapshot's block is checked against the current harvester quorum, its
+    /// proposals against the farmer quorum, and the state root of the
+    /// snapshot against the one the block's header commits to, before any
+    /// data is written. The block then becomes the last confirmed block and
+    /// is applied on top of the imported state.
+    pub fn import_state(
+        &mut self,
+        snapshot: &StateSnapshot,
+        sig_engine: &SignerEngine,
+    ) -> Result<ApplyBlockResult> {
+        let certificate = snapshot.certificate()?;
+
+        self.dag
+            .verify_certificate(certificate, sig_engine)
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        for proposal in snapshot.proposals.iter() {
+            self.dag.verify_proposal(proposal, sig_engine)?;
+        }
+
+        self.database.import_state(snapshot)?;
+
+        // NOTE: the block is confirmed before its state is applied, so
+        // `restore_dag` applies it if the node stops in between
+        self.dag
+            .append_snapshot_block(&snapshot.block, &snapshot.proposals)?;
+
+        self.apply_convergence_block(&snapshot.block, &snapshot.proposals)
+            .map_err(|err| NodeError::Other(format!("{err:?}")))
     }
 
     /// Produces the read handle for the VrrbDb instance in this
@@ -147,6 +202,20 @@ impl StateManager {
         self.mempool.factory()
     }
 
+    /// Returns the tracker holding the lifecycle status of the transactions
+    /// seen by this node
+    pub fn txn_status_tracker(&self) -> TxnStatusTracker {
+        self.mempool.status_tracker()
+    }
+
+    /// Marks the transactions of a `ProposalBlock` as included in it
+    pub(crate) fn mark_txns_included(&self, block: &ProposalBlock) {
+        let tracker = self.mempool.status_tracker();
+        block.txns.keys().for_each(|id| {
+            tracker.update(id, TxnStatus::Included(block.hash.clone()));
+        });
+    }
+
     pub fn transactions_root_hash(&self) -> Result<String> {
         let root_hash = self.database.transactions_root_hash()?;
         let root_hash_hex = hex::encode(root_hash.0);
@@ -191,99 +260,28 @@ impl StateManager {
         self.database.commit_state();
     }
 
-    /// Given the hash of a `ConvergenceBlock` this method
-    /// updates the StateStore, ClaimStore and TransactionStore
-    /// for all new claims and transactions (excluding
-    /// ClaimStaking transactions currently).
-    pub fn update_state(&mut self, block_hash: BlockHash) -> Result<()> {
-        if let Some(mut round_blocks) = self.get_proposal_blocks(block_hash) {
-            let update_list = self.get_update_list(&mut round_blocks);
-            let update_args = get_update_args(update_list);
-            let consolidated_update_args = consolidate_update_args(update_args);
-            consolidated_update_args.into_iter().for_each(|(_, args)| {
-                if let Err(err) = self.database.update_account(args) {
-                    telemetry::error!("error updating account: {err}");
-                }
-            });
-
-            let proposals = round_blocks.proposals.clone();
+    /// Applies a `ConvergenceBlock` confirmed by the harvester quorum to
+    /// state, along with the claims and transactions it includes from the
+    /// `ProposalBlock`s it references, all at once. Its transactions are then
+    /// marked as finalized.
+    pub fn apply_confirmed_block(&mut self, block: &ConvergenceBlock) -> Result<ApplyBlockResult> {
+        let proposals = self.get_convergence_proposals(block);
+        let result = self
+            .apply_convergence_block(block, &proposals)
+            .map_err(|err| NodeError::Other(format!("{err:?}")))?;
 
-            self.update_txn_trie(&proposals);
-            self.update_claim_store(&proposals);
+        let tracker = self.mempool.status_tracker();
+        block.txns.values().flatten().for_each(|digest| {
+            tracker.update(digest, TxnStatus::Finalized);
+        });
 
-            return Ok(());
+        // NOTE: expired transactions are pruned once per block so they do
+        // not linger in the pool until the next one is inserted
+        if let Err(err) = self.mempool.prune_expired() {
+            telemetry::error!("error pruning expired mempool transactions: {err}");
         }
 
-        Err(NodeError::Other(
-            "Convergene block not found in DAG".to_string(),
-        ))
-    }
-
-    /// Provided a reference to an array of `ProposalBlock`s
-    /// making up the current round's `ConvergenceBlock`, writes all
-    /// the conflict resolved transactions into the `TransactionTrie`
-    fn update_txn_trie(&mut self, proposals: &[ProposalBlock]) {
-        let consolidated: HashSet<TransactionKind> = {
-            let nested: Vec<HashSet<TransactionKind>> = proposals
-                .iter()
-                .map(|block| block.txns.iter().map(|(_, v)| v.clone()).collect())
-                .collect();
-
-            nested.into_iter().flatten().collect()
-        };
-
-        self.database
-            .extend_transactions(consolidated.into_iter().collect());
-    }
-
-    /// Provided a reference to an array of `ProposalBlock`s
-    /// making up the current round's `ConvergenceBlock`, writes
-    /// all the new, conflict resolved, claims into the `ClaimStore`
-    fn update_claim_store(&mut self, proposals: &[ProposalBlock]) {
-        let consolidated: HashSet<(U256, Option<Claim>)> = {
-            let nested: Vec<HashSet<(U256, Option<Claim>)>> = {
-                proposals
-                    .iter()
-                    .map(|block| {
-                        block
-                            .claims
-                            .iter()
-                            .map(|(k, v)| (*k, Some(v.clone())))
-                            .collect()
-                    })
-                    .collect()
-            };
-
-            nested.into_iter().flatten().collect()
-        };
-
-        self.database
-            .extend_claims(consolidated.into_iter().collect());
-    }
-
-    /// Provides a method to convert a `RoundBlocks` wrapper struct into
-    /// a HashSet of unique `StateUpdate`s
-    fn get_update_list(&self, round_blocks: &mut RoundBlocks) -> HashSet<StateUpdate> {
-        let convergence = round_blocks.convergence.clone();
-        let filtered_proposals: Vec<ProposalBlock> = round_blocks
-            .proposals
-            .iter_mut()
-            .map(|block| {
-                if let Some(digests) = convergence.txns.get(&block.hash) {
-                    block.txns.retain(|digest, _| digests.contains(digest))
-                }
-                block.clone()
-            })
-            .collect();
-
-        let mut updates: HashSet<StateUpdate> = HashSet::new();
-
-        filtered_proposals.iter().for_each(|block| {
-            let subset = HashSet::from_block(block.clone());
-            updates.extend(subset);
-        });
-
-        updates
+        Ok(result)
     }
 
     /// Inserts an account into the `VrrbDb` `StateStore`. This method Should
@@ -295,68 +293,9 @@ impl StateManager {
     }
 
     pub fn extend_accounts(&mut self, accounts: Vec<(Address, Option<Account>)>) -> Result<()> {
-        self.database.extend_accounts(accounts);
-        Ok(())
-    }
-
-    /// Enters into the DAG and collects and returns the current round
-    /// `ConvergenceBlock` and all its source `ProposalBlock`s
-    fn get_proposal_blocks(&self, index: BlockHash) -> Option<RoundBlocks> {
-        let guard_result = self.dag.read();
-
-        if let Ok(guard) = guard_result {
-            let vertex_option = guard.get_vertex(index);
-            match &vertex_option {
-                Some(vertex) => {
-                    if let Block::Convergence { block } = vertex.get_data() {
-                        let proposals = self.convert_sources(self.get_sources(vertex));
-
-                        return Some(RoundBlocks {
-                            convergence: block,
-                            proposals,
-                        });
-                    }
-                }
-                None => {}
-            }
-        }
-
-        None
-    }
-
-    /// Enters into the DAG and gets all the sources of a given vertex
-    /// this is used primarily to capture all the `ProposalBlock`s
-    /// that make up the current round `ConvergenceBlock`
-    fn get_sources(&self, vertex: &Vertex<Block, BlockHash>) -> Vec<Vertex<Block, BlockHash>> {
-        let mut source_vertices = Vec::new();
-        let guard_result = self.dag.read();
-        if let Ok(guard) = guard_result {
-            let sources = vertex.get_sources();
-            sources.iter().for_each(|index| {
-                let source_option = guard.get_vertex(index.to_string());
-                if let Some(source) = source_option {
-                    source_vertices.push(source.clone());
-                }
-            });
-        }
-
-        source_vertices
-    }
-
-    /// Converts the discovered `source` `Vertex`s into a Vector of
-    /// `ProposalBlock`s, filtering any other types of blocks out.
-    fn convert_sources(&self, sources: Vec<Vertex<Block, BlockHash>>) -> Vec<ProposalBlock> {
-        let blocks: Vec<Block> = sources.iter().map(|vtx| vtx.get_data()).collect();
-
-        let mut proposals = Vec::new();
-
-        blocks.iter().for_each(|block| {
-            if let Block::Proposal { block } = &block {
-                proposals.push(block.clone())
-            }
-        });
-
-        proposals
+        self.database
+            .extend_accounts(accounts)
+            .map_err(|err| NodeError::Other(err.to_string()))
     }
 
     pub fn handle_block_received(
@@ -366,22 +305,15 @@ impl StateManager {
     ) -> Result<Event> {
         match block {
             Block::Genesis { ref mut block } => {
-                if let Err(e) = self.dag.append_genesis(block) {
-                    let err_note = format!("Encountered GraphError: {e:?}");
-                    return Err(NodeError::Other(err_note));
-                };
+                self.dag.append_genesis(block, &sig_engine)?;
             }
             Block::Proposal { ref mut block } => {
-                if let Err(e) = self.dag.append_proposal(block, sig_engine.clone()) {
-                    let err_note = format!("Encountered GraphError: {e:?}");
-                    return Err(NodeError::Other(err_note));
-                }
+                self.dag.append_proposal(block, sig_engine.clone())?;
+
+                self.mark_txns_included(block);
             }
             Block::Convergence { ref mut block } => {
-                if let Err(e) = self.dag.append_convergence(block) {
-                    let err_note = format!("Encountered GraphError: {e:?}");
-                    return Err(NodeError::Other(err_note));
-                }
+                self.dag.append_convergence(block, &sig_engine)?;
 
                 if block.certificate.is_none() {
                     if let Some(header) = self.dag.last_confirmed_block_header() {
@@ -399,6 +331,31 @@ impl StateManager {
         Ok(Event::BlockAppended(block.hash()))
     }
 
+    /// Appends a block fetched from a peer while catching up with the network,
+    /// applying it to state if it confirms a round. Convergence blocks must be
+    /// certified and come after the proposal blocks they reference.
+    pub fn append_synced_block(
+        &mut self,
+        mut block: Block,
+        sig_engine: SignerEngine,
+    ) -> Result<()> {
+        self.handle_block_received(&mut block, sig_engine)?;
+
+        match block {
+            Block::Genesis { block } => {
+                self.apply_block(Block::Genesis { block })?;
+            }
+            Block::Convergence { block } => {
+                let proposals = self.get_convergence_proposals(&block);
+                self.apply_convergence_block(&block, &proposals)
+                    .map_err(|err| NodeError::Other(format!("{err:?}")))?;
+            }
+            Block::Proposal { .. } => {}
+        }
+
+        Ok(())
+    }
+
     pub fn apply_block(&mut self, block: Block) -> Result<ApplyBlockResult> {
         let apply_result = self
             .database
@@ -427,6 +384,102 @@ impl StateManager {
         Ok(())
     }
 
+    /// Rebuilds the DAG from the blocks persisted in the database, then
+    /// applies the confirmed convergence blocks whose state was not applied
+    /// before the node stopped. Returns the number of blocks restored.
+    pub fn restore_dag(&mut self) -> Result<usize> {
+        let restored = self.dag.restore()?;
+
+        let reapplied = self.apply_restored_blocks()?;
+        if reapplied > 0 {
+            telemetry::info!("applied {reapplied} confirmed blocks missing from state");
+        }
+
+        Ok(restored)
+    }
+
+    /// Blocks are confirmed within the DAG before their state is applied, so
+    /// a node stopping in between restarts with confirmed blocks that are
+    /// missing from state. Those come after the last block recorded in the
+    /// state history, and were built on top of the current state.
+    fn apply_restored_blocks(&mut self) -> Result<usize> {
+        let start = self
+            .database
+            .history()
+            .latest()
+            .map(|version| version.block_height + 1)
+            .unwrap_or_default();
+
+        let mut applied = 0;
+        for block in self
+            .database
+            .block_store()
+            .range_by_height(start, usize::MAX)?
+        {
+            let Block::Convergence { block } = block else {
+                continue;
+            };
+
+            if block.header.state_root_hash != self.state_root_hash()? {
+                continue;
+            }
+
+            let proposals = self.get_convergence_proposals(&block);
+            self.apply_convergence_block(&block, &proposals)
+                .map_err(|err| NodeError::Other(format!("{err:?}")))?;
+
+            applied += 1;
+        }
+
+        Ok(applied)
+    }
+
+    /// Reinserts the transactions recorded in the mempool journal, if one is
+    /// configured. Every transaction is validated against the current state
+    /// first, and the ones that are no longer valid or have expired are
+    /// dropped from the journal. Returns the number of transactions restored.
+    pub fn restore_mempool(&mut self) -> Result<usize> {
+        let Some(journal) = self.mempool_journal.clone() else {
+            return Ok(0);
+        };
+
+        let validator = TxnValidator::new();
+        let state_reader = self.database.state_store_factory();
+        let mut restored = 0;
+
+        for record in journal.replay()? {
+            let txn_id = record.txn_id.clone();
+
+            let admitted = validator
+                .validate(state_reader.clone(), &record.txn)
+                .map_err(|err| err.to_string())
+                .and_then(|_| {
+                    self.mempool
+                        .insert_record(record)
+                        .map_err(|err| err.to_string())
+                });
+
+            match admitted {
+                Ok(_) => restored += 1,
+                Err(err) => {
+                    warn!("dropping journaled transaction {txn_id}: {err}");
+                    self.txn_status_tracker()
+                        .update(&txn_id, TxnStatus::Rejected(err));
+                    self.mempool
+                        .remove(&txn_id)
+                        .map_err(|err| NodeError::Other(err.to_string()))?;
+                }
+            }
+        }
+
+        let expired = self
+            .mempool
+            .prune_expired()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        Ok(restored.saturating_sub(expired))
+    }
+
     /// Return the number of key-value pairs in the map.
     ///
     pub fn mempool_len(&self) -> usize {
@@ -443,10 +496,6 @@ impl StateManager {
         Ok(())
     }
 
-    pub fn handle_quorum_members_received(&mut self, quorum_members: QuorumMembers) {
-        self.dag.set_quorum_members(quorum_members)
-    }
-
     pub fn get_claims(&self, claim_hashes: Vec<ClaimHash>) -> Result<Claims> {
         Ok(self
             .database
@@ -475,6 +524,17 @@ impl StateManager {
             .collect())
     }
 
+    /// Returns the claim stored under `hash` in the current claim trie.
+    pub fn get_claim(&self, hash: &ClaimHash) -> Result<Claim> {
+        let claim = self
+            .database
+            .claim_store_factory()
+            .handle()
+            .get_by_hash(hash)?;
+
+        Ok(claim)
+    }
+
     pub fn update_account(&mut self, update_args: UpdateArgs) -> Result<()> {
         self.database
             .update_account(update_args)
@@ -1,6 +1,5 @@
 mod dag;
 mod manager;
-mod utils;
 
 pub use dag::*;
 pub use manager::*;
@@ -13,25 +12,30 @@ mod tests {
         sync::{Arc, RwLock},
     };
 
-    use block::{Block, BlockHash};
+    use block::{Block, BlockHash, Certificate, ConvergenceBlock, ProposalBlock};
     use bulldag::{graph::BullDag, vertex::Vertex};
+    use hbbft::{
+        crypto::{poly::Poly, Fr, FrRepr, PublicKeySet, SecretKeySet},
+        pairing::PrimeField,
+    };
 
     use mempool::LeftRightMempool;
     use miner::test_helpers::{create_address, create_claim};
-    use primitives::Address;
+    use primitives::{Address, NodeId, QuorumKind, RawSignature};
     use serial_test::serial;
     use signer::engine::SignerEngine;
 
     use storage::storage_utils::remove_vrrb_data_dir;
-    use storage::vrrbdb::{VrrbDb, VrrbDbConfig};
+    use storage::vrrbdb::{AccessMode, VrrbDb, VrrbDbConfig};
 
     use vrrb_core::transactions::TransactionKind;
     use vrrb_core::{account::Account, claim::Claim, keypair::KeyPair};
 
     use super::*;
     use crate::test_utils::{
-        create_keypair, produce_accounts, produce_convergence_block, produce_genesis_block,
-        produce_proposal_blocks,
+        create_keypair, dummy_convergence_block, dummy_proposal_block, produce_accounts,
+        produce_convergence_block_on, produce_genesis_block, produce_proposal_blocks,
+        produce_random_claim,
     };
 
     #[tokio::test]
@@ -42,7 +46,7 @@ mod tests {
 
         let db_config = VrrbDbConfig::default();
 
-        let db = VrrbDb::new(db_config);
+        let db = VrrbDb::new(db_config).unwrap();
         let mempool = LeftRightMempool::default();
 
         let dag: Arc<RwLock<BullDag<Block, String>>> = Arc::new(RwLock::new(BullDag::new()));
@@ -59,6 +63,7 @@ mod tests {
             database: db,
             dag: dag.clone(),
             claim,
+            mempool_journal: None,
         });
 
         state_module
@@ -72,18 +77,20 @@ mod tests {
     #[serial]
     async fn vrrbdb_should_update_with_new_block() {
         let db_config = VrrbDbConfig::default().with_path(std::env::temp_dir().join("db"));
-        let db = VrrbDb::new(db_config);
+        let db = VrrbDb::new(db_config).unwrap();
         let mempool = LeftRightMempool::default();
 
         let accounts: Vec<(Address, Option<Account>)> = produce_accounts(5);
         let dag: StateDag = Arc::new(RwLock::new(BullDag::new()));
 
         let keypair = KeyPair::random();
-        let sig_engine = SignerEngine::new(
+        let mut sig_engine = SignerEngine::new(
             *keypair.get_miner_public_key(),
             *keypair.get_miner_secret_key(),
         );
         let pk = *keypair.get_miner_public_key();
+        // NOTE: proposals are built on behalf of the default node id
+        sig_engine.set_quorum_members(vec![(QuorumKind::Farmer, vec![(NodeId::default(), pk)])]);
         let addr = create_address(&pk);
         let ip_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
         let signature = Claim::signature_for_valid_claim(
@@ -99,6 +106,7 @@ mod tests {
             database: db,
             claim,
             dag: dag.clone(),
+            mempool_journal: None,
         };
         let mut state_module = StateManager::new(state_config);
         let state_res = state_module.extend_accounts(accounts.clone());
@@ -112,7 +120,8 @@ mod tests {
             guard.add_vertex(&gvtx);
         }
 
-        let proposals = produce_proposal_blocks(genesis.hash, accounts.clone(), 5, 5, sig_engine);
+        // NOTE: a single proposal, every sender spends its next nonce once
+        let proposals = produce_proposal_blocks(genesis.hash, accounts.clone(), 1, 5, sig_engine);
 
         let edges: Vec<(Vertex<Block, BlockHash>, Vertex<Block, BlockHash>)> = {
             proposals
@@ -131,21 +140,527 @@ mod tests {
                 .for_each(|(source, reference)| guard.add_edge(&(source, reference)));
         }
 
-        let block_hash = produce_convergence_block(dag).unwrap();
-        state_module.update_state(block_hash).unwrap();
+        let state_root_hash = state_module.state_root_hash().unwrap();
+        let block_hash = produce_convergence_block_on(dag.clone(), state_root_hash).unwrap();
+        let block = match dag
+            .read()
+            .unwrap()
+            .get_vertex(block_hash)
+            .unwrap()
+            .get_data()
+        {
+            Block::Convergence { block } => block,
+            _ => panic!("expected a convergence block"),
+        };
 
-        state_module.commit();
+        state_module.apply_confirmed_block(&block).unwrap();
 
         let handle = state_module.read_handle();
         let store = handle.state_store_values().unwrap();
 
         for (address, _) in accounts.iter() {
-            let account = store.get(address).unwrap();
-            let digests = account.digests().clone();
+            assert_eq!(store.get(address).unwrap().nonce(), 1);
+        }
+
+        assert_eq!(
+            handle.transaction_store_values().unwrap().len(),
+            accounts.len()
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn dag_is_restored_from_the_database() {
+        let db_config = VrrbDbConfig::default()
+            .with_path(std::env::temp_dir().join(format!("dag-{}", uuid::Uuid::new_v4())));
+
+        let keypair = KeyPair::random();
+        let mut sig_engine = SignerEngine::new(
+            *keypair.get_miner_public_key(),
+            *keypair.get_miner_secret_key(),
+        );
+        let pk = *keypair.get_miner_public_key();
+        // NOTE: proposals are built on behalf of the default node id, which
+        // also certifies the genesis block
+        sig_engine.set_quorum_members(vec![
+            (QuorumKind::Farmer, vec![(NodeId::default(), pk)]),
+            (QuorumKind::Harvester, vec![(NodeId::default(), pk)]),
+        ]);
+        let addr = create_address(&pk);
+        let ip_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+        let signature = Claim::signature_for_valid_claim(
+            pk,
+            ip_address,
+            keypair.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+        let claim = create_claim(&pk, &addr, ip_address, signature);
+
+        let mut genesis = produce_genesis_block();
+        genesis.certificate = Some(Certificate {
+            signatures: vec![(NodeId::default(), sig_engine.sign(&genesis.hash).unwrap())],
+            inauguration: None,
+            root_hash: genesis.header.txn_hash.clone(),
+            block_hash: genesis.hash.clone(),
+            threshold_signature: None,
+        });
+        let proposals = produce_proposal_blocks(
+            genesis.hash.clone(),
+            produce_accounts(3),
+            3,
+            3,
+            sig_engine.clone(),
+        );
 
-            assert_eq!(digests.get_sent().len(), 5);
-            assert_eq!(digests.get_recv().len(), 5);
-            assert_eq!(digests.get_stake().len(), 0);
+        {
+            let mut state_module = StateManager::new(StateManagerConfig {
+                mempool: LeftRightMempool::default(),
+                database: VrrbDb::new(db_config.clone()).unwrap(),
+                claim: claim.clone(),
+                dag: Arc::new(RwLock::new(BullDag::new())),
+                mempool_journal: None,
+            });
+
+            state_module
+                .dag
+                .append_genesis(&genesis, &sig_engine)
+                .unwrap();
+            for proposal in proposals.iter() {
+                state_module
+                    .dag
+                    .append_proposal(proposal, sig_engine.clone())
+                    .unwrap();
+            }
         }
+
+        let dag: StateDag = Arc::new(RwLock::new(BullDag::new()));
+        let mut state_module = StateManager::new(StateManagerConfig {
+            mempool: LeftRightMempool::default(),
+            database: VrrbDb::new(db_config).unwrap(),
+            claim,
+            dag: dag.clone(),
+            mempool_journal: None,
+        });
+
+        assert_eq!(state_module.restore_dag().unwrap(), 4);
+        assert_eq!(
+            state_module.dag.last_confirmed_block_header(),
+            Some(genesis.header.clone())
+        );
+
+        let guard = dag.read().unwrap();
+        let genesis_vertex = guard.get_vertex(genesis.hash.clone()).unwrap();
+        for proposal in proposals.iter() {
+            let vertex = guard.get_vertex(proposal.hash.clone()).unwrap();
+            assert!(vertex
+                .get_sources()
+                .iter()
+                .any(|source| source.to_string() == genesis.hash));
+        }
+        assert_eq!(genesis_vertex.get_data().hash(), genesis.hash);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn confirmed_blocks_missing_from_state_are_applied_on_restore() {
+        let db_config = VrrbDbConfig::default()
+            .with_path(std::env::temp_dir().join(format!("dag-{}", uuid::Uuid::new_v4())));
+        let claim = produce_random_claim(1);
+        let mut harvesters = harvester_sig_engines(3);
+        let (_, verifier) = harvesters[2].clone();
+        let mut block = dummy_convergence_block();
+
+        {
+            let mut state_module = StateManager::new(StateManagerConfig {
+                mempool: LeftRightMempool::default(),
+                database: VrrbDb::new(db_config.clone()).unwrap(),
+                claim: claim.clone(),
+                dag: Arc::new(RwLock::new(BullDag::new())),
+                mempool_journal: None,
+            });
+
+            block.header.state_root_hash = state_module.state_root_hash().unwrap();
+            block.certificate = Some(certify(&block, &mut harvesters[..2], &block.hash));
+
+            // NOTE: the node stops right after confirming the block, before
+            // its state is applied
+            state_module
+                .dag
+                .append_convergence(&block, &verifier)
+                .unwrap();
+            assert!(state_module
+                .read_handle()
+                .state_version_at_height(block.header.block_height)
+                .is_err());
+        }
+
+        let mut state_module = StateManager::new(StateManagerConfig {
+            mempool: LeftRightMempool::default(),
+            database: VrrbDb::new(db_config).unwrap(),
+            claim,
+            dag: Arc::new(RwLock::new(BullDag::new())),
+            mempool_journal: None,
+        });
+
+        assert_eq!(state_module.restore_dag().unwrap(), 1);
+        let version = state_module
+            .read_handle()
+            .state_version_at_height(block.header.block_height)
+            .unwrap();
+        assert_eq!(version.block_hash, block.hash);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn imported_snapshots_confirm_and_apply_their_block() {
+        let mut harvesters = harvester_sig_engines(3);
+        let (_, verifier) = harvesters[2].clone();
+        let new_state_module =
+            || {
+                StateManager::new(StateManagerConfig {
+                    mempool: LeftRightMempool::default(),
+                    database: VrrbDb::new(VrrbDbConfig::default().with_path(
+                        std::env::temp_dir().join(format!("dag-{}", uuid::Uuid::new_v4())),
+                    ))
+                    .unwrap(),
+                    claim: produce_random_claim(1),
+                    dag: Arc::new(RwLock::new(BullDag::new())),
+                    mempool_journal: None,
+                })
+            };
+
+        let mut exporter = new_state_module();
+        let mut block = dummy_convergence_block();
+        block.header.state_root_hash = exporter.state_root_hash().unwrap();
+        block.hash = block.header.convergence_block_hash();
+        block.certificate = Some(certify(&block, &mut harvesters[..2], &block.hash));
+
+        exporter.dag.append_convergence(&block, &verifier).unwrap();
+        let snapshot = exporter.export_state(block.header.block_height).unwrap();
+
+        let mut importer = new_state_module();
+        importer.import_state(&snapshot, &verifier).unwrap();
+
+        assert_eq!(
+            importer.dag.last_confirmed_block(),
+            Some(Block::Convergence {
+                block: block.clone()
+            })
+        );
+        assert!(importer.dag.contains_block(&block.hash));
+        assert!(importer
+            .dag
+            .block_store()
+            .get_by_height(block.header.block_height)
+            .unwrap()
+            .is_some());
+        let version = importer
+            .read_handle()
+            .state_version_at_height(block.header.block_height)
+            .unwrap();
+        assert_eq!(version.block_hash, block.hash);
+    }
+
+    /// Builds the signer engines of a harvester quorum of `n` members, each
+    /// aware of every member's key
+    fn harvester_sig_engines(n: usize) -> Vec<(NodeId, SignerEngine)> {
+        let keypairs: Vec<(NodeId, KeyPair)> = (0..n)
+            .map(|i| (format!("harvester-{i}"), KeyPair::random()))
+            .collect();
+
+        let members: Vec<(NodeId, _)> = keypairs
+            .iter()
+            .map(|(node_id, keypair)| (node_id.clone(), *keypair.get_miner_public_key()))
+            .collect();
+
+        keypairs
+            .into_iter()
+            .map(|(node_id, keypair)| {
+                let mut sig_engine = SignerEngine::new(
+                    *keypair.get_miner_public_key(),
+                    *keypair.get_miner_secret_key(),
+                );
+                sig_engine.set_quorum_members(vec![(QuorumKind::Harvester, members.clone())]);
+
+                (node_id, sig_engine)
+            })
+            .collect()
+    }
+
+    fn certify(
+        block: &ConvergenceBlock,
+        signers: &mut [(NodeId, SignerEngine)],
+        payload: &str,
+    ) -> Certificate {
+        Certificate {
+            signatures: signers
+                .iter_mut()
+                .map(|(node_id, sig_engine)| (node_id.clone(), sig_engine.sign(payload).unwrap()))
+                .collect(),
+            inauguration: None,
+            root_hash: block.header.txn_hash.clone(),
+            block_hash: block.hash.clone(),
+            threshold_signature: None,
+        }
+    }
+
+    fn certified_block(certificate: Certificate) -> ConvergenceBlock {
+        ConvergenceBlock {
+            certificate: Some(certificate),
+            ..dummy_convergence_block()
+        }
+    }
+
+    #[test]
+    fn convergence_blocks_with_valid_certificates_are_appended() {
+        let mut dag = DagModule::new(
+            Arc::new(RwLock::new(BullDag::new())),
+            produce_random_claim(1),
+        );
+        let mut harvesters = harvester_sig_engines(3);
+        let (_, verifier) = harvesters[2].clone();
+
+        let block = dummy_convergence_block();
+        let certificate = certify(&block, &mut harvesters[..2], &block.hash);
+        let block = certified_block(certificate);
+
+        let appended = dag.append_convergence(&block, &verifier).unwrap();
+
+        assert_eq!(appended, Some(block.clone()));
+        assert!(dag.contains_block(&block.hash));
+    }
+
+    #[test]
+    #[serial]
+    fn blocks_that_fail_to_persist_are_rejected() {
+        let db_config = VrrbDbConfig::default()
+            .with_path(std::env::temp_dir().join(format!("dag-{}", uuid::Uuid::new_v4())));
+        drop(VrrbDb::new(db_config.clone()).unwrap());
+        let db = VrrbDb::open(db_config, AccessMode::ReadOnly).unwrap();
+
+        let mut dag = DagModule::new(
+            Arc::new(RwLock::new(BullDag::new())),
+            produce_random_claim(1),
+        )
+        .with_block_store(db.block_store());
+        let mut harvesters = harvester_sig_engines(3);
+        let (_, verifier) = harvesters[2].clone();
+
+        let block = dummy_convergence_block();
+        let certificate = certify(&block, &mut harvesters[..2], &block.hash);
+        let block = certified_block(certificate);
+
+        assert!(matches!(
+            dag.append_convergence(&block, &verifier),
+            Err(DagError::Persistence { .. })
+        ));
+        assert!(!dag.contains_block(&block.hash));
+        assert!(dag.last_confirmed_block().is_none());
+    }
+
+    #[test]
+    fn convergence_blocks_without_certificates_stay_pending() {
+        let mut dag = DagModule::new(
+            Arc::new(RwLock::new(BullDag::new())),
+            produce_random_claim(1),
+        );
+        let harvesters = harvester_sig_engines(3);
+        let block = dummy_convergence_block();
+
+        assert_eq!(
+            dag.append_convergence(&block, &harvesters[0].1).unwrap(),
+            None
+        );
+        assert!(!dag.contains_block(&block.hash));
+        assert!(dag.get_pending_convergence_block_mut(&block.hash).is_some());
+    }
+
+    #[test]
+    fn convergence_blocks_with_forged_certificates_are_rejected() {
+        let mut dag = DagModule::new(
+            Arc::new(RwLock::new(BullDag::new())),
+            produce_random_claim(1),
+        );
+        let mut harvesters = harvester_sig_engines(3);
+        let (_, verifier) = harvesters[0].clone();
+        let block = dummy_convergence_block();
+
+        let mut outsiders = harvester_sig_engines(2);
+        outsiders[0].0 = "outsider".to_string();
+        let mut forged = certify(&block, &mut harvesters[..1], &block.hash);
+        forged
+            .signatures
+            .extend(certify(&block, &mut outsiders[..1], &block.hash).signatures);
+        assert!(matches!(
+            dag.append_convergence(&certified_block(forged), &verifier),
+            Err(DagError::NotAHarvester(signer)) if signer == "outsider"
+        ));
+
+        let mut duplicated = certify(&block, &mut harvesters[..1], &block.hash);
+        duplicated.signatures.push(duplicated.signatures[0].clone());
+        assert!(matches!(
+            dag.append_convergence(&certified_block(duplicated), &verifier),
+            Err(DagError::DuplicateSigner(_))
+        ));
+
+        let too_few = certify(&block, &mut harvesters[..1], &block.hash);
+        assert!(matches!(
+            dag.append_convergence(&certified_block(too_few), &verifier),
+            Err(DagError::ThresholdNotReached {
+                signatures: 1,
+                threshold: 2
+            })
+        ));
+
+        let wrong_payload = certify(&block, &mut harvesters[..2], "another block");
+        assert!(matches!(
+            dag.append_convergence(&certified_block(wrong_payload), &verifier),
+            Err(DagError::InvalidSignature { .. })
+        ));
+
+        let mut mismatched = certify(&block, &mut harvesters[..2], &block.hash);
+        mismatched.block_hash = "another block".to_string();
+        assert!(matches!(
+            dag.append_convergence(&certified_block(mismatched), &verifier),
+            Err(DagError::CertificateMismatch { .. })
+        ));
+
+        assert!(!dag.contains_block(&block.hash));
+    }
+
+    /// Builds the public key set of a harvester quorum along with the
+    /// threshold signature over `payload` its members would combine
+    fn threshold_sign(payload: &str) -> (PublicKeySet, RawSignature) {
+        let secret = Fr::from_repr(FrRepr::from(42)).unwrap();
+        let secret_key_set = SecretKeySet::from(Poly::constant(secret));
+        let signature = secret_key_set.secret_key().sign(payload);
+
+        (secret_key_set.public_keys(), signature.to_bytes().to_vec())
+    }
+
+    #[test]
+    fn certificates_need_a_threshold_signature_once_the_harvester_key_set_is_known() {
+        let mut dag = DagModule::new(
+            Arc::new(RwLock::new(BullDag::new())),
+            produce_random_claim(1),
+        );
+        let mut harvesters = harvester_sig_engines(3);
+        let (_, verifier) = harvesters[2].clone();
+
+        let block = dummy_convergence_block();
+        let certificate = certify(&block, &mut harvesters[..2], &block.hash);
+        let (public_key_set, threshold_signature) = threshold_sign(&block.hash);
+        let (_, forged_signature) = threshold_sign("another block");
+
+        dag.set_harvester_public_key_set(public_key_set);
+
+        assert!(matches!(
+            dag.verify_certificate(&certificate, &verifier),
+            Err(DagError::MissingThresholdSignature(_))
+        ));
+
+        let forged = Certificate {
+            threshold_signature: Some(forged_signature),
+            ..certificate.clone()
+        };
+        assert!(matches!(
+            dag.verify_certificate(&forged, &verifier),
+            Err(DagError::InvalidThresholdSignature(_))
+        ));
+
+        let block = certified_block(Certificate {
+            threshold_signature: Some(threshold_signature),
+            ..certificate
+        });
+        assert_eq!(
+            dag.append_convergence(&block, &verifier).unwrap(),
+            Some(block.clone())
+        );
+        assert!(dag.contains_block(&block.hash));
+    }
+
+    #[test]
+    fn genesis_blocks_stay_pending_until_certified() {
+        let mut dag = DagModule::new(
+            Arc::new(RwLock::new(BullDag::new())),
+            produce_random_claim(1),
+        );
+        let mut harvesters = harvester_sig_engines(3);
+        let (_, verifier) = harvesters[2].clone();
+        let genesis = produce_genesis_block();
+
+        assert_eq!(dag.append_genesis(&genesis, &verifier).unwrap(), None);
+        assert!(!dag.contains_block(&genesis.hash));
+        assert!(dag.last_confirmed_block().is_none());
+
+        let unsigned = Certificate {
+            signatures: vec![],
+            inauguration: None,
+            root_hash: genesis.header.txn_hash.clone(),
+            block_hash: genesis.hash.clone(),
+            threshold_signature: None,
+        };
+        assert!(matches!(
+            dag.append_certificate_to_genesis_block(&genesis.hash, &unsigned, &verifier),
+            Err(DagError::ThresholdNotReached { .. })
+        ));
+        assert!(!dag.contains_block(&genesis.hash));
+
+        let certificate = Certificate {
+            signatures: harvesters[..2]
+                .iter_mut()
+                .map(|(node_id, sig_engine)| {
+                    (node_id.clone(), sig_engine.sign(&genesis.hash).unwrap())
+                })
+                .collect(),
+            ..unsigned
+        };
+        let certified = dag
+            .append_certificate_to_genesis_block(&genesis.hash, &certificate, &verifier)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(certified.certificate, Some(certificate));
+        assert!(dag.contains_block(&genesis.hash));
+        assert_eq!(
+            dag.last_confirmed_block_header(),
+            Some(genesis.header.clone())
+        );
+    }
+
+    #[test]
+    fn proposal_blocks_from_non_farmers_or_unsigned_proposers_are_rejected() {
+        let mut dag = DagModule::new(
+            Arc::new(RwLock::new(BullDag::new())),
+            produce_random_claim(1),
+        );
+        let keypair = KeyPair::random();
+        let mut sig_engine = SignerEngine::new(
+            *keypair.get_miner_public_key(),
+            *keypair.get_miner_secret_key(),
+        );
+
+        let proposal = dummy_proposal_block(sig_engine.clone());
+        assert!(matches!(
+            dag.append_proposal(&proposal, sig_engine.clone()),
+            Err(DagError::UnknownProposer(_))
+        ));
+
+        // NOTE: proposals are built on behalf of the default node id
+        let pk = *keypair.get_miner_public_key();
+        sig_engine.set_quorum_members(vec![(QuorumKind::Harvester, vec![(NodeId::default(), pk)])]);
+        assert!(matches!(
+            dag.append_proposal(&proposal, sig_engine.clone()),
+            Err(DagError::UnknownProposer(_))
+        ));
+
+        let unsigned = ProposalBlock {
+            signature: None,
+            ..proposal
+        };
+        assert!(matches!(
+            dag.append_proposal(&unsigned, sig_engine),
+            Err(DagError::MissingSignature(_))
+        ));
     }
 }
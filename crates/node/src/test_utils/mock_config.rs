@@ -51,6 +51,7 @@ pub fn create_mock_full_node_config() -> NodeConfig {
         .kademlia_liveness_address(kademlia_liveness_address)
         .public_ip_address(public_ip_address)
         .disable_networking(false)
+        .in_memory_storage(true)
         .quorum_config(None)
         .threshold_config(ThresholdConfig::default())
         .whitelisted_nodes(vec![])
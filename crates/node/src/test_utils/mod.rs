@@ -19,6 +19,7 @@ pub use mock_config::*;
 pub use node_network::*;
 use primitives::{generate_account_keypair, Address, NodeId, NodeType, QuorumKind};
 use rand::{seq::SliceRandom, thread_rng};
+use ritelinked::LinkedHashMap;
 pub use runtime_network::*;
 use secp256k1::{Message, PublicKey, SecretKey};
 use sha256::digest;
@@ -163,6 +164,7 @@ pub fn produce_proposal_blocks(
                 0,
                 txn_list,
                 claim_list,
+                LinkedHashMap::new(),
                 from,
                 sig_engine.clone(),
             )
@@ -171,9 +173,19 @@ pub fn produce_proposal_blocks(
 }
 
 pub fn produce_convergence_block(dag: Arc<RwLock<BullDag<Block, BlockHash>>>) -> Option<BlockHash> {
+    produce_convergence_block_on(dag, String::new())
+}
+
+/// Mines a `ConvergenceBlock` over the proposal blocks in the DAG, built on
+/// top of the state with the given root hash
+pub fn produce_convergence_block_on(
+    dag: Arc<RwLock<BullDag<Block, BlockHash>>>,
+    state_root_hash: String,
+) -> Option<BlockHash> {
     let keypair = Keypair::random();
     let mut miner = miner::test_helpers::create_miner_from_keypair(&keypair);
     miner.dag = dag.clone();
+    miner.state_root_hash = state_root_hash;
     let last_block = miner::test_helpers::get_genesis_block_from_dag(dag.clone());
 
     if let Some(block) = last_block {
@@ -253,6 +265,8 @@ pub fn create_txn_from_accounts(
         txn.token().clone(),
         txn.amount(),
         txn.nonce(),
+        None,
+        0,
     );
 
     let _digest = TransactionDigest::from(txn_digest_vec);
@@ -303,6 +317,8 @@ pub fn create_txn_from_accounts_invalid_signature(
         txn.token().clone(),
         txn.amount(),
         txn.nonce(),
+        None,
+        0,
     );
 
     let _digest = TransactionDigest::from(txn_digest_vec);
@@ -351,6 +367,8 @@ pub fn create_txn_from_accounts_invalid_timestamp(
         txn.token().clone(),
         txn.amount(),
         txn.nonce(),
+        None,
+        0,
     );
 
     let _digest = TransactionDigest::from(txn_digest_vec);
@@ -554,7 +572,7 @@ fn form_groups_with_peer_data(
 pub fn create_sender_receiver_addresses() -> ((Account, Address), Address) {
     let (_, sender_public_key) = generate_account_keypair();
     let mut sender_account = Account::new(sender_public_key.into());
-    let update_field = AccountField::Credits(100000);
+    let update_field = AccountField::Credits(INITIAL_ACCOUNT_CREDITS);
     let _ = sender_account.update_field(update_field);
     let sender_address = Address::new(sender_public_key);
 
@@ -713,6 +731,7 @@ pub fn dummy_convergence_block() -> ConvergenceBlock {
             txn_hash: Default::default(),
             miner_claim: produce_random_claim(22),
             claim_list_hash: Default::default(),
+            state_root_hash: Default::default(),
             block_reward: Default::default(),
             next_block_reward: Default::default(),
             miner_signature: signature,
@@ -731,7 +750,7 @@ pub fn dummy_proposal_block(sig_engine: signer::engine::SignerEngine) -> Proposa
     let kp2 = Keypair::random();
     let address2 = Address::new(kp2.miner_kp.1);
     let mut account1 = Account::new(address1.clone());
-    let update_field = AccountField::Credits(100000);
+    let update_field = AccountField::Credits(INITIAL_ACCOUNT_CREDITS);
     let _ = account1.update_field(update_field.clone());
     let mut account2 = Account::new(address2.clone());
     let _ = account2.update_field(update_field.clone());
@@ -755,7 +774,7 @@ pub fn dummy_proposal_block_and_accounts(
     let kp2 = Keypair::random();
     let address2 = Address::new(kp2.miner_kp.1);
     let mut account1 = Account::new(address1.clone());
-    let update_field = AccountField::Credits(100000);
+    let update_field = AccountField::Credits(INITIAL_ACCOUNT_CREDITS);
     let _ = account1.update_field(update_field.clone());
     let mut account2 = Account::new(address2.clone());
     let _ = account2.update_field(update_field.clone());
@@ -95,6 +95,8 @@ pub async fn create_test_network_from_config(n: u16, base_config: Option<NodeCon
     config.whitelisted_nodes = whitelisted_nodes.clone();
     config.prometheus_bind_port = port;
 
+    let bootstrap_node_id = config.id.clone();
+    let bootstrap_public_key = config.keypair.validator_public_key_owned();
     let node_0 = Node::start(config).await.unwrap();
 
     let bootstrap_peer_data = BootstrapPeerData {
@@ -102,6 +104,8 @@ pub async fn create_test_network_from_config(n: u16, base_config: Option<NodeCon
         udp_gossip_addr: node_0.udp_gossip_address(),
         raptorq_gossip_addr: node_0.raptorq_gossip_address(),
         kademlia_liveness_addr: node_0.kademlia_liveness_address(),
+        node_id: Some(bootstrap_node_id),
+        validator_public_key: Some(bootstrap_public_key),
     };
 
     nodes.push(node_0);
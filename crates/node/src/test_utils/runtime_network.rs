@@ -101,6 +101,8 @@ pub async fn create_node_runtime_network(
         udp_gossip_addr: node_0.config.udp_gossip_address,
         raptorq_gossip_addr: node_0.config.raptorq_gossip_address,
         kademlia_liveness_addr: node_0.config.kademlia_liveness_address,
+        node_id: Some(node_0.config.id.clone()),
+        validator_public_key: Some(node_0.config.keypair.validator_public_key_owned()),
     };
 
     nodes.push_back(node_0);
@@ -85,9 +85,16 @@ async fn genesis_block_can_be_certified() {
         })
         .collect();
     let mut chosen_harvester = harvesters.pop().unwrap();
+    // NOTE: the genesis block stays pending within the DAG until certified
+    let sig_engine = chosen_harvester.consensus_driver.sig_engine();
     assert!(chosen_harvester
         .state_driver
-        .append_genesis(&genesis_block)
+        .handle_block_received(
+            &mut Block::Genesis {
+                block: genesis_block.clone(),
+            },
+            sig_engine,
+        )
         .is_ok());
     let mut sigs: Vec<Signature> = Vec::new();
     for node in harvesters.iter_mut() {
@@ -98,7 +105,16 @@ async fn genesis_block_can_be_certified() {
             .await
             .unwrap(),
         );
-        assert!(node.state_driver.append_genesis(&genesis_block).is_ok());
+        let sig_engine = node.consensus_driver.sig_engine();
+        assert!(node
+            .state_driver
+            .handle_block_received(
+                &mut Block::Genesis {
+                    block: genesis_block.clone(),
+                },
+                sig_engine,
+            )
+            .is_ok());
     }
     let mut res: Result<Certificate, NodeError> = Err(NodeError::Other("".to_string()));
     for (sig, harvester) in sigs.into_iter().zip(harvesters.iter()) {
@@ -182,9 +198,16 @@ async fn all_nodes_append_certified_genesis_block_to_dag() {
             .unwrap();
     });
     let mut chosen_harvester = harvesters.pop().unwrap();
+    // NOTE: the genesis block stays pending within the DAG until certified
+    let sig_engine = chosen_harvester.consensus_driver.sig_engine();
     assert!(chosen_harvester
         .state_driver
-        .append_genesis(&genesis_block)
+        .handle_block_received(
+            &mut Block::Genesis {
+                block: genesis_block.clone(),
+            },
+            sig_engine,
+        )
         .is_ok());
     let mut sigs: Vec<Signature> = Vec::new();
     for node in harvesters.iter_mut() {
@@ -195,7 +218,16 @@ async fn all_nodes_append_certified_genesis_block_to_dag() {
             .await
             .unwrap(),
         );
-        assert!(node.state_driver.append_genesis(&genesis_block).is_ok());
+        let sig_engine = node.consensus_driver.sig_engine();
+        assert!(node
+            .state_driver
+            .handle_block_received(
+                &mut Block::Genesis {
+                    block: genesis_block.clone(),
+                },
+                sig_engine,
+            )
+            .is_ok());
     }
     let mut res: Result<Certificate, NodeError> = Err(NodeError::Other("".to_string()));
     for (sig, harvester) in sigs.into_iter().zip(harvesters.iter()) {
@@ -102,9 +102,10 @@ async fn harvester_nodes_form_certificate() {
         .collect();
     let convergence_block = dummy_convergence_block();
     let mut chosen_harvester = harvesters.pop().unwrap();
-    let _ = chosen_harvester
-        .state_driver
-        .append_convergence(&convergence_block);
+    let _ = chosen_harvester.state_driver.append_convergence(
+        &convergence_block,
+        &chosen_harvester.consensus_driver.sig_engine(),
+    );
     let mut sigs: Vec<Signature> = Vec::new();
     for harvester in harvesters.iter_mut() {
         // 2 of 3 harvester nodes sign a convergence block
@@ -116,7 +117,7 @@ async fn harvester_nodes_form_certificate() {
         );
         let _ = harvester
             .state_driver
-            .append_convergence(&convergence_block);
+            .append_convergence(&convergence_block, &harvester.consensus_driver.sig_engine());
     }
     let mut res: Result<Certificate, NodeError> = Err(NodeError::Other("".to_string()));
     // all harvester nodes get the other's signatures
@@ -157,9 +158,10 @@ async fn certificate_formed_includes_pending_quorum() {
     let convergence_block = dummy_convergence_block();
     let mut chosen_harvester = harvesters.pop().unwrap();
 
-    let _ = chosen_harvester
-        .state_driver
-        .append_convergence(&convergence_block);
+    let _ = chosen_harvester.state_driver.append_convergence(
+        &convergence_block,
+        &chosen_harvester.consensus_driver.sig_engine(),
+    );
 
     let mut sigs: Vec<Signature> = Vec::new();
 
@@ -173,7 +175,7 @@ async fn certificate_formed_includes_pending_quorum() {
         );
         let _ = harvester
             .state_driver
-            .append_convergence(&convergence_block);
+            .append_convergence(&convergence_block, &harvester.consensus_driver.sig_engine());
     }
 
     let mut eligible_claims = produce_random_claims(21)
@@ -265,9 +267,10 @@ async fn all_nodes_append_certificate_to_convergence_block() {
             .unwrap();
     });
     let mut chosen_harvester = harvesters.pop().unwrap();
-    let _ = chosen_harvester
-        .state_driver
-        .append_convergence(&convergence_block);
+    let _ = chosen_harvester.state_driver.append_convergence(
+        &convergence_block,
+        &chosen_harvester.consensus_driver.sig_engine(),
+    );
     let mut sigs: Vec<Signature> = Vec::new();
     for harvester in harvesters.iter_mut() {
         // 2 of 3 harvester nodes sign a convergence block
@@ -279,7 +282,7 @@ async fn all_nodes_append_certificate_to_convergence_block() {
         );
         let _ = harvester
             .state_driver
-            .append_convergence(&convergence_block);
+            .append_convergence(&convergence_block, &harvester.consensus_driver.sig_engine());
     }
     let mut res: Result<Certificate, NodeError> = Err(NodeError::Other("".to_string()));
     // all harvester nodes get the other's signatures
@@ -369,9 +372,10 @@ async fn all_nodes_append_certified_convergence_block_to_dag() {
             .unwrap();
     });
     let mut chosen_harvester = harvesters.pop().unwrap();
-    let _ = chosen_harvester
-        .state_driver
-        .append_convergence(&convergence_block);
+    let _ = chosen_harvester.state_driver.append_convergence(
+        &convergence_block,
+        &chosen_harvester.consensus_driver.sig_engine(),
+    );
     let mut sigs: Vec<Signature> = Vec::new();
     for harvester in harvesters.iter_mut() {
         // 2 of 3 harvester nodes sign a convergence block
@@ -383,7 +387,7 @@ async fn all_nodes_append_certified_convergence_block_to_dag() {
         );
         let _ = harvester
             .state_driver
-            .append_convergence(&convergence_block);
+            .append_convergence(&convergence_block, &harvester.consensus_driver.sig_engine());
     }
     let mut res: Result<Certificate, NodeError> = Err(NodeError::Other("".to_string()));
     // all harvester nodes get the other's signatures
@@ -484,6 +488,13 @@ async fn all_nodes_update_state_upon_successfully_appending_certified_convergenc
         node.state_driver
             .insert_account(address2.clone(), account2.clone())
             .unwrap();
+    });
+
+    // NOTE: every node starts from the same state, the block is built on top
+    // of it once the accounts were inserted
+    convergence_block.header.state_root_hash = harvesters[0].state_root_hash().unwrap();
+
+    harvesters.iter_mut().for_each(|node| {
         node.consensus_driver.miner_election_results = Some(miner_election_results.clone());
         node.state_driver.write_vertex(&vtx).unwrap();
         node.state_driver
@@ -516,9 +527,10 @@ async fn all_nodes_update_state_upon_successfully_appending_certified_convergenc
     });
 
     let mut chosen_harvester = harvesters.pop().unwrap();
-    let _ = chosen_harvester
-        .state_driver
-        .append_convergence(&convergence_block);
+    let _ = chosen_harvester.state_driver.append_convergence(
+        &convergence_block,
+        &chosen_harvester.consensus_driver.sig_engine(),
+    );
 
     let mut sigs: Vec<Signature> = Vec::new();
     for harvester in harvesters.iter_mut() {
@@ -531,7 +543,7 @@ async fn all_nodes_update_state_upon_successfully_appending_certified_convergenc
         );
         let _ = harvester
             .state_driver
-            .append_convergence(&convergence_block);
+            .append_convergence(&convergence_block, &harvester.consensus_driver.sig_engine());
     }
     let mut res: Result<Certificate, NodeError> = Err(NodeError::Other("".to_string()));
     // all harvester nodes get the other's signatures
@@ -1,6 +1,16 @@
-use node::{test_utils::create_mock_full_node_config, Node};
-use primitives::{generate_account_keypair, Address};
+use block::{Block, Certificate};
+use events::{BlockRequest, DEFAULT_BUFFER};
+use node::{
+    node_runtime::NodeRuntime,
+    test_utils::{
+        create_mock_full_node_config, create_quorum_assigned_node_runtime_network,
+        dummy_convergence_block, dummy_proposal_block,
+    },
+    Node, NodeError,
+};
+use primitives::{generate_account_keypair, Address, QuorumKind, Signature};
 use secp256k1::Message;
+use storage::storage_utils::remove_vrrb_data_dir;
 use vrrb_core::transactions::TransactionKind;
 use vrrb_rpc::rpc::{api::RpcApiClient, client::create_client};
 
@@ -53,3 +63,99 @@ async fn nodes_can_synchronize_state() {
     assert!(vrrb_node_1.stop().await.unwrap());
     assert!(vrrb_node_2.stop().await.unwrap());
 }
+
+#[tokio::test]
+#[serial_test::serial]
+async fn lagging_nodes_catch_up_with_certified_blocks_only() {
+    remove_vrrb_data_dir();
+    let (events_tx, _rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+    let nodes = create_quorum_assigned_node_runtime_network(8, 3, events_tx.clone()).await;
+
+    let (mut harvesters, mut others): (Vec<NodeRuntime>, Vec<NodeRuntime>) = nodes
+        .into_iter()
+        .filter(|nr| !nr.consensus_driver.is_bootstrap_node())
+        .partition(|nr| nr.consensus_driver.quorum_kind() == Some(QuorumKind::Harvester));
+    let mut lagging = others.pop().unwrap();
+
+    let proposal_block = dummy_proposal_block(lagging.consensus_driver.sig_engine());
+    let mut convergence_block = dummy_convergence_block();
+    convergence_block.header.ref_hashes = vec![proposal_block.hash.clone()];
+    convergence_block.header.state_root_hash = lagging.state_root_hash().unwrap();
+
+    // NOTE: every node knows the proposal, but only the harvesters see the
+    // convergence block referencing it
+    let pblock: Block = proposal_block.into();
+    let vtx = pblock.into();
+    lagging.state_driver.write_vertex(&vtx).unwrap();
+    harvesters.iter_mut().for_each(|node| {
+        node.state_driver.write_vertex(&vtx).unwrap();
+        node.state_driver
+            .handle_block_received(
+                &mut Block::Convergence {
+                    block: convergence_block.clone(),
+                },
+                node.consensus_driver.sig_engine(),
+            )
+            .unwrap();
+    });
+
+    let mut chosen_harvester = harvesters.pop().unwrap();
+    let mut sigs: Vec<Signature> = Vec::new();
+    for harvester in harvesters.iter_mut() {
+        sigs.push(
+            harvester
+                .handle_sign_convergence_block(convergence_block.clone())
+                .await
+                .unwrap(),
+        );
+    }
+    let mut res: Result<Certificate, NodeError> = Err(NodeError::Other("".to_string()));
+    for (sig, harvester) in sigs.into_iter().zip(harvesters.iter()) {
+        res = chosen_harvester
+            .handle_harvester_signature_received(
+                convergence_block.hash.clone(),
+                harvester.config.id.clone(),
+                sig,
+            )
+            .await;
+    }
+    let certificate = res.unwrap();
+    chosen_harvester
+        .handle_convergence_block_certificate_created(certificate.clone())
+        .await
+        .unwrap();
+
+    let request = BlockRequest::ByHash(vec![convergence_block.hash.clone()]);
+    let blocks = chosen_harvester
+        .handle_blocks_request_received(&request)
+        .unwrap();
+    assert_eq!(blocks.len(), 1);
+
+    let forge = |signatures: Vec<_>| {
+        let mut certificate = certificate.clone();
+        certificate.signatures = signatures;
+        let mut block = convergence_block.clone();
+        block.certificate = Some(certificate);
+        vec![Block::Convergence { block }]
+    };
+    // NOTE: certificates without signatures, with a signer counted twice or
+    // signed by a node outside of the harvester quorum are all forged
+    let (signer, signature) = certificate.signatures[0].clone();
+    let mut outsider_signed = certificate.signatures.clone();
+    outsider_signed.push((lagging.config.id.clone(), signature.clone()));
+    let forgeries = vec![
+        forge(vec![]),
+        forge(vec![(signer, signature); certificate.signatures.len()]),
+        forge(outsider_signed),
+    ];
+    for forged in forgeries {
+        assert!(lagging.handle_sync_blocks_received(forged).is_err());
+        assert!(
+            !lagging.certified_convergence_block_exists_within_dag(convergence_block.hash.clone())
+        );
+    }
+
+    assert_eq!(lagging.handle_sync_blocks_received(blocks).unwrap(), 1);
+    assert!(lagging.certified_convergence_block_exists_within_dag(convergence_block.hash.clone()));
+    assert_eq!(lagging.chain_tip(), chosen_harvester.chain_tip());
+}
@@ -9,7 +9,9 @@ use crate::{ByteSlice, ByteVec};
 pub const DIGEST_LENGTH: usize = 32;
 
 /// Represents a SHA-256 digest produced from any serializable data type
-#[derive(Debug, Default, Clone, Copy, Hash, Deserialize, Serialize, Eq, PartialEq)]
+#[derive(
+    Debug, Default, Clone, Copy, Hash, Deserialize, Serialize, Eq, PartialEq, PartialOrd, Ord,
+)]
 pub struct Digest([u8; DIGEST_LENGTH]);
 
 impl From<ByteVec> for Digest {
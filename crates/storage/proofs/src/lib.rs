@@ -0,0 +1,109 @@
+//! Merkle inclusion proofs for the values stored in the state and
+//! transaction tries, along with a verifier that only depends on the trie's
+//! hashing scheme so it can be embedded in light clients and bridges.
+
+use patriecia::{KeyHash, RootHash, SparseMerkleProof, Version};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, ProofError>;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ProofError {
+    #[error("failed to encode or decode proof data: {0}")]
+    Encoding(String),
+
+    #[error("proof was generated for root hash {actual}, expected {expected}")]
+    RootHashMismatch { expected: String, actual: String },
+
+    #[error("proof does not prove the given value")]
+    ValueMismatch,
+
+    #[error("proof verification failed: {0}")]
+    Invalid(String),
+}
+
+/// Proof that a value is stored under a key in a trie with a given root
+/// hash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InclusionProof {
+    /// Version of the trie the proof was generated at
+    pub version: Version,
+
+    /// Hex encoded root hash of the trie at `version`
+    pub root_hash: String,
+
+    /// Bincode encoded value the proof was generated for
+    pub value: Vec<u8>,
+
+    /// Bincode encoded sparse Merkle proof linking the value to the root hash
+    pub proof: Vec<u8>,
+}
+
+/// Hashes a key the same way the tries do: the SHA-256 of its bincode
+/// encoding.
+pub fn key_hash<K: Serialize>(key: &K) -> Result<KeyHash> {
+    let key_bytes = bincode::serialize(key).map_err(|err| ProofError::Encoding(err.to_string()))?;
+
+    Ok(KeyHash::with::<Sha256>(key_bytes))
+}
+
+impl InclusionProof {
+    /// Builds a proof out of a sparse Merkle proof produced by the trie.
+    pub fn new(
+        version: Version,
+        root_hash: RootHash,
+        value: Vec<u8>,
+        proof: &SparseMerkleProof<Sha256>,
+    ) -> Result<Self> {
+        let proof =
+            bincode::serialize(proof).map_err(|err| ProofError::Encoding(err.to_string()))?;
+
+        Ok(Self {
+            version,
+            root_hash: hex::encode(root_hash.0),
+            value,
+            proof,
+        })
+    }
+
+    /// Decodes the value the proof was generated for.
+    pub fn decode_value<V: for<'de> Deserialize<'de>>(&self) -> Result<V> {
+        bincode::deserialize(&self.value).map_err(|err| ProofError::Encoding(err.to_string()))
+    }
+
+    /// Verifies that `value` is stored under `key` in the trie whose hex
+    /// encoded root hash is `expected_root_hash`.
+    pub fn verify<K: Serialize, V: Serialize>(
+        &self,
+        expected_root_hash: &str,
+        key: &K,
+        value: &V,
+    ) -> Result<()> {
+        if self.root_hash != expected_root_hash {
+            return Err(ProofError::RootHashMismatch {
+                expected: expected_root_hash.to_string(),
+                actual: self.root_hash.clone(),
+            });
+        }
+
+        let value =
+            bincode::serialize(value).map_err(|err| ProofError::Encoding(err.to_string()))?;
+        if value != self.value {
+            return Err(ProofError::ValueMismatch);
+        }
+
+        let root_hash: [u8; 32] = hex::decode(&self.root_hash)
+            .map_err(|err| ProofError::Encoding(err.to_string()))?
+            .try_into()
+            .map_err(|_| ProofError::Encoding("root hash must be 32 bytes long".to_string()))?;
+
+        let proof: SparseMerkleProof<Sha256> = bincode::deserialize(&self.proof)
+            .map_err(|err| ProofError::Encoding(err.to_string()))?;
+
+        proof
+            .verify(RootHash(root_hash), key_hash(key)?, Some(value))
+            .map_err(|err| ProofError::Invalid(err.to_string()))
+    }
+}
@@ -1,2 +1,3 @@
+pub use storage_proofs;
 pub use storage_utils;
 pub use vrrbdb;
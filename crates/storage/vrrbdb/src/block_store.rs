@@ -0,0 +1,178 @@
+use std::path::PathBuf;
+
+use block::{Block, BlockHash};
+use storage_utils::Result;
+
+use crate::{
+    column_store::{decode, encode, ColumnStore},
+    AccessMode,
+};
+
+/// Column family mapping block hashes to blocks of any kind.
+pub const BLOCKS_COLUMN_FAMILY: &str = "blocks";
+
+/// Column family mapping the height of confirmed blocks, genesis and
+/// convergence, to their hashes.
+pub const BLOCK_HEIGHTS_COLUMN_FAMILY: &str = "block_heights";
+
+/// Column family mapping block hashes to the hashes of the blocks they are
+/// linked from within the DAG.
+pub const BLOCK_EDGES_COLUMN_FAMILY: &str = "block_edges";
+
+const COLUMN_FAMILIES: [&str; 3] = [
+    BLOCKS_COLUMN_FAMILY,
+    BLOCK_HEIGHTS_COLUMN_FAMILY,
+    BLOCK_EDGES_COLUMN_FAMILY,
+];
+
+/// Block appended to the DAG along with the blocks it is linked from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredBlock {
+    pub block: Block,
+    pub sources: Vec<BlockHash>,
+}
+
+/// Blocks appended to the DAG, kept so it can be rebuilt after a restart.
+/// They live outside the tries since they are not part of the state.
+#[derive(Debug, Clone)]
+pub struct BlockStore {
+    store: ColumnStore,
+}
+
+impl Default for BlockStore {
+    fn default() -> Self {
+        Self::in_memory()
+    }
+}
+
+impl BlockStore {
+    /// Opens the store at `path`, creating it if missing unless opened
+    /// read-only.
+    pub fn open(path: PathBuf, access: AccessMode) -> Result<Self> {
+        Ok(Self {
+            store: ColumnStore::open(path, access, &COLUMN_FAMILIES)?,
+        })
+    }
+
+    pub fn in_memory() -> Self {
+        Self {
+            store: ColumnStore::in_memory(&COLUMN_FAMILIES),
+        }
+    }
+
+    /// Stores a block appended to the DAG along with the hashes of the blocks
+    /// it is linked from. Storing a block again replaces it, which is how
+    /// blocks certified after being stored get their certificate persisted.
+    pub fn insert(&self, block: &Block, sources: &[BlockHash]) -> Result<()> {
+        let hash = encode(&block.hash())?;
+
+        let mut writes = vec![
+            (BLOCKS_COLUMN_FAMILY, hash.clone(), encode(block)?),
+            (BLOCK_EDGES_COLUMN_FAMILY, hash.clone(), encode(&sources)?),
+        ];
+
+        if let Some(block_height) = confirmed_height(block) {
+            writes.push((
+                BLOCK_HEIGHTS_COLUMN_FAMILY,
+                block_height.to_be_bytes().to_vec(),
+                hash,
+            ));
+        }
+
+        self.store.write(writes)
+    }
+
+    /// Returns the block with the given hash, if stored
+    pub fn get(&self, block_hash: &str) -> Result<Option<Block>> {
+        self.store
+            .get(BLOCKS_COLUMN_FAMILY, &encode(&block_hash)?)?
+            .map(|block| decode(&block))
+            .transpose()
+    }
+
+    /// Returns the hashes of the blocks the block with the given hash is
+    /// linked from, empty if it isn't stored
+    pub fn sources(&self, block_hash: &str) -> Result<Vec<BlockHash>> {
+        Ok(self
+            .store
+            .get(BLOCK_EDGES_COLUMN_FAMILY, &encode(&block_hash)?)?
+            .map(|sources| decode(&sources))
+            .transpose()?
+            .unwrap_or_default())
+    }
+
+    /// Returns the confirmed block at the given height, if stored
+    pub fn get_by_height(&self, block_height: u128) -> Result<Option<Block>> {
+        let hash = self
+            .store
+            .get(BLOCK_HEIGHTS_COLUMN_FAMILY, &block_height.to_be_bytes())?;
+
+        match hash {
+            Some(hash) => self.get(&decode::<BlockHash>(&hash)?),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns up to `limit` confirmed blocks, ordered by height, starting at
+    /// `start_height`
+    pub fn range_by_height(&self, start_height: u128, limit: usize) -> Result<Vec<Block>> {
+        self.store
+            .scan_from(
+                BLOCK_HEIGHTS_COLUMN_FAMILY,
+                &start_height.to_be_bytes(),
+                limit,
+            )?
+            .into_iter()
+            .filter_map(|(_, hash)| match decode::<BlockHash>(&hash) {
+                Ok(hash) => self.get(&hash).transpose(),
+                Err(err) => Some(Err(err)),
+            })
+            .collect()
+    }
+
+    /// Returns the stored confirmed block with the greatest height
+    pub fn last_confirmed_block(&self) -> Result<Option<Block>> {
+        match self.store.last(BLOCK_HEIGHTS_COLUMN_FAMILY)? {
+            Some((_, hash)) => self.get(&decode::<BlockHash>(&hash)?),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns every stored block along with the blocks it is linked from
+    pub fn entries(&self) -> Result<Vec<StoredBlock>> {
+        self.store
+            .scan_prefix(BLOCKS_COLUMN_FAMILY, &[])?
+            .into_iter()
+            .map(|(hash, block)| {
+                let sources = self
+                    .store
+                    .get(BLOCK_EDGES_COLUMN_FAMILY, &hash)?
+                    .map(|sources| decode(&sources))
+                    .transpose()?
+                    .unwrap_or_default();
+
+                Ok(StoredBlock {
+                    block: decode(&block)?,
+                    sources,
+                })
+            })
+            .collect()
+    }
+
+    /// Number of stored blocks
+    pub fn len(&self) -> Result<usize> {
+        Ok(self.store.scan_prefix(BLOCKS_COLUMN_FAMILY, &[])?.len())
+    }
+
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.store.last(BLOCKS_COLUMN_FAMILY)?.is_none())
+    }
+}
+
+fn confirmed_height(block: &Block) -> Option<u128> {
+    match block {
+        Block::Genesis { block } => Some(block.header.block_height),
+        Block::Convergence { block } => Some(block.header.block_height),
+        Block::Proposal { .. } => None,
+    }
+}
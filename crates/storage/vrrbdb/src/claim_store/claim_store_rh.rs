@@ -1,21 +1,25 @@
 use std::collections::HashMap;
 
+use ethereum_types::U256;
 use integral_db::{JellyfishMerkleTreeWrapper, ReadHandleFactory};
-use patriecia::{JellyfishMerkleTree, Version};
-use primitives::NodeId;
+use patriecia::{JellyfishMerkleTree, RootHash, Version};
+use primitives::{Address, NodeId};
 use sha2::Sha256;
 use storage_utils::{Result, StorageError};
 use vrrb_core::claim::Claim;
 
-use crate::RocksDbAdapter;
+use crate::{
+    inspect::{root_hash_at, root_hashes, verify_trie_integrity},
+    DbAdapter, IntegrityReport, VersionedRootHash,
+};
 
 #[derive(Debug, Clone)]
 pub struct ClaimStoreReadHandle {
-    inner: JellyfishMerkleTreeWrapper<RocksDbAdapter, Sha256>,
+    inner: JellyfishMerkleTreeWrapper<DbAdapter, Sha256>,
 }
 
 impl ClaimStoreReadHandle {
-    pub fn new(inner: JellyfishMerkleTreeWrapper<RocksDbAdapter, Sha256>) -> Self {
+    pub fn new(inner: JellyfishMerkleTreeWrapper<DbAdapter, Sha256>) -> Self {
         Self { inner }
     }
 
@@ -42,11 +46,39 @@ impl ClaimStoreReadHandle {
         claims
     }
 
+    /// Returns the latest version of the claim stored under its hash.
+    pub fn get_by_hash(&self, hash: &U256) -> Result<Claim> {
+        self.inner
+            .get(hash, self.inner.version())
+            .map_err(|err| StorageError::Other(format!("claim {hash} not found: {err}")))
+    }
+
+    /// Returns the claim of the node with the given id.
+    pub fn get_by_node_id(&self, node_id: &NodeId) -> Result<Claim> {
+        self.entries()?
+            .remove(node_id)
+            .ok_or_else(|| StorageError::Other(format!("no claim found for node {node_id}")))
+    }
+
+    /// Returns every claim owned by the given address.
+    pub fn get_by_address(&self, address: &Address) -> Result<Vec<Claim>> {
+        Ok(self
+            .entries()?
+            .into_values()
+            .filter(|claim| &claim.address == address)
+            .collect())
+    }
+
     pub fn entries(&self) -> Result<HashMap<NodeId, Claim>> {
+        self.entries_at_version(self.inner.version())
+    }
+
+    /// Returns every claim stored in the trie as of the given version.
+    pub fn entries_at_version(&self, version: Version) -> Result<HashMap<NodeId, Claim>> {
         // TODO: revisit and refactor into inner wrapper
         Ok(self
             .inner
-            .iter(self.inner.version())
+            .iter(version)
             .map_err(|err| {
                 StorageError::Other(format!("unable to create iterator from trie: {}", err))
             })?
@@ -62,6 +94,11 @@ impl ClaimStoreReadHandle {
             .collect())
     }
 
+    /// Returns the version of the trie this handle reads from
+    pub fn version(&self) -> Version {
+        self.inner.version()
+    }
+
     /// Returns a number of initialized claims in the database
     pub fn len(&self) -> usize {
         self.inner.len()
@@ -75,11 +112,11 @@ impl ClaimStoreReadHandle {
 
 #[derive(Debug, Clone)]
 pub struct ClaimStoreReadHandleFactory {
-    inner: ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>,
+    inner: ReadHandleFactory<JellyfishMerkleTree<DbAdapter, Sha256>>,
 }
 
 impl ClaimStoreReadHandleFactory {
-    pub fn new(inner: ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>) -> Self {
+    pub fn new(inner: ReadHandleFactory<JellyfishMerkleTree<DbAdapter, Sha256>>) -> Self {
         Self { inner }
     }
 
@@ -95,4 +132,20 @@ impl ClaimStoreReadHandleFactory {
 
         ClaimStoreReadHandle { inner }
     }
+
+    /// Returns the root hash of the trie at one of its versions still stored
+    pub fn root_hash_at_version(&self, version: Version) -> Result<RootHash> {
+        root_hash_at(&self.inner, version)
+    }
+
+    /// Returns the root hash of every version of the trie still stored
+    pub fn root_hashes(&self) -> Result<Vec<VersionedRootHash>> {
+        root_hashes(&self.inner)
+    }
+
+    /// Checks every leaf of the latest version of the trie against its root
+    /// hash
+    pub fn verify_integrity(&self) -> Result<IntegrityReport> {
+        verify_trie_integrity(&self.inner)
+    }
 }
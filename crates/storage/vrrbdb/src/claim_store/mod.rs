@@ -1,13 +1,20 @@
-use std::{path::Path, sync::Arc};
+use std::{net::SocketAddr, path::Path, sync::Arc};
 
 use ethereum_types::U256;
 use integral_db::LeftRightTrie;
 use patriecia::RootHash;
+use primitives::PublicKey;
 use sha2::Sha256;
 use storage_utils::{Result, StorageError};
-use vrrb_core::claim::Claim;
+use vrrb_core::{
+    claim::{Claim, Eligibility},
+    staking::Stake,
+};
 
-use crate::RocksDbAdapter;
+use crate::{
+    staging::{stage_version, StagedVersion},
+    AccessMode, DbAdapter, RocksDbAdapter,
+};
 
 mod claim_store_rh;
 pub use claim_store_rh::*;
@@ -15,9 +22,57 @@ pub use claim_store_rh::*;
 pub type Claims = Vec<Claim>;
 pub type FailedClaimUpdates = Vec<(U256, Claims, Result<()>)>;
 
+/// Change applied to a claim already in the store.
+#[derive(Debug, Clone)]
+pub enum ClaimUpdate {
+    /// Moves the claim to a new socket address. The signature must be
+    /// produced by the claim's key over the new address.
+    IpAddress {
+        signature: String,
+        public_key: PublicKey,
+        ip_address: SocketAddr,
+    },
+
+    /// Adds a certified stake to the claim
+    Stake(Stake),
+
+    /// Changes the roles the claim's node can be elected for
+    Eligibility(Eligibility),
+}
+
+impl ClaimUpdate {
+    fn apply(self, claim: &mut Claim) -> Result<()> {
+        match self {
+            ClaimUpdate::IpAddress {
+                signature,
+                public_key,
+                ip_address,
+            } => {
+                if public_key != claim.public_key {
+                    return Err(StorageError::Other(
+                        "claim can only be moved by its owner".to_string(),
+                    ));
+                }
+
+                claim
+                    .update_claim_socketaddr(signature, public_key, ip_address)
+                    .map_err(|err| StorageError::Other(err.to_string()))
+            }
+            ClaimUpdate::Stake(stake) => claim
+                .update_stake(stake)
+                .map_err(|err| StorageError::Other(err.to_string())),
+            ClaimUpdate::Eligibility(eligibility) => {
+                claim.eligibility = eligibility;
+                Ok(())
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ClaimStore {
-    trie: LeftRightTrie<'static, U256, Claim, RocksDbAdapter, Sha256>,
+    trie: LeftRightTrie<'static, U256, Claim, DbAdapter, Sha256>,
+    db_adapter: DbAdapter,
 }
 
 impl Default for ClaimStore {
@@ -29,9 +84,7 @@ impl Default for ClaimStore {
 
         let db_adapter = RocksDbAdapter::new(db_path, "claims").unwrap_or_default();
 
-        let trie = LeftRightTrie::new(Arc::new(db_adapter));
-
-        Self { trie }
+        Self::with_backend(DbAdapter::new(db_adapter))
     }
 }
 
@@ -40,9 +93,33 @@ impl ClaimStore {
     pub fn new(path: &Path) -> Self {
         let path = path.join("claims");
         let db_adapter = RocksDbAdapter::new(path, "claims").unwrap_or_default();
-        let trie = LeftRightTrie::new(Arc::new(db_adapter));
 
-        Self { trie }
+        Self::with_backend(DbAdapter::new(db_adapter))
+    }
+
+    /// Returns a store kept entirely in memory
+    pub fn in_memory() -> Self {
+        Self::with_backend(DbAdapter::in_memory())
+    }
+
+    pub fn with_backend(db_adapter: DbAdapter) -> Self {
+        let trie = LeftRightTrie::new(Arc::new(db_adapter.clone()));
+
+        Self { trie, db_adapter }
+    }
+
+    /// Opens the store at `path`, failing instead of falling back to the
+    /// default location when the database cannot be opened.
+    pub fn open(path: &Path, access: AccessMode) -> Result<Self> {
+        let path = path.join("claims");
+        let db_adapter = RocksDbAdapter::open(path, "claims", access)?;
+
+        Ok(Self::with_backend(DbAdapter::new(db_adapter)))
+    }
+
+    /// Returns the database backing the trie.
+    pub(crate) fn db_adapter(&self) -> DbAdapter {
+        self.db_adapter.clone()
     }
 
     /// Returns new ReadHandle to the VrrDb data. As long as the returned value
@@ -72,6 +149,10 @@ impl ClaimStore {
         //            ));
         //        }
 
+        self.db_adapter
+            .put_preimages([&claim.hash])
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
         self.trie.insert(claim.hash, claim);
 
         Ok(())
@@ -122,24 +203,55 @@ impl ClaimStore {
         failed_inserts
     }
 
-    /// Retain returns new ClaimDb with which all Claims that fulfill `filter`
-    /// cloned to it.
-    pub fn retain<F>(&self, _filter: F) -> ClaimStore
+    /// Retain returns a new, in-memory ClaimStore holding a copy of every
+    /// Claim that fulfills `filter`.
+    pub fn retain<F>(&self, mut filter: F) -> Result<ClaimStore>
     where
         F: FnMut(&Claim) -> bool,
     {
-        todo!()
-        // let mut subdb = ClaimStore::new(self.);
-        //
-        // self.trie.entries().iter().for_each(|(key, value)| {
-        //     let claim = value.to_owned();
-        //     if filter(&claim) {
-        //         subdb.insert_uncommited(key.to_string(), claim);
-        //     }
-        // });
-        //
-        // subdb.trie.publish();
-        // subdb
+        let mut subdb = ClaimStore::in_memory();
+
+        for claim in self.read_handle().entries()?.into_values() {
+            if filter(&claim) {
+                subdb.insert_uncommited(claim)?;
+            }
+        }
+
+        subdb.commit();
+
+        Ok(subdb)
+    }
+
+    fn update_uncommited(&mut self, hash: U256, update: ClaimUpdate) -> Result<Claim> {
+        let mut claim = self.read_handle().get_by_hash(&hash)?;
+
+        update.apply(&mut claim)?;
+
+        self.trie.insert(hash, claim.clone());
+
+        Ok(claim)
+    }
+
+    /// Applies an update to the claim stored under `hash`, returning the
+    /// updated claim.
+    ///
+    /// If succesful commits the change. Otherwise returns an error and leaves
+    /// the claim untouched.
+    pub fn update(&mut self, hash: U256, update: ClaimUpdate) -> Result<Claim> {
+        let claim = self.update_uncommited(hash, update)?;
+        self.commit();
+        Ok(claim)
+    }
+
+    /// Removes the claim stored under `hash`, returning it. Abandoned claims
+    /// can no longer be elected.
+    pub fn abandon(&mut self, hash: U256) -> Result<Claim> {
+        let claim = self.read_handle().get_by_hash(&hash)?;
+
+        self.trie.extend(vec![(hash, None)]);
+        self.commit();
+
+        Ok(claim)
     }
 
     /// Returns a number of initialized claims in the database
@@ -157,132 +269,31 @@ impl ClaimStore {
             .map_err(|e| StorageError::Other(e.to_string()))
     }
 
-    // TODO: We need to figure out what "updating" a claim means, if anything
-    // for now I am leaving these methods out. There will only be inserts,
-    // however, updating a claim should include:
-    // 1. Stake
-    // 2. !Eligible
-    //
-    /// Updates a given claim if it exists within the store
-    //    fn update_uncommited(&mut self, key: NodeId, update: UpdateArgs) ->
-    // Result<()> {        let mut claim = self
-    //            .read_handle()
-    //            .get(&key)
-    //            .map_err(|err| StorageError::Other(err.to_string()))?;
-    //
-    //        claim
-    //            .update(update)
-    //            .map_err(|err| StorageError::Other(err.to_string()))?;
-    //
-    //        Ok(())
-    //    }
-    //
-    //    /// Updates an Claim in the database under given PublicKey
-    //    ///
-    //    /// If succesful commits the change. Otherwise returns an error.
-    //    pub fn update(&mut self, key: NodeId, update: UpdateArgs) -> Result<()>
-    // {ClaimStoreRead        self.commit_changes();
-    //        Ok(())
-    //    }
-    //
-    //    // IDEA: Insted of grouping updates by key in advance, we'll just clear
-    // oplog    // from given keys in case error hapens Cannot borrow oplog
-    // mutably though    /// Updates claims with batch of updates provied in a
-    // `updates` vector.    ///
-    //    /// If there are multiple updates for single PublicKey, those are sorted
-    // by    /// the `nonce` and applied in correct order.
-    //    ///
-    //    /// If at least one update for given claim fails, the whole batch for that
-    //    /// `PublicKey` is abandoned.
-    //    ///
-    //    /// All failed batches are returned in vector, with all data - PublicKey
-    // for    /// the claim for which the update failed, vector of all updates
-    // for that    /// claim, and error that prevented the update.
-    //    pub fn batch_update(
-    //        &mut self,
-    //        mut updates: Vec<(NodeId, UpdateArgs)>,
-    //    ) -> Option<FailedClaimUpdates> {
-    //        // Store and return all failures as (PublicKey, AllPushedUpdates,
-    // Error)        // This way caller is provided with all info -> They know
-    // which claims were        // not modified, have a list of all updates to
-    // try again And an error        // thrown so that they can fix it
-    //        let mut failed = FailedClaimUpdates::new();
-    //
-    //        // We sort updates by nonce (that's impl of Ord in ClaimField)
-    //        // This way all provided updates are used in order (doesn't matter for
-    // different        // claims, but important for multiple ops on single
-    // PubKey)        updates.sort_by(|a, b| a.1.cmp(&b.1));
-    //
-    //        // We'll segregate the batch of updates by key (since it's possible
-    // that in        // provided Vec there is a chance that not every PublicKey
-    // is unique)        let mut update_batches = HashMap::<&NodeId,
-    // Vec<UpdateArgs>>::new();
-    //
-    //        updates.iter().for_each(|update| {
-    //            if let Some(vec_of_updates) = update_batches.get_mut(&update.0) {
-    //                vec_of_updates.push(update.1.clone());
-    //            } else {
-    //                update_batches.insert(&update.0, vec![update.1.clone()]);
-    //            }
-    //        });
-    //
-    //        // For each PublicKey we try to apply every ClaimFieldsUpdate on a
-    // copy of        // current claim if event one fails, the whole batch is
-    // abandoned with        // no changes on ClaimDb when that happens, the
-    // key, batch of updates and        // error are pushed into result vec On
-    // success we update the claim at        // given index (PublicKey) We don't
-    // need to commit the changes, since we        // never go back to that key
-    // in this function, saving a lot of time (we        // don't need to wait
-    // for all readers to finish)        update_batches.drain().for_each(|(k,
-    // v)| {            let mut fail: (bool, Result<()>) = (false, Ok(()));
-    //            let mut final_claim = Claim::default();
-    //
-    //            let claim_result = self.read_handle().get(k);
-    //
-    //            match claim_result {
-    //                Ok(mut claim) => {
-    //                    for update in v.as_slice() {
-    //                        let update_result = claim
-    //                            .update(update.clone())
-    //                            .map_err(|err|
-    // StorageError::Other(err.to_string()));
-    //
-    //                        if let Err(err) = update_result {
-    //                            fail = (true, Err(err));
-    //                            break;
-    //                        }
-    //                    }
-    //                    final_claim = claim;
-    //                },
-    //                Err(err) => fail = (true, Err(err)),
-    //            }
-    //
-    //            if fail.0 {
-    //                failed.push((k.to_owned(), v, fail.1));
-    //            } else {
-    //                // TODO: implement an update method on underlying lr trie
-    //                self.trie.insert(k.to_owned(), final_claim);
-    //            };
-    //        });
-    //
-    //        if failed.len() != updates.len() {
-    //            self.commit_changes();
-    //        };
-    //
-    //        if failed.is_empty() {
-    //            return None;
-    //        }
-    //
-    //        Some(failed)
-    //    }
     pub fn root_hash(&self) -> Result<RootHash> {
         self.trie
             .root_latest()
             .map_err(|e| StorageError::Other(e.to_string()))
     }
 
-    pub fn extend(&mut self, claims: Vec<(U256, Option<Claim>)>) {
-        self.trie.extend(claims)
+    pub fn extend(&mut self, claims: Vec<(U256, Option<Claim>)>) -> Result<()> {
+        self.db_adapter
+            .put_preimages(
+                claims
+                    .iter()
+                    .filter(|(_, claim)| claim.is_some())
+                    .map(|(hash, _)| hash),
+            )
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        self.trie.extend(claims);
+
+        Ok(())
+    }
+
+    /// Computes the version extending the trie with `claims` would publish,
+    /// without writing or publishing it.
+    pub(crate) fn stage(&self, claims: &[(U256, Option<Claim>)]) -> Result<StagedVersion> {
+        stage_version(&self.trie.factory(), claims)
     }
 
     pub fn factory(&self) -> ClaimStoreReadHandleFactory {
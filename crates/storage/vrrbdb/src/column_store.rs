@@ -0,0 +1,218 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    sync::Arc,
+};
+
+use parking_lot::RwLock;
+use rocksdb::{Direction, IteratorMode};
+use serde::{Deserialize, Serialize};
+use storage_utils::{Result, StorageError};
+
+use crate::{AccessMode, Database};
+
+type MemoryColumnFamilies = HashMap<&'static str, BTreeMap<Vec<u8>, Vec<u8>>>;
+
+/// Key-value store split into column families, backed either by a RocksDB
+/// `Database` or by ordered maps kept in memory. Used by the stores that live
+/// outside the tries.
+#[derive(Debug, Clone)]
+pub(crate) enum ColumnStore {
+    RocksDb(Database),
+    InMemory(Arc<RwLock<MemoryColumnFamilies>>),
+}
+
+impl ColumnStore {
+    /// Opens the database stored at `path`, creating it if missing unless
+    /// opened read-only.
+    pub(crate) fn open(
+        path: PathBuf,
+        access: AccessMode,
+        column_families: &[&'static str],
+    ) -> Result<Self> {
+        let column_families: Vec<String> = column_families
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+
+        Ok(ColumnStore::RocksDb(Database::open(
+            path,
+            access,
+            &column_families,
+        )?))
+    }
+
+    pub(crate) fn in_memory(column_families: &[&'static str]) -> Self {
+        let column_families = column_families
+            .iter()
+            .map(|name| (*name, BTreeMap::new()))
+            .collect();
+
+        ColumnStore::InMemory(Arc::new(RwLock::new(column_families)))
+    }
+
+    /// Writes every entry at once
+    pub(crate) fn write(&self, writes: Vec<(&'static str, Vec<u8>, Vec<u8>)>) -> Result<()> {
+        match self {
+            ColumnStore::RocksDb(database) => database.write(
+                writes
+                    .into_iter()
+                    .map(|(name, key, value)| (name, key, Some(value))),
+            ),
+            ColumnStore::InMemory(column_families) => {
+                let mut column_families = column_families.write();
+                for (name, key, value) in writes {
+                    column_families.entry(name).or_default().insert(key, value);
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn get(&self, name: &'static str, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        match self {
+            ColumnStore::RocksDb(database) => database.get(name, key),
+            ColumnStore::InMemory(column_families) => Ok(column_families
+                .read()
+                .get(name)
+                .and_then(|column_family| column_family.get(key).cloned())),
+        }
+    }
+
+    /// Returns every entry whose key starts with `prefix`, in key order.
+    pub(crate) fn scan_prefix(
+        &self,
+        name: &'static str,
+        prefix: &[u8],
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        match self {
+            ColumnStore::RocksDb(database) => {
+                let mut entries = Vec::new();
+                let iter =
+                    database.iterator(name, IteratorMode::From(prefix, Direction::Forward))?;
+
+                for entry in iter {
+                    let (key, value) = entry.map_err(|err| StorageError::Other(err.to_string()))?;
+                    if !key.starts_with(prefix) {
+                        break;
+                    }
+
+                    entries.push((key.into_vec(), value.into_vec()));
+                }
+
+                Ok(entries)
+            }
+            ColumnStore::InMemory(column_families) => Ok(column_families
+                .read()
+                .get(name)
+                .map(|column_family| {
+                    column_family
+                        .range(prefix.to_vec()..)
+                        .take_while(|(key, _)| key.starts_with(prefix))
+                        .map(|(key, value)| (key.clone(), value.clone()))
+                        .collect()
+                })
+                .unwrap_or_default()),
+        }
+    }
+
+    /// Returns up to `limit` entries whose key starts with `prefix`, in key
+    /// order, skipping the first `offset`. Entries past the page are never
+    /// read.
+    pub(crate) fn scan_prefix_page(
+        &self,
+        name: &'static str,
+        prefix: &[u8],
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        match self {
+            ColumnStore::RocksDb(database) => database
+                .iterator(name, IteratorMode::From(prefix, Direction::Forward))?
+                .take_while(|entry| {
+                    entry
+                        .as_ref()
+                        .map_or(true, |(key, _)| key.starts_with(prefix))
+                })
+                .skip(offset)
+                .take(limit)
+                .map(|entry| {
+                    entry
+                        .map(|(key, value)| (key.into_vec(), value.into_vec()))
+                        .map_err(|err| StorageError::Other(err.to_string()))
+                })
+                .collect(),
+            ColumnStore::InMemory(column_families) => Ok(column_families
+                .read()
+                .get(name)
+                .map(|column_family| {
+                    column_family
+                        .range(prefix.to_vec()..)
+                        .take_while(|(key, _)| key.starts_with(prefix))
+                        .skip(offset)
+                        .take(limit)
+                        .map(|(key, value)| (key.clone(), value.clone()))
+                        .collect()
+                })
+                .unwrap_or_default()),
+        }
+    }
+
+    /// Returns up to `limit` entries whose key is not lower than `from`, in
+    /// key order.
+    pub(crate) fn scan_from(
+        &self,
+        name: &'static str,
+        from: &[u8],
+        limit: usize,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        match self {
+            ColumnStore::RocksDb(database) => database
+                .iterator(name, IteratorMode::From(from, Direction::Forward))?
+                .take(limit)
+                .map(|entry| {
+                    entry
+                        .map(|(key, value)| (key.into_vec(), value.into_vec()))
+                        .map_err(|err| StorageError::Other(err.to_string()))
+                })
+                .collect(),
+            ColumnStore::InMemory(column_families) => Ok(column_families
+                .read()
+                .get(name)
+                .map(|column_family| {
+                    column_family
+                        .range(from.to_vec()..)
+                        .take(limit)
+                        .map(|(key, value)| (key.clone(), value.clone()))
+                        .collect()
+                })
+                .unwrap_or_default()),
+        }
+    }
+
+    /// Returns the entry with the greatest key, if any
+    pub(crate) fn last(&self, name: &'static str) -> Result<Option<(Vec<u8>, Vec<u8>)>> {
+        match self {
+            ColumnStore::RocksDb(database) => database
+                .iterator(name, IteratorMode::End)?
+                .next()
+                .transpose()
+                .map(|entry| entry.map(|(key, value)| (key.into_vec(), value.into_vec())))
+                .map_err(|err| StorageError::Other(err.to_string())),
+            ColumnStore::InMemory(column_families) => Ok(column_families
+                .read()
+                .get(name)
+                .and_then(|column_family| column_family.last_key_value())
+                .map(|(key, value)| (key.clone(), value.clone()))),
+        }
+    }
+}
+
+pub(crate) fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    bincode::serialize(value).map_err(|err| StorageError::Other(err.to_string()))
+}
+
+pub(crate) fn decode<T: for<'de> Deserialize<'de>>(bytes: &[u8]) -> Result<T> {
+    bincode::deserialize(bytes).map_err(|err| StorageError::Other(err.to_string()))
+}
@@ -0,0 +1,292 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use parking_lot::Mutex;
+use rocksdb::{ColumnFamily, DBIteratorWithThreadMode, Direction, IteratorMode, WriteBatch, DB};
+use storage_utils::{Result, StorageError};
+
+use crate::{rocksdb_adapter::base_db_options, AccessMode};
+
+/// Writes made since `Database::begin_write` was called. They are kept in a
+/// single `WriteBatch`, along with a copy reads go through first.
+#[derive(Default)]
+struct StagedWrites {
+    batch: WriteBatch,
+    overlay: BTreeMap<(String, Vec<u8>), Option<Vec<u8>>>,
+}
+
+/// RocksDB database shared by several stores, each keeping its entries in
+/// column families of its own.
+///
+/// Writes made between `begin_write` and `commit_write` are staged instead of
+/// being written right away, then committed in a single `WriteBatch`, so
+/// either every store sees them or none does.
+#[derive(Clone)]
+pub struct Database {
+    db: Arc<DB>,
+    staged: Arc<Mutex<Option<StagedWrites>>>,
+}
+
+impl fmt::Debug for Database {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Database")
+            .field("path", &self.db.path())
+            .field("staging", &self.staged.lock().is_some())
+            .finish()
+    }
+}
+
+impl Database {
+    /// Opens the database stored at `path`, creating it along with any of
+    /// `column_families` missing unless opened read-only.
+    pub fn open(path: PathBuf, access: AccessMode, column_families: &[String]) -> Result<Self> {
+        let mut options = base_db_options();
+
+        let existing = DB::list_cf(&options, &path).unwrap_or_default();
+
+        let db = match access {
+            AccessMode::ReadWrite => {
+                options.create_if_missing(true);
+                options.create_missing_column_families(true);
+
+                let mut names = existing;
+                for name in column_families {
+                    if !names.contains(name) {
+                        names.push(name.clone());
+                    }
+                }
+
+                DB::open_cf(&options, &path, names)
+            }
+            AccessMode::ReadOnly => DB::open_cf_for_read_only(&options, &path, existing, false),
+        }
+        .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        Ok(Self {
+            db: Arc::new(db),
+            staged: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        self.db.path()
+    }
+
+    /// Lists every column family of the database.
+    pub fn column_families(&self) -> Result<Vec<String>> {
+        DB::list_cf(&rocksdb::Options::default(), self.db.path())
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    pub(crate) fn column_family(&self, name: &str) -> Result<&ColumnFamily> {
+        self.db
+            .cf_handle(name)
+            .ok_or_else(|| StorageError::Other(format!("column family {name} not found")))
+    }
+
+    /// Starts staging every write made to the database until `commit_write`
+    /// or `discard_write` is called.
+    pub fn begin_write(&self) -> Result<()> {
+        let mut staged = self.staged.lock();
+        if staged.is_some() {
+            return Err(StorageError::Other(
+                "a write is already in progress".to_string(),
+            ));
+        }
+
+        *staged = Some(StagedWrites::default());
+
+        Ok(())
+    }
+
+    /// Writes everything staged since `begin_write` at once.
+    pub fn commit_write(&self) -> Result<()> {
+        let staged = self
+            .staged
+            .lock()
+            .take()
+            .ok_or_else(|| StorageError::Other("no write is in progress".to_string()))?;
+
+        self.db
+            .write(staged.batch)
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    /// Drops everything staged since `begin_write`.
+    pub fn discard_write(&self) {
+        self.staged.lock().take();
+    }
+
+    /// Returns the value stored under `key`, including writes staged but not
+    /// committed yet.
+    pub(crate) fn get(&self, name: &str, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        if let Some(staged) = self.staged.lock().as_ref() {
+            if let Some(value) = staged.overlay.get(&(name.to_string(), key.to_vec())) {
+                return Ok(value.clone());
+            }
+        }
+
+        self.db
+            .get_cf(self.column_family(name)?, key)
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    /// Walks back over the entries whose key starts with `prefix`, from the
+    /// greatest key not above `from` down, including writes staged but not
+    /// committed yet. Returns the first entry `accept` returns true for.
+    pub(crate) fn find_back(
+        &self,
+        name: &str,
+        prefix: &[u8],
+        from: &[u8],
+        mut accept: impl FnMut(&[u8], &[u8]) -> Result<bool>,
+    ) -> Result<Option<(Vec<u8>, Vec<u8>)>> {
+        let mut staged_entries: Vec<(Vec<u8>, Option<Vec<u8>>)> = match self.staged.lock().as_ref()
+        {
+            Some(staged) => staged
+                .overlay
+                .range((name.to_string(), prefix.to_vec())..=(name.to_string(), from.to_vec()))
+                .filter(|((_, key), _)| key.starts_with(prefix))
+                .map(|((_, key), value)| (key.clone(), value.clone()))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let mut committed = self
+            .db
+            .iterator_cf(
+                self.column_family(name)?,
+                IteratorMode::From(from, Direction::Reverse),
+            )
+            .take_while(|entry| {
+                entry
+                    .as_ref()
+                    .map_or(true, |(key, _)| key.starts_with(prefix))
+            })
+            .map(|entry| {
+                entry
+                    .map(|(key, value)| (key.into_vec(), value.into_vec()))
+                    .map_err(|err| StorageError::Other(err.to_string()))
+            });
+
+        let mut committed_entry = committed.next().transpose()?;
+
+        loop {
+            let take_staged = match (staged_entries.last(), &committed_entry) {
+                (None, None) => return Ok(None),
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (Some((staged_key, _)), Some((committed_key, _))) => staged_key >= committed_key,
+            };
+
+            let (key, value) = match (take_staged, staged_entries.pop(), committed_entry.take()) {
+                (true, Some((key, value)), committed_value) => {
+                    // NOTE: staged writes shadow what is committed under the
+                    // same key
+                    committed_entry = match committed_value {
+                        Some((committed_key, _)) if committed_key == key => {
+                            committed.next().transpose()?
+                        }
+                        committed_value => committed_value,
+                    };
+
+                    (key, value)
+                }
+                (_, staged_entry, Some((key, value))) => {
+                    staged_entries.extend(staged_entry);
+                    committed_entry = committed.next().transpose()?;
+
+                    (key, Some(value))
+                }
+                _ => return Ok(None),
+            };
+
+            if let Some(value) = value {
+                if accept(&key, &value)? {
+                    return Ok(Some((key, value)));
+                }
+            }
+        }
+    }
+
+    /// Writes every entry at once, or stages them if a write is in progress.
+    /// Entries without a value are deleted.
+    pub(crate) fn write<'a>(
+        &self,
+        writes: impl IntoIterator<Item = (&'a str, Vec<u8>, Option<Vec<u8>>)>,
+    ) -> Result<()> {
+        let mut staged = self.staged.lock();
+
+        match staged.as_mut() {
+            Some(staged) => {
+                for (name, key, value) in writes {
+                    let column_family = self.column_family(name)?;
+                    match &value {
+                        Some(value) => staged.batch.put_cf(column_family, &key, value),
+                        None => staged.batch.delete_cf(column_family, &key),
+                    }
+
+                    staged.overlay.insert((name.to_string(), key), value);
+                }
+
+                Ok(())
+            }
+            None => self.write_now(writes),
+        }
+    }
+
+    /// Writes every entry at once, even while a write is in progress, so they
+    /// are neither committed nor discarded along with what is staged. Meant
+    /// for writes unrelated to the staged ones, such as pruning.
+    pub(crate) fn write_unstaged<'a>(
+        &self,
+        writes: impl IntoIterator<Item = (&'a str, Vec<u8>, Option<Vec<u8>>)>,
+    ) -> Result<()> {
+        // NOTE: the lock is held so a staged write cannot be committed in
+        // between
+        let _staged = self.staged.lock();
+
+        self.write_now(writes)
+    }
+
+    fn write_now<'a>(
+        &self,
+        writes: impl IntoIterator<Item = (&'a str, Vec<u8>, Option<Vec<u8>>)>,
+    ) -> Result<()> {
+        let mut batch = WriteBatch::default();
+        for (name, key, value) in writes {
+            let column_family = self.column_family(name)?;
+            match value {
+                Some(value) => batch.put_cf(column_family, key, value),
+                None => batch.delete_cf(column_family, key),
+            }
+        }
+
+        self.db
+            .write(batch)
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    /// Iterates over the entries of a column family. Writes staged but not
+    /// committed yet are not visible.
+    pub(crate) fn iterator(
+        &self,
+        name: &str,
+        mode: IteratorMode,
+    ) -> Result<DBIteratorWithThreadMode<'_, DB>> {
+        Ok(self.db.iterator_cf(self.column_family(name)?, mode))
+    }
+
+    /// Compacts a column family, reclaiming the space left behind by deleted
+    /// and overwritten entries.
+    pub(crate) fn compact(&self, name: &str) -> Result<()> {
+        self.db
+            .compact_range_cf(self.column_family(name)?, None::<&[u8]>, None::<&[u8]>);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use integral_db::{JellyfishMerkleTreeWrapper, ReadHandleFactory};
+use patriecia::{JellyfishMerkleTree, RootHash, Version};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use storage_utils::{Result, StorageError};
+
+use crate::DbAdapter;
+
+/// Root hash of a trie at one of its versions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionedRootHash {
+    pub version: Version,
+    pub root_hash: String,
+}
+
+/// Outcome of checking every leaf of a trie against the root hash stored for
+/// its latest version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    pub version: Version,
+    pub root_hash: String,
+    pub verified_leaves: usize,
+    /// Hex encoded key hashes of the leaves that could not be proven
+    pub invalid_leaves: Vec<String>,
+}
+
+impl IntegrityReport {
+    pub fn is_valid(&self) -> bool {
+        self.invalid_leaves.is_empty()
+    }
+}
+
+pub(crate) fn latest_tree(
+    factory: &ReadHandleFactory<JellyfishMerkleTree<DbAdapter, Sha256>>,
+) -> Result<(JellyfishMerkleTree<DbAdapter, Sha256>, Version)> {
+    let tree = factory
+        .handle()
+        .enter()
+        .map(|guard| guard.clone())
+        .ok_or(StorageError::Other("trie is not available".to_string()))?;
+
+    let version = JellyfishMerkleTreeWrapper::new(tree.clone()).version();
+
+    Ok((tree, version))
+}
+
+/// Returns the root hash of a trie at one of its versions still stored.
+pub(crate) fn root_hash_at(
+    factory: &ReadHandleFactory<JellyfishMerkleTree<DbAdapter, Sha256>>,
+    version: Version,
+) -> Result<RootHash> {
+    let (tree, _) = latest_tree(factory)?;
+
+    tree.get_root_hash(version)
+        .map_err(|err| StorageError::Other(format!("no root stored for version {version}: {err}")))
+}
+
+/// Returns the root hash of every version of a trie still stored.
+/// Versions whose nodes were pruned are skipped.
+pub(crate) fn root_hashes(
+    factory: &ReadHandleFactory<JellyfishMerkleTree<DbAdapter, Sha256>>,
+) -> Result<Vec<VersionedRootHash>> {
+    let (tree, latest_version) = latest_tree(factory)?;
+
+    Ok((0..=latest_version)
+        .filter_map(|version| {
+            tree.get_root_hash(version)
+                .ok()
+                .map(|root_hash| VersionedRootHash {
+                    version,
+                    root_hash: hex::encode(root_hash.0),
+                })
+        })
+        .collect())
+}
+
+/// Proves every leaf of the latest version of a trie against its stored root
+/// hash, detecting missing or corrupted nodes.
+pub(crate) fn verify_trie_integrity(
+    factory: &ReadHandleFactory<JellyfishMerkleTree<DbAdapter, Sha256>>,
+) -> Result<IntegrityReport> {
+    let (tree, version) = latest_tree(factory)?;
+
+    let root_hash = tree
+        .get_root_hash(version)
+        .map_err(|err| StorageError::Other(err.to_string()))?;
+
+    let leaves = JellyfishMerkleTreeWrapper::new(tree.clone())
+        .iter(version)
+        .map_err(|err| {
+            StorageError::Other(format!("unable to create iterator from trie: {}", err))
+        })?;
+
+    let mut verified_leaves = 0;
+    let mut invalid_leaves = Vec::new();
+
+    for leaf in leaves {
+        let (key_hash, value) = leaf.map_err(|err| StorageError::Other(err.to_string()))?;
+
+        let proven = tree
+            .get_with_proof(key_hash, version)
+            .map(|(_, proof)| proof.verify(root_hash, key_hash, Some(value)).is_ok())
+            .unwrap_or(false);
+
+        if proven {
+            verified_leaves += 1;
+        } else {
+            invalid_leaves.push(hex::encode(key_hash.0));
+        }
+    }
+
+    Ok(IntegrityReport {
+        version,
+        root_hash: hex::encode(root_hash.0),
+        verified_leaves,
+        invalid_leaves,
+    })
+}
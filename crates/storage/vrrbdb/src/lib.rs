@@ -1,17 +1,40 @@
+mod block_store;
 mod claim_store;
+mod column_store;
+mod database;
+mod inspect;
+mod memory_adapter;
+mod mempool_journal;
+mod proofs;
+mod pruner;
 pub mod result;
 mod rocksdb_adapter;
+mod snapshot;
+mod staging;
+mod state_history;
 mod state_store;
+mod storage_backend;
 pub mod test_utils;
+mod transaction_index;
 mod transaction_store;
 pub mod types;
 mod vrrbdb;
 mod vrrbdb_read_handle;
 mod vrrbdb_serialized_values;
 
+pub use block_store::*;
 pub use claim_store::*;
+pub use database::*;
+pub use inspect::*;
+pub use memory_adapter::*;
+pub use mempool_journal::*;
+pub use pruner::*;
 pub use rocksdb_adapter::*;
+pub use snapshot::*;
+pub use state_history::*;
 pub use state_store::*;
+pub use storage_backend::*;
+pub use transaction_index::*;
 pub use transaction_store::*;
 pub use types::*;
 pub use vrrbdb_read_handle::*;
@@ -0,0 +1,165 @@
+use std::collections::{hash_map::IntoIter, BTreeSet, HashMap};
+
+use anyhow::Result;
+use parking_lot::RwLock;
+use patriecia::{
+    KeyHash, LeafNode, Node, NodeBatch, NodeKey, OwnedValue, StaleNodeIndex, Vers, Version,
+};
+
+use crate::{put_value, value_at_version, Preimage, StorageBackend, ValueHistory};
+
+/// Storage backend keeping a trie entirely in memory.
+#[derive(Debug, Default)]
+pub struct MemoryDbAdapter {
+    data: RwLock<MemoryDbInner>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryDbInner {
+    nodes: HashMap<NodeKey, Node>,
+    stale_nodes: BTreeSet<StaleNodeIndex>,
+    value_history: HashMap<KeyHash, ValueHistory>,
+    preimages: HashMap<KeyHash, Preimage>,
+    latest_version: Option<Version>,
+}
+
+impl MemoryDbAdapter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StorageBackend for MemoryDbAdapter {
+    fn get_node_option(&self, node_key: &NodeKey) -> Result<Option<Node>> {
+        Ok(self.data.read().nodes.get(node_key).cloned())
+    }
+
+    fn get_value_option(&self, max_version: Vers, key_hash: KeyHash) -> Result<Option<OwnedValue>> {
+        Ok(self
+            .data
+            .read()
+            .value_history
+            .get(&key_hash)
+            .and_then(|history| value_at_version(history, max_version)))
+    }
+
+    fn get_rightmost_leaf(&self) -> Result<Option<(NodeKey, LeafNode)>> {
+        let locked = self.data.read();
+        let mut key_and_node: Option<(NodeKey, LeafNode)> = None;
+
+        for (node_key, node) in locked.nodes.iter() {
+            if let Node::Leaf(leaf_node) = node {
+                if key_and_node.is_none()
+                    || leaf_node.key_hash() > key_and_node.as_ref().unwrap().1.key_hash()
+                {
+                    key_and_node.replace((node_key.clone(), leaf_node.clone()));
+                }
+            }
+        }
+
+        Ok(key_and_node)
+    }
+
+    fn write_node_batch(&self, node_batch: &NodeBatch) -> Result<()> {
+        let mut locked = self.data.write();
+
+        for (node_key, node) in node_batch.nodes() {
+            locked.latest_version = locked.latest_version.max(Some(node_key.version()));
+            locked.nodes.insert(node_key.clone(), node.clone());
+        }
+
+        for ((version, key_hash), value) in node_batch.values() {
+            put_value(
+                &mut locked.value_history,
+                version.into(),
+                *key_hash,
+                value.clone(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn put_stale_node_index(&self, index: StaleNodeIndex) -> Result<()> {
+        // NOTE: versions staged for a block are written once with the block
+        // and again when they are published, retiring the same nodes twice
+        self.data.write().stale_nodes.insert(index);
+        Ok(())
+    }
+
+    fn nodes(&self) -> IntoIter<NodeKey, Node> {
+        self.data.read().nodes.clone().into_iter()
+    }
+
+    fn value_history(&self) -> IntoIter<KeyHash, ValueHistory> {
+        self.data.read().value_history.clone().into_iter()
+    }
+
+    fn put_preimages(&self, preimages: Vec<(KeyHash, Preimage)>) -> Result<()> {
+        self.data.write().preimages.extend(preimages);
+        Ok(())
+    }
+
+    fn get_preimage(&self, key_hash: KeyHash) -> Result<Option<Preimage>> {
+        Ok(self.data.read().preimages.get(&key_hash).cloned())
+    }
+
+    fn latest_version(&self) -> Option<Version> {
+        self.data.read().latest_version
+    }
+
+    fn prune_stale_nodes(
+        &self,
+        stale_since_version: Version,
+        limit: usize,
+    ) -> Result<Vec<StaleNodeIndex>> {
+        let mut locked = self.data.write();
+
+        let pruned: Vec<StaleNodeIndex> = locked
+            .stale_nodes
+            .iter()
+            .take(limit)
+            .take_while(|index| index.stale_since_version <= stale_since_version)
+            .cloned()
+            .collect();
+
+        for index in pruned.iter() {
+            locked.nodes.remove(&index.node_key);
+            locked.stale_nodes.remove(index);
+        }
+
+        Ok(pruned)
+    }
+
+    /// Deletes every stale value at once, `limit` is ignored.
+    fn prune_stale_values(&self, stale_since_version: Version, _limit: usize) -> Result<usize> {
+        let stale_since_version: Vers = (&stale_since_version).into();
+        let mut pruned = 0;
+
+        for history in self.data.write().value_history.values_mut() {
+            // NOTE: the latest value at or before the version is kept, reads
+            // from that version on still need it
+            if let Some(live) = history
+                .iter()
+                .rposition(|(version, _)| *version <= stale_since_version)
+            {
+                history.drain(..live);
+                pruned += live;
+            }
+        }
+
+        Ok(pruned)
+    }
+
+    fn stale_node_count(&self) -> Result<usize> {
+        Ok(self.data.read().stale_nodes.len())
+    }
+
+    fn column_families(&self) -> Result<Vec<String>> {
+        Ok(vec![])
+    }
+
+    fn compact(&self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use std::{path::PathBuf, sync::Arc};
+
+use mempool::{error::MempoolError, MempoolJournal, MempoolOp, TxnRecord};
+use rocksdb::{ColumnFamily, IteratorMode, DB};
+use storage_utils::{Result, StorageError};
+use telemetry::error;
+use vrrb_core::transactions::TransactionDigest;
+
+use crate::rocksdb_adapter::{base_db_options, new_db_instance};
+
+pub const MEMPOOL_JOURNAL_COLUMN_FAMILY: &str = "mempool_journal";
+
+/// Journal of the operations applied to a node's mempool, kept in its own
+/// RocksDB column family so pending transactions survive a restart.
+///
+/// Entries are keyed by transaction digest: `MempoolOp::Add` stores the
+/// record and `MempoolOp::Remove` deletes it, so the journal never holds
+/// more than the pool itself.
+#[derive(Debug, Clone)]
+pub struct MempoolJournalStore {
+    db: Arc<DB>,
+}
+
+impl MempoolJournalStore {
+    /// Opens the journal stored at `path`, creating it if missing
+    pub fn new(path: PathBuf) -> Result<Self> {
+        let mut options = base_db_options();
+        options.set_error_if_exists(false);
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let db = new_db_instance(options, path, MEMPOOL_JOURNAL_COLUMN_FAMILY)?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    fn column_family(&self) -> Result<&ColumnFamily> {
+        self.db
+            .cf_handle(MEMPOOL_JOURNAL_COLUMN_FAMILY)
+            .ok_or_else(|| {
+                StorageError::Other(format!(
+                    "column family {MEMPOOL_JOURNAL_COLUMN_FAMILY} not found"
+                ))
+            })
+    }
+
+    /// Applies `op` to the journal
+    pub fn write(&self, op: &MempoolOp) -> Result<()> {
+        let cf = self.column_family()?;
+
+        match op {
+            MempoolOp::Add(record) => {
+                let key = encode_key(&record.txn_id)?;
+                let value = bincode::serialize(record.as_ref())
+                    .map_err(|err| StorageError::Other(err.to_string()))?;
+
+                self.db.put_cf(cf, key, value)
+            }
+            MempoolOp::Remove(id) => self.db.delete_cf(cf, encode_key(id)?),
+        }
+        .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    /// Returns every record currently in the journal, in the order they were
+    /// added to the mempool. Entries that can no longer be decoded are
+    /// skipped.
+    pub fn replay(&self) -> Result<Vec<TxnRecord>> {
+        let cf = self.column_family()?;
+
+        let mut records: Vec<TxnRecord> = self
+            .db
+            .iterator_cf(cf, IteratorMode::Start)
+            .filter_map(|entry| match entry {
+                Ok((_, value)) => bincode::deserialize(&value)
+                    .map_err(|err| error!("could not decode journaled transaction: {err}"))
+                    .ok(),
+                Err(err) => {
+                    error!("could not read mempool journal: {err}");
+                    None
+                }
+            })
+            .collect();
+
+        records.sort_by(|a, b| {
+            a.added_timestamp
+                .cmp(&b.added_timestamp)
+                .then_with(|| a.txn_id.cmp(&b.txn_id))
+        });
+
+        Ok(records)
+    }
+}
+
+impl MempoolJournal for MempoolJournalStore {
+    fn record(&self, op: &MempoolOp) -> mempool::Result<()> {
+        self.write(op)
+            .map_err(|err| MempoolError::JournalWriteFailed(err.to_string()))
+    }
+}
+
+fn encode_key(id: &TransactionDigest) -> Result<Vec<u8>> {
+    bincode::serialize(id).map_err(|err| StorageError::Other(err.to_string()))
+}
@@ -0,0 +1,39 @@
+use integral_db::{JellyfishMerkleTreeWrapper, ReadHandleFactory};
+use patriecia::JellyfishMerkleTree;
+use serde::Serialize;
+use sha2::Sha256;
+use storage_proofs::{key_hash, InclusionProof};
+use storage_utils::{Result, StorageError};
+
+use crate::DbAdapter;
+
+/// Produces a proof that a value is stored under `key` in the latest
+/// published version of a trie.
+pub(crate) fn generate_inclusion_proof<K: Serialize>(
+    factory: &ReadHandleFactory<JellyfishMerkleTree<DbAdapter, Sha256>>,
+    key: &K,
+) -> Result<InclusionProof> {
+    let tree = factory
+        .handle()
+        .enter()
+        .map(|guard| guard.clone())
+        .ok_or(StorageError::Other("trie is not available".to_string()))?;
+
+    let version = JellyfishMerkleTreeWrapper::new(tree.clone()).version();
+    let key_hash = key_hash(key).map_err(|err| StorageError::Other(err.to_string()))?;
+
+    let (value, proof) = tree
+        .get_with_proof(key_hash, version)
+        .map_err(|err| StorageError::Other(err.to_string()))?;
+
+    let value = value.ok_or(StorageError::Other(
+        "no value is stored under the given key".to_string(),
+    ))?;
+
+    let root_hash = tree
+        .get_root_hash(version)
+        .map_err(|err| StorageError::Other(err.to_string()))?;
+
+    InclusionProof::new(version, root_hash, value, &proof)
+        .map_err(|err| StorageError::Other(err.to_string()))
+}
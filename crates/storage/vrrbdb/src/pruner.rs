@@ -0,0 +1,237 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, SyncSender, TrySendError},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+};
+
+use patriecia::Version;
+use serde::{Deserialize, Serialize};
+use storage_utils::{Result, StorageError};
+use telemetry::{error, info};
+
+use crate::DbAdapter;
+
+/// Number of trie versions kept around by default before stale nodes are
+/// pruned.
+pub const DEFAULT_PRUNING_WINDOW: Version = 10_000;
+
+/// Maximum number of stale nodes deleted per store in a single write.
+pub const DEFAULT_PRUNING_BATCH_SIZE: usize = 1_000;
+
+/// Controls whether nodes replaced by newer versions of the tries are
+/// deleted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PruningMode {
+    /// Keeps every version of the tries. Meant for archive nodes.
+    Archive,
+
+    /// Deletes nodes that have been stale for more than `window` versions.
+    /// Past state older than that can no longer be read.
+    Prune { window: Version, batch_size: usize },
+}
+
+impl Default for PruningMode {
+    fn default() -> Self {
+        PruningMode::Prune {
+            window: DEFAULT_PRUNING_WINDOW,
+            batch_size: DEFAULT_PRUNING_BATCH_SIZE,
+        }
+    }
+}
+
+/// Progress of the pruner since the database was opened.
+#[derive(Debug, Default)]
+pub struct PrunerMetrics {
+    pruned_nodes: AtomicU64,
+    pruned_values: AtomicU64,
+    completed_runs: AtomicU64,
+    failed_runs: AtomicU64,
+}
+
+impl PrunerMetrics {
+    /// Total number of stale nodes deleted.
+    pub fn pruned_nodes(&self) -> u64 {
+        self.pruned_nodes.load(Ordering::Relaxed)
+    }
+
+    /// Total number of values replaced by later versions deleted from the
+    /// value histories.
+    pub fn pruned_values(&self) -> u64 {
+        self.pruned_values.load(Ordering::Relaxed)
+    }
+
+    /// Number of pruning runs that went through every store.
+    pub fn completed_runs(&self) -> u64 {
+        self.completed_runs.load(Ordering::Relaxed)
+    }
+
+    /// Number of pruning runs interrupted by an error.
+    pub fn failed_runs(&self) -> u64 {
+        self.failed_runs.load(Ordering::Relaxed)
+    }
+}
+
+/// Deletes stale trie nodes and values on a background thread so commits
+/// never wait on it. Every commit wakes the pruner up, runs are coalesced
+/// while one is already pending.
+#[derive(Debug)]
+pub struct StatePruner {
+    mode: PruningMode,
+    stores: Vec<DbAdapter>,
+    metrics: Arc<PrunerMetrics>,
+    notifier: Option<SyncSender<()>>,
+    /// Background thread, joined on drop so the stores it holds are closed
+    /// by the time the pruner is gone
+    worker: Option<JoinHandle<()>>,
+}
+
+impl Default for StatePruner {
+    fn default() -> Self {
+        Self::new(PruningMode::Archive, vec![])
+    }
+}
+
+impl StatePruner {
+    pub fn new(mode: PruningMode, stores: Vec<DbAdapter>) -> Self {
+        let metrics = Arc::new(PrunerMetrics::default());
+
+        let (notifier, worker) = match mode {
+            PruningMode::Archive => (None, None),
+            PruningMode::Prune { .. } => {
+                let (notifier, wakeups) = mpsc::sync_channel::<()>(1);
+                let worker_stores = stores.clone();
+                let worker_metrics = metrics.clone();
+
+                let spawned = thread::Builder::new()
+                    .name("state-pruner".to_string())
+                    .spawn(move || {
+                        while wakeups.recv().is_ok() {
+                            if let Err(err) = prune(mode, &worker_stores, &worker_metrics) {
+                                error!("failed to prune stale trie nodes: {err}");
+                            }
+                        }
+                    });
+
+                match spawned {
+                    Ok(worker) => (Some(notifier), Some(worker)),
+                    Err(err) => {
+                        error!("failed to start the state pruner: {err}");
+                        (None, None)
+                    }
+                }
+            }
+        };
+
+        Self {
+            mode,
+            stores,
+            metrics,
+            notifier,
+            worker,
+        }
+    }
+
+    pub fn mode(&self) -> PruningMode {
+        self.mode
+    }
+
+    pub fn metrics(&self) -> Arc<PrunerMetrics> {
+        self.metrics.clone()
+    }
+
+    /// Wakes up the background thread. Never blocks.
+    pub fn notify(&self) {
+        if let Some(notifier) = &self.notifier {
+            if let Err(TrySendError::Disconnected(_)) = notifier.try_send(()) {
+                error!("state pruner is no longer running");
+            }
+        }
+    }
+
+    /// Prunes every store on the calling thread, returning the number of
+    /// nodes deleted.
+    pub fn prune_now(&self) -> Result<u64> {
+        prune(self.mode, &self.stores, &self.metrics)
+    }
+}
+
+impl Drop for StatePruner {
+    fn drop(&mut self) {
+        // NOTE: dropping the notifier stops the background thread once its
+        // current run is over
+        self.notifier.take();
+
+        if let Some(worker) = self.worker.take() {
+            if worker.join().is_err() {
+                error!("state pruner panicked");
+            }
+        }
+    }
+}
+
+fn prune(mode: PruningMode, stores: &[DbAdapter], metrics: &PrunerMetrics) -> Result<u64> {
+    let PruningMode::Prune { window, batch_size } = mode else {
+        return Ok(0);
+    };
+    let batch_size = batch_size.max(1);
+
+    let mut pruned_nodes = 0;
+
+    for store in stores {
+        let Some(latest_version) = store.backend().latest_version() else {
+            continue;
+        };
+
+        let Some(stale_since_version) = latest_version.checked_sub(window) else {
+            continue;
+        };
+
+        loop {
+            let pruned = store
+                .backend()
+                .prune_stale_nodes(stale_since_version, batch_size)
+                .map_err(|err| {
+                    metrics.failed_runs.fetch_add(1, Ordering::Relaxed);
+                    StorageError::Other(err.to_string())
+                })?;
+
+            pruned_nodes += pruned.len() as u64;
+            metrics
+                .pruned_nodes
+                .fetch_add(pruned.len() as u64, Ordering::Relaxed);
+
+            if pruned.len() < batch_size {
+                break;
+            }
+        }
+
+        loop {
+            let pruned = store
+                .backend()
+                .prune_stale_values(stale_since_version, batch_size)
+                .map_err(|err| {
+                    metrics.failed_runs.fetch_add(1, Ordering::Relaxed);
+                    StorageError::Other(err.to_string())
+                })?;
+
+            metrics
+                .pruned_values
+                .fetch_add(pruned as u64, Ordering::Relaxed);
+
+            if pruned < batch_size {
+                break;
+            }
+        }
+    }
+
+    metrics.completed_runs.fetch_add(1, Ordering::Relaxed);
+
+    if pruned_nodes > 0 {
+        info!("pruned {pruned_nodes} stale trie nodes");
+    }
+
+    Ok(pruned_nodes)
+}
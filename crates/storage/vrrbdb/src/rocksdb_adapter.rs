@@ -4,8 +4,8 @@ use std::collections::{hash_map::Entry, BTreeSet, HashMap};
 use anyhow::{bail, Result};
 use parking_lot::RwLock;
 use patriecia::{
-    KeyHash, LeafNode, Node, NodeBatch, NodeKey, OwnedValue, Preimage, StaleNodeIndex, TreeReader,
-    TreeUpdateBatch, TreeWriter, Vers, VersionedDatabase,
+    KeyHash, LeafNode, Node, NodeBatch, NodeKey, OwnedValue, StaleNodeIndex, TreeReader,
+    TreeUpdateBatch, TreeWriter, Vers, Version, VersionedDatabase,
 };
 use primitives::{get_vrrb_environment, Environment, DEFAULT_VRRB_DB_PATH};
 use rocksdb::{IteratorMode, DB, DEFAULT_COLUMN_FAMILY_NAME};
@@ -13,30 +13,118 @@ use std::sync::Arc;
 use storage_utils::{get_node_data_dir, StorageError};
 use telemetry::error;
 
-#[derive(Debug)]
+use crate::{Database, Preimage, StorageBackend, ValueHistory};
+
+/// Suffix of the column family holding the version history of every value
+/// written to a trie, one entry per version keyed by the hash of the value's
+/// key followed by the version.
+pub const VALUE_HISTORY_COLUMN_FAMILY: &str = "value_history";
+
+/// Suffix of the column family holding the preimages of the key hashes used by
+/// a trie.
+pub const PREIMAGES_COLUMN_FAMILY: &str = "preimages";
+
+/// Suffix of the column family holding the indices of trie nodes replaced by
+/// later versions, keyed by the version they became stale at so the pruner
+/// can walk them oldest first.
+pub const STALE_NODES_COLUMN_FAMILY: &str = "stale_nodes";
+
+/// Suffix of the column family holding the value history entries replaced by
+/// later versions, keyed by the version they became stale at followed by
+/// their key in the value history.
+pub const STALE_VALUES_COLUMN_FAMILY: &str = "stale_values";
+
+/// How the database backing a store is opened.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AccessMode {
+    #[default]
+    ReadWrite,
+
+    /// Opens the database without taking its lock, so it can be inspected
+    /// while a node is using it. Every write fails.
+    ReadOnly,
+}
+
+/// Trie store kept in column families of a RocksDB `Database`, which may be
+/// shared with other stores. Nodes are kept in the column family named after
+/// the store, everything else in column families prefixed with its name.
+#[derive(Debug, Clone)]
 pub struct RocksDbAdapter {
+    database: Database,
     data: Arc<RwLock<RocksDbInner>>,
     column: String,
 }
-#[derive(Debug)]
+
+#[derive(Debug, Default)]
 pub struct RocksDbInner {
-    db: DB,
     stale_nodes: BTreeSet<StaleNodeIndex>,
-    value_history: HashMap<KeyHash, Vec<(Vers, Option<OwnedValue>)>>,
-    preimages: HashMap<KeyHash, Preimage>,
+    /// Highest version of any node written since the database was opened.
+    latest_version: Option<Version>,
 }
-impl RocksDbInner {
-    fn new(instance: DB) -> Self {
-        Self {
-            db: instance,
-            stale_nodes: BTreeSet::new(),
-            value_history: HashMap::new(),
-            preimages: HashMap::new(),
-        }
-    }
+
+/// Names of the column families a store named `column` keeps its trie in.
+pub fn store_column_families(column: &str) -> Vec<String> {
+    vec![
+        column.to_string(),
+        format!("{column}_{VALUE_HISTORY_COLUMN_FAMILY}"),
+        format!("{column}_{PREIMAGES_COLUMN_FAMILY}"),
+        format!("{column}_{STALE_NODES_COLUMN_FAMILY}"),
+        format!("{column}_{STALE_VALUES_COLUMN_FAMILY}"),
+    ]
 }
 
-fn base_db_options() -> rocksdb::Options {
+/// Encodes a stale node index so that indices sort by the version they became
+/// stale at.
+fn stale_node_key(index: &StaleNodeIndex) -> Result<Vec<u8>> {
+    let mut key = index.stale_since_version.to_be_bytes().to_vec();
+    key.extend(bincode::serialize(&index.node_key)?);
+
+    Ok(key)
+}
+
+fn decode_stale_node_key(bytes: &[u8]) -> Result<StaleNodeIndex> {
+    anyhow::ensure!(bytes.len() > 8, "stale node index is too short");
+
+    let (version_bytes, node_key_bytes) = bytes.split_at(8);
+    let mut stale_since_version = [0u8; 8];
+    stale_since_version.copy_from_slice(version_bytes);
+
+    Ok(StaleNodeIndex {
+        stale_since_version: Version::from_be_bytes(stale_since_version),
+        node_key: bincode::deserialize(node_key_bytes)?,
+    })
+}
+
+/// Encodes the key a value is stored under in the value history, so that the
+/// versions of a key sort next to each other, oldest first.
+fn value_history_key(key_hash: &KeyHash, version: Version) -> Result<Vec<u8>> {
+    let mut key = bincode::serialize(key_hash)?;
+    key.extend(version.to_be_bytes());
+
+    Ok(key)
+}
+
+fn decode_value_history_key(bytes: &[u8]) -> Result<(KeyHash, Version)> {
+    anyhow::ensure!(bytes.len() > 8, "value history key is too short");
+
+    let (key_hash_bytes, version_bytes) = bytes.split_at(bytes.len() - 8);
+
+    Ok((
+        bincode::deserialize(key_hash_bytes)?,
+        Version::from_be_bytes(version_bytes.try_into()?),
+    ))
+}
+
+/// Returns the latest value of a history written at or before `max_version`.
+pub(crate) fn value_at_version(history: &ValueHistory, max_version: Vers) -> Option<OwnedValue> {
+    history
+        .iter()
+        .rev()
+        .find(|(version, _)| *version <= max_version)
+        .and_then(|(_, value)| value.clone())
+}
+
+pub(crate) fn base_db_options() -> rocksdb::Options {
     let mut options = rocksdb::Options::default();
 
     let environ = get_vrrb_environment();
@@ -61,7 +149,7 @@ fn base_db_options() -> rocksdb::Options {
     options
 }
 
-fn new_db_instance(
+pub(crate) fn new_db_instance(
     options: rocksdb::Options,
     path: std::path::PathBuf,
     column_family: &str,
@@ -78,12 +166,13 @@ fn new_db_instance(
         }
     };
 
-    let column_family_exists = cfs.iter().any(|cf| cf == column_family);
+    let is_missing =
+        column_family != DEFAULT_COLUMN_FAMILY_NAME && !cfs.iter().any(|cf| cf == column_family);
 
     let mut instance = rocksdb::DB::open_cf(&options, &path, cfs)
         .map_err(|err| StorageError::Other(err.to_string()))?;
 
-    if !column_family_exists && column_family != DEFAULT_COLUMN_FAMILY_NAME {
+    if is_missing {
         instance
             .create_cf(column_family, &options)
             .map_err(|err| StorageError::Other(err.to_string()))?;
@@ -94,83 +183,75 @@ fn new_db_instance(
 
 impl RocksDbAdapter {
     pub fn new(path: std::path::PathBuf, column_family: &str) -> storage_utils::Result<Self> {
-        let mut options = base_db_options();
-        options.set_error_if_exists(false);
-        options.create_if_missing(true);
-        options.create_missing_column_families(true);
+        Self::open(path, column_family, AccessMode::ReadWrite)
+    }
 
-        let instance = new_db_instance(options, path, column_family)
-            .map_err(|err| StorageError::Other(err.to_string()))?;
+    /// Opens a database of its own for the store at `path`.
+    pub fn open(
+        path: std::path::PathBuf,
+        column_family: &str,
+        access: AccessMode,
+    ) -> storage_utils::Result<Self> {
+        let database = Database::open(path, access, &store_column_families(column_family))?;
+
+        Ok(Self::with_database(database, column_family))
+    }
 
-        Ok(Self {
-            data: Arc::new(RwLock::new(RocksDbInner::new(instance))),
+    /// Keeps the store in column families of a database shared with other
+    /// stores, which must have been opened with them.
+    pub fn with_database(database: Database, column_family: &str) -> Self {
+        Self {
+            data: Arc::new(RwLock::new(RocksDbInner::default())),
+            database,
             column: column_family.to_string(),
-        })
+        }
     }
 
-    pub fn write_tree_update_batch(&self, batch: TreeUpdateBatch) -> Result<()> {
-        self.write_node_batch(&batch.node_batch)?;
-        batch
-            .stale_node_index_batch
-            .into_iter()
-            .map(|i| self.put_stale_node_index(i))
-            .collect::<Result<Vec<_>>>()?;
-        Ok(())
+    fn value_history_column_family(&self) -> String {
+        format!("{}_{VALUE_HISTORY_COLUMN_FAMILY}", self.column)
     }
 
-    pub fn put_stale_node_index(&self, index: StaleNodeIndex) -> Result<()> {
-        let is_new_entry = self.data.write().stale_nodes.insert(index);
-        anyhow::ensure!(is_new_entry, "Duplicated retire log");
-        Ok(())
+    fn preimages_column_family(&self) -> String {
+        format!("{}_{PREIMAGES_COLUMN_FAMILY}", self.column)
     }
-}
 
-// TODO: handle these unwrap
-impl Clone for RocksDbAdapter {
-    fn clone(&self) -> Self {
-        let locked = self.data.read();
-        let mut options = base_db_options();
-        options.set_error_if_exists(false);
+    fn stale_nodes_column_family(&self) -> String {
+        format!("{}_{STALE_NODES_COLUMN_FAMILY}", self.column)
+    }
 
-        let db = new_db_instance(options, locked.db.path().into(), self.column.as_str()).unwrap();
+    fn stale_values_column_family(&self) -> String {
+        format!("{}_{STALE_VALUES_COLUMN_FAMILY}", self.column)
+    }
 
-        Self {
-            data: Arc::new(RwLock::new(RocksDbInner {
-                db,
-                stale_nodes: locked.stale_nodes.clone(),
-                value_history: locked.value_history.clone(),
-                preimages: locked.preimages.clone(),
-            })),
-            column: self.column.clone(),
-        }
+    /// Returns the version of the latest value written under `key_hash`
+    /// before `version`, if any.
+    fn previous_value_version(
+        &self,
+        key_hash: &KeyHash,
+        version: Version,
+    ) -> Result<Option<Version>> {
+        let Some(before) = version.checked_sub(1) else {
+            return Ok(None);
+        };
+
+        let previous = self.database.find_back(
+            &self.value_history_column_family(),
+            &bincode::serialize(key_hash)?,
+            &value_history_key(key_hash, before)?,
+            |_, _| Ok(true),
+        )?;
+
+        previous
+            .map(|(key, _)| decode_value_history_key(&key).map(|(_, version)| version))
+            .transpose()
     }
 }
 
 impl Default for RocksDbAdapter {
     fn default() -> Self {
-        let mut options = base_db_options();
-        options.set_error_if_exists(false);
-        options.create_if_missing(true);
-        options.create_missing_column_families(true);
-
         //
         // TODO: fix this unwrap
-        let db = new_db_instance(
-            options,
-            DEFAULT_VRRB_DB_PATH.into(),
-            DEFAULT_COLUMN_FAMILY_NAME,
-        )
-        .unwrap();
-
-        Self {
-            data: Arc::new(RwLock::new(RocksDbInner {
-                db,
-                stale_nodes: BTreeSet::new(),
-                value_history: HashMap::new(),
-                preimages: HashMap::new(),
-            })),
-            column: DEFAULT_COLUMN_FAMILY_NAME.to_string(),
-        }
+        Self::new(DEFAULT_VRRB_DB_PATH.into(), DEFAULT_COLUMN_FAMILY_NAME).unwrap()
     }
 }
 
@@ -180,7 +261,7 @@ impl VersionedDatabase for RocksDbAdapter {
     type HistoryIter = IntoIter<patriecia::KeyHash, Vec<(Vers, Option<OwnedValue>)>>;
 
     fn get(&self, max_version: Self::Version, node_key: KeyHash) -> Result<Option<OwnedValue>> {
-        self.get_value_option(max_version, node_key)
+        TreeReader::get_value_option(self, max_version, node_key)
     }
 
     fn update_batch(&self, tree_update_batch: TreeUpdateBatch) -> Result<()> {
@@ -188,17 +269,18 @@ impl VersionedDatabase for RocksDbAdapter {
     }
 
     fn nodes(&self) -> IntoIter<NodeKey, Node> {
-        let locked = self.data.read();
-        let iter = locked.db.iterator(IteratorMode::Start);
         let mut map = HashMap::new();
-        for (boxed_key, boxed_node) in iter.flatten() {
-            let key_bytes = boxed_key.into_vec();
-            let node_bytes = boxed_node.into_vec();
-            if let Ok(node_key) = bincode::deserialize::<NodeKey>(&key_bytes) {
-                if let Ok(node) = bincode::deserialize::<Node>(&node_bytes) {
-                    map.insert(node_key, node);
-                }
-            };
+
+        if let Ok(iter) = self.database.iterator(&self.column, IteratorMode::Start) {
+            for (boxed_key, boxed_node) in iter.flatten() {
+                let key_bytes = boxed_key.into_vec();
+                let node_bytes = boxed_node.into_vec();
+                if let Ok(node_key) = bincode::deserialize::<NodeKey>(&key_bytes) {
+                    if let Ok(node) = bincode::deserialize::<Node>(&node_bytes) {
+                        map.insert(node_key, node);
+                    }
+                };
+            }
         }
 
         map.into_iter()
@@ -210,7 +292,25 @@ impl VersionedDatabase for RocksDbAdapter {
         patriecia::KeyHash,
         Vec<(Self::Version, Option<patriecia::OwnedValue>)>,
     > {
-        self.data.read().value_history.clone().into_iter()
+        let mut map: HashMap<KeyHash, ValueHistory> = HashMap::new();
+
+        if let Ok(iter) = self
+            .database
+            .iterator(&self.value_history_column_family(), IteratorMode::Start)
+        {
+            for (boxed_key, boxed_value) in iter.flatten() {
+                let Ok((key_hash, _)) = decode_value_history_key(&boxed_key) else {
+                    continue;
+                };
+
+                if let Ok(entry) = bincode::deserialize::<(Vers, Option<OwnedValue>)>(&boxed_value)
+                {
+                    map.entry(key_hash).or_default().push(entry);
+                }
+            }
+        }
+
+        map.into_iter()
     }
 }
 impl TreeReader for RocksDbAdapter {
@@ -218,7 +318,7 @@ impl TreeReader for RocksDbAdapter {
 
     fn get_node_option(&self, node_key: &NodeKey) -> Result<Option<Node>> {
         let key_bytes = bincode::serialize(node_key)?;
-        if let Ok(Some(bytes)) = self.data.read().db.get(key_bytes) {
+        if let Ok(Some(bytes)) = self.database.get(&self.column, &key_bytes) {
             if let Ok(node) = bincode::deserialize(&bytes) {
                 Ok(Some(node))
             } else {
@@ -234,24 +334,32 @@ impl TreeReader for RocksDbAdapter {
         max_version: Vers,
         key_hash: patriecia::KeyHash,
     ) -> Result<Option<patriecia::OwnedValue>> {
-        match self.data.read().value_history.get(&key_hash) {
-            Some(version_history) => {
-                for (version, value) in version_history.iter().rev() {
-                    if *version <= max_version {
-                        return Ok(value.clone());
-                    }
+        let prefix = bincode::serialize(&key_hash)?;
+        let from = value_history_key(&key_hash, Version::MAX)?;
+
+        let mut value = None;
+        self.database.find_back(
+            &self.value_history_column_family(),
+            &prefix,
+            &from,
+            |_, bytes| {
+                let (version, entry): (Vers, Option<OwnedValue>) = bincode::deserialize(bytes)?;
+                if version > max_version {
+                    return Ok(false);
                 }
-                Ok(None)
-            }
-            None => Ok(None),
-        }
+
+                value = entry;
+                Ok(true)
+            },
+        )?;
+
+        Ok(value)
     }
 
     fn get_rightmost_leaf(&self) -> Result<Option<(NodeKey, LeafNode)>> {
-        let locked = self.data.read();
         let mut key_and_node: Option<(NodeKey, LeafNode)> = None;
 
-        let iter = locked.db.iterator(IteratorMode::Start);
+        let iter = self.database.iterator(&self.column, IteratorMode::Start)?;
         for (boxed_key, boxed_value) in iter.flatten() {
             let node_key: NodeKey = bincode::deserialize(&boxed_key.into_vec())?;
             let node_value: Node = bincode::deserialize(&boxed_value.into_vec())?;
@@ -270,26 +378,230 @@ impl TreeReader for RocksDbAdapter {
 impl TreeWriter for RocksDbAdapter {
     fn write_node_batch(&self, node_batch: &NodeBatch) -> Result<()> {
         let mut locked = self.data.write();
+        let history_column_family = self.value_history_column_family();
+        let stale_values_column_family = self.stale_values_column_family();
+        let mut writes = Vec::new();
+        let mut latest_version = locked.latest_version;
+
         for (node_key, node) in node_batch.nodes() {
+            latest_version = latest_version.max(Some(node_key.version()));
             let node_key_bytes = bincode::serialize(&node_key)?;
             let node_bytes = bincode::serialize(&node)?;
-            locked.db.put(node_key_bytes, node_bytes)?;
+            writes.push((self.column.as_str(), node_key_bytes, Some(node_bytes)));
         }
 
+        // NOTE: values written earlier in the batch may not be visible
+        // through the database yet
+        let mut written_versions: HashMap<KeyHash, Version> = HashMap::new();
+
         for ((version, key_hash), value) in node_batch.values() {
-            put_value(
-                &mut locked.value_history,
-                version.into(),
-                *key_hash,
-                value.clone(),
-            )?
+            let previous_version = match written_versions.insert(*key_hash, *version) {
+                Some(previous_version) => Some(previous_version),
+                None => self.previous_value_version(key_hash, *version)?,
+            };
+
+            // NOTE: the previous value is no longer needed to read versions
+            // from this one on, it is pruned once this version falls out of
+            // the pruning window
+            if let Some(previous_version) = previous_version.filter(|previous| previous < version) {
+                let mut stale_key = version.to_be_bytes().to_vec();
+                stale_key.extend(value_history_key(key_hash, previous_version)?);
+
+                writes.push((stale_values_column_family.as_str(), stale_key, Some(vec![])));
+            }
+
+            let entry: (Vers, &Option<OwnedValue>) = (version.into(), value);
+            writes.push((
+                history_column_family.as_str(),
+                value_history_key(key_hash, *version)?,
+                Some(bincode::serialize(&entry)?),
+            ));
         }
+
+        self.database.write(writes)?;
+
+        locked.latest_version = latest_version;
+
         Ok(())
     }
 }
 
+impl StorageBackend for RocksDbAdapter {
+    fn get_node_option(&self, node_key: &NodeKey) -> Result<Option<Node>> {
+        TreeReader::get_node_option(self, node_key)
+    }
+
+    fn get_value_option(&self, max_version: Vers, key_hash: KeyHash) -> Result<Option<OwnedValue>> {
+        TreeReader::get_value_option(self, max_version, key_hash)
+    }
+
+    fn get_rightmost_leaf(&self) -> Result<Option<(NodeKey, LeafNode)>> {
+        TreeReader::get_rightmost_leaf(self)
+    }
+
+    fn write_node_batch(&self, node_batch: &NodeBatch) -> Result<()> {
+        TreeWriter::write_node_batch(self, node_batch)
+    }
+
+    fn nodes(&self) -> IntoIter<NodeKey, Node> {
+        VersionedDatabase::nodes(self)
+    }
+
+    fn value_history(&self) -> IntoIter<KeyHash, ValueHistory> {
+        VersionedDatabase::value_history(self)
+    }
+
+    fn put_stale_node_index(&self, index: StaleNodeIndex) -> Result<()> {
+        let mut locked = self.data.write();
+
+        self.database.write([(
+            self.stale_nodes_column_family().as_str(),
+            stale_node_key(&index)?,
+            Some(vec![]),
+        )])?;
+
+        // NOTE: versions staged for a block are written once with the block
+        // and again when they are published, retiring the same nodes twice
+        locked.stale_nodes.insert(index);
+        Ok(())
+    }
+
+    /// Lists the column families the store is kept in.
+    fn column_families(&self) -> Result<Vec<String>> {
+        let store_column_families = store_column_families(&self.column);
+
+        Ok(self
+            .database
+            .column_families()?
+            .into_iter()
+            .filter(|name| store_column_families.contains(name))
+            .collect())
+    }
+
+    /// Compacts every column family the store is kept in, reclaiming the
+    /// space left behind by deleted and overwritten entries.
+    fn compact(&self) -> Result<()> {
+        for name in StorageBackend::column_families(self)? {
+            self.database.compact(&name)?;
+        }
+
+        Ok(())
+    }
+
+    fn latest_version(&self) -> Option<Version> {
+        self.data.read().latest_version
+    }
+
+    fn prune_stale_nodes(
+        &self,
+        stale_since_version: Version,
+        limit: usize,
+    ) -> Result<Vec<StaleNodeIndex>> {
+        let mut locked = self.data.write();
+        let column_family = self.stale_nodes_column_family();
+
+        let mut pruned = Vec::new();
+        let mut writes = Vec::new();
+
+        for entry in self
+            .database
+            .iterator(&column_family, IteratorMode::Start)?
+            .take(limit)
+        {
+            let (index_bytes, _) = entry?;
+            let index = decode_stale_node_key(&index_bytes)?;
+
+            if index.stale_since_version > stale_since_version {
+                break;
+            }
+
+            writes.push((
+                self.column.as_str(),
+                bincode::serialize(&index.node_key)?,
+                None,
+            ));
+            writes.push((column_family.as_str(), index_bytes.into_vec(), None));
+            pruned.push(index);
+        }
+
+        // NOTE: pruned entries are never part of a block being written, they
+        // must not be dropped if that block is discarded
+        self.database.write_unstaged(writes)?;
+
+        for index in pruned.iter() {
+            locked.stale_nodes.remove(index);
+        }
+
+        Ok(pruned)
+    }
+
+    fn prune_stale_values(&self, stale_since_version: Version, limit: usize) -> Result<usize> {
+        let column_family = self.stale_values_column_family();
+        let history_column_family = self.value_history_column_family();
+
+        let mut writes = Vec::new();
+
+        for entry in self
+            .database
+            .iterator(&column_family, IteratorMode::Start)?
+            .take(limit)
+        {
+            let (index_bytes, _) = entry?;
+            anyhow::ensure!(index_bytes.len() > 8, "stale value index is too short");
+
+            let (version_bytes, history_key) = index_bytes.split_at(8);
+            if Version::from_be_bytes(version_bytes.try_into()?) > stale_since_version {
+                break;
+            }
+
+            writes.push((history_column_family.as_str(), history_key.to_vec(), None));
+            writes.push((column_family.as_str(), index_bytes.to_vec(), None));
+        }
+
+        let pruned = writes.len() / 2;
+        self.database.write_unstaged(writes)?;
+
+        Ok(pruned)
+    }
+
+    /// Number of stale nodes still stored on disk.
+    fn stale_node_count(&self) -> Result<usize> {
+        Ok(self
+            .database
+            .iterator(&self.stale_nodes_column_family(), IteratorMode::Start)?
+            .count())
+    }
+
+    /// Stores the preimages of key hashes so they survive restarts.
+    fn put_preimages(&self, preimages: Vec<(KeyHash, Preimage)>) -> Result<()> {
+        let column_family = self.preimages_column_family();
+
+        let writes = preimages
+            .into_iter()
+            .map(|(key_hash, preimage)| {
+                Ok((
+                    column_family.as_str(),
+                    bincode::serialize(&key_hash)?,
+                    Some(preimage),
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.database.write(writes)?;
+
+        Ok(())
+    }
+
+    fn get_preimage(&self, key_hash: KeyHash) -> Result<Option<Preimage>> {
+        Ok(self.database.get(
+            &self.preimages_column_family(),
+            &bincode::serialize(&key_hash)?,
+        )?)
+    }
+}
+
 pub fn put_value(
-    value_history: &mut HashMap<KeyHash, Vec<(Vers, Option<OwnedValue>)>>,
+    value_history: &mut HashMap<KeyHash, ValueHistory>,
     version: Vers,
     key: KeyHash,
     value: Option<OwnedValue>,
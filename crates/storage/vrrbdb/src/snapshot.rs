@@ -0,0 +1,140 @@
+use std::path::Path;
+
+use block::{Certificate, ConvergenceBlock, ProposalBlock};
+use primitives::Address;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use storage_utils::{Result, StorageError};
+use vrrb_core::{account::Account, claim::Claim, transactions::TransactionKind};
+
+/// Version of the snapshot encoding. Bumped whenever the layout of
+/// `StateSnapshot` changes so older nodes refuse snapshots they cannot read.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 3;
+
+/// Copy of the state, transaction and claim tries taken right before a
+/// certified `ConvergenceBlock` was applied, so the root of the state is the
+/// one its header commits to. New nodes import it to bootstrap instead of
+/// replaying every block since genesis, then apply the block along with the
+/// proposals it references and carry on from there.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    pub block_height: u128,
+    pub block: ConvergenceBlock,
+    pub proposals: Vec<ProposalBlock>,
+    pub transactions_root_hash: String,
+    pub claims_root_hash: String,
+    pub accounts: Vec<(Address, Account)>,
+    pub transactions: Vec<TransactionKind>,
+    pub claims: Vec<Claim>,
+}
+
+/// On-disk layout of a snapshot: the encoded `StateSnapshot` along with the
+/// format version it was written with and its checksum.
+#[derive(Debug, Serialize, Deserialize)]
+struct SnapshotEnvelope {
+    format_version: u32,
+    checksum: Vec<u8>,
+    payload: Vec<u8>,
+}
+
+impl StateSnapshot {
+    /// Returns the certificate of the block the snapshot was taken at,
+    /// ensuring it was issued for that block and its header, and that every
+    /// proposal of the snapshot is referenced by that header.
+    pub fn certificate(&self) -> Result<&Certificate> {
+        let certificate = self
+            .block
+            .certificate
+            .as_ref()
+            .ok_or(StorageError::Other(format!(
+                "snapshot block {} is not certified",
+                self.block.hash
+            )))?;
+
+        if certificate.block_hash != self.block.hash {
+            return Err(StorageError::Other(format!(
+                "snapshot certificate is for block {}, not {}",
+                certificate.block_hash, self.block.hash
+            )));
+        }
+
+        if self.block.header.convergence_block_hash() != self.block.hash {
+            return Err(StorageError::Other(format!(
+                "snapshot block {} does not match its header",
+                self.block.hash
+            )));
+        }
+
+        if self.block.header.state_root_hash.is_empty() {
+            return Err(StorageError::Other(format!(
+                "snapshot block {} does not commit to a state root",
+                self.block.hash
+            )));
+        }
+
+        if self.block.header.block_height != self.block_height {
+            return Err(StorageError::Other(format!(
+                "snapshot height {} does not match block height {}",
+                self.block_height, self.block.header.block_height
+            )));
+        }
+
+        if let Some(proposal) = self
+            .proposals
+            .iter()
+            .find(|proposal| !self.block.header.ref_hashes.contains(&proposal.hash))
+        {
+            return Err(StorageError::Other(format!(
+                "snapshot proposal {} is not referenced by block {}",
+                proposal.hash, self.block.hash
+            )));
+        }
+
+        Ok(certificate)
+    }
+
+    /// Serializes the snapshot into a versioned, checksummed byte array.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let payload =
+            bincode::serialize(self).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        let envelope = SnapshotEnvelope {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            checksum: Sha256::digest(&payload).to_vec(),
+            payload,
+        };
+
+        bincode::serialize(&envelope).map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    /// Deserializes a snapshot produced by `StateSnapshot::encode`, rejecting
+    /// unknown format versions and corrupted payloads.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let envelope: SnapshotEnvelope =
+            bincode::deserialize(bytes).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        if envelope.format_version != SNAPSHOT_FORMAT_VERSION {
+            return Err(StorageError::Other(format!(
+                "unsupported snapshot format version {}, expected {}",
+                envelope.format_version, SNAPSHOT_FORMAT_VERSION
+            )));
+        }
+
+        if Sha256::digest(&envelope.payload).as_slice() != envelope.checksum.as_slice() {
+            return Err(StorageError::Other(
+                "snapshot checksum does not match its contents".to_string(),
+            ));
+        }
+
+        bincode::deserialize(&envelope.payload).map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    pub fn write_to_file(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.encode()?).map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    pub fn read_from_file(path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path).map_err(|err| StorageError::Other(err.to_string()))?;
+        Self::decode(&bytes)
+    }
+}
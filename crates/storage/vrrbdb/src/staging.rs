@@ -0,0 +1,83 @@
+use integral_db::ReadHandleFactory;
+use patriecia::{JellyfishMerkleTree, KeyHash, RootHash, TreeUpdateBatch, Version};
+use serde::Serialize;
+use sha2::Sha256;
+use storage_utils::{Result, StorageError};
+
+use crate::{inspect::latest_tree, DbAdapter, Preimage};
+
+/// Version of a trie computed out of a set of changes, neither written to the
+/// trie's database nor published to its readers yet.
+#[derive(Debug)]
+pub(crate) struct StagedVersion {
+    pub version: Version,
+    pub root_hash: RootHash,
+    preimages: Vec<(KeyHash, Preimage)>,
+    update_batch: TreeUpdateBatch,
+}
+
+impl StagedVersion {
+    /// Writes the nodes of the version, along with the preimages of the keys
+    /// it stores values under, to the trie's database. Readers only see the
+    /// version once the changes it was staged from are published.
+    pub fn write(self, db_adapter: &DbAdapter) -> Result<()> {
+        db_adapter
+            .backend()
+            .put_preimages(self.preimages)
+            .and_then(|_| {
+                db_adapter
+                    .backend()
+                    .write_tree_update_batch(self.update_batch)
+            })
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+}
+
+/// Computes the version publishing `changes` to the trie read through
+/// `factory` produces, on top of its latest published version. Keys and
+/// values are encoded the way the trie encodes them.
+pub(crate) fn stage_version<K: Serialize, V: Serialize>(
+    factory: &ReadHandleFactory<JellyfishMerkleTree<DbAdapter, Sha256>>,
+    changes: &[(K, Option<V>)],
+) -> Result<StagedVersion> {
+    let (tree, latest_version) = latest_tree(factory)?;
+
+    // NOTE: a trie nothing was published to has no root yet, its first
+    // version is the one it starts at
+    let version = match tree.get_root_hash(latest_version) {
+        Ok(_) => latest_version + 1,
+        Err(_) => latest_version,
+    };
+
+    let mut preimages = Vec::new();
+    let mut value_set = Vec::with_capacity(changes.len());
+
+    for (key, value) in changes {
+        let preimage =
+            bincode::serialize(key).map_err(|err| StorageError::Other(err.to_string()))?;
+        let key_hash = KeyHash::with::<Sha256>(&preimage);
+
+        let value = value
+            .as_ref()
+            .map(bincode::serialize)
+            .transpose()
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        if value.is_some() {
+            preimages.push((key_hash, preimage));
+        }
+
+        value_set.push((key_hash, value));
+    }
+
+    let (root_hash, update_batch) = tree
+        .put_value_set(value_set, version)
+        .map_err(|err| StorageError::Other(err.to_string()))?;
+
+    Ok(StagedVersion {
+        version,
+        root_hash,
+        preimages,
+        update_batch,
+    })
+}
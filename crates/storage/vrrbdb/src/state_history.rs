@@ -0,0 +1,181 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use parking_lot::RwLock;
+use patriecia::Version;
+use rocksdb::IteratorMode;
+use serde::{Deserialize, Serialize};
+use storage_utils::{Result, StorageError};
+
+use crate::{
+    column_store::{decode, encode},
+    Database,
+};
+
+/// Number of blocks whose state can be queried by default.
+pub const DEFAULT_STATE_HISTORY_WINDOW: usize = 1_000;
+
+/// Column family holding the versions recorded by a `StateHistory`, keyed by
+/// block height.
+pub const STATE_HISTORY_COLUMN_FAMILY: &str = "state_history";
+
+/// Versions of the state, transaction and claim tries right after a block was
+/// applied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateVersion {
+    pub block_height: u128,
+    pub block_hash: String,
+    pub state_root_hash: String,
+    pub state_version: Version,
+    pub transactions_version: Version,
+    pub claims_version: Version,
+}
+
+/// Index of the trie versions produced by the last `window` applied blocks,
+/// used to answer queries about past state. Clones share the same index so
+/// read handles see blocks applied after they were created.
+#[derive(Debug, Clone)]
+pub struct StateHistory {
+    versions: Arc<RwLock<VecDeque<StateVersion>>>,
+    window: usize,
+    /// Where the index is persisted so it survives restarts, if anywhere
+    database: Option<Database>,
+}
+
+impl Default for StateHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_STATE_HISTORY_WINDOW)
+    }
+}
+
+impl StateHistory {
+    pub fn new(window: usize) -> Self {
+        Self {
+            versions: Arc::new(RwLock::new(VecDeque::new())),
+            window,
+            database: None,
+        }
+    }
+
+    /// Keeps the index in a column family of a database shared with other
+    /// stores, restoring the versions of the last `window` blocks persisted
+    /// there.
+    pub fn with_database(database: Database, window: usize) -> Result<Self> {
+        let mut versions = VecDeque::new();
+
+        for entry in database
+            .iterator(STATE_HISTORY_COLUMN_FAMILY, IteratorMode::End)?
+            .take(window)
+        {
+            let (_, version) = entry.map_err(|err| StorageError::Other(err.to_string()))?;
+            versions.push_front(decode(&version)?);
+        }
+
+        Ok(Self {
+            versions: Arc::new(RwLock::new(versions)),
+            window,
+            database: Some(database),
+        })
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Persists the versions produced by a block, and forgets the ones it
+    /// pushes out of the window, without recording them in memory yet. Meant
+    /// to be written along with the block, then followed by `record` once
+    /// the write is committed.
+    pub(crate) fn persist(&self, version: &StateVersion) -> Result<()> {
+        let Some(database) = &self.database else {
+            return Ok(());
+        };
+
+        let versions = self.versions.read();
+        let retained: Vec<&StateVersion> = versions
+            .iter()
+            .filter(|recorded| recorded.block_height != version.block_height)
+            .collect();
+        let evicted = (retained.len() + 1).saturating_sub(self.window);
+
+        let mut writes = vec![(
+            STATE_HISTORY_COLUMN_FAMILY,
+            version.block_height.to_be_bytes().to_vec(),
+            Some(encode(version)?),
+        )];
+
+        for recorded in retained.into_iter().take(evicted) {
+            writes.push((
+                STATE_HISTORY_COLUMN_FAMILY,
+                recorded.block_height.to_be_bytes().to_vec(),
+                None,
+            ));
+        }
+
+        database.write(writes)
+    }
+
+    /// Records the versions produced by a block, forgetting the oldest ones
+    /// once the window is exceeded. A block applied again replaces its
+    /// previous record.
+    pub fn record(&self, version: StateVersion) {
+        let mut versions = self.versions.write();
+
+        versions.retain(|recorded| recorded.block_height != version.block_height);
+        versions.push_back(version);
+
+        while versions.len() > self.window {
+            versions.pop_front();
+        }
+    }
+
+    /// Returns the versions produced by the block at the given height, if
+    /// still within the window.
+    pub fn at_height(&self, block_height: u128) -> Option<StateVersion> {
+        self.versions
+            .read()
+            .iter()
+            .find(|version| version.block_height == block_height)
+            .cloned()
+    }
+
+    /// Returns the versions produced by the last block applied below the
+    /// given height, i.e. the state the block at that height was built on, if
+    /// still within the window.
+    pub fn before_height(&self, block_height: u128) -> Option<StateVersion> {
+        self.versions
+            .read()
+            .iter()
+            .rev()
+            .find(|version| version.block_height < block_height)
+            .cloned()
+    }
+
+    /// Returns the versions of the latest block that produced the given state
+    /// root hash, if still within the window.
+    pub fn at_state_root(&self, state_root_hash: &str) -> Option<StateVersion> {
+        self.versions
+            .read()
+            .iter()
+            .rev()
+            .find(|version| version.state_root_hash == state_root_hash)
+            .cloned()
+    }
+
+    /// Returns the versions produced by the oldest block still within the
+    /// window.
+    pub fn oldest(&self) -> Option<StateVersion> {
+        self.versions.read().front().cloned()
+    }
+
+    pub fn latest(&self) -> Option<StateVersion> {
+        self.versions.read().back().cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.versions.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
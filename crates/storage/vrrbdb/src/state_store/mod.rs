@@ -7,7 +7,10 @@ use sha2::Sha256;
 use storage_utils::{Result, StorageError};
 use vrrb_core::account::{Account, UpdateArgs};
 
-use crate::RocksDbAdapter;
+use crate::{
+    staging::{stage_version, StagedVersion},
+    AccessMode, DbAdapter, RocksDbAdapter,
+};
 
 mod state_store_rh;
 pub use state_store_rh::*;
@@ -17,7 +20,8 @@ pub type FailedAccountUpdates = Vec<(Address, Vec<UpdateArgs>, Result<()>)>;
 
 #[derive(Debug, Clone)]
 pub struct StateStore {
-    trie: LeftRightTrie<'static, Address, Account, RocksDbAdapter, Sha256>,
+    trie: LeftRightTrie<'static, Address, Account, DbAdapter, Sha256>,
+    db_adapter: DbAdapter,
 }
 
 impl Default for StateStore {
@@ -29,9 +33,7 @@ impl Default for StateStore {
 
         let db_adapter = RocksDbAdapter::new(db_path, "state").unwrap_or_default();
 
-        let trie = LeftRightTrie::new(Arc::new(db_adapter));
-
-        Self { trie }
+        Self::with_backend(DbAdapter::new(db_adapter))
     }
 }
 
@@ -41,9 +43,33 @@ impl StateStore {
     pub fn new(path: &Path) -> Self {
         let path = path.join("state");
         let db_adapter = RocksDbAdapter::new(path, "state").unwrap_or_default();
-        let trie = LeftRightTrie::new(Arc::new(db_adapter));
 
-        Self { trie }
+        Self::with_backend(DbAdapter::new(db_adapter))
+    }
+
+    /// Returns a store kept entirely in memory
+    pub fn in_memory() -> Self {
+        Self::with_backend(DbAdapter::in_memory())
+    }
+
+    pub fn with_backend(db_adapter: DbAdapter) -> Self {
+        let trie = LeftRightTrie::new(Arc::new(db_adapter.clone()));
+
+        Self { trie, db_adapter }
+    }
+
+    /// Opens the store at `path`, failing instead of falling back to the
+    /// default location when the database cannot be opened.
+    pub fn open(path: &Path, access: AccessMode) -> Result<Self> {
+        let path = path.join("state");
+        let db_adapter = RocksDbAdapter::open(path, "state", access)?;
+
+        Ok(Self::with_backend(DbAdapter::new(db_adapter)))
+    }
+
+    /// Returns the database backing the trie.
+    pub(crate) fn db_adapter(&self) -> DbAdapter {
+        self.db_adapter.clone()
     }
 
     /// Returns new ReadHandle to the VrrDb data. As long as the returned value
@@ -82,6 +108,10 @@ impl StateStore {
             ));
         }
 
+        self.db_adapter
+            .put_preimages([&key])
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
         self.trie.insert(key, account);
 
         Ok(())
@@ -167,13 +197,32 @@ impl StateStore {
             .map_err(|e| StorageError::Other(e.to_string()))
     }
 
-    /// Updates a given account if it exists within the store
+    /// Ensures an update carrying a transaction nonce moves the account's
+    /// nonce forward, so a transaction can never be applied twice. Updates
+    /// without a nonce (credits, fees, rewards) are always allowed.
+    pub(crate) fn check_nonce(account: &Account, update: &UpdateArgs) -> Result<()> {
+        match update.nonce {
+            Some(nonce) if nonce <= account.nonce() => Err(StorageError::Other(format!(
+                "stale nonce {} for account {}, current nonce is {}",
+                nonce,
+                account.address(),
+                account.nonce()
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Updates a given account if it exists within the store. If the update
+    /// carries the nonce of an applied transaction the account's nonce is
+    /// advanced to it.
     pub fn update_uncommited(&mut self, key: Address, update: UpdateArgs) -> Result<()> {
         let mut account = self
             .read_handle()
             .get(&key)
             .map_err(|err| StorageError::Other(err.to_string()))?;
 
+        Self::check_nonce(&account, &update)?;
+
         account
             .update(update)
             .map_err(|err| StorageError::Other(err.to_string()))?;
@@ -249,9 +298,11 @@ impl StateStore {
             match account_result {
                 Ok(mut account) => {
                     for update in v.as_slice() {
-                        let update_result = account
-                            .update(update.clone())
-                            .map_err(|err| StorageError::Other(err.to_string()));
+                        let update_result = Self::check_nonce(&account, update).and_then(|_| {
+                            account
+                                .update(update.clone())
+                                .map_err(|err| StorageError::Other(err.to_string()))
+                        });
 
                         if let Err(err) = update_result {
                             fail = (true, Err(err));
@@ -288,8 +339,25 @@ impl StateStore {
             .map_err(|e| StorageError::Other(e.to_string()))
     }
 
-    pub fn extend(&mut self, accounts: Vec<(Address, Option<Account>)>) {
-        self.trie.extend(accounts)
+    pub fn extend(&mut self, accounts: Vec<(Address, Option<Account>)>) -> Result<()> {
+        self.db_adapter
+            .put_preimages(
+                accounts
+                    .iter()
+                    .filter(|(_, account)| account.is_some())
+                    .map(|(key, _)| key),
+            )
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        self.trie.extend(accounts);
+
+        Ok(())
+    }
+
+    /// Computes the version extending the trie with `accounts` would
+    /// publish, without writing or publishing it.
+    pub(crate) fn stage(&self, accounts: &[(Address, Option<Account>)]) -> Result<StagedVersion> {
+        stage_version(&self.trie.factory(), accounts)
     }
 
     pub fn factory(&self) -> StateStoreReadHandleFactory {
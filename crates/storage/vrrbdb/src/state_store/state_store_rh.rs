@@ -1,21 +1,26 @@
 use std::collections::HashMap;
 
 use integral_db::{JellyfishMerkleTreeWrapper, ReadHandleFactory};
-use patriecia::JellyfishMerkleTree;
+use patriecia::{JellyfishMerkleTree, RootHash, Version};
 use primitives::Address;
 use sha2::Sha256;
+use storage_proofs::InclusionProof;
 use storage_utils::{Result, StorageError};
 use vrrb_core::account::Account;
 
-use crate::RocksDbAdapter;
+use crate::{
+    inspect::{root_hash_at, root_hashes, verify_trie_integrity},
+    proofs::generate_inclusion_proof,
+    DbAdapter, IntegrityReport, VersionedRootHash,
+};
 
 #[derive(Debug, Clone)]
 pub struct StateStoreReadHandle {
-    pub inner: JellyfishMerkleTreeWrapper<RocksDbAdapter, Sha256>,
+    pub inner: JellyfishMerkleTreeWrapper<DbAdapter, Sha256>,
 }
 
 impl StateStoreReadHandle {
-    pub fn new(inner: JellyfishMerkleTreeWrapper<RocksDbAdapter, Sha256>) -> Self {
+    pub fn new(inner: JellyfishMerkleTreeWrapper<DbAdapter, Sha256>) -> Self {
         Self { inner }
     }
 
@@ -23,11 +28,21 @@ impl StateStoreReadHandle {
     /// Returns `Some(Account)` if an account exist under given PublicKey.
     /// Otherwise returns `None`.
     pub fn get(&self, key: &Address) -> Result<Account> {
+        self.get_at_version(key, self.inner.version())
+    }
+
+    /// Returns the account stored under `key` as of the given trie version.
+    pub fn get_at_version(&self, key: &Address, version: Version) -> Result<Account> {
         self.inner
-            .get(key, self.inner.version())
+            .get(key, version)
             .map_err(|err| StorageError::Other(err.to_string()))
     }
 
+    /// Returns the version of the trie this handle reads from
+    pub fn version(&self) -> Version {
+        self.inner.version()
+    }
+
     /// Get a batch of accounts by providing Vec of PublicKeysHash
     ///
     /// Returns HashMap indexed by PublicKeys and containing either
@@ -44,11 +59,16 @@ impl StateStoreReadHandle {
     }
 
     pub fn entries(&self) -> Result<HashMap<Address, Account>> {
+        self.entries_at_version(self.inner.version())
+    }
+
+    /// Returns every account stored in the trie as of the given version.
+    pub fn entries_at_version(&self, version: Version) -> Result<HashMap<Address, Account>> {
         // TODO: revisit and refactor into inner wrapper
 
         Ok(self
             .inner
-            .iter(self.inner.version())
+            .iter(version)
             .map_err(|err| {
                 StorageError::Other(format!("unable to create iterator from trie: {}", err))
             })?
@@ -75,11 +95,11 @@ impl StateStoreReadHandle {
 
 #[derive(Debug, Clone)]
 pub struct StateStoreReadHandleFactory {
-    inner: ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>,
+    inner: ReadHandleFactory<JellyfishMerkleTree<DbAdapter, Sha256>>,
 }
 
 impl StateStoreReadHandleFactory {
-    pub fn new(inner: ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>) -> Self {
+    pub fn new(inner: ReadHandleFactory<JellyfishMerkleTree<DbAdapter, Sha256>>) -> Self {
         Self { inner }
     }
 
@@ -95,4 +115,26 @@ impl StateStoreReadHandleFactory {
 
         StateStoreReadHandle { inner }
     }
+
+    /// Produces a proof that the account stored under `key` is included in
+    /// the latest version of the state trie.
+    pub fn get_proof(&self, key: &Address) -> Result<InclusionProof> {
+        generate_inclusion_proof(&self.inner, key)
+    }
+
+    /// Returns the root hash of the trie at one of its versions still stored
+    pub fn root_hash_at_version(&self, version: Version) -> Result<RootHash> {
+        root_hash_at(&self.inner, version)
+    }
+
+    /// Returns the root hash of every version of the trie still stored
+    pub fn root_hashes(&self) -> Result<Vec<VersionedRootHash>> {
+        root_hashes(&self.inner)
+    }
+
+    /// Checks every leaf of the latest version of the trie against its root
+    /// hash
+    pub fn verify_integrity(&self) -> Result<IntegrityReport> {
+        verify_trie_integrity(&self.inner)
+    }
 }
@@ -0,0 +1,179 @@
+use std::{collections::hash_map::IntoIter, fmt::Debug, sync::Arc};
+
+use anyhow::Result;
+use patriecia::{
+    KeyHash, LeafNode, Node, NodeBatch, NodeKey, OwnedValue, StaleNodeIndex, TreeReader,
+    TreeUpdateBatch, TreeWriter, Vers, Version, VersionedDatabase,
+};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::MemoryDbAdapter;
+
+pub type ValueHistory = Vec<(Vers, Option<OwnedValue>)>;
+
+/// Serialized key a trie files a value under, the hash of which is its
+/// `KeyHash`.
+pub type Preimage = Vec<u8>;
+
+/// Selects where the stores of a `VrrbDb` keep their tries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StorageBackendType {
+    /// Persists every store to RocksDB under the configured path
+    #[default]
+    RocksDb,
+
+    /// Keeps every store in memory. Nothing survives once the database is
+    /// dropped, meant for tests and short lived nodes.
+    InMemory,
+}
+
+/// Storage the tries are written to. Implemented by `RocksDbAdapter` and
+/// `MemoryDbAdapter`.
+pub trait StorageBackend: Debug + Send + Sync {
+    fn get_node_option(&self, node_key: &NodeKey) -> Result<Option<Node>>;
+
+    /// Returns the latest value written under `key_hash` at or before
+    /// `max_version`.
+    fn get_value_option(&self, max_version: Vers, key_hash: KeyHash) -> Result<Option<OwnedValue>>;
+
+    fn get_rightmost_leaf(&self) -> Result<Option<(NodeKey, LeafNode)>>;
+
+    fn write_node_batch(&self, node_batch: &NodeBatch) -> Result<()>;
+
+    fn put_stale_node_index(&self, index: StaleNodeIndex) -> Result<()>;
+
+    fn nodes(&self) -> IntoIter<NodeKey, Node>;
+
+    fn value_history(&self) -> IntoIter<KeyHash, ValueHistory>;
+
+    /// Stores the preimages of key hashes, all at once.
+    fn put_preimages(&self, preimages: Vec<(KeyHash, Preimage)>) -> Result<()>;
+
+    /// Returns the preimage of a key hash, if one was ever stored.
+    fn get_preimage(&self, key_hash: KeyHash) -> Result<Option<Preimage>>;
+
+    /// Highest version written to the trie since the backend was opened.
+    fn latest_version(&self) -> Option<Version>;
+
+    /// Deletes up to `limit` nodes that became stale at or before
+    /// `stale_since_version`, oldest first, along with their stale indices.
+    /// Returns the indices of the nodes that were deleted.
+    fn prune_stale_nodes(
+        &self,
+        stale_since_version: Version,
+        limit: usize,
+    ) -> Result<Vec<StaleNodeIndex>>;
+
+    /// Deletes up to `limit` values that were replaced by a later version at
+    /// or before `stale_since_version`, oldest first. Returns the number of
+    /// values deleted.
+    fn prune_stale_values(&self, stale_since_version: Version, limit: usize) -> Result<usize>;
+
+    /// Number of stale nodes still stored.
+    fn stale_node_count(&self) -> Result<usize>;
+
+    /// Lists the column families of the underlying database, if it has any.
+    fn column_families(&self) -> Result<Vec<String>>;
+
+    /// Reclaims the space left behind by deleted and overwritten entries.
+    fn compact(&self) -> Result<()>;
+
+    fn write_tree_update_batch(&self, batch: TreeUpdateBatch) -> Result<()> {
+        self.write_node_batch(&batch.node_batch)?;
+        batch
+            .stale_node_index_batch
+            .into_iter()
+            .try_for_each(|index| self.put_stale_node_index(index))
+    }
+}
+
+/// Shared handle to the `StorageBackend` of a store, used as the database of
+/// its trie.
+#[derive(Debug, Clone)]
+pub struct DbAdapter {
+    inner: Arc<dyn StorageBackend>,
+}
+
+impl DbAdapter {
+    pub fn new(backend: impl StorageBackend + 'static) -> Self {
+        Self {
+            inner: Arc::new(backend),
+        }
+    }
+
+    pub fn in_memory() -> Self {
+        Self::new(MemoryDbAdapter::default())
+    }
+
+    pub fn backend(&self) -> &dyn StorageBackend {
+        self.inner.as_ref()
+    }
+
+    /// Stores the preimages of the hashes `keys` are filed under in the
+    /// trie, so keys can be recovered from their hashes.
+    pub(crate) fn put_preimages<'a, K: Serialize + 'a>(
+        &self,
+        keys: impl IntoIterator<Item = &'a K>,
+    ) -> Result<()> {
+        let preimages = keys
+            .into_iter()
+            .map(|key| {
+                let preimage = bincode::serialize(key)?;
+                Ok((KeyHash::with::<Sha256>(&preimage), preimage))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.inner.put_preimages(preimages)
+    }
+}
+
+impl Default for DbAdapter {
+    fn default() -> Self {
+        Self::in_memory()
+    }
+}
+
+impl VersionedDatabase for DbAdapter {
+    type Version = Vers;
+    type NodeIter = IntoIter<NodeKey, Node>;
+    type HistoryIter = IntoIter<KeyHash, ValueHistory>;
+
+    fn get(&self, max_version: Self::Version, node_key: KeyHash) -> Result<Option<OwnedValue>> {
+        self.inner.get_value_option(max_version, node_key)
+    }
+
+    fn update_batch(&self, tree_update_batch: TreeUpdateBatch) -> Result<()> {
+        self.inner.write_tree_update_batch(tree_update_batch)
+    }
+
+    fn nodes(&self) -> Self::NodeIter {
+        self.inner.nodes()
+    }
+
+    fn value_history(&self) -> Self::HistoryIter {
+        self.inner.value_history()
+    }
+}
+
+impl TreeReader for DbAdapter {
+    type Version = Vers;
+
+    fn get_node_option(&self, node_key: &NodeKey) -> Result<Option<Node>> {
+        self.inner.get_node_option(node_key)
+    }
+
+    fn get_value_option(&self, max_version: Vers, key_hash: KeyHash) -> Result<Option<OwnedValue>> {
+        self.inner.get_value_option(max_version, key_hash)
+    }
+
+    fn get_rightmost_leaf(&self) -> Result<Option<(NodeKey, LeafNode)>> {
+        self.inner.get_rightmost_leaf()
+    }
+}
+
+impl TreeWriter for DbAdapter {
+    fn write_node_batch(&self, node_batch: &NodeBatch) -> Result<()> {
+        self.inner.write_node_batch(node_batch)
+    }
+}
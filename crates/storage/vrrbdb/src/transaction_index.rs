@@ -0,0 +1,213 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::PathBuf,
+};
+
+use primitives::Address;
+use serde::{Deserialize, Serialize};
+use storage_utils::{Result, StorageError};
+use vrrb_core::transactions::TransactionDigest;
+
+use crate::{
+    column_store::{decode, encode, ColumnStore},
+    AccessMode, Database,
+};
+
+/// Column family mapping addresses to the transactions they sent or received,
+/// keyed by address, block height and digest so an address' transactions are
+/// stored next to each other in the order they were included.
+pub const ADDRESS_TRANSACTIONS_COLUMN_FAMILY: &str = "address_transactions";
+
+/// Column family mapping transaction digests to the block that included them.
+pub const TRANSACTION_BLOCKS_COLUMN_FAMILY: &str = "transaction_blocks";
+
+/// Column family mapping addresses to the number of transactions they sent
+/// or received, so they can be counted without going through all of them.
+pub const ADDRESS_TRANSACTION_COUNTS_COLUMN_FAMILY: &str = "address_transaction_counts";
+
+pub(crate) const COLUMN_FAMILIES: [&str; 3] = [
+    ADDRESS_TRANSACTIONS_COLUMN_FAMILY,
+    TRANSACTION_BLOCKS_COLUMN_FAMILY,
+    ADDRESS_TRANSACTION_COUNTS_COLUMN_FAMILY,
+];
+
+/// Block a transaction was included in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionLocation {
+    pub block_hash: String,
+    pub block_height: u128,
+}
+
+/// Transaction sent or received by an address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressTransaction {
+    pub digest: TransactionDigest,
+    pub block_height: u128,
+}
+
+/// Transaction included in a block along with the addresses it involves.
+#[derive(Debug, Clone)]
+pub struct IndexEntry {
+    pub digest: TransactionDigest,
+    pub addresses: Vec<Address>,
+}
+
+/// Secondary indexes over the transactions included in applied blocks. They
+/// live outside the tries since they are not part of the state.
+#[derive(Debug, Clone)]
+pub struct TransactionIndex {
+    store: ColumnStore,
+}
+
+impl Default for TransactionIndex {
+    fn default() -> Self {
+        Self::in_memory()
+    }
+}
+
+impl TransactionIndex {
+    /// Opens the index stored at `path`, creating it if missing unless opened
+    /// read-only.
+    pub fn open(path: PathBuf, access: AccessMode) -> Result<Self> {
+        Ok(Self {
+            store: ColumnStore::open(path, access, &COLUMN_FAMILIES)?,
+        })
+    }
+
+    /// Keeps the index in column families of a database shared with other
+    /// stores, which must have been opened with them.
+    pub fn with_database(database: Database) -> Self {
+        Self {
+            store: ColumnStore::RocksDb(database),
+        }
+    }
+
+    pub fn in_memory() -> Self {
+        Self {
+            store: ColumnStore::in_memory(&COLUMN_FAMILIES),
+        }
+    }
+
+    /// Indexes the transactions included in a block. Indexing the same block
+    /// again leaves the index unchanged.
+    pub fn index_block(
+        &self,
+        block_hash: &str,
+        block_height: u128,
+        entries: &[IndexEntry],
+    ) -> Result<()> {
+        let location = encode(&TransactionLocation {
+            block_hash: block_hash.to_string(),
+            block_height,
+        })?;
+
+        let mut writes = Vec::new();
+        let mut indexed = HashSet::new();
+        let mut new_transactions: BTreeMap<Vec<u8>, u64> = BTreeMap::new();
+
+        for entry in entries {
+            let digest = encode(&entry.digest)?;
+
+            writes.push((
+                TRANSACTION_BLOCKS_COLUMN_FAMILY,
+                digest.clone(),
+                location.clone(),
+            ));
+
+            let mut addresses = entry.addresses.clone();
+            addresses.sort();
+            addresses.dedup();
+
+            for address in addresses {
+                let mut key = address.raw_address().to_vec();
+                key.extend(block_height.to_be_bytes());
+                key.extend(&digest);
+
+                // NOTE: only transactions not indexed yet are counted
+                if !indexed.insert(key.clone())
+                    || self
+                        .store
+                        .get(ADDRESS_TRANSACTIONS_COLUMN_FAMILY, &key)?
+                        .is_some()
+                {
+                    continue;
+                }
+
+                *new_transactions
+                    .entry(address.raw_address().to_vec())
+                    .or_default() += 1;
+
+                writes.push((ADDRESS_TRANSACTIONS_COLUMN_FAMILY, key, digest.clone()));
+            }
+        }
+
+        for (address, count) in new_transactions {
+            let count = self.stored_transaction_count(&address)? + count;
+
+            writes.push((
+                ADDRESS_TRANSACTION_COUNTS_COLUMN_FAMILY,
+                address,
+                count.to_be_bytes().to_vec(),
+            ));
+        }
+
+        self.store.write(writes)
+    }
+
+    /// Returns the block that included a transaction, if it was indexed.
+    pub fn transaction_location(
+        &self,
+        digest: &TransactionDigest,
+    ) -> Result<Option<TransactionLocation>> {
+        self.store
+            .get(TRANSACTION_BLOCKS_COLUMN_FAMILY, &encode(digest)?)?
+            .map(|location| decode(&location))
+            .transpose()
+    }
+
+    /// Returns up to `limit` transactions sent or received by `address`,
+    /// ordered by the height of the block that included them, skipping the
+    /// first `offset`.
+    pub fn transactions_by_address(
+        &self,
+        address: &Address,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<AddressTransaction>> {
+        let prefix = address.raw_address();
+
+        self.store
+            .scan_prefix_page(ADDRESS_TRANSACTIONS_COLUMN_FAMILY, &prefix, offset, limit)?
+            .into_iter()
+            .map(|(key, digest)| {
+                let mut block_height = [0u8; 16];
+                block_height.copy_from_slice(&key[prefix.len()..prefix.len() + 16]);
+
+                Ok(AddressTransaction {
+                    digest: decode(&digest)?,
+                    block_height: u128::from_be_bytes(block_height),
+                })
+            })
+            .collect()
+    }
+
+    /// Number of indexed transactions sent or received by `address`.
+    pub fn transaction_count(&self, address: &Address) -> Result<usize> {
+        Ok(self.stored_transaction_count(&address.raw_address())? as usize)
+    }
+
+    fn stored_transaction_count(&self, address: &[u8]) -> Result<u64> {
+        let Some(count) = self
+            .store
+            .get(ADDRESS_TRANSACTION_COUNTS_COLUMN_FAMILY, address)?
+        else {
+            return Ok(0);
+        };
+
+        let count = count.try_into().map_err(|_| {
+            StorageError::Other("transaction count is not 8 bytes long".to_string())
+        })?;
+
+        Ok(u64::from_be_bytes(count))
+    }
+}
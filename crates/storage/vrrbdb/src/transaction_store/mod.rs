@@ -1,11 +1,15 @@
 use std::{path::Path, sync::Arc};
 
-use integral_db::{LeftRightTrie, Proof, H256};
+use integral_db::LeftRightTrie;
 use patriecia::RootHash;
 use sha2::Sha256;
+use storage_proofs::InclusionProof;
 use storage_utils::{Result, StorageError};
 
-use crate::RocksDbAdapter;
+use crate::{
+    staging::{stage_version, StagedVersion},
+    AccessMode, DbAdapter, RocksDbAdapter,
+};
 
 mod transaction_store_rh;
 pub use transaction_store_rh::*;
@@ -13,7 +17,8 @@ use vrrb_core::transactions::{Transaction, TransactionDigest, TransactionKind};
 
 #[derive(Debug, Clone)]
 pub struct TransactionStore {
-    trie: LeftRightTrie<'static, TransactionDigest, TransactionKind, RocksDbAdapter, Sha256>,
+    trie: LeftRightTrie<'static, TransactionDigest, TransactionKind, DbAdapter, Sha256>,
+    db_adapter: DbAdapter,
 }
 
 impl Default for TransactionStore {
@@ -25,9 +30,7 @@ impl Default for TransactionStore {
 
         let db_adapter = RocksDbAdapter::new(db_path, "transactions").unwrap_or_default();
 
-        let trie = LeftRightTrie::new(Arc::new(db_adapter));
-
-        Self { trie }
+        Self::with_backend(DbAdapter::new(db_adapter))
     }
 }
 
@@ -36,9 +39,33 @@ impl TransactionStore {
     pub fn new(path: &Path) -> Self {
         let path = path.join("transactions");
         let db_adapter = RocksDbAdapter::new(path, "transactions").unwrap_or_default();
-        let trie = LeftRightTrie::new(Arc::new(db_adapter));
 
-        Self { trie }
+        Self::with_backend(DbAdapter::new(db_adapter))
+    }
+
+    /// Returns a store kept entirely in memory
+    pub fn in_memory() -> Self {
+        Self::with_backend(DbAdapter::in_memory())
+    }
+
+    pub fn with_backend(db_adapter: DbAdapter) -> Self {
+        let trie = LeftRightTrie::new(Arc::new(db_adapter.clone()));
+
+        Self { trie, db_adapter }
+    }
+
+    /// Opens the store at `path`, failing instead of falling back to the
+    /// default location when the database cannot be opened.
+    pub fn open(path: &Path, access: AccessMode) -> Result<Self> {
+        let path = path.join("transactions");
+        let db_adapter = RocksDbAdapter::open(path, "transactions", access)?;
+
+        Ok(Self::with_backend(DbAdapter::new(db_adapter)))
+    }
+
+    /// Returns the database backing the trie.
+    pub(crate) fn db_adapter(&self) -> DbAdapter {
+        self.db_adapter.clone()
     }
 
     pub fn factory(&self) -> TransactionStoreReadHandleFactory {
@@ -57,17 +84,38 @@ impl TransactionStore {
     }
 
     pub fn insert(&mut self, txn: TransactionKind) -> Result<()> {
+        self.db_adapter
+            .put_preimages([&txn.id()])
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
         self.trie.insert(txn.id(), txn);
         Ok(())
     }
 
-    pub fn extend(&mut self, transactions: Vec<TransactionKind>) {
-        let transactions = transactions
+    pub fn extend(&mut self, transactions: Vec<TransactionKind>) -> Result<()> {
+        let transactions: Vec<_> = transactions
             .into_iter()
             .map(|txn| (txn.id(), Some(txn)))
             .collect();
 
-        self.trie.extend(transactions)
+        self.db_adapter
+            .put_preimages(transactions.iter().map(|(digest, _)| digest))
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        self.trie.extend(transactions);
+
+        Ok(())
+    }
+
+    /// Computes the version extending the trie with `transactions` would
+    /// publish, without writing or publishing it.
+    pub(crate) fn stage(&self, transactions: &[TransactionKind]) -> Result<StagedVersion> {
+        let transactions: Vec<_> = transactions
+            .iter()
+            .map(|txn| (txn.id(), Some(txn)))
+            .collect();
+
+        stage_version(&self.trie.factory(), &transactions)
     }
 
     pub fn root_hash(&self) -> Result<RootHash> {
@@ -76,11 +124,9 @@ impl TransactionStore {
             .map_err(|e| StorageError::Other(e.to_string()))
     }
 
-    pub fn get_proof(&self) -> Result<Vec<Proof>> {
-        todo!()
-    }
-
-    pub fn verify_proof(&self) -> Option<H256> {
-        todo!()
+    /// Produces a proof that the transaction with the given digest is
+    /// included in the latest committed version of the trie.
+    pub fn get_proof(&self, digest: &TransactionDigest) -> Result<InclusionProof> {
+        self.factory().get_proof(digest)
     }
 }
@@ -1,20 +1,25 @@
 use std::collections::HashMap;
 
 use integral_db::{JellyfishMerkleTreeWrapper, ReadHandleFactory};
-use patriecia::{JellyfishMerkleTree, Version};
+use patriecia::{JellyfishMerkleTree, RootHash, Version};
 use sha2::Sha256;
+use storage_proofs::InclusionProof;
 use storage_utils::{Result, StorageError};
 use vrrb_core::transactions::{Transaction, TransactionDigest, TransactionKind};
 
-use crate::RocksDbAdapter;
+use crate::{
+    inspect::{root_hash_at, root_hashes, verify_trie_integrity},
+    proofs::generate_inclusion_proof,
+    DbAdapter, IntegrityReport, VersionedRootHash,
+};
 
 #[derive(Debug, Clone)]
 pub struct TransactionStoreReadHandle {
-    inner: JellyfishMerkleTreeWrapper<RocksDbAdapter, Sha256>,
+    inner: JellyfishMerkleTreeWrapper<DbAdapter, Sha256>,
 }
 
 impl TransactionStoreReadHandle {
-    pub fn new(inner: JellyfishMerkleTreeWrapper<RocksDbAdapter, Sha256>) -> Self {
+    pub fn new(inner: JellyfishMerkleTreeWrapper<DbAdapter, Sha256>) -> Self {
         Self { inner }
     }
 
@@ -24,6 +29,17 @@ impl TransactionStoreReadHandle {
             .map_err(|err| StorageError::Other(err.to_string()))
     }
 
+    /// Returns the transaction stored under `key` in the latest version of
+    /// the trie
+    pub fn get_latest(&self, key: &TransactionDigest) -> Result<TransactionKind> {
+        self.get(key, self.inner.version())
+    }
+
+    /// Returns the version of the trie this handle reads from
+    pub fn version(&self) -> Version {
+        self.inner.version()
+    }
+
     pub fn batch_get(
         &self,
         keys: Vec<TransactionDigest>,
@@ -40,10 +56,18 @@ impl TransactionStoreReadHandle {
     }
 
     pub fn entries(&self) -> Result<HashMap<TransactionDigest, TransactionKind>> {
+        self.entries_at_version(self.inner.version())
+    }
+
+    /// Returns every transaction stored in the trie as of the given version.
+    pub fn entries_at_version(
+        &self,
+        version: Version,
+    ) -> Result<HashMap<TransactionDigest, TransactionKind>> {
         // TODO: revisit and refactor into inner wrapper
         Ok(self
             .inner
-            .iter(self.inner.version())
+            .iter(version)
             .map_err(|err| {
                 StorageError::Other(format!("unable to create iterator from trie: {}", err))
             })?
@@ -71,11 +95,11 @@ impl TransactionStoreReadHandle {
 
 #[derive(Debug, Clone)]
 pub struct TransactionStoreReadHandleFactory {
-    inner: ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>,
+    inner: ReadHandleFactory<JellyfishMerkleTree<DbAdapter, Sha256>>,
 }
 
 impl TransactionStoreReadHandleFactory {
-    pub fn new(inner: ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>) -> Self {
+    pub fn new(inner: ReadHandleFactory<JellyfishMerkleTree<DbAdapter, Sha256>>) -> Self {
         Self { inner }
     }
 
@@ -91,4 +115,26 @@ impl TransactionStoreReadHandleFactory {
 
         TransactionStoreReadHandle { inner }
     }
+
+    /// Produces a proof that the transaction with the given digest is
+    /// included in the latest version of the transaction trie.
+    pub fn get_proof(&self, key: &TransactionDigest) -> Result<InclusionProof> {
+        generate_inclusion_proof(&self.inner, key)
+    }
+
+    /// Returns the root hash of the trie at one of its versions still stored
+    pub fn root_hash_at_version(&self, version: Version) -> Result<RootHash> {
+        root_hash_at(&self.inner, version)
+    }
+
+    /// Returns the root hash of every version of the trie still stored
+    pub fn root_hashes(&self) -> Result<Vec<VersionedRootHash>> {
+        root_hashes(&self.inner)
+    }
+
+    /// Checks every leaf of the latest version of the trie against its root
+    /// hash
+    pub fn verify_integrity(&self) -> Result<IntegrityReport> {
+        verify_trie_integrity(&self.inner)
+    }
 }
@@ -2,7 +2,7 @@ use std::{collections::HashSet, str::FromStr};
 
 use block::{ConvergenceBlock, ProposalBlock};
 use primitives::Address;
-use vrrb_core::account::{AccountDigests, UpdateArgs};
+use vrrb_core::account::UpdateArgs;
 use vrrb_core::transactions::{Token, Transaction, TransactionDigest, TransactionKind};
 
 /// Provides a wrapper around the current rounds `ConvergenceBlock` and
@@ -16,13 +16,15 @@ pub struct RoundBlocks {
 
 /// Provides variants to parse to ensure state module handles updates
 /// properly, whether it be an Account receiving tokens, and
-/// account sending tokens, a new claim, claim staking (TODO),
+/// account sending tokens, a new claim, claim staking, stake withdrawals,
 /// fees or rewards (TODO).
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub enum UpdateAccount {
     Sender,
     Receiver,
     Claim,
+    Stake,
+    Unstake,
     Fee,
     Reward,
 }
@@ -36,6 +38,9 @@ pub struct StateUpdate {
     pub address: Address,
     pub token: Option<Token>,
     pub amount: u128,
+    /// The fee the sender of a transaction pays on top of `amount`, zero for
+    /// every other kind of update
+    pub fee: u128,
     pub nonce: Option<u128>,
     pub storage: Option<String>,
     pub package_address: Option<String>,
@@ -49,6 +54,7 @@ impl From<(Address, u128)> for StateUpdate {
             address: value.0.to_owned(),
             token: Some(Token::default()),
             amount: value.1,
+            fee: 0,
             nonce: None,
             storage: None,
             package_address: None,
@@ -58,14 +64,32 @@ impl From<(Address, u128)> for StateUpdate {
     }
 }
 
+impl StateUpdate {
+    /// Credits `address` with `amount` of the fees paid by transactions it
+    /// validated or proposed.
+    pub fn fee(address: Address, amount: u128) -> Self {
+        Self {
+            address,
+            token: Some(Token::default()),
+            amount,
+            fee: 0,
+            nonce: None,
+            storage: None,
+            package_address: None,
+            digest: TransactionDigest::default(),
+            update_account: UpdateAccount::Fee,
+        }
+    }
+}
+
 /// A wrapper to provide convenient conversion from
 /// a Transaction to two StateUpdates, one for the
 /// sender, one for the receiver. Can also provide some
 /// verification around this struct.
-// TODO: receiver update here can be used to provide
-// ClaimStaking functionality, in which the `update_account`
-// field for the `receiver_update` herein, can be used to
-// produce a `Claim` update instead of only `Account` updates
+///
+/// For staking, withdrawal and delegation transactions the
+/// `receiver_update` is a `Claim` update which records the
+/// stake digest on the account owning the receiving claim.
 #[derive(Debug)]
 pub struct IntoUpdates {
     pub sender_update: StateUpdate,
@@ -85,47 +109,60 @@ pub trait FromTxn {
 }
 
 /// Converts a `StateUpdate` into `UpdateArgs`
+///
+/// NOTE: the digests of the transactions an account took part in are not
+/// recorded on the account, its history is served by the `TransactionIndex`
 impl From<StateUpdate> for UpdateArgs {
     fn from(item: StateUpdate) -> UpdateArgs {
-        let mut digest = AccountDigests::default();
         match &item.update_account {
-            UpdateAccount::Sender => {
-                digest.insert_sent(item.digest);
-                UpdateArgs {
-                    address: item.address,
-                    nonce: item.nonce,
-                    credits: None,
-                    debits: Some(item.amount),
-                    storage: Some(item.storage.clone()),
-                    package_address: Some(item.package_address.clone()),
-                    digests: Some(digest.clone()),
-                }
-            }
-            UpdateAccount::Receiver => {
-                digest.insert_recv(item.digest);
-                UpdateArgs {
-                    address: item.address,
-                    nonce: item.nonce,
-                    credits: Some(item.amount),
-                    debits: None,
-                    storage: Some(item.storage.clone()),
-                    package_address: Some(item.package_address.clone()),
-                    digests: Some(digest.clone()),
-                }
-            }
-            UpdateAccount::Claim => {
-                // RFC: Should we separate "claim" txn from "stake" txn
-                digest.insert_stake(item.digest);
-                UpdateArgs {
-                    address: item.address,
-                    nonce: item.nonce,
-                    credits: None,
-                    debits: None,
-                    storage: None,
-                    package_address: None,
-                    digests: Some(digest.clone()),
-                }
-            }
+            UpdateAccount::Sender => UpdateArgs {
+                address: item.address,
+                nonce: item.nonce,
+                credits: None,
+                debits: Some(item.amount.saturating_add(item.fee)),
+                storage: Some(item.storage.clone()),
+                package_address: Some(item.package_address.clone()),
+                digests: None,
+            },
+            UpdateAccount::Receiver => UpdateArgs {
+                address: item.address,
+                nonce: item.nonce,
+                credits: Some(item.amount),
+                debits: None,
+                storage: Some(item.storage.clone()),
+                package_address: Some(item.package_address.clone()),
+                digests: None,
+            },
+            // RFC: Should we separate "claim" txn from "stake" txn
+            UpdateAccount::Claim => UpdateArgs {
+                address: item.address,
+                nonce: item.nonce,
+                credits: None,
+                debits: None,
+                storage: None,
+                package_address: None,
+                digests: None,
+            },
+            UpdateAccount::Stake => UpdateArgs {
+                address: item.address,
+                nonce: item.nonce,
+                credits: None,
+                debits: Some(item.amount.saturating_add(item.fee)),
+                storage: None,
+                package_address: None,
+                digests: None,
+            },
+            // NOTE: the withdrawn stake is credited before the fee is
+            // debited, so it can be paid out of the withdrawal
+            UpdateAccount::Unstake => UpdateArgs {
+                address: item.address,
+                nonce: item.nonce,
+                credits: Some(item.amount),
+                debits: Some(item.fee),
+                storage: None,
+                package_address: None,
+                digests: None,
+            },
             UpdateAccount::Fee => UpdateArgs {
                 address: item.address,
                 nonce: item.nonce,
@@ -168,18 +205,7 @@ impl FromBlock for HashSet<StateUpdate> {
             set.extend(validator_fees);
         });
 
-        let fee_update = StateUpdate {
-            address: block.from.address,
-            token: Some(Token::default()),
-            nonce: None,
-            amount: proposer_fees,
-            storage: None,
-            package_address: None,
-            digest: TransactionDigest::default(),
-            update_account: UpdateAccount::Fee,
-        };
-
-        set.insert(fee_update);
+        set.insert(StateUpdate::fee(block.from.address, proposer_fees));
 
         set
     }
@@ -190,26 +216,36 @@ impl FromBlock for HashSet<StateUpdate> {
 /// one for the sender and one for the receiver
 impl FromTxn for IntoUpdates {
     fn from_txn(txn: TransactionKind) -> IntoUpdates {
+        let (sender_account, receiver_account) = match txn {
+            TransactionKind::Transfer(_) => (UpdateAccount::Sender, UpdateAccount::Receiver),
+            TransactionKind::Stake(_) | TransactionKind::Delegate(_) => {
+                (UpdateAccount::Stake, UpdateAccount::Claim)
+            }
+            TransactionKind::Withdrawal(_) => (UpdateAccount::Unstake, UpdateAccount::Claim),
+        };
+
         let sender_update = StateUpdate {
             address: txn.sender_address(),
             token: Some(txn.token()),
             amount: txn.amount(),
+            fee: txn.fee(),
             nonce: Some(txn.nonce()),
             storage: None,
             package_address: None,
             digest: txn.id(),
-            update_account: UpdateAccount::Sender,
+            update_account: sender_account,
         };
 
         let receiver_update = StateUpdate {
             address: txn.receiver_address(),
             token: Some(txn.token()),
             amount: txn.amount(),
+            fee: 0,
             nonce: None,
             storage: None,
             package_address: None,
             digest: txn.id(),
-            update_account: UpdateAccount::Receiver,
+            update_account: receiver_account,
         };
 
         IntoUpdates {
@@ -227,6 +263,10 @@ impl FromTxn for HashSet<StateUpdate> {
         let fees = txn.validator_fee_share();
         if let Some(mut validator_set) = txn.validators() {
             validator_set.retain(|_, vote| *vote);
+            if validator_set.is_empty() {
+                return set;
+            }
+
             let validator_share = fees / (validator_set.len() as u128);
             validator_set.iter().for_each(|(k, _v)| {
                 let address = Address::from_str(k);
@@ -235,6 +275,7 @@ impl FromTxn for HashSet<StateUpdate> {
                         address: addr,
                         token: None,
                         amount: validator_share,
+                        fee: 0,
                         nonce: None,
                         storage: None,
                         package_address: None,
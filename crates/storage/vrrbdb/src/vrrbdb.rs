@@ -1,23 +1,57 @@
-use std::path::PathBuf;
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+};
 
-use block::{Block, ConvergenceBlock, GenesisBlock, GenesisRewards, ProposalBlock};
+use block::{Block, BlockHash, ConvergenceBlock, GenesisBlock, GenesisRewards, ProposalBlock};
 use ethereum_types::U256;
-use patriecia::RootHash;
+use patriecia::{RootHash, Version};
 use primitives::Address;
 
 use storage_utils::{Result, StorageError};
-use vrrb_core::transactions::{Transaction, TransactionKind, Transfer};
+use vrrb_core::transactions::{Transaction, TransactionKind};
 use vrrb_core::{
     account::{Account, UpdateArgs},
     claim::Claim,
 };
 
 use crate::{
-    ClaimStore, ClaimStoreReadHandleFactory, FromTxn, IntoUpdates, StateStore,
-    StateStoreReadHandleFactory, StateUpdate, TransactionStore, TransactionStoreReadHandleFactory,
-    VrrbDbReadHandle,
+    snapshot::StateSnapshot, staging::StagedVersion, store_column_families, transaction_index,
+    AccessMode, BlockStore, ClaimStore, ClaimStoreReadHandleFactory, ClaimUpdate, Database,
+    DbAdapter, FromTxn, IndexEntry, IntoUpdates, PrunerMetrics, PruningMode, RocksDbAdapter,
+    StateHistory, StatePruner, StateStore, StateStoreReadHandleFactory, StateUpdate, StateVersion,
+    StorageBackendType, TransactionIndex, TransactionStore, TransactionStoreReadHandleFactory,
+    VrrbDbReadHandle, DEFAULT_STATE_HISTORY_WINDOW, STATE_HISTORY_COLUMN_FAMILY,
 };
 
+/// Directory under `VrrbDbConfig::path` holding the database shared by the
+/// tries and the transaction index, so a block is written to all of them at
+/// once
+pub const LEDGER_DIR: &str = "ledger";
+
+/// Directory under `VrrbDbConfig::path` holding the blocks of the DAG
+const BLOCK_STORE_DIR: &str = "blocks";
+
+/// Names of the tries kept in the ledger database
+const STATE_STORE: &str = "state";
+const TRANSACTION_STORE: &str = "transactions";
+const CLAIM_STORE: &str = "claims";
+
+/// Column families of every store kept in the ledger database
+fn ledger_column_families() -> Vec<String> {
+    [STATE_STORE, TRANSACTION_STORE, CLAIM_STORE]
+        .into_iter()
+        .flat_map(store_column_families)
+        .chain(
+            transaction_index::COLUMN_FAMILIES
+                .iter()
+                .chain([&STATE_HISTORY_COLUMN_FAMILY])
+                .map(|name| name.to_string()),
+        )
+        .collect()
+}
+
 #[derive(Debug, Clone)]
 pub struct VrrbDbConfig {
     pub path: PathBuf,
@@ -25,6 +59,13 @@ pub struct VrrbDbConfig {
     pub transaction_store_path: Option<String>,
     pub event_store_path: Option<String>,
     pub claim_store_path: Option<String>,
+    /// Number of applied blocks whose state can still be queried
+    pub history_window: usize,
+    /// Whether trie nodes replaced by newer versions are deleted
+    pub pruning: PruningMode,
+    /// Where the stores keep their tries. `path` is ignored for in-memory
+    /// databases
+    pub backend: StorageBackendType,
 }
 
 impl VrrbDbConfig {
@@ -33,6 +74,22 @@ impl VrrbDbConfig {
 
         self.clone()
     }
+
+    /// Rejects pruning windows too short to keep the state of every block in
+    /// the history window. Every applied block produces at least one version
+    /// of the tries.
+    pub fn validate(&self) -> Result<()> {
+        if let PruningMode::Prune { window, .. } = self.pruning {
+            if window < self.history_window as Version {
+                return Err(StorageError::Other(format!(
+                    "pruning window of {window} versions cannot cover a history window of {} blocks",
+                    self.history_window
+                )));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -59,6 +116,82 @@ impl ApplyBlockResult {
     }
 }
 
+/// Account, claim and transaction changes produced while applying a block,
+/// kept in memory until the whole block has been validated.
+#[derive(Debug, Default)]
+struct BlockBatch {
+    accounts: HashMap<Address, Account>,
+    claims: HashMap<U256, Claim>,
+    abandoned_claims: HashSet<U256>,
+    transactions: Vec<TransactionKind>,
+}
+
+/// Changes a `BlockBatch` makes to each trie, along with the versions of the
+/// tries they were staged into.
+struct StagedLedger {
+    accounts: Vec<(Address, Option<Account>)>,
+    claims: Vec<(U256, Option<Claim>)>,
+    transactions: Vec<TransactionKind>,
+    staged_state: Option<StagedVersion>,
+    staged_claims: Option<StagedVersion>,
+    staged_transactions: Option<StagedVersion>,
+}
+
+impl StagedLedger {
+    /// Root hash of the state trie once the ledger is written
+    fn state_root_hash(&self, db: &VrrbDb) -> Result<RootHash> {
+        match &self.staged_state {
+            Some(staged) => Ok(staged.root_hash),
+            None => db.state_root_hash(),
+        }
+    }
+
+    /// Root hash of the transaction trie once the ledger is written
+    fn transactions_root_hash(&self, db: &VrrbDb) -> Result<RootHash> {
+        match &self.staged_transactions {
+            Some(staged) => Ok(staged.root_hash),
+            None => db.transactions_root_hash(),
+        }
+    }
+
+    /// Root hash of the claim trie once the ledger is written
+    fn claims_root_hash(&self, db: &VrrbDb) -> Result<RootHash> {
+        match &self.staged_claims {
+            Some(staged) => Ok(staged.root_hash),
+            None => db.claims_root_hash(),
+        }
+    }
+
+    fn state_version(&self, db: &VrrbDb) -> Version {
+        match &self.staged_state {
+            Some(staged) => staged.version,
+            None => db.state_store_factory().handle().version(),
+        }
+    }
+
+    fn transactions_version(&self, db: &VrrbDb) -> Version {
+        match &self.staged_transactions {
+            Some(staged) => staged.version,
+            None => db.transaction_store_factory().handle().version(),
+        }
+    }
+
+    fn claims_version(&self, db: &VrrbDb) -> Version {
+        match &self.staged_claims {
+            Some(staged) => staged.version,
+            None => db.claim_store_factory().handle().version(),
+        }
+    }
+}
+
+/// Block a ledger write is made for, indexed and recorded in the state
+/// history along with the changes it made.
+struct BlockRecord<'a> {
+    hash: &'a str,
+    height: u128,
+    index_entries: Vec<IndexEntry>,
+}
+
 impl Default for VrrbDbConfig {
     fn default() -> Self {
         let path = storage_utils::get_node_data_dir()
@@ -71,32 +204,310 @@ impl Default for VrrbDbConfig {
             transaction_store_path: None,
             event_store_path: None,
             claim_store_path: None,
+            history_window: DEFAULT_STATE_HISTORY_WINDOW,
+            pruning: PruningMode::default(),
+            backend: StorageBackendType::default(),
         }
     }
 }
 
 #[derive(Debug, Default)]
 pub struct VrrbDb {
+    /// Database shared by the tries and the transaction index, if they are
+    /// persisted
+    database: Option<Database>,
     state_store: StateStore,
     transaction_store: TransactionStore,
     claim_store: ClaimStore,
+    transaction_index: TransactionIndex,
+    block_store: BlockStore,
+    history: StateHistory,
+    pruner: Arc<StatePruner>,
 }
 
 impl VrrbDb {
-    pub fn new(config: VrrbDbConfig) -> Self {
-        let state_store = StateStore::new(&config.path);
-        let transaction_store = TransactionStore::new(&config.path);
-        let claim_store = ClaimStore::new(&config.path);
+    /// Opens the database `config` describes with the backend it selects.
+    pub fn new(config: VrrbDbConfig) -> Result<Self> {
+        config.validate()?;
+
+        match config.backend {
+            StorageBackendType::RocksDb => Self::open(config, AccessMode::ReadWrite),
+            StorageBackendType::InMemory => Ok(Self::in_memory(&config)),
+        }
+    }
+
+    fn in_memory(config: &VrrbDbConfig) -> Self {
+        Self::from_stores(
+            None,
+            StateStore::in_memory(),
+            TransactionStore::in_memory(),
+            ClaimStore::in_memory(),
+            TransactionIndex::in_memory(),
+            BlockStore::in_memory(),
+            StateHistory::new(config.history_window),
+            config.pruning,
+        )
+    }
+
+    /// Opens the database at `config.path`, failing if any of its stores
+    /// cannot be opened. Databases opened read-only are never pruned.
+    pub fn open(config: VrrbDbConfig, access: AccessMode) -> Result<Self> {
+        config.validate()?;
+
+        if config.backend == StorageBackendType::InMemory {
+            return Err(StorageError::Other(
+                "in-memory databases cannot be opened from a path".to_string(),
+            ));
+        }
+
+        let database = Database::open(
+            config.path.join(LEDGER_DIR),
+            access,
+            &ledger_column_families(),
+        )?;
+        let ledger_store = |column_family: &str| {
+            DbAdapter::new(RocksDbAdapter::with_database(
+                database.clone(),
+                column_family,
+            ))
+        };
+
+        let state_store = StateStore::with_backend(ledger_store(STATE_STORE));
+        let transaction_store = TransactionStore::with_backend(ledger_store(TRANSACTION_STORE));
+        let claim_store = ClaimStore::with_backend(ledger_store(CLAIM_STORE));
+        let transaction_index = TransactionIndex::with_database(database.clone());
+        let history = StateHistory::with_database(database.clone(), config.history_window)?;
+        let block_store_path = config.path.join(BLOCK_STORE_DIR);
+        // Databases written before blocks were persisted have nothing to read
+        // them from
+        let block_store = if access == AccessMode::ReadOnly && !block_store_path.exists() {
+            BlockStore::in_memory()
+        } else {
+            BlockStore::open(block_store_path, access)?
+        };
+
+        let pruning = match access {
+            AccessMode::ReadWrite => config.pruning,
+            AccessMode::ReadOnly => PruningMode::Archive,
+        };
+
+        Ok(Self::from_stores(
+            Some(database),
+            state_store,
+            transaction_store,
+            claim_store,
+            transaction_index,
+            block_store,
+            history,
+            pruning,
+        ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn from_stores(
+        database: Option<Database>,
+        state_store: StateStore,
+        transaction_store: TransactionStore,
+        claim_store: ClaimStore,
+        transaction_index: TransactionIndex,
+        block_store: BlockStore,
+        history: StateHistory,
+        pruning: PruningMode,
+    ) -> Self {
+        let pruner = StatePruner::new(
+            pruning,
+            vec![
+                state_store.db_adapter(),
+                transaction_store.db_adapter(),
+                claim_store.db_adapter(),
+            ],
+        );
 
         Self {
+            database,
             state_store,
             transaction_store,
             claim_store,
+            transaction_index,
+            block_store,
+            history,
+            pruner: Arc::new(pruner),
         }
     }
 
-    pub fn export_state(&self) {
-        todo!("implement once integral-db is ready to be consumed");
+    /// Takes a snapshot of every store as it was right before the certified
+    /// `ConvergenceBlock` stored at `block_height` was applied, along with the
+    /// proposals that block references. The versions of the tries the block
+    /// was built on are looked up in the state history, so the block may have
+    /// been applied since as long as that state is still retained.
+    pub fn export_state(&self, block_height: u128) -> Result<StateSnapshot> {
+        let block = match self.block_store.get_by_height(block_height)? {
+            Some(Block::Convergence { block }) => block,
+            _ => {
+                return Err(StorageError::Other(format!(
+                    "no convergence block stored at height {block_height}"
+                )))
+            }
+        };
+
+        let state_factory = self.state_store_factory();
+        let transaction_factory = self.transaction_store_factory();
+        let claim_factory = self.claim_store_factory();
+
+        // NOTE: a block is built on the state produced by the last block
+        // applied before it, or on the current one if none was recorded
+        let (state_version, transactions_version, claims_version, roots) =
+            match self.history.before_height(block_height) {
+                Some(version) => (
+                    version.state_version,
+                    version.transactions_version,
+                    version.claims_version,
+                    (
+                        state_factory.root_hash_at_version(version.state_version),
+                        transaction_factory.root_hash_at_version(version.transactions_version),
+                        claim_factory.root_hash_at_version(version.claims_version),
+                    ),
+                ),
+                None => (
+                    state_factory.handle().version(),
+                    transaction_factory.handle().version(),
+                    claim_factory.handle().version(),
+                    (
+                        self.state_root_hash(),
+                        self.transactions_root_hash(),
+                        self.claims_root_hash(),
+                    ),
+                ),
+            };
+
+        let (state_root_hash, transactions_root_hash, claims_root_hash) = roots;
+
+        let state_root_hash = hex::encode(state_root_hash?.0);
+        if block.header.state_root_hash != state_root_hash {
+            return Err(StorageError::Other(format!(
+                "state {} block {} was built on is no longer retained",
+                block.header.state_root_hash, block.hash
+            )));
+        }
+
+        let proposals = block
+            .header
+            .ref_hashes
+            .iter()
+            .filter_map(|hash| match self.block_store.get(hash) {
+                Ok(Some(Block::Proposal { block })) => Some(Ok(block)),
+                Ok(Some(_)) => None,
+                Ok(None) => Some(Err(StorageError::Other(format!(
+                    "proposal {hash} referenced by block {} is not stored",
+                    block.hash
+                )))),
+                Err(err) => Some(Err(err)),
+            })
+            .collect::<Result<Vec<ProposalBlock>>>()?;
+
+        let accounts = state_factory
+            .handle()
+            .entries_at_version(state_version)?
+            .into_iter()
+            .collect();
+
+        let transactions = transaction_factory
+            .handle()
+            .entries_at_version(transactions_version)?
+            .into_values()
+            .collect();
+
+        let claims = claim_factory
+            .handle()
+            .entries_at_version(claims_version)?
+            .into_values()
+            .collect();
+
+        let snapshot = StateSnapshot {
+            block_height,
+            block,
+            proposals,
+            transactions_root_hash: hex::encode(transactions_root_hash?.0),
+            claims_root_hash: hex::encode(claims_root_hash?.0),
+            accounts,
+            transactions,
+            claims,
+        };
+
+        snapshot.certificate()?;
+
+        Ok(snapshot)
+    }
+
+    /// Loads a snapshot into an empty database. The snapshot's tries are
+    /// staged first and only written once the state root matches the one
+    /// certified by the header of the snapshot's block, and the other roots
+    /// the ones the snapshot was taken with. The snapshot's block itself is
+    /// left for the caller to apply once it was stored along with its
+    /// proposals.
+    pub fn import_state(&mut self, snapshot: &StateSnapshot) -> Result<ApplyBlockResult> {
+        snapshot.certificate()?;
+
+        if !self.state_store.is_empty()?
+            || !self.transaction_store_factory().handle().is_empty()
+            || !self.claim_store.is_empty()?
+        {
+            return Err(StorageError::Other(
+                "snapshots can only be imported into an empty database".to_string(),
+            ));
+        }
+
+        let batch = BlockBatch {
+            accounts: snapshot.accounts.iter().cloned().collect(),
+            claims: snapshot
+                .claims
+                .iter()
+                .map(|claim| (claim.hash, claim.clone()))
+                .collect(),
+            abandoned_claims: HashSet::new(),
+            transactions: snapshot.transactions.clone(),
+        };
+
+        let staged = self.stage_ledger(batch)?;
+
+        let state_root_hash = staged.state_root_hash(self)?;
+        let transactions_root_hash = staged.transactions_root_hash(self)?;
+
+        let roots = [
+            (
+                "state",
+                state_root_hash,
+                &snapshot.block.header.state_root_hash,
+            ),
+            (
+                "transactions",
+                transactions_root_hash,
+                &snapshot.transactions_root_hash,
+            ),
+            (
+                "claims",
+                staged.claims_root_hash(self)?,
+                &snapshot.claims_root_hash,
+            ),
+        ];
+
+        for (store, root_hash, expected) in roots {
+            let root_hash = hex::encode(root_hash.0);
+            if &root_hash != expected {
+                return Err(StorageError::Other(format!(
+                    "imported {store} root hash {root_hash} does not match certified root hash {expected}"
+                )));
+            }
+        }
+
+        // NOTE: no state version is recorded, the imported state is the one
+        // right before the snapshot's block and no block was applied yet
+        self.write_ledger(staged, None)?;
+
+        Ok(ApplyBlockResult {
+            state_root_hash,
+            transactions_root_hash,
+        })
     }
 
     pub fn commit_transactions(&mut self) {
@@ -116,6 +527,9 @@ impl VrrbDb {
             self.state_store.factory(),
             self.transaction_store_factory(),
             self.claim_store_factory(),
+            self.transaction_index.clone(),
+            self.block_store.clone(),
+            self.history.clone(),
         )
     }
 
@@ -124,11 +538,265 @@ impl VrrbDb {
         transaction_store: TransactionStore,
         claim_store: ClaimStore,
     ) -> Self {
-        Self {
+        Self::from_stores(
+            None,
             state_store,
             transaction_store,
             claim_store,
+            TransactionIndex::in_memory(),
+            BlockStore::in_memory(),
+            StateHistory::default(),
+            PruningMode::default(),
+        )
+    }
+
+    /// Persists a block appended to the DAG along with the hashes of the
+    /// blocks it is linked from
+    pub fn insert_block(&self, block: &Block, sources: &[BlockHash]) -> Result<()> {
+        self.block_store.insert(block, sources)
+    }
+
+    /// Returns the blocks appended to the DAG
+    pub fn block_store(&self) -> BlockStore {
+        self.block_store.clone()
+    }
+
+    /// Returns the index of the trie versions produced by the blocks applied
+    /// within the retained history window
+    pub fn history(&self) -> StateHistory {
+        self.history.clone()
+    }
+
+    /// Records the versions produced by a block once it was written, then
+    /// wakes up the pruner.
+    fn record_state_version(&self, version: StateVersion) {
+        self.history.record(version);
+        self.pruner.notify();
+    }
+
+    /// Stages the changes of `batch` into new versions of the tries they
+    /// touch, without writing or publishing any of them.
+    fn stage_ledger(&self, batch: BlockBatch) -> Result<StagedLedger> {
+        let BlockBatch {
+            accounts,
+            mut claims,
+            abandoned_claims,
+            transactions,
+        } = batch;
+
+        claims.retain(|hash, _| !abandoned_claims.contains(hash));
+
+        let accounts: Vec<_> = accounts
+            .into_iter()
+            .map(|(address, account)| (address, Some(account)))
+            .collect();
+        let claims: Vec<_> = claims
+            .into_iter()
+            .map(|(hash, claim)| (hash, Some(claim)))
+            .chain(abandoned_claims.into_iter().map(|hash| (hash, None)))
+            .collect();
+
+        // NOTE: tries the batch leaves untouched keep their current version
+        let staged_state = (!accounts.is_empty())
+            .then(|| self.state_store.stage(&accounts))
+            .transpose()?;
+        let staged_claims = (!claims.is_empty())
+            .then(|| self.claim_store.stage(&claims))
+            .transpose()?;
+        let staged_transactions = (!transactions.is_empty())
+            .then(|| self.transaction_store.stage(&transactions))
+            .transpose()?;
+
+        Ok(StagedLedger {
+            accounts,
+            claims,
+            transactions,
+            staged_state,
+            staged_claims,
+            staged_transactions,
+        })
+    }
+
+    /// Writes the staged versions of the tries to the ledger database along
+    /// with the index and state version of the block they were staged for,
+    /// all at once or not at all. The tries are only published once the
+    /// database was committed, and are then checked to have published the
+    /// versions that were staged.
+    fn write_ledger(&mut self, staged: StagedLedger, block: Option<BlockRecord>) -> Result<()> {
+        let version = match &block {
+            Some(block) => Some(StateVersion {
+                block_height: block.height,
+                block_hash: block.hash.to_string(),
+                state_root_hash: hex::encode(staged.state_root_hash(self)?.0),
+                state_version: staged.state_version(self),
+                transactions_version: staged.transactions_version(self),
+                claims_version: staged.claims_version(self),
+            }),
+            None => None,
+        };
+
+        let StagedLedger {
+            accounts,
+            claims,
+            transactions,
+            staged_state,
+            staged_claims,
+            staged_transactions,
+        } = staged;
+
+        let expected = |staged: &Option<StagedVersion>| {
+            staged
+                .as_ref()
+                .map(|staged| (staged.version, staged.root_hash))
+        };
+        let expected_state = expected(&staged_state);
+        let expected_claims = expected(&staged_claims);
+        let expected_transactions = expected(&staged_transactions);
+
+        match self.database.clone() {
+            Some(database) => {
+                database.begin_write()?;
+
+                let written = [
+                    (staged_state, self.state_store.db_adapter()),
+                    (staged_claims, self.claim_store.db_adapter()),
+                    (staged_transactions, self.transaction_store.db_adapter()),
+                ]
+                .into_iter()
+                .filter_map(|(staged, db_adapter)| staged.map(|staged| (staged, db_adapter)))
+                .try_for_each(|(staged, db_adapter)| staged.write(&db_adapter))
+                .and_then(|_| self.write_block_record(block.as_ref(), version.as_ref()));
+
+                if let Err(err) = written {
+                    database.discard_write();
+                    return Err(err);
+                }
+
+                database.commit_write()?;
+            }
+            // NOTE: nothing is shared between the stores, publishing the
+            // tries writes their versions
+            None => self.write_block_record(block.as_ref(), version.as_ref())?,
+        }
+
+        if !accounts.is_empty() {
+            self.state_store.extend(accounts)?;
+            self.state_store.commit();
+        }
+
+        if !claims.is_empty() {
+            self.claim_store.extend(claims)?;
+            self.claim_store.commit();
+        }
+
+        if !transactions.is_empty() {
+            self.transaction_store.extend(transactions)?;
+            self.transaction_store.commit();
         }
+
+        let published = [
+            (
+                "state",
+                expected_state,
+                self.state_store_factory().handle().version(),
+                self.state_root_hash(),
+            ),
+            (
+                "claims",
+                expected_claims,
+                self.claim_store_factory().handle().version(),
+                self.claims_root_hash(),
+            ),
+            (
+                "transactions",
+                expected_transactions,
+                self.transaction_store_factory().handle().version(),
+                self.transactions_root_hash(),
+            ),
+        ];
+
+        for (store, expected, version, root_hash) in published {
+            let Some((expected_version, expected_root_hash)) = expected else {
+                continue;
+            };
+
+            if version != expected_version || root_hash? != expected_root_hash {
+                return Err(StorageError::Other(format!(
+                    "published {store} trie does not match version {expected_version} staged for it"
+                )));
+            }
+        }
+
+        if let Some(version) = version {
+            self.record_state_version(version);
+        }
+
+        Ok(())
+    }
+
+    /// Indexes the transactions of the block a ledger write is made for and
+    /// persists the state version it produces.
+    fn write_block_record(
+        &self,
+        block: Option<&BlockRecord>,
+        version: Option<&StateVersion>,
+    ) -> Result<()> {
+        let (Some(block), Some(version)) = (block, version) else {
+            return Ok(());
+        };
+
+        self.transaction_index
+            .index_block(block.hash, block.height, &block.index_entries)?;
+        self.history.persist(version)
+    }
+
+    pub fn pruning_mode(&self) -> PruningMode {
+        self.pruner.mode()
+    }
+
+    /// Returns the progress made by the pruner since the database was opened
+    pub fn pruner_metrics(&self) -> Arc<PrunerMetrics> {
+        self.pruner.metrics()
+    }
+
+    /// Lists the column families of each store's database, keyed by store
+    pub fn column_families(&self) -> Result<Vec<(String, Vec<String>)>> {
+        self.stores()
+            .into_iter()
+            .map(|(store, db_adapter)| {
+                db_adapter
+                    .backend()
+                    .column_families()
+                    .map(|column_families| (store.to_string(), column_families))
+                    .map_err(|err| StorageError::Other(err.to_string()))
+            })
+            .collect()
+    }
+
+    /// Compacts the database of every store. Requires the database to be
+    /// opened for writing.
+    pub fn compact(&self) -> Result<()> {
+        for (store, db_adapter) in self.stores() {
+            db_adapter.backend().compact().map_err(|err| {
+                StorageError::Other(format!("failed to compact the {store} store: {err}"))
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn stores(&self) -> [(&'static str, DbAdapter); 3] {
+        [
+            ("state", self.state_store.db_adapter()),
+            ("transactions", self.transaction_store.db_adapter()),
+            ("claims", self.claim_store.db_adapter()),
+        ]
+    }
+
+    /// Prunes stale trie nodes right away instead of waiting for the
+    /// background pruner, returning the number of nodes deleted.
+    pub fn prune(&self) -> Result<u64> {
+        self.pruner.prune_now()
     }
 
     /// Returns the current state store trie's root hash.
@@ -170,8 +838,8 @@ impl VrrbDb {
     }
 
     /// Adds multiplpe accounts to current state tree.
-    pub fn extend_accounts(&mut self, accounts: Vec<(Address, Option<Account>)>) {
-        self.state_store.extend(accounts);
+    pub fn extend_accounts(&mut self, accounts: Vec<(Address, Option<Account>)>) -> Result<()> {
+        self.state_store.extend(accounts)
     }
 
     /// Updates an account on the current state tree.
@@ -189,8 +857,11 @@ impl VrrbDb {
 
     /// Adds multiplpe transactions to current state tree. Does not check if
     /// accounts involved in the transaction actually exist.
-    pub fn extend_transactions_unchecked(&mut self, transactions: Vec<TransactionKind>) {
-        self.transaction_store.extend(transactions);
+    pub fn extend_transactions_unchecked(
+        &mut self,
+        transactions: Vec<TransactionKind>,
+    ) -> Result<()> {
+        self.transaction_store.extend(transactions)
     }
 
     /// Inserts a confirmed transaction to the ledger. Does not check if
@@ -201,8 +872,8 @@ impl VrrbDb {
 
     /// Adds multiplpe transactions to current transaction tree. Does not check
     /// if accounts involved in the transaction actually exist.
-    pub fn extend_transactions(&mut self, transactions: Vec<TransactionKind>) {
-        self.transaction_store.extend(transactions);
+    pub fn extend_transactions(&mut self, transactions: Vec<TransactionKind>) -> Result<()> {
+        self.transaction_store.extend(transactions)
     }
 
     /// Inserts a confirmed claim to the current claim tree.
@@ -211,7 +882,7 @@ impl VrrbDb {
     }
 
     /// Adds multiple claims to the current claim tree.  
-    pub fn extend_claims_unchecked(&mut self, claims: Vec<(U256, Option<Claim>)>) {
+    pub fn extend_claims_unchecked(&mut self, claims: Vec<(U256, Option<Claim>)>) -> Result<()> {
         self.claim_store.extend(claims)
     }
 
@@ -221,131 +892,313 @@ impl VrrbDb {
     }
 
     /// Inserts multiple claims into the current claim trie
-    pub fn extend_claims(&mut self, claims: Vec<(U256, Option<Claim>)>) {
+    pub fn extend_claims(&mut self, claims: Vec<(U256, Option<Claim>)>) -> Result<()> {
         self.claim_store.extend(claims)
     }
 
-    /// Updates a calim in the current claim trie.
-    pub fn update_claim(&mut self, _key: Address, _args: UpdateArgs) {
-        todo!()
+    /// Updates the claim stored under `hash` in the current claim trie,
+    /// returning the updated claim.
+    pub fn update_claim(&mut self, hash: U256, update: ClaimUpdate) -> Result<Claim> {
+        self.claim_store.update(hash, update)
+    }
+
+    /// Removes an abandoned claim from the current claim trie, returning it.
+    pub fn abandon_claim(&mut self, hash: U256) -> Result<Claim> {
+        self.claim_store.abandon(hash)
+    }
+
+    /// Returns the latest version of an account, taking into account the
+    /// changes already staged in `batch`.
+    fn staged_account(&self, batch: &BlockBatch, address: &Address) -> Result<Account> {
+        match batch.accounts.get(address) {
+            Some(account) => Ok(account.clone()),
+            None => self.state_store.get_account(address),
+        }
     }
 
-    fn apply_transfer(&mut self, read_handle: VrrbDbReadHandle, txn: Transfer) -> Result<()> {
-        let txn = TransactionKind::Transfer(txn);
+    /// Applies an update to the staged version of an account, checking it
+    /// the same way `StateStore::update_uncommited` does.
+    fn stage_account_update(&self, batch: &mut BlockBatch, update: UpdateArgs) -> Result<()> {
+        let address = update.address.clone();
+        let mut account = self.staged_account(batch, &address)?;
+
+        StateStore::check_nonce(&account, &update)?;
+
+        account
+            .update(update)
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        batch.accounts.insert(address, account);
+
+        Ok(())
+    }
+
+    /// Stages a new, empty account for `address` unless one already exists.
+    fn stage_account_if_missing(&self, batch: &mut BlockBatch, address: &Address) {
+        if self.staged_account(batch, address).is_err() {
+            batch
+                .accounts
+                .insert(address.clone(), Account::new(address.clone()));
+        }
+    }
 
+    fn stage_transfer(&self, batch: &mut BlockBatch, txn: TransactionKind) -> Result<()> {
         let sender_address = txn.sender_address();
         let receiver_address = txn.receiver_address();
 
-        // TODO: create methods to check if these exist
-        read_handle.get_account_by_address(&sender_address)?;
-        if let Err(StorageError::Other(_err)) =
-            read_handle.get_account_by_address(&receiver_address)
-        {
-            let account = Account::new(receiver_address.clone());
-            self.insert_account(receiver_address.clone(), account)?;
-        };
+        self.staged_account(batch, &sender_address)?;
+        self.stage_account_if_missing(batch, &receiver_address);
 
         let updates = IntoUpdates::from_txn(txn.clone());
 
-        self.state_store
-            .update_uncommited(sender_address.clone(), updates.sender_update.into())?;
+        self.stage_account_update(batch, updates.sender_update.into())?;
+        self.stage_account_update(batch, updates.receiver_update.into())?;
 
-        self.state_store
-            .update_uncommited(receiver_address.clone(), updates.receiver_update.into())?;
+        // TODO: update transaction's state
+        batch.transactions.push(txn);
 
-        self.state_store.commit();
+        Ok(())
+    }
 
-        // TODO: update transaction's state
-        self.transaction_store.insert(txn)?;
+    /// Returns the latest version of the claim with the given hash, taking
+    /// into account the changes already staged in `batch`.
+    fn staged_claim(&self, batch: &BlockBatch, hash: &U256) -> Result<Claim> {
+        if let Some(claim) = batch.claims.get(hash) {
+            return Ok(claim.clone());
+        }
+
+        self.claim_store_factory().handle().get_by_hash(hash)
+    }
+
+    fn stage_stake(&self, batch: &mut BlockBatch, txn: TransactionKind) -> Result<()> {
+        let stake_txn = txn.stake_transaction().cloned().ok_or(StorageError::Other(
+            "transaction does not carry a stake".to_string(),
+        ))?;
+
+        let sender_address = txn.sender_address();
+        let receiver_address = txn.receiver_address();
+
+        self.staged_account(batch, &sender_address)?;
+
+        let mut claim = self.staged_claim(batch, &stake_txn.claim_hash())?;
+        claim
+            .apply_quorum_certified_stake(stake_txn.stake().clone())
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+        batch.claims.insert(claim.hash, claim);
+
+        self.stage_account_if_missing(batch, &receiver_address);
+
+        // NOTE: sender and receiver are the same account unless the stake was
+        // delegated, the second update is applied on top of the first one
+        let updates = IntoUpdates::from_txn(txn.clone());
+
+        self.stage_account_update(batch, updates.sender_update.into())?;
+        self.stage_account_update(batch, updates.receiver_update.into())?;
+
+        batch.transactions.push(txn);
 
         Ok(())
     }
 
-    fn apply_genesis_rewards(
-        &mut self,
-        read_handle: VrrbDbReadHandle,
+    /// Stages a claim introduced by a proposal, unless a claim with the same
+    /// hash is already stored.
+    fn stage_claim(&self, batch: &mut BlockBatch, claim: &Claim) {
+        if self.staged_claim(batch, &claim.hash).is_err() {
+            batch.claims.insert(claim.hash, claim.clone());
+        }
+    }
+
+    /// Stages the removal of a claim abandoned by its owner, once the
+    /// abandonment is checked against the claim's key.
+    fn stage_abandonment(
+        &self,
+        batch: &mut BlockBatch,
+        hash: &U256,
+        signature: &str,
+    ) -> Result<()> {
+        // NOTE: the claim may have been abandoned by an earlier block already
+        let Ok(claim) = self.staged_claim(batch, hash) else {
+            return Ok(());
+        };
+
+        claim
+            .verify_abandonment(signature.to_string())
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        batch.abandoned_claims.insert(*hash);
+
+        Ok(())
+    }
+
+    fn stage_genesis_rewards(
+        &self,
+        batch: &mut BlockBatch,
         genesis_rewards: &GenesisRewards,
     ) -> Result<()> {
         for (receiver_address, reward) in &genesis_rewards.0 {
-            // TODO: create methods to check if these exist
-            if let Err(StorageError::Other(_err)) =
-                read_handle.get_account_by_address(&receiver_address.0)
-            {
-                let account = Account::new(receiver_address.0.clone());
-                self.insert_account(receiver_address.0.clone(), account)?;
-            };
+            self.stage_account_if_missing(batch, &receiver_address.0);
+
             let update = StateUpdate::from((receiver_address.0.clone(), *reward)).into();
-            self.state_store
-                .update_uncommited(receiver_address.0.clone(), update)?;
-            self.state_store.commit();
+            self.stage_account_update(batch, update)?;
         }
 
         Ok(())
     }
 
-    fn apply_txn(
-        &mut self,
-        read_handle: VrrbDbReadHandle,
-        txn_kind: TransactionKind,
-    ) -> Result<()> {
+    /// Stages a fee credit, opening the credited account if it does not exist
+    /// yet.
+    fn stage_fee(&self, batch: &mut BlockBatch, update: StateUpdate) -> Result<()> {
+        self.stage_account_if_missing(batch, &update.address);
+        self.stage_account_update(batch, update.into())
+    }
+
+    /// Credits the validators that voted for a transaction with their share
+    /// of the fee its sender paid.
+    fn stage_validator_fees(&self, batch: &mut BlockBatch, txn: TransactionKind) -> Result<()> {
+        for update in HashSet::<StateUpdate>::from_txn(txn) {
+            self.stage_fee(batch, update)?;
+        }
+
+        Ok(())
+    }
+
+    fn stage_txn(&self, batch: &mut BlockBatch, txn_kind: TransactionKind) -> Result<()> {
         match txn_kind {
-            TransactionKind::Transfer(txn) => self.apply_transfer(read_handle, txn),
+            TransactionKind::Transfer(_) => self.stage_transfer(batch, txn_kind),
+            TransactionKind::Stake(_)
+            | TransactionKind::Withdrawal(_)
+            | TransactionKind::Delegate(_) => self.stage_stake(batch, txn_kind),
         }
     }
 
+    /// Applies the claims and transactions a `ConvergenceBlock` includes from
+    /// its referenced `ProposalBlock`s, credits the fees they paid to the
+    /// validators that voted for them and to the proposers that included
+    /// them, and removes the claims those proposals carry a valid
+    /// abandonment for.
+    ///
+    /// Application is all-or-nothing: every update is staged in memory first
+    /// and only written to the stores once all of them succeeded, so a
+    /// failing transaction leaves the state, transaction and claim tries
+    /// untouched. The new versions of the tries are staged and written along
+    /// with the transaction index and the state history in a single write,
+    /// and only published once that write was committed.
+    ///
+    /// The block must have been built on top of the current state, as
+    /// committed to by its header.
     pub fn apply_convergence_block(
         &mut self,
         convergence: &ConvergenceBlock,
         proposals: &[ProposalBlock],
     ) -> Result<ApplyBlockResult> {
-        let read_handle = self.read_handle();
-        for (proposal, txn_set) in &convergence.txns {
-            let block = proposals
+        let state_root_hash = hex::encode(self.state_root_hash()?.0);
+        if convergence.header.state_root_hash != state_root_hash {
+            return Err(StorageError::Other(format!(
+                "block {} was built on state root hash {}, but the current one is {}",
+                convergence.hash, convergence.header.state_root_hash, state_root_hash
+            )));
+        }
+
+        let mut batch = BlockBatch::default();
+
+        let find_proposal = |hash: &String| {
+            proposals
                 .iter()
-                .find(|pblock| pblock.hash == proposal.clone())
+                .find(|pblock| &pblock.hash == hash)
                 .ok_or(StorageError::Other(format!(
                     "unable to find proposal block with hash {}",
-                    &proposal
-                )))?;
+                    hash
+                )))
+        };
+
+        // NOTE: claims are staged first so transactions staking to a claim
+        // introduced by the same block can find it
+        for (proposal, claim_set) in &convergence.claims {
+            let block = find_proposal(proposal)?;
+
+            for (hash, claim) in &block.claims {
+                if claim_set.contains(hash) {
+                    self.stage_claim(&mut batch, claim);
+                }
+            }
+        }
+
+        for (proposal, txn_set) in &convergence.txns {
+            let block = find_proposal(proposal)?;
 
             let mut txns = block.txns.clone();
             txns.retain(|digest, _| txn_set.contains(digest));
+
+            let mut proposer_fees = 0u128;
             for (_digest, txn_kind) in txns {
-                self.apply_txn(read_handle.clone(), txn_kind)?;
+                proposer_fees = proposer_fees.saturating_add(txn_kind.proposer_fee_share());
+
+                self.stage_txn(&mut batch, txn_kind.clone())?;
+                self.stage_validator_fees(&mut batch, txn_kind)?;
             }
-        }
 
-        self.transaction_store.commit();
-        self.state_store.commit();
+            if proposer_fees > 0 {
+                self.stage_fee(
+                    &mut batch,
+                    StateUpdate::fee(block.from.address.clone(), proposer_fees),
+                )?;
+            }
 
-        let state_root_hash = self.state_store.root_hash()?;
-        let transactions_root_hash = self.transaction_store.root_hash()?;
+            for (hash, signature) in &block.abandoned_claims {
+                self.stage_abandonment(&mut batch, hash, signature)?;
+            }
+        }
+
+        let index_entries: Vec<IndexEntry> = batch
+            .transactions
+            .iter()
+            .map(|txn| IndexEntry {
+                digest: txn.id(),
+                addresses: vec![txn.sender_address(), txn.receiver_address()],
+            })
+            .collect();
+
+        let staged = self.stage_ledger(batch)?;
+
+        self.write_ledger(
+            staged,
+            Some(BlockRecord {
+                hash: &convergence.hash,
+                height: convergence.header.block_height,
+                index_entries,
+            }),
+        )?;
 
         Ok(ApplyBlockResult {
-            state_root_hash,
-            transactions_root_hash,
+            state_root_hash: self.state_root_hash()?,
+            transactions_root_hash: self.transactions_root_hash()?,
         })
     }
 
     pub fn apply_genesis_block(&mut self, block: GenesisBlock) -> Result<ApplyBlockResult> {
-        let read_handle = self.read_handle();
-
         if block.genesis_rewards.0.is_empty() {
             return Err(StorageError::Other(
                 "genesis block must contain at least one reward".to_string(),
             ));
         }
-        self.apply_genesis_rewards(read_handle.clone(), &block.genesis_rewards)?;
 
-        self.transaction_store.commit();
-        self.state_store.commit();
+        let mut batch = BlockBatch::default();
+        self.stage_genesis_rewards(&mut batch, &block.genesis_rewards)?;
 
-        let state_root_hash = self.state_store.root_hash()?;
-        let transactions_root_hash = RootHash(Default::default());
+        let staged = self.stage_ledger(batch)?;
+
+        self.write_ledger(
+            staged,
+            Some(BlockRecord {
+                hash: &block.hash,
+                height: block.header.block_height,
+                index_entries: vec![],
+            }),
+        )?;
 
         Ok(ApplyBlockResult {
-            state_root_hash,
-            transactions_root_hash,
+            state_root_hash: self.state_root_hash()?,
+            transactions_root_hash: RootHash(Default::default()),
         })
     }
 
@@ -367,9 +1220,14 @@ impl VrrbDb {
 impl Clone for VrrbDb {
     fn clone(&self) -> VrrbDb {
         Self {
+            database: self.database.clone(),
             state_store: self.state_store.clone(),
             transaction_store: self.transaction_store.clone(),
             claim_store: self.claim_store.clone(),
+            transaction_index: self.transaction_index.clone(),
+            block_store: self.block_store.clone(),
+            history: self.history.clone(),
+            pruner: self.pruner.clone(),
         }
     }
 }
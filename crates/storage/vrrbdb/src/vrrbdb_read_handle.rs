@@ -1,13 +1,18 @@
 use std::collections::HashMap;
 
+use block::Block;
+use patriecia::Version;
 use primitives::{Address, NodeId};
+use storage_proofs::InclusionProof;
 use storage_utils::StorageError;
 use vrrb_core::transactions::{TransactionDigest, TransactionKind};
 use vrrb_core::{account::Account, claim::Claim};
 
 use crate::result::Result;
 use crate::{
-    ClaimStoreReadHandleFactory, StateStoreReadHandleFactory, TransactionStoreReadHandleFactory,
+    AddressTransaction, BlockStore, ClaimStoreReadHandleFactory, StateHistory,
+    StateStoreReadHandleFactory, StateVersion, TransactionIndex, TransactionLocation,
+    TransactionStoreReadHandleFactory,
 };
 
 #[derive(Debug, Clone)]
@@ -15,6 +20,9 @@ pub struct VrrbDbReadHandle {
     state_store_handle_factory: StateStoreReadHandleFactory,
     transaction_store_handle_factory: TransactionStoreReadHandleFactory,
     claim_store_handle_factory: ClaimStoreReadHandleFactory,
+    transaction_index: TransactionIndex,
+    block_store: BlockStore,
+    history: StateHistory,
 }
 
 impl VrrbDbReadHandle {
@@ -22,11 +30,17 @@ impl VrrbDbReadHandle {
         state_store_handle_factory: StateStoreReadHandleFactory,
         transaction_store_handle_factory: TransactionStoreReadHandleFactory,
         claim_store_handle_factory: ClaimStoreReadHandleFactory,
+        transaction_index: TransactionIndex,
+        block_store: BlockStore,
+        history: StateHistory,
     ) -> Self {
         Self {
             state_store_handle_factory,
             transaction_store_handle_factory,
             claim_store_handle_factory,
+            transaction_index,
+            block_store,
+            history,
         }
     }
 
@@ -48,6 +62,20 @@ impl VrrbDbReadHandle {
         self.claim_store_handle_factory.handle().entries()
     }
 
+    /// Returns the claim of the node with the given id
+    pub fn get_claim_by_node_id(&self, node_id: &NodeId) -> Result<Claim> {
+        self.claim_store_handle_factory
+            .handle()
+            .get_by_node_id(node_id)
+    }
+
+    /// Returns every claim owned by the given address
+    pub fn get_claims_by_address(&self, address: &Address) -> Result<Vec<Claim>> {
+        self.claim_store_handle_factory
+            .handle()
+            .get_by_address(address)
+    }
+
     pub fn get_account_by_address(&self, address: &Address) -> Result<Account> {
         self.state_store_handle_factory
             .handle()
@@ -56,4 +84,146 @@ impl VrrbDbReadHandle {
                 StorageError::Other(format!("Failed to get account by address: {:?}", err))
             })
     }
+
+    pub fn get_transaction_by_digest(&self, digest: &TransactionDigest) -> Result<TransactionKind> {
+        self.transaction_store_handle_factory
+            .handle()
+            .get_latest(digest)
+            .map_err(|err| {
+                StorageError::Other(format!("Failed to get transaction {digest}: {:?}", err))
+            })
+    }
+
+    /// Produces a proof that an account is included in the latest version of
+    /// the state trie
+    pub fn get_account_proof(&self, address: &Address) -> Result<InclusionProof> {
+        self.state_store_handle_factory.get_proof(address)
+    }
+
+    /// Produces a proof that a transaction is included in the latest version
+    /// of the transaction trie
+    pub fn get_transaction_proof(&self, digest: &TransactionDigest) -> Result<InclusionProof> {
+        self.transaction_store_handle_factory.get_proof(digest)
+    }
+
+    /// Returns up to `limit` transactions sent or received by an address,
+    /// oldest first, skipping the first `offset`
+    pub fn get_transactions_by_address(
+        &self,
+        address: &Address,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<AddressTransaction>> {
+        self.transaction_index
+            .transactions_by_address(address, offset, limit)
+    }
+
+    /// Returns the number of transactions sent or received by an address
+    pub fn get_transaction_count(&self, address: &Address) -> Result<usize> {
+        self.transaction_index.transaction_count(address)
+    }
+
+    /// Returns the block that included a transaction
+    pub fn get_transaction_block(&self, digest: &TransactionDigest) -> Result<TransactionLocation> {
+        self.transaction_index
+            .transaction_location(digest)?
+            .ok_or_else(|| StorageError::Other(format!("transaction {digest} was not indexed")))
+    }
+
+    /// Returns any block appended to the DAG, confirmed or not, by its hash
+    pub fn get_block(&self, block_hash: &str) -> Result<Option<Block>> {
+        self.block_store.get(block_hash)
+    }
+
+    /// Returns the confirmed block at the given height, if any
+    pub fn get_block_by_height(&self, block_height: u128) -> Result<Option<Block>> {
+        self.block_store.get_by_height(block_height)
+    }
+
+    /// Returns up to `limit` confirmed blocks, ordered by height, starting at
+    /// `start_height`
+    pub fn get_blocks(&self, start_height: u128, limit: usize) -> Result<Vec<Block>> {
+        self.block_store.range_by_height(start_height, limit)
+    }
+
+    /// Returns the confirmed block with the greatest height, if any
+    pub fn get_last_confirmed_block(&self) -> Result<Option<Block>> {
+        self.block_store.last_confirmed_block()
+    }
+
+    /// Returns the trie versions produced by the block at the given height,
+    /// failing if it is unknown or no longer within the retained history.
+    pub fn state_version_at_height(&self, block_height: u128) -> Result<StateVersion> {
+        self.history.at_height(block_height).ok_or_else(|| {
+            StorageError::Other(format!("no state retained for block height {block_height}"))
+        })
+    }
+
+    /// Returns the trie versions of the latest block that produced the given
+    /// state root hash, failing if it is no longer within the retained
+    /// history.
+    pub fn state_version_at_root(&self, state_root_hash: &str) -> Result<StateVersion> {
+        self.history.at_state_root(state_root_hash).ok_or_else(|| {
+            StorageError::Other(format!(
+                "no state retained for state root hash {state_root_hash}"
+            ))
+        })
+    }
+
+    /// Returns an account as of the given version of the state trie
+    pub fn get_account_at_version(&self, address: &Address, version: Version) -> Result<Account> {
+        self.state_store_handle_factory
+            .handle()
+            .get_at_version(address, version)
+            .map_err(|err| {
+                StorageError::Other(format!(
+                    "Failed to get account by address at version {version:?}: {err:?}"
+                ))
+            })
+    }
+
+    /// Returns an account as it was right after the block at the given
+    /// height was applied
+    pub fn get_account_at_height(&self, address: &Address, block_height: u128) -> Result<Account> {
+        let version = self.state_version_at_height(block_height)?;
+        self.get_account_at_version(address, version.state_version)
+    }
+
+    /// Returns an account as it was when the state trie had the given root
+    /// hash
+    pub fn get_account_at_root(&self, address: &Address, state_root_hash: &str) -> Result<Account> {
+        let version = self.state_version_at_root(state_root_hash)?;
+        self.get_account_at_version(address, version.state_version)
+    }
+
+    /// Returns a copy of all values stored within the claim trie as of the
+    /// given version
+    pub fn claim_store_values_at_version(
+        &self,
+        version: Version,
+    ) -> Result<HashMap<NodeId, Claim>> {
+        self.claim_store_handle_factory
+            .handle()
+            .entries_at_version(version)
+    }
+
+    /// Returns a copy of all claims as they were right after the block at
+    /// the given height was applied
+    pub fn claim_store_values_at_height(
+        &self,
+        block_height: u128,
+    ) -> Result<HashMap<NodeId, Claim>> {
+        let version = self.state_version_at_height(block_height)?;
+        self.claim_store_values_at_version(version.claims_version)
+    }
+
+    /// Returns a copy of all claims as they were when the state trie had the
+    /// given root hash
+    pub fn claim_store_values_at_root(
+        &self,
+        state_root_hash: &str,
+    ) -> Result<HashMap<NodeId, Claim>> {
+        let version = self.state_version_at_root(state_root_hash)?;
+        self.claim_store_values_at_version(version.claims_version)
+    }
 }
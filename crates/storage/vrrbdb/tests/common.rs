@@ -1,9 +1,11 @@
 use std::net::SocketAddr;
 
+use block::{header::BlockHeader, ConvergenceBlock, ProposalBlock};
 use primitives::{Address, SecretKey};
 use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use ritelinked::{LinkedHashMap, LinkedHashSet};
 use secp256k1::{Message, Secp256k1};
-use vrrb_core::transactions::{NewTransferArgs, TransactionKind, Transfer};
+use vrrb_core::transactions::{NewTransferArgs, Transaction, TransactionKind, Transfer};
 use vrrb_core::{claim::Claim, keypair::Keypair};
 
 // NOTE: this is used to generate random filenames so files created by tests
@@ -87,3 +89,46 @@ pub fn _generate_random_claim() -> Claim {
     )
     .unwrap()
 }
+
+/// Builds a `ConvergenceBlock` including every transaction of a single
+/// `ProposalBlock`, on top of the state with the given root hash
+pub fn _build_convergence_block(
+    state_root_hash: String,
+    txns: Vec<TransactionKind>,
+) -> (ConvergenceBlock, ProposalBlock) {
+    let claim = _generate_random_claim();
+    let secret_key = Keypair::random().miner_kp.0;
+
+    let proposal = ProposalBlock {
+        ref_block: "genesis".to_string(),
+        round: 1,
+        epoch: 0,
+        txns: txns.iter().map(|txn| (txn.id(), txn.clone())).collect(),
+        claims: LinkedHashMap::new(),
+        abandoned_claims: LinkedHashMap::new(),
+        from: claim.clone(),
+        hash: "proposal".to_string(),
+        signature: None,
+    };
+
+    let mut consolidated_txns = LinkedHashMap::new();
+    consolidated_txns.insert(
+        proposal.hash.clone(),
+        txns.iter()
+            .map(|txn| txn.id())
+            .collect::<LinkedHashSet<_>>(),
+    );
+
+    let mut header = BlockHeader::genesis(0, 1, 0, claim, secret_key, String::new());
+    header.state_root_hash = state_root_hash;
+
+    let convergence = ConvergenceBlock {
+        header,
+        txns: consolidated_txns,
+        claims: LinkedHashMap::new(),
+        hash: "convergence".to_string(),
+        certificate: None,
+    };
+
+    (convergence, proposal)
+}
@@ -0,0 +1,189 @@
+use std::{collections::HashMap, env};
+
+use secp256k1::{Message, Secp256k1};
+use serial_test::serial;
+use vrrb_core::{
+    account::{Account, AccountField},
+    transactions::{NewTransferArgs, Transaction, TransactionKind, Transfer, BASE_FEE},
+};
+use vrrbdb::{VrrbDb, VrrbDbConfig};
+mod common;
+
+use common::{
+    _build_convergence_block, _generate_random_address, _generate_random_string,
+    _generate_random_transaction,
+};
+
+#[test]
+#[serial]
+fn failed_convergence_blocks_leave_state_untouched() {
+    let temp_dir_path = env::temp_dir();
+    let state_backup_path = temp_dir_path.join(_generate_random_string());
+
+    let mut db = VrrbDb::new(VrrbDbConfig::default().with_path(state_backup_path)).unwrap();
+
+    let (sender_secret_key, sender_address) = _generate_random_address();
+    let (_, receiver_address) = _generate_random_address();
+    let (unknown_secret_key, unknown_address) = _generate_random_address();
+
+    let mut sender = Account::new(sender_address.clone());
+    sender
+        .update_field(AccountField::Credits(BASE_FEE + 1000))
+        .unwrap();
+    db.insert_account(sender_address.clone(), sender).unwrap();
+
+    let state_root_hash = db.state_root_hash().unwrap();
+    let sender = db
+        .read_handle()
+        .get_account_by_address(&sender_address)
+        .unwrap();
+
+    let valid_txn = _generate_random_transaction(
+        sender_secret_key,
+        sender_address.clone(),
+        receiver_address.clone(),
+    );
+    let invalid_txn = _generate_random_transaction(
+        unknown_secret_key,
+        unknown_address.clone(),
+        receiver_address.clone(),
+    );
+
+    let (convergence, proposal) = _build_convergence_block(
+        hex::encode(state_root_hash.0),
+        vec![valid_txn.clone(), invalid_txn],
+    );
+
+    db.apply_convergence_block(&convergence, &[proposal])
+        .unwrap_err();
+
+    let read_handle = db.read_handle();
+
+    assert_eq!(db.state_root_hash().unwrap(), state_root_hash);
+    assert_eq!(
+        read_handle.get_account_by_address(&sender_address).unwrap(),
+        sender
+    );
+    assert!(read_handle
+        .get_account_by_address(&receiver_address)
+        .is_err());
+    assert!(read_handle.transaction_store_values().unwrap().is_empty());
+
+    let (convergence, proposal) =
+        _build_convergence_block(hex::encode(state_root_hash.0), vec![valid_txn.clone()]);
+
+    let result = db
+        .apply_convergence_block(&convergence, &[proposal])
+        .unwrap();
+
+    let read_handle = db.read_handle();
+    let sender = read_handle.get_account_by_address(&sender_address).unwrap();
+
+    assert_ne!(result.state_root_hash_str(), hex::encode(state_root_hash.0));
+    assert_eq!(sender.nonce(), valid_txn.nonce());
+    assert_eq!(sender.debits(), valid_txn.amount() + valid_txn.fee());
+    // NOTE: the history of an account is kept in the transaction index
+    assert!(sender.digests().is_empty());
+    assert!(read_handle
+        .get_account_by_address(&receiver_address)
+        .is_ok());
+    assert!(read_handle
+        .transaction_store_values()
+        .unwrap()
+        .contains_key(&valid_txn.id()));
+}
+
+#[test]
+#[serial]
+fn blocks_built_on_another_state_are_rejected() {
+    let path = env::temp_dir().join(_generate_random_string());
+    let mut db = VrrbDb::new(VrrbDbConfig::default().with_path(path)).unwrap();
+
+    let (sender_secret_key, sender_address) = _generate_random_address();
+    let (_, receiver_address) = _generate_random_address();
+
+    let mut sender = Account::new(sender_address.clone());
+    sender
+        .update_field(AccountField::Credits(BASE_FEE + 1000))
+        .unwrap();
+    db.insert_account(sender_address.clone(), sender).unwrap();
+
+    let state_root_hash = db.state_root_hash().unwrap();
+    let txn = _generate_random_transaction(sender_secret_key, sender_address, receiver_address);
+
+    let (convergence, proposal) = _build_convergence_block("stale".to_string(), vec![txn]);
+
+    db.apply_convergence_block(&convergence, &[proposal])
+        .unwrap_err();
+
+    assert_eq!(db.state_root_hash().unwrap(), state_root_hash);
+    assert!(db
+        .read_handle()
+        .transaction_store_values()
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+#[serial]
+fn fees_are_credited_to_validators_and_proposers() {
+    let path = env::temp_dir().join(_generate_random_string());
+    let mut db = VrrbDb::new(VrrbDbConfig::default().with_path(path)).unwrap();
+
+    let (sender_secret_key, sender_address) = _generate_random_address();
+    let (_, receiver_address) = _generate_random_address();
+    let (_, validator_address) = _generate_random_address();
+    let (_, absent_validator_address) = _generate_random_address();
+
+    let mut sender = Account::new(sender_address.clone());
+    sender
+        .update_field(AccountField::Credits(BASE_FEE + 1000))
+        .unwrap();
+    db.insert_account(sender_address.clone(), sender).unwrap();
+
+    let secp = Secp256k1::new();
+    let message = Message::from_hashed_data::<secp256k1::hashes::sha256::Hash>(b"vrrb");
+
+    let txn = TransactionKind::Transfer(Transfer::new(NewTransferArgs {
+        timestamp: 0,
+        sender_address: sender_address.clone(),
+        sender_public_key: sender_secret_key.public_key(&secp),
+        receiver_address,
+        token: None,
+        amount: 100,
+        signature: secp.sign_ecdsa(&message, &sender_secret_key),
+        validators: Some(HashMap::from([
+            (validator_address.to_string(), true),
+            (absent_validator_address.to_string(), false),
+        ])),
+        nonce: 10,
+    }));
+
+    let state_root_hash = db.state_root_hash().unwrap();
+    let (convergence, proposal) =
+        _build_convergence_block(hex::encode(state_root_hash.0), vec![txn.clone()]);
+    let proposer_address = proposal.from.address.clone();
+
+    db.apply_convergence_block(&convergence, &[proposal])
+        .unwrap();
+
+    let read_handle = db.read_handle();
+
+    let validator = read_handle
+        .get_account_by_address(&validator_address)
+        .unwrap();
+    let proposer = read_handle
+        .get_account_by_address(&proposer_address)
+        .unwrap();
+
+    assert_eq!(validator.credits(), txn.validator_fee_share());
+    assert_eq!(proposer.credits(), txn.proposer_fee_share());
+    assert_eq!(
+        validator.credits() + proposer.credits(),
+        txn.fee(),
+        "every fee paid is credited"
+    );
+    assert!(read_handle
+        .get_account_by_address(&absent_validator_address)
+        .is_err());
+}
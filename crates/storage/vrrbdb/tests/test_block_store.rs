@@ -0,0 +1,86 @@
+use std::env;
+
+use block::Block;
+use serial_test::serial;
+use vrrbdb::{AccessMode, BlockStore, StoredBlock, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::{_build_convergence_block, _generate_random_string};
+
+/// Builds a proposal block and the convergence blocks at heights 1 and 2
+/// confirming it
+fn build_blocks() -> (Block, Block, Block) {
+    let (mut first, proposal) = _build_convergence_block(String::new(), vec![]);
+    first.header.block_height = 1;
+    first.hash = "convergence-1".to_string();
+
+    let mut second = first.clone();
+    second.header.block_height = 2;
+    second.hash = "convergence-2".to_string();
+
+    (
+        Block::Proposal { block: proposal },
+        Block::Convergence { block: first },
+        Block::Convergence { block: second },
+    )
+}
+
+#[test]
+fn blocks_are_stored_by_hash_and_height() {
+    let store = BlockStore::in_memory();
+    let (proposal, first, second) = build_blocks();
+
+    store.insert(&proposal, &["genesis".to_string()]).unwrap();
+    store.insert(&first, &[proposal.hash()]).unwrap();
+    store.insert(&second, &[]).unwrap();
+
+    assert_eq!(store.len().unwrap(), 3);
+    assert_eq!(store.get(&proposal.hash()).unwrap(), Some(proposal.clone()));
+    assert_eq!(store.get("unknown").unwrap(), None);
+
+    assert_eq!(store.get_by_height(1).unwrap(), Some(first.clone()));
+    assert_eq!(store.get_by_height(2).unwrap(), Some(second.clone()));
+    assert_eq!(store.get_by_height(3).unwrap(), None);
+    assert_eq!(store.last_confirmed_block().unwrap(), Some(second));
+
+    assert_eq!(store.sources(&first.hash()).unwrap(), vec![proposal.hash()]);
+    assert!(store.sources(&second.hash()).unwrap().is_empty());
+    assert!(store.sources("unknown").unwrap().is_empty());
+
+    let entries = store.entries().unwrap();
+    assert!(entries.contains(&StoredBlock {
+        block: first,
+        sources: vec![proposal.hash()],
+    }));
+}
+
+#[test]
+fn empty_store_has_no_confirmed_block() {
+    let store = BlockStore::in_memory();
+
+    assert!(store.is_empty().unwrap());
+    assert_eq!(store.last_confirmed_block().unwrap(), None);
+    assert!(store.entries().unwrap().is_empty());
+}
+
+#[test]
+#[serial]
+fn blocks_survive_reopening_the_database() {
+    let path = env::temp_dir().join(_generate_random_string());
+    let config = VrrbDbConfig::default().with_path(path);
+    let (proposal, first, _) = build_blocks();
+
+    {
+        let db = VrrbDb::new(config.clone()).unwrap();
+        db.insert_block(&proposal, &["genesis".to_string()])
+            .unwrap();
+        db.insert_block(&first, &[proposal.hash()]).unwrap();
+    }
+
+    let db = VrrbDb::open(config, AccessMode::ReadOnly).unwrap();
+    let store = db.block_store();
+
+    assert_eq!(store.len().unwrap(), 2);
+    assert_eq!(store.last_confirmed_block().unwrap(), Some(first));
+    assert_eq!(store.get(&proposal.hash()).unwrap(), Some(proposal));
+}
@@ -1,17 +1,24 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, net::SocketAddr};
 
-use primitives::NodeId;
-use vrrb_core::claim::Claim;
-use vrrbdb::{VrrbDb, VrrbDbConfig};
+use primitives::{Address, NodeId};
+use secp256k1::Message;
+use vrrb_core::{
+    account::{Account, AccountField},
+    claim::{Claim, Eligibility},
+    keypair::Keypair,
+    staking::{Stake, StakeUpdate},
+    transactions::{Transaction, TransactionKind, BASE_FEE},
+};
+use vrrbdb::{ClaimUpdate, VrrbDb, VrrbDbConfig};
 
 mod common;
-use common::_generate_random_claim;
+use common::{_build_convergence_block, _generate_random_claim};
 use serial_test::serial;
 
 #[test]
 #[serial]
 fn claims_can_be_added() {
-    let mut db = VrrbDb::new(VrrbDbConfig::default());
+    let mut db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
 
     let claim1 = _generate_random_claim();
     let claim2 = _generate_random_claim();
@@ -31,9 +38,232 @@ fn claims_can_be_added() {
         (claim3.hash, Some(claim3)),
         (claim4.hash, Some(claim4)),
         (claim5.hash, Some(claim5)),
-    ]);
+    ])
+    .unwrap();
 
     let entries = db.claim_store_factory().handle().entries().unwrap();
 
     assert_eq!(entries.len(), 5);
 }
+
+#[test]
+#[serial]
+fn stake_transactions_update_claim_stake() {
+    let mut db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+
+    let keypair = Keypair::random();
+    let public_key = *keypair.get_miner_public_key();
+    let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    let signature = Claim::signature_for_valid_claim(
+        public_key,
+        ip_address,
+        keypair.get_miner_secret_key().secret_bytes().to_vec(),
+    )
+    .unwrap();
+    let claim = Claim::new(
+        public_key,
+        Address::new(public_key),
+        ip_address,
+        signature.clone(),
+        signature,
+    )
+    .unwrap();
+    let node_id = claim.node_id.clone();
+    let claim_hash = claim.hash;
+
+    db.insert_claim(claim).unwrap();
+
+    let mut staker = Account::new(Address::new(public_key));
+    staker
+        .update_field(AccountField::Credits(BASE_FEE + 20_000))
+        .unwrap();
+    db.insert_account(Address::new(public_key), staker).unwrap();
+
+    let stake = Stake::new(
+        StakeUpdate::Add(10_000),
+        keypair.miner_kp.0,
+        public_key,
+        Address::new(public_key),
+        None,
+    )
+    .unwrap();
+    let placeholder_signature = keypair
+        .miner_kp
+        .0
+        .sign_ecdsa(Message::from_slice(&[1u8; 32]).unwrap());
+
+    let mut txn = TransactionKind::stake_builder()
+        .stake(stake)
+        .claim_hash(claim_hash)
+        .nonce(1)
+        .signature(placeholder_signature)
+        .build_kind()
+        .unwrap();
+    txn.sign(&keypair.miner_kp.0);
+
+    let (mut convergence, proposal) =
+        _build_convergence_block(hex::encode(db.state_root_hash().unwrap().0), vec![txn]);
+    db.apply_convergence_block(&convergence, &[proposal.clone()])
+        .unwrap();
+
+    let entries = db.claim_store_factory().handle().entries().unwrap();
+    assert_eq!(entries.get(&node_id).unwrap().get_stake(), 10_000);
+
+    // NOTE: replaying the block must not stake the same amount twice
+    convergence.header.state_root_hash = hex::encode(db.state_root_hash().unwrap().0);
+    assert!(db
+        .apply_convergence_block(&convergence, &[proposal])
+        .is_err());
+
+    let entries = db.claim_store_factory().handle().entries().unwrap();
+    assert_eq!(entries.get(&node_id).unwrap().get_stake(), 10_000);
+}
+
+#[test]
+#[serial]
+fn claims_can_be_moved_to_a_new_ip_address() {
+    let mut db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+
+    let keypair = Keypair::random();
+    let public_key = *keypair.get_miner_public_key();
+    let secret_key = keypair.get_miner_secret_key().secret_bytes().to_vec();
+    let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    let signature =
+        Claim::signature_for_valid_claim(public_key, ip_address, secret_key.clone()).unwrap();
+    let claim = Claim::new(
+        public_key,
+        Address::new(public_key),
+        ip_address,
+        signature.clone(),
+        signature,
+    )
+    .unwrap();
+
+    db.insert_claim(claim.clone()).unwrap();
+
+    let new_ip_address = "127.0.0.1:9090".parse::<SocketAddr>().unwrap();
+    let new_signature =
+        Claim::signature_for_valid_claim(public_key, new_ip_address, secret_key).unwrap();
+
+    let stranger = _generate_random_claim();
+    assert!(db
+        .update_claim(
+            claim.hash,
+            ClaimUpdate::IpAddress {
+                signature: new_signature.clone(),
+                public_key: stranger.public_key,
+                ip_address: new_ip_address,
+            },
+        )
+        .is_err());
+
+    let updated = db
+        .update_claim(
+            claim.hash,
+            ClaimUpdate::IpAddress {
+                signature: new_signature,
+                public_key,
+                ip_address: new_ip_address,
+            },
+        )
+        .unwrap();
+    assert_eq!(updated.ip_address, new_ip_address);
+
+    let stored = db
+        .read_handle()
+        .get_claim_by_node_id(&claim.node_id)
+        .unwrap();
+    assert_eq!(stored.ip_address, new_ip_address);
+    assert_eq!(stored.hash, claim.hash);
+}
+
+#[test]
+#[serial]
+fn claims_can_be_looked_up_and_abandoned() {
+    let mut db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+
+    let claim = _generate_random_claim();
+    let other = _generate_random_claim();
+    db.insert_claim(claim.clone()).unwrap();
+    db.insert_claim(other.clone()).unwrap();
+
+    let updated = db
+        .update_claim(claim.hash, ClaimUpdate::Eligibility(Eligibility::Miner))
+        .unwrap();
+    assert_eq!(updated.eligibility, Eligibility::Miner);
+
+    let read_handle = db.read_handle();
+    assert_eq!(
+        read_handle.get_claims_by_address(&claim.address).unwrap(),
+        vec![updated.clone()]
+    );
+    assert_eq!(
+        read_handle.get_claim_by_node_id(&claim.node_id).unwrap(),
+        updated
+    );
+
+    let abandoned = db.abandon_claim(claim.hash).unwrap();
+    assert_eq!(abandoned, updated);
+
+    let read_handle = db.read_handle();
+    assert!(read_handle.get_claim_by_node_id(&claim.node_id).is_err());
+    assert!(read_handle.get_claim_by_node_id(&other.node_id).is_ok());
+    assert!(db.abandon_claim(claim.hash).is_err());
+    assert!(db
+        .update_claim(claim.hash, ClaimUpdate::Eligibility(Eligibility::None))
+        .is_err());
+}
+
+#[test]
+#[serial]
+fn claims_are_abandoned_by_blocks_carrying_their_owner_signature() {
+    let mut db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+
+    let keypair = Keypair::random();
+    let public_key = *keypair.get_miner_public_key();
+    let secret_key = keypair.get_miner_secret_key().secret_bytes().to_vec();
+    let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    let signature =
+        Claim::signature_for_valid_claim(public_key, ip_address, secret_key.clone()).unwrap();
+    let claim = Claim::new(
+        public_key,
+        Address::new(public_key),
+        ip_address,
+        signature.clone(),
+        signature,
+    )
+    .unwrap();
+    db.insert_claim(claim.clone()).unwrap();
+
+    // NOTE: abandonments signed by any other key are rejected with the block
+    let forged = claim
+        .sign_abandonment(
+            Keypair::random()
+                .get_miner_secret_key()
+                .secret_bytes()
+                .to_vec(),
+        )
+        .unwrap();
+    let (convergence, mut proposal) =
+        _build_convergence_block(hex::encode(db.state_root_hash().unwrap().0), vec![]);
+    proposal.abandoned_claims.insert(claim.hash, forged);
+    assert!(db
+        .apply_convergence_block(&convergence, &[proposal])
+        .is_err());
+    assert!(db
+        .read_handle()
+        .get_claim_by_node_id(&claim.node_id)
+        .is_ok());
+
+    let abandonment = claim.sign_abandonment(secret_key).unwrap();
+    let (convergence, mut proposal) =
+        _build_convergence_block(hex::encode(db.state_root_hash().unwrap().0), vec![]);
+    proposal.abandoned_claims.insert(claim.hash, abandonment);
+    db.apply_convergence_block(&convergence, &[proposal])
+        .unwrap();
+
+    assert!(db
+        .read_handle()
+        .get_claim_by_node_id(&claim.node_id)
+        .is_err());
+}
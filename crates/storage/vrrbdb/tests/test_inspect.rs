@@ -0,0 +1,77 @@
+use std::env;
+
+use serial_test::serial;
+use vrrb_core::account::{Account, AccountField};
+use vrrbdb::{AccessMode, VrrbDb, VrrbDbConfig, STALE_NODES_COLUMN_FAMILY};
+
+mod common;
+use common::{
+    _generate_random_address, _generate_random_claim, _generate_random_string,
+    _generate_random_valid_transaction,
+};
+
+/// Populates a database and closes it, returning its config along with the
+/// state root hash it was left with.
+fn populated_config() -> (VrrbDbConfig, String) {
+    let path = env::temp_dir().join(_generate_random_string());
+    let config = VrrbDbConfig::default().with_path(path);
+
+    let mut db = VrrbDb::new(config.clone()).unwrap();
+    for _ in 0..3 {
+        let (_, address) = _generate_random_address();
+        let mut account = Account::new(address.clone());
+        account.update_field(AccountField::Credits(500)).unwrap();
+        db.insert_account(address, account).unwrap();
+    }
+
+    db.insert_claim(_generate_random_claim()).unwrap();
+    db.insert_transaction(_generate_random_valid_transaction())
+        .unwrap();
+
+    (config, hex::encode(db.state_root_hash().unwrap().0))
+}
+
+#[test]
+#[serial]
+fn databases_can_be_inspected_read_only() {
+    let (config, state_root_hash) = populated_config();
+    let db = VrrbDb::open(config, AccessMode::ReadOnly).unwrap();
+
+    let column_families = db.column_families().unwrap();
+    assert_eq!(column_families.len(), 3);
+    assert!(column_families.iter().all(|(_, names)| names
+        .iter()
+        .any(|name| name.ends_with(STALE_NODES_COLUMN_FAMILY))));
+
+    assert_eq!(
+        db.state_store_factory().handle().entries().unwrap().len(),
+        3
+    );
+
+    let roots = db.state_store_factory().root_hashes().unwrap();
+    assert_eq!(roots.last().unwrap().root_hash, state_root_hash);
+
+    let report = db.state_store_factory().verify_integrity().unwrap();
+    assert!(report.is_valid());
+    assert_eq!(report.verified_leaves, 3);
+
+    assert!(db
+        .transaction_store_factory()
+        .verify_integrity()
+        .unwrap()
+        .is_valid());
+    assert!(db
+        .claim_store_factory()
+        .verify_integrity()
+        .unwrap()
+        .is_valid());
+}
+
+#[test]
+#[serial]
+fn read_only_databases_must_exist() {
+    let path = env::temp_dir().join(_generate_random_string());
+    let config = VrrbDbConfig::default().with_path(path);
+
+    assert!(VrrbDb::open(config, AccessMode::ReadOnly).is_err());
+}
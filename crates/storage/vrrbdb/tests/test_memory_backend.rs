@@ -0,0 +1,74 @@
+use std::{env, path::PathBuf};
+
+use serial_test::serial;
+use vrrb_core::account::{Account, AccountField};
+use vrrbdb::{StorageBackendType, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::{
+    _generate_random_address, _generate_random_claim, _generate_random_string,
+    _generate_random_valid_transaction,
+};
+
+fn new_db(backend: StorageBackendType) -> (VrrbDb, PathBuf) {
+    let path = env::temp_dir().join(_generate_random_string());
+    let mut config = VrrbDbConfig::default().with_path(path.clone());
+    config.backend = backend;
+
+    (VrrbDb::new(config).unwrap(), path)
+}
+
+#[test]
+#[serial]
+fn in_memory_databases_match_rocksdb_ones() {
+    let (mut in_memory, path) = new_db(StorageBackendType::InMemory);
+    let (mut on_disk, _) = new_db(StorageBackendType::RocksDb);
+
+    let claim = _generate_random_claim();
+    let txn = _generate_random_valid_transaction();
+
+    for db in [&mut in_memory, &mut on_disk] {
+        db.insert_claim(claim.clone()).unwrap();
+        db.insert_transaction(txn.clone()).unwrap();
+    }
+
+    for _ in 0..3 {
+        let (_, address) = _generate_random_address();
+        let mut account = Account::new(address.clone());
+        account.update_field(AccountField::Credits(500)).unwrap();
+
+        in_memory
+            .insert_account(address.clone(), account.clone())
+            .unwrap();
+        on_disk.insert_account(address, account).unwrap();
+    }
+
+    assert_eq!(
+        in_memory.state_root_hash().unwrap(),
+        on_disk.state_root_hash().unwrap()
+    );
+    assert_eq!(
+        in_memory.transactions_root_hash().unwrap(),
+        on_disk.transactions_root_hash().unwrap()
+    );
+    assert_eq!(
+        in_memory.claims_root_hash().unwrap(),
+        on_disk.claims_root_hash().unwrap()
+    );
+    assert_eq!(
+        in_memory.state_store_factory().handle().entries().unwrap(),
+        on_disk.state_store_factory().handle().entries().unwrap()
+    );
+
+    assert!(!path.exists());
+}
+
+#[test]
+#[serial]
+fn databases_that_cannot_be_opened_are_not_kept_in_memory() {
+    let (_db, path) = new_db(StorageBackendType::RocksDb);
+
+    // NOTE: the database is still locked by the first instance
+    let config = VrrbDbConfig::default().with_path(path);
+    assert!(VrrbDb::new(config).is_err());
+}
@@ -0,0 +1,60 @@
+use std::env;
+
+use mempool::{LeftRightMempool, MempoolOp, TxnRecord};
+use vrrb_core::transactions::Transaction;
+use vrrbdb::MempoolJournalStore;
+
+mod common;
+
+use common::{_generate_random_string, _generate_random_valid_transaction};
+
+#[test]
+fn journaled_records_survive_reopening() {
+    let journal_path = env::temp_dir().join(_generate_random_string());
+
+    let kept = TxnRecord::new(_generate_random_valid_transaction());
+    let removed = TxnRecord::new(_generate_random_valid_transaction());
+
+    {
+        let journal = MempoolJournalStore::new(journal_path.clone()).unwrap();
+
+        journal
+            .write(&MempoolOp::Add(Box::new(kept.clone())))
+            .unwrap();
+        journal
+            .write(&MempoolOp::Add(Box::new(removed.clone())))
+            .unwrap();
+        journal
+            .write(&MempoolOp::Remove(removed.txn_id.clone()))
+            .unwrap();
+    }
+
+    let journal = MempoolJournalStore::new(journal_path).unwrap();
+    let records = journal.replay().unwrap();
+
+    assert_eq!(records, vec![kept]);
+}
+
+#[test]
+fn mempool_operations_are_journaled() {
+    let journal_path = env::temp_dir().join(_generate_random_string());
+    let journal = MempoolJournalStore::new(journal_path).unwrap();
+
+    let mut mempool = LeftRightMempool::new().with_journal(std::sync::Arc::new(journal.clone()));
+
+    let txn1 = _generate_random_valid_transaction();
+    let txn2 = _generate_random_valid_transaction();
+
+    mempool.insert(txn1.clone()).unwrap();
+    mempool.insert(txn2.clone()).unwrap();
+    mempool.remove(&txn1.id()).unwrap();
+
+    let journaled: Vec<_> = journal
+        .replay()
+        .unwrap()
+        .into_iter()
+        .map(|record| record.txn_id)
+        .collect();
+
+    assert_eq!(journaled, vec![txn2.id()]);
+}
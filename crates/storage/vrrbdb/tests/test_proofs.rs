@@ -0,0 +1,86 @@
+use std::env;
+
+use serial_test::serial;
+use storage_proofs::ProofError;
+use vrrb_core::{
+    account::{Account, AccountField},
+    transactions::Transaction,
+};
+use vrrbdb::{VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::{
+    _generate_random_address, _generate_random_string, _generate_random_valid_transaction,
+};
+
+fn new_db() -> VrrbDb {
+    let path = env::temp_dir().join(_generate_random_string());
+    VrrbDb::new(VrrbDbConfig::default().with_path(path)).unwrap()
+}
+
+#[test]
+#[serial]
+fn account_proofs_verify_against_the_state_root() {
+    let mut db = new_db();
+
+    let (_, address) = _generate_random_address();
+    let mut account = Account::new(address.clone());
+    account.update_field(AccountField::Credits(500)).unwrap();
+    db.insert_account(address.clone(), account.clone()).unwrap();
+
+    let (_, other_address) = _generate_random_address();
+    db.insert_account(other_address.clone(), Account::new(other_address))
+        .unwrap();
+
+    let state_root_hash = hex::encode(db.state_root_hash().unwrap().0);
+    let proof = db.read_handle().get_account_proof(&address).unwrap();
+
+    assert_eq!(proof.decode_value::<Account>().unwrap(), account);
+    proof.verify(&state_root_hash, &address, &account).unwrap();
+
+    let mut tampered = account.clone();
+    tampered.update_field(AccountField::Credits(1)).unwrap();
+
+    assert_eq!(
+        proof.verify(&state_root_hash, &address, &tampered),
+        Err(ProofError::ValueMismatch)
+    );
+    assert!(matches!(
+        proof.verify(&hex::encode([0u8; 32]), &address, &account),
+        Err(ProofError::RootHashMismatch { .. })
+    ));
+}
+
+#[test]
+#[serial]
+fn transaction_proofs_verify_against_the_transactions_root() {
+    let mut db = new_db();
+
+    let txn = _generate_random_valid_transaction();
+    db.insert_transaction(txn.clone()).unwrap();
+    db.insert_transaction(_generate_random_valid_transaction())
+        .unwrap();
+
+    let transactions_root_hash = hex::encode(db.transactions_root_hash().unwrap().0);
+    let proof = db.read_handle().get_transaction_proof(&txn.id()).unwrap();
+
+    proof
+        .verify(&transactions_root_hash, &txn.id(), &txn)
+        .unwrap();
+
+    let other_txn = _generate_random_valid_transaction();
+
+    assert_eq!(
+        proof.verify(&transactions_root_hash, &txn.id(), &other_txn),
+        Err(ProofError::ValueMismatch)
+    );
+}
+
+#[test]
+#[serial]
+fn proofs_cannot_be_produced_for_missing_values() {
+    let db = new_db();
+    let (_, address) = _generate_random_address();
+
+    assert!(db.read_handle().get_account_proof(&address).is_err());
+}
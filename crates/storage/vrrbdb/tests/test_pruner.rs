@@ -0,0 +1,126 @@
+use std::env;
+
+use primitives::Address;
+use serial_test::serial;
+use vrrb_core::account::{Account, AccountField, UpdateArgs};
+use vrrbdb::{AccessMode, PruningMode, StorageBackendType, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::{_generate_random_address, _generate_random_string};
+
+fn new_db(pruning: PruningMode) -> VrrbDb {
+    let path = env::temp_dir().join(_generate_random_string());
+    let mut config = VrrbDbConfig::default().with_path(path);
+    config.pruning = pruning;
+    config.history_window = 1;
+
+    VrrbDb::new(config).unwrap()
+}
+
+/// Inserts accounts one commit at a time so every insert replaces the nodes
+/// written by the previous one.
+fn insert_accounts(db: &mut VrrbDb, count: usize) -> Vec<(Address, Account)> {
+    (0..count)
+        .map(|_| {
+            let (_, address) = _generate_random_address();
+            let mut account = Account::new(address.clone());
+            account.update_field(AccountField::Credits(100)).unwrap();
+            db.insert_account(address.clone(), account.clone()).unwrap();
+
+            (address, account)
+        })
+        .collect()
+}
+
+#[test]
+#[serial]
+fn stale_nodes_are_pruned_without_affecting_the_latest_state() {
+    let mut db = new_db(PruningMode::Prune {
+        window: 1,
+        batch_size: 2,
+    });
+
+    let accounts = insert_accounts(&mut db, 10);
+    let state_root_hash = db.state_root_hash().unwrap();
+
+    let pruned = db.prune().unwrap();
+
+    assert!(pruned > 0);
+    assert!(db.pruner_metrics().pruned_nodes() >= pruned);
+    assert_eq!(db.state_root_hash().unwrap(), state_root_hash);
+
+    let read_handle = db.read_handle();
+    for (address, account) in accounts {
+        assert_eq!(
+            read_handle.get_account_by_address(&address).unwrap(),
+            account
+        );
+    }
+
+    assert_eq!(db.prune().unwrap(), 0);
+}
+
+#[test]
+#[serial]
+fn values_replaced_outside_the_window_are_pruned() {
+    let mut db = new_db(PruningMode::Prune {
+        window: 1,
+        batch_size: 2,
+    });
+
+    let (address, _) = insert_accounts(&mut db, 1).remove(0);
+
+    for credits in 1..=5 {
+        db.update_account(UpdateArgs {
+            address: address.clone(),
+            nonce: None,
+            credits: Some(credits),
+            debits: None,
+            storage: None,
+            package_address: None,
+            digests: None,
+        })
+        .unwrap();
+    }
+
+    db.prune().unwrap();
+
+    assert!(db.pruner_metrics().pruned_values() > 0);
+    assert_eq!(
+        db.read_handle()
+            .get_account_by_address(&address)
+            .unwrap()
+            .credits(),
+        115
+    );
+}
+
+#[test]
+#[serial]
+fn pruning_windows_shorter_than_the_history_window_are_rejected() {
+    let path = env::temp_dir().join(_generate_random_string());
+    let mut config = VrrbDbConfig::default().with_path(path);
+    config.history_window = 10;
+    config.pruning = PruningMode::Prune {
+        window: 5,
+        batch_size: 2,
+    };
+
+    assert!(config.validate().is_err());
+    assert!(VrrbDb::open(config.clone(), AccessMode::ReadWrite).is_err());
+
+    // NOTE: in-memory databases are held to the same windows
+    config.backend = StorageBackendType::InMemory;
+    assert!(VrrbDb::new(config).is_err());
+}
+
+#[test]
+#[serial]
+fn archive_nodes_never_prune() {
+    let mut db = new_db(PruningMode::Archive);
+
+    insert_accounts(&mut db, 10);
+
+    assert_eq!(db.prune().unwrap(), 0);
+    assert_eq!(db.pruner_metrics().pruned_nodes(), 0);
+}
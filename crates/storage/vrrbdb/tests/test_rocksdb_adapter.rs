@@ -0,0 +1,66 @@
+use std::{collections::HashMap, env};
+
+use patriecia::{TreeReader, VersionedDatabase};
+use serial_test::serial;
+use vrrb_core::account::{Account, UpdateArgs};
+use vrrbdb::{RocksDbAdapter, VrrbDb, VrrbDbConfig, LEDGER_DIR};
+
+mod common;
+use common::{_generate_random_address, _generate_random_string};
+
+#[test]
+#[serial]
+fn value_history_survives_restarts() {
+    let path = env::temp_dir().join(_generate_random_string());
+
+    let mut db = VrrbDb::new(VrrbDbConfig::default().with_path(path.clone())).unwrap();
+
+    let (_, addr1) = _generate_random_address();
+    let (_, addr2) = _generate_random_address();
+
+    db.insert_account(addr1.clone(), Account::new(addr1.clone()))
+        .unwrap();
+    db.insert_account(addr2.clone(), Account::new(addr2))
+        .unwrap();
+
+    db.update_account(UpdateArgs {
+        address: addr1.clone(),
+        nonce: None,
+        credits: Some(100),
+        debits: None,
+        storage: None,
+        package_address: None,
+        digests: None,
+    })
+    .unwrap();
+
+    drop(db);
+
+    let adapter = RocksDbAdapter::new(path.join(LEDGER_DIR), "state").unwrap();
+    let history: HashMap<_, _> = adapter.value_history().collect();
+    drop(adapter);
+
+    assert_eq!(history.len(), 2);
+
+    let (key_hash, versions) = history
+        .iter()
+        .find(|(_, versions)| versions.len() > 1)
+        .unwrap();
+
+    assert_ne!(versions[0].1, versions[1].1);
+
+    // NOTE: reopened again so every version is read back from disk
+    let adapter = RocksDbAdapter::new(path.join(LEDGER_DIR), "state").unwrap();
+
+    for (version, value) in versions {
+        assert_eq!(
+            adapter.get_value_option(*version, *key_hash).unwrap(),
+            value.clone()
+        );
+    }
+
+    assert_eq!(
+        vrrbdb::StorageBackend::get_preimage(&adapter, *key_hash).unwrap(),
+        Some(bincode::serialize(&addr1).unwrap())
+    );
+}
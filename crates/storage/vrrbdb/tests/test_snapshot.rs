@@ -0,0 +1,289 @@
+use std::env;
+
+use block::{Block, Certificate, ConvergenceBlock, ProposalBlock};
+use serial_test::serial;
+use vrrb_core::{
+    account::{Account, AccountField},
+    transactions::{TransactionKind, BASE_FEE},
+};
+use vrrbdb::{StateSnapshot, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::{
+    _build_convergence_block, _generate_random_address, _generate_random_claim,
+    _generate_random_string, _generate_random_transaction, _generate_random_valid_transaction,
+};
+
+fn new_db() -> VrrbDb {
+    let path = env::temp_dir().join(_generate_random_string());
+    VrrbDb::new(VrrbDbConfig::default().with_path(path)).unwrap()
+}
+
+/// Builds a certified block at `block_height` on top of the state with the
+/// given root hash, along with the proposal it references.
+fn certified_block_on(
+    block_height: u128,
+    state_root_hash: String,
+    txns: Vec<TransactionKind>,
+) -> (ConvergenceBlock, ProposalBlock) {
+    let (mut block, mut proposal) = _build_convergence_block(state_root_hash, txns);
+
+    proposal.hash = format!("proposal-{block_height}");
+    block.txns = block
+        .txns
+        .into_iter()
+        .map(|(_, txns)| (proposal.hash.clone(), txns))
+        .collect();
+    block.header.block_height = block_height;
+    block.header.ref_hashes = vec![proposal.hash.clone()];
+    block.hash = block.header.convergence_block_hash();
+    block.certificate = Some(Certificate {
+        signatures: vec![],
+        inauguration: None,
+        root_hash: String::new(),
+        block_hash: block.hash.clone(),
+        threshold_signature: None,
+    });
+
+    (block, proposal)
+}
+
+/// Stores a certified block at height 1 on top of the current state of `db`.
+fn store_certified_block(db: &VrrbDb) -> (ConvergenceBlock, ProposalBlock) {
+    let (block, proposal) =
+        certified_block_on(1, hex::encode(db.state_root_hash().unwrap().0), vec![]);
+    store_block(db, &block, &proposal);
+
+    (block, proposal)
+}
+
+fn store_block(db: &VrrbDb, block: &ConvergenceBlock, proposal: &ProposalBlock) {
+    db.insert_block(
+        &Block::Proposal {
+            block: proposal.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+    db.insert_block(
+        &Block::Convergence {
+            block: block.clone(),
+        },
+        &[proposal.hash.clone()],
+    )
+    .unwrap();
+}
+
+fn populated_db() -> VrrbDb {
+    let mut db = new_db();
+
+    for _ in 0..3 {
+        let (_, address) = _generate_random_address();
+        let mut account = Account::new(address.clone());
+        account.update_field(AccountField::Credits(500)).unwrap();
+        db.insert_account(address, account).unwrap();
+    }
+
+    db.insert_claim(_generate_random_claim()).unwrap();
+    db.insert_transaction(_generate_random_valid_transaction())
+        .unwrap();
+
+    db
+}
+
+#[test]
+#[serial]
+fn snapshots_can_be_exported_and_imported() {
+    let db = populated_db();
+    let (_, proposal) = store_certified_block(&db);
+    let snapshot = db.export_state(1).unwrap();
+
+    assert_eq!(snapshot.proposals, vec![proposal]);
+
+    let encoded = snapshot.encode().unwrap();
+    let decoded = StateSnapshot::decode(&encoded).unwrap();
+
+    assert_eq!(decoded, snapshot);
+
+    let mut imported = new_db();
+    imported.import_state(&decoded).unwrap();
+
+    assert_eq!(
+        imported.state_root_hash().unwrap(),
+        db.state_root_hash().unwrap()
+    );
+    assert_eq!(
+        imported.transactions_root_hash().unwrap(),
+        db.transactions_root_hash().unwrap()
+    );
+    assert_eq!(
+        imported.claims_root_hash().unwrap(),
+        db.claims_root_hash().unwrap()
+    );
+    assert_eq!(
+        imported.state_store_factory().handle().entries().unwrap(),
+        db.state_store_factory().handle().entries().unwrap()
+    );
+}
+
+#[test]
+#[serial]
+fn snapshots_are_taken_at_the_state_their_block_was_built_on() {
+    let mut db = populated_db();
+    let (_, receiver) = _generate_random_address();
+
+    let senders: Vec<_> = (0..2)
+        .map(|_| {
+            let (secret_key, sender) = _generate_random_address();
+            let mut account = Account::new(sender.clone());
+            account
+                .update_field(AccountField::Credits(BASE_FEE + 1000))
+                .unwrap();
+            db.insert_account(sender.clone(), account).unwrap();
+
+            (secret_key, sender)
+        })
+        .collect();
+
+    // NOTE: the state the first block is built on is only recorded once a
+    // block produced it
+    let (block, proposal) =
+        certified_block_on(0, hex::encode(db.state_root_hash().unwrap().0), vec![]);
+    db.apply_convergence_block(&block, &[proposal]).unwrap();
+
+    let mut built_on = vec![];
+    for (block_height, (secret_key, sender)) in (1..).zip(senders) {
+        let state_root_hash = hex::encode(db.state_root_hash().unwrap().0);
+        built_on.push((
+            state_root_hash.clone(),
+            db.state_store_factory().handle().entries().unwrap(),
+        ));
+
+        let txn = _generate_random_transaction(secret_key, sender, receiver.clone());
+        let (block, proposal) = certified_block_on(block_height, state_root_hash, vec![txn]);
+        store_block(&db, &block, &proposal);
+
+        db.apply_convergence_block(&block, &[proposal]).unwrap();
+    }
+
+    // NOTE: both blocks were applied since, the snapshot is taken from the
+    // state history
+    for (block_height, (state_root_hash, accounts)) in (1..).zip(built_on) {
+        let snapshot = db.export_state(block_height).unwrap();
+
+        assert_eq!(snapshot.block.header.state_root_hash, state_root_hash);
+        assert_eq!(snapshot.accounts.len(), accounts.len());
+
+        let mut imported = new_db();
+        imported.import_state(&snapshot).unwrap();
+
+        assert_eq!(
+            hex::encode(imported.state_root_hash().unwrap().0),
+            state_root_hash
+        );
+        assert_eq!(
+            imported.state_store_factory().handle().entries().unwrap(),
+            accounts
+        );
+    }
+}
+
+#[test]
+#[serial]
+fn heights_without_a_convergence_block_cannot_be_snapshotted() {
+    let db = populated_db();
+    store_certified_block(&db);
+
+    assert!(db.export_state(2).is_err());
+}
+
+#[test]
+#[serial]
+fn uncertified_blocks_cannot_be_snapshotted() {
+    let db = populated_db();
+    let (mut block, proposal) =
+        certified_block_on(1, hex::encode(db.state_root_hash().unwrap().0), vec![]);
+    block.certificate = None;
+    store_block(&db, &block, &proposal);
+
+    assert!(db.export_state(1).is_err());
+}
+
+#[test]
+#[serial]
+fn blocks_not_built_on_a_retained_state_cannot_be_snapshotted() {
+    let db = populated_db();
+    let (block, proposal) = certified_block_on(
+        1,
+        hex::encode(new_db().state_root_hash().unwrap().0),
+        vec![],
+    );
+    store_block(&db, &block, &proposal);
+
+    assert!(db.export_state(1).is_err());
+}
+
+#[test]
+#[serial]
+fn corrupted_snapshots_are_rejected() {
+    let db = populated_db();
+    store_certified_block(&db);
+    let snapshot = db.export_state(1).unwrap();
+
+    let mut encoded = snapshot.encode().unwrap();
+    let last = encoded.len() - 1;
+    encoded[last] ^= 0xff;
+
+    assert!(StateSnapshot::decode(&encoded).is_err());
+}
+
+#[test]
+#[serial]
+fn snapshots_not_matching_their_root_hashes_are_rejected() {
+    let db = populated_db();
+    store_certified_block(&db);
+    let mut snapshot = db.export_state(1).unwrap();
+
+    snapshot.accounts[0]
+        .1
+        .update_field(AccountField::Credits(1))
+        .unwrap();
+
+    let mut imported = new_db();
+
+    assert!(imported.import_state(&snapshot).is_err());
+    assert!(imported
+        .state_store_factory()
+        .handle()
+        .entries()
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+#[serial]
+fn snapshots_whose_block_header_was_altered_are_rejected() {
+    let db = populated_db();
+    store_certified_block(&db);
+    let mut snapshot = db.export_state(1).unwrap();
+
+    // NOTE: the certificate was issued over the hash of the original header
+    snapshot.accounts.clear();
+    snapshot.block.header.state_root_hash = hex::encode(new_db().state_root_hash().unwrap().0);
+
+    assert!(new_db().import_state(&snapshot).is_err());
+}
+
+#[test]
+#[serial]
+fn snapshots_with_proposals_their_block_does_not_reference_are_rejected() {
+    let db = populated_db();
+    store_certified_block(&db);
+    let mut snapshot = db.export_state(1).unwrap();
+
+    let (_, mut proposal) = _build_convergence_block(String::new(), vec![]);
+    proposal.hash = "unreferenced".to_string();
+    snapshot.proposals.push(proposal);
+
+    assert!(new_db().import_state(&snapshot).is_err());
+}
@@ -0,0 +1,124 @@
+use std::{env, path::PathBuf};
+
+use primitives::Address;
+use serial_test::serial;
+use vrrb_core::{
+    account::{Account, AccountField},
+    transactions::BASE_FEE,
+};
+use vrrbdb::{VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::{
+    _build_convergence_block, _generate_random_address, _generate_random_string,
+    _generate_random_transaction,
+};
+
+fn new_db(history_window: usize) -> VrrbDb {
+    open_db(
+        env::temp_dir().join(_generate_random_string()),
+        history_window,
+    )
+}
+
+fn open_db(path: PathBuf, history_window: usize) -> VrrbDb {
+    let mut config = VrrbDbConfig::default().with_path(path);
+    config.history_window = history_window;
+
+    VrrbDb::new(config).unwrap()
+}
+
+/// Funds two senders, then applies one block per sender at heights 1 and 2,
+/// each transferring 100 to `receiver`.
+fn apply_blocks(db: &mut VrrbDb, receiver: &Address) {
+    for block_height in 1..=2 {
+        let (secret_key, sender) = _generate_random_address();
+
+        let mut account = Account::new(sender.clone());
+        account
+            .update_field(AccountField::Credits(BASE_FEE + 1000))
+            .unwrap();
+        db.insert_account(sender.clone(), account).unwrap();
+
+        let txn = _generate_random_transaction(secret_key, sender, receiver.clone());
+        let (mut convergence, proposal) =
+            _build_convergence_block(hex::encode(db.state_root_hash().unwrap().0), vec![txn]);
+        convergence.header.block_height = block_height;
+        convergence.hash = format!("convergence-{block_height}");
+
+        db.apply_convergence_block(&convergence, &[proposal])
+            .unwrap();
+    }
+}
+
+#[test]
+#[serial]
+fn accounts_can_be_read_at_past_blocks() {
+    let mut db = new_db(10);
+    let (_, receiver) = _generate_random_address();
+
+    apply_blocks(&mut db, &receiver);
+
+    let read_handle = db.read_handle();
+
+    let account = read_handle.get_account_at_height(&receiver, 1).unwrap();
+    assert_eq!(account.credits(), 100);
+
+    let account = read_handle.get_account_at_height(&receiver, 2).unwrap();
+    assert_eq!(account.credits(), 200);
+
+    let latest = read_handle.get_account_by_address(&receiver).unwrap();
+    assert_eq!(latest.credits(), 200);
+
+    let first_root = read_handle
+        .state_version_at_height(1)
+        .unwrap()
+        .state_root_hash;
+    let account = read_handle
+        .get_account_at_root(&receiver, &first_root)
+        .unwrap();
+    assert_eq!(account.credits(), 100);
+
+    assert!(read_handle.get_account_at_height(&receiver, 3).is_err());
+}
+
+#[test]
+#[serial]
+fn state_outside_the_history_window_is_forgotten() {
+    let mut db = new_db(1);
+    let (_, receiver) = _generate_random_address();
+
+    apply_blocks(&mut db, &receiver);
+
+    let read_handle = db.read_handle();
+
+    assert!(read_handle.get_account_at_height(&receiver, 1).is_err());
+    assert!(read_handle.get_account_at_height(&receiver, 2).is_ok());
+    assert_eq!(db.history().len(), 1);
+}
+
+#[test]
+#[serial]
+fn history_survives_restarts() {
+    let path = env::temp_dir().join(_generate_random_string());
+    let mut db = open_db(path.clone(), 1);
+    let (_, receiver) = _generate_random_address();
+
+    apply_blocks(&mut db, &receiver);
+    drop(db);
+
+    // NOTE: reopened with a wider window, versions forgotten before the
+    // restart must stay forgotten
+    let db = open_db(path, 10);
+    let read_handle = db.read_handle();
+
+    assert_eq!(db.history().len(), 1);
+    assert!(read_handle.get_account_at_height(&receiver, 1).is_err());
+    assert_eq!(
+        read_handle
+            .get_account_at_height(&receiver, 2)
+            .unwrap()
+            .credits(),
+        200
+    );
+}
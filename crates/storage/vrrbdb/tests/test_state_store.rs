@@ -8,7 +8,7 @@ use serial_test::serial;
 #[test]
 #[serial]
 fn accounts_can_be_added() {
-    let mut db = VrrbDb::new(VrrbDbConfig::default());
+    let mut db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
 
     let (_secret_key, addr1) = _generate_random_address();
     let (_, addr2) = _generate_random_address();
@@ -30,7 +30,8 @@ fn accounts_can_be_added() {
         (addr3.clone(), Some(Account::new(addr3))),
         (addr4.clone(), Some(Account::new(addr4))),
         (addr5.clone(), Some(Account::new(addr5))),
-    ]);
+    ])
+    .unwrap();
 
     let entries = db.state_store_factory().handle().entries().unwrap();
 
@@ -0,0 +1,122 @@
+use std::env;
+
+use primitives::Address;
+use serial_test::serial;
+use vrrb_core::{
+    account::{Account, AccountField},
+    transactions::{Transaction, TransactionDigest, BASE_FEE},
+};
+use vrrbdb::{AccessMode, TransactionLocation, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::{
+    _build_convergence_block, _generate_random_address, _generate_random_string,
+    _generate_random_transaction,
+};
+
+/// Funds a new sender for each of the blocks at heights 1 to 3, each
+/// transferring 100 to `receiver`. Returns the digests of the transfers, in
+/// block order.
+fn apply_blocks(db: &mut VrrbDb, receiver: &Address) -> Vec<(Address, TransactionDigest)> {
+    (1..=3)
+        .map(|block_height| {
+            let (secret_key, sender) = _generate_random_address();
+
+            let mut account = Account::new(sender.clone());
+            account
+                .update_field(AccountField::Credits(BASE_FEE + 1000))
+                .unwrap();
+            db.insert_account(sender.clone(), account).unwrap();
+
+            let txn = _generate_random_transaction(secret_key, sender.clone(), receiver.clone());
+            let digest = txn.id();
+
+            let (mut convergence, proposal) =
+                _build_convergence_block(hex::encode(db.state_root_hash().unwrap().0), vec![txn]);
+            convergence.header.block_height = block_height;
+            convergence.hash = format!("convergence-{block_height}");
+
+            db.apply_convergence_block(&convergence, &[proposal])
+                .unwrap();
+
+            (sender, digest)
+        })
+        .collect()
+}
+
+#[test]
+#[serial]
+fn transactions_are_indexed_by_address() {
+    let path = env::temp_dir().join(_generate_random_string());
+    let mut db = VrrbDb::new(VrrbDbConfig::default().with_path(path)).unwrap();
+    let (_, receiver) = _generate_random_address();
+
+    let transfers = apply_blocks(&mut db, &receiver);
+    let read_handle = db.read_handle();
+
+    assert_eq!(read_handle.get_transaction_count(&receiver).unwrap(), 3);
+
+    let received = read_handle
+        .get_transactions_by_address(&receiver, 0, 10)
+        .unwrap();
+    let digests: Vec<TransactionDigest> = received.iter().map(|txn| txn.digest.clone()).collect();
+    let heights: Vec<u128> = received.iter().map(|txn| txn.block_height).collect();
+
+    assert_eq!(
+        digests,
+        transfers
+            .iter()
+            .map(|(_, digest)| digest.clone())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(heights, vec![1, 2, 3]);
+
+    let page = read_handle
+        .get_transactions_by_address(&receiver, 1, 1)
+        .unwrap();
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].digest, transfers[1].1);
+
+    let (sender, digest) = &transfers[0];
+    assert_eq!(read_handle.get_transaction_count(sender).unwrap(), 1);
+    assert_eq!(
+        read_handle
+            .get_transactions_by_address(sender, 0, 10)
+            .unwrap()[0]
+            .digest,
+        *digest
+    );
+
+    let (_, stranger) = _generate_random_address();
+    assert_eq!(read_handle.get_transaction_count(&stranger).unwrap(), 0);
+}
+
+#[test]
+#[serial]
+fn transactions_are_indexed_by_block() {
+    let path = env::temp_dir().join(_generate_random_string());
+    let config = VrrbDbConfig::default().with_path(path);
+    let (_, receiver) = _generate_random_address();
+
+    let transfers = {
+        let mut db = VrrbDb::new(config.clone()).unwrap();
+        apply_blocks(&mut db, &receiver)
+    };
+
+    let db = VrrbDb::open(config, AccessMode::ReadOnly).unwrap();
+    let read_handle = db.read_handle();
+
+    let (_, digest) = &transfers[1];
+    assert_eq!(
+        read_handle.get_transaction_block(digest).unwrap(),
+        TransactionLocation {
+            block_hash: "convergence-2".to_string(),
+            block_height: 2,
+        }
+    );
+    assert_eq!(read_handle.get_transaction_count(&receiver).unwrap(), 3);
+
+    assert!(read_handle
+        .get_transaction_block(&TransactionDigest::default())
+        .is_err());
+}
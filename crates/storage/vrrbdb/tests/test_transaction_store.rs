@@ -1,7 +1,7 @@
 use std::env;
 
 use serial_test::serial;
-use vrrbdb::{VrrbDb, VrrbDbConfig};
+use vrrbdb::{PruningMode, StorageBackendType, VrrbDb, VrrbDbConfig, DEFAULT_STATE_HISTORY_WINDOW};
 mod common;
 
 use common::{_generate_random_string, _generate_random_valid_transaction};
@@ -18,7 +18,11 @@ fn transactions_can_be_added() {
         transaction_store_path: None,
         event_store_path: None,
         claim_store_path: None,
-    });
+        history_window: DEFAULT_STATE_HISTORY_WINDOW,
+        pruning: PruningMode::default(),
+        backend: StorageBackendType::RocksDb,
+    })
+    .unwrap();
 
     let txn1 = _generate_random_valid_transaction();
     let txn2 = _generate_random_valid_transaction();
@@ -34,7 +38,8 @@ fn transactions_can_be_added() {
         _generate_random_valid_transaction(),
         _generate_random_valid_transaction(),
         _generate_random_valid_transaction(),
-    ]);
+    ])
+    .unwrap();
 
     let entries = db.transaction_store_factory().handle().entries().unwrap();
 
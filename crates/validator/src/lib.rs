@@ -15,10 +15,16 @@ mod tests {
     use rand::{rngs::StdRng, Rng};
     use secp256k1::ecdsa;
     use storage::vrrbdb::{VrrbDb, VrrbDbConfig};
+    use vrrb_core::account::{Account, UpdateArgs};
     use vrrb_core::keypair::KeyPair;
-    use vrrb_core::transactions::{NewTransferArgs, TransactionKind, Transfer};
+    use vrrb_core::transactions::{
+        NewTransferArgs, Transaction, TransactionKind, Transfer, BASE_FEE,
+    };
 
-    use crate::validator_core_manager::ValidatorCoreManager;
+    use crate::{
+        txn_validator::{TxnValidator, TxnValidatorError},
+        validator_core_manager::ValidatorCoreManager,
+    };
 
     // TODO: Use proper txns when there will be proper txn validation
     // implemented
@@ -60,7 +66,7 @@ mod tests {
     #[test]
     fn should_validate_a_list_of_invalid_transactions() {
         let db_config = VrrbDbConfig::default();
-        let db = VrrbDb::new(db_config);
+        let db = VrrbDb::new(db_config).unwrap();
         let mempool = LeftRightMempool::default();
 
         let mut valcore_manager = ValidatorCoreManager::new(
@@ -90,4 +96,95 @@ mod tests {
             valcore_manager.validate(batch, mempool.factory(), db.state_store_factory());
         assert_eq!(validated, target);
     }
+
+    fn temp_db() -> VrrbDb {
+        let path = std::env::temp_dir().join(format!("vrrb-validator-{}", rand::random::<u64>()));
+
+        VrrbDb::new(VrrbDbConfig::default().with_path(path)).unwrap()
+    }
+
+    #[test]
+    fn should_reject_a_replayed_transfer() {
+        let mut db = temp_db();
+        let validator = TxnValidator::new();
+
+        let sender_kp = KeyPair::random();
+        let recv_kp = KeyPair::random();
+        let sender_address = Address::new(*sender_kp.get_miner_public_key());
+        let recv_address = Address::new(*recv_kp.get_miner_public_key());
+
+        let mut sender_account = Account::new(sender_address.clone());
+        sender_account.set_credits(BASE_FEE + 1_000);
+        db.insert_account(sender_address.clone(), sender_account)
+            .unwrap();
+
+        let mut txn = TransactionKind::Transfer(Transfer::new(NewTransferArgs {
+            timestamp: chrono::Utc::now().timestamp(),
+            sender_address: sender_address.clone(),
+            sender_public_key: *sender_kp.get_miner_public_key(),
+            receiver_address: recv_address,
+            token: None,
+            amount: 100,
+            signature: _mock_txn_signature(),
+            validators: Some(HashMap::<String, bool>::new()),
+            nonce: 1,
+        }));
+        txn.sign(sender_kp.get_miner_secret_key());
+
+        assert!(validator.validate(db.state_store_factory(), &txn).is_ok());
+
+        db.update_account(UpdateArgs {
+            address: sender_address,
+            nonce: Some(txn.nonce()),
+            credits: None,
+            debits: Some(txn.amount()),
+            storage: None,
+            package_address: None,
+            digests: None,
+        })
+        .unwrap();
+
+        assert_eq!(
+            validator.validate(db.state_store_factory(), &txn),
+            Err(TxnValidatorError::StaleNonce(1, 2))
+        );
+    }
+
+    #[test]
+    fn should_reject_a_transfer_with_a_nonce_gap() {
+        let mut db = temp_db();
+        let validator = TxnValidator::new();
+
+        let sender_kp = KeyPair::random();
+        let recv_kp = KeyPair::random();
+        let sender_address = Address::new(*sender_kp.get_miner_public_key());
+        let recv_address = Address::new(*recv_kp.get_miner_public_key());
+
+        let mut sender_account = Account::new(sender_address.clone());
+        sender_account.set_credits(BASE_FEE + 1_000);
+        db.insert_account(sender_address.clone(), sender_account)
+            .unwrap();
+
+        let nonce = crate::txn_validator::MAX_NONCE_GAP + 1;
+        let mut txn = TransactionKind::Transfer(Transfer::new(NewTransferArgs {
+            timestamp: chrono::Utc::now().timestamp(),
+            sender_address,
+            sender_public_key: *sender_kp.get_miner_public_key(),
+            receiver_address: recv_address,
+            token: None,
+            amount: 100,
+            signature: _mock_txn_signature(),
+            validators: Some(HashMap::<String, bool>::new()),
+            nonce,
+        }));
+        txn.sign(sender_kp.get_miner_secret_key());
+
+        assert_eq!(
+            validator.validate(db.state_store_factory(), &txn),
+            Err(TxnValidatorError::NonceGap(
+                nonce,
+                crate::txn_validator::MAX_NONCE_GAP
+            ))
+        );
+    }
 }
@@ -1,13 +1,22 @@
 use std::result::Result as StdResult;
 
+use primitives::Address;
 use sha2::{Digest, Sha256};
 use storage::vrrbdb::StateStoreReadHandleFactory;
-use vrrb_core::transactions::{Transaction, TransactionKind};
+use vrrb_core::{
+    staking::StakeUpdate,
+    transactions::{Transaction, TransactionKind, BASE_FEE},
+};
 
 pub type Result<T> = StdResult<T, TxnValidatorError>;
 
 pub const ADDRESS_PREFIX: &str = "0x192";
 
+/// How far ahead of the sender's account nonce a transaction nonce may be.
+/// Allows a sender to have several transactions in flight at once while
+/// rejecting nonces that could never be applied.
+pub const MAX_NONCE_GAP: u128 = 32;
+
 pub enum TxnFees {
     Slow,
     Fast,
@@ -56,6 +65,18 @@ pub enum TxnValidatorError {
     AccountNotFound(String),
     #[error("transaction payload not valid")]
     PayloadInvalid(String),
+
+    #[error("invalid stake transaction: {0}")]
+    InvalidStake(String),
+
+    #[error("nonce {0} has already been used, expected at least {1}")]
+    StaleNonce(u128, u128),
+
+    #[error("nonce {0} is too far ahead of the account nonce, expected at most {1}")]
+    NonceGap(u128, u128),
+
+    #[error("max fee {0} is lower than the base fee {1}")]
+    FeeTooLow(u128, u128),
     #[error("other")]
     Other(String),
 }
@@ -86,12 +107,96 @@ impl TxnValidator {
         state_reader: StateStoreReadHandleFactory,
         txn: &TransactionKind,
     ) -> Result<()> {
-        self.validate_amount(state_reader, txn)
+        self.validate_amount(state_reader.clone(), txn)
+            .and_then(|_| self.validate_nonce(state_reader, txn))
             .and_then(|_| self.validate_public_key(txn))
             //           .and_then(|_| self.validate_sender_address(txn))
             //           .and_then(|_| self.validate_receiver_address(txn))
             .and_then(|_| self.validate_signature(txn))
             .and_then(|_| self.validate_timestamp(txn))
+            .and_then(|_| self.validate_fee(txn))
+            .and_then(|_| self.validate_stake(txn))
+    }
+
+    /// Txn fee validator. A transfer has to pay at least the base fee, so a
+    /// `max_fee` below it can never be included.
+    pub fn validate_fee(&self, txn: &TransactionKind) -> Result<()> {
+        if let TransactionKind::Transfer(transfer) = txn {
+            if let Some(max_fee) = transfer.max_fee {
+                if max_fee < BASE_FEE {
+                    return Err(TxnValidatorError::FeeTooLow(max_fee, BASE_FEE));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validates the `Stake` wrapped by staking, withdrawal and delegation
+    /// transactions. Transfers are left untouched.
+    pub fn validate_stake(&self, txn: &TransactionKind) -> Result<()> {
+        let stake_txn = match txn.stake_transaction() {
+            Some(stake_txn) => stake_txn,
+            None => return Ok(()),
+        };
+
+        let stake = stake_txn.stake();
+        stake
+            .verify()
+            .map_err(|err| TxnValidatorError::InvalidStake(err.to_string()))?;
+
+        // NOTE: stakes are credited back to the account they are posted from
+        // when withdrawn, which has to be the account that signed them
+        if stake.get_sender() != Address::new(stake.get_pubkey()) {
+            return Err(TxnValidatorError::InvalidStake(
+                "stake is not signed by the account it is posted from".to_string(),
+            ));
+        }
+
+        if txn.amount() == 0 {
+            return Err(TxnValidatorError::TxnAmountIncorrect);
+        }
+
+        match (txn, stake.get_amount()) {
+            (TransactionKind::Stake(_), StakeUpdate::Add(_)) if !stake_txn.is_delegation() => {
+                Ok(())
+            }
+            (TransactionKind::Delegate(_), StakeUpdate::Add(_)) if stake_txn.is_delegation() => {
+                Ok(())
+            }
+            (TransactionKind::Withdrawal(_), StakeUpdate::Withdrawal(_)) => Ok(()),
+            _ => Err(TxnValidatorError::InvalidStake(
+                "stake update does not match the transaction kind".to_string(),
+            )),
+        }
+    }
+
+    /// Txn nonce validator. The nonce has to be greater than the nonce of
+    /// the sender's account, which protects against replaying transactions
+    /// that were already applied, and at most `MAX_NONCE_GAP` ahead of it.
+    pub fn validate_nonce(
+        &self,
+        state_reader: StateStoreReadHandleFactory,
+        txn: &TransactionKind,
+    ) -> Result<()> {
+        let address = txn.sender_address();
+        let account = state_reader
+            .handle()
+            .get(&address)
+            .map_err(|_| TxnValidatorError::SenderAddressIncorrect)?;
+
+        let expected = account.nonce() + 1;
+        let max_nonce = account.nonce().saturating_add(MAX_NONCE_GAP);
+
+        if txn.nonce() < expected {
+            return Err(TxnValidatorError::StaleNonce(txn.nonce(), expected));
+        }
+
+        if txn.nonce() > max_nonce {
+            return Err(TxnValidatorError::NonceGap(txn.nonce(), max_nonce));
+        }
+
+        Ok(())
     }
 
     /// Txn signature validator.
@@ -160,8 +265,9 @@ impl TxnValidator {
         }
     }
 
-    /// Txn receiver validator
-    // TODO, to be synchronized with transaction fees.
+    /// Checks that the sender's balance covers the amount sent along with the
+    /// fee paid for the transaction. The fee of a withdrawal is paid out of
+    /// the stake it withdraws first.
     pub fn validate_amount(
         &self,
         state_reader: StateStoreReadHandleFactory,
@@ -172,8 +278,20 @@ impl TxnValidator {
             .handle()
             .get(&address)
             .map_err(|_| TxnValidatorError::SenderAddressIncorrect)?;
+
+        // NOTE: withdrawals credit the withdrawn stake to the sender's account
+        // before debiting their fee, as `UpdateAccount::Unstake` does, so the
+        // balance only covers the part of the fee the stake doesn't
+        let required = match txn {
+            TransactionKind::Withdrawal(_) => txn.fee().saturating_sub(txn.amount()),
+            _ => txn
+                .amount()
+                .checked_add(txn.fee())
+                .ok_or(TxnValidatorError::TxnAmountIncorrect)?,
+        };
+
         if (account.credits() - account.debits())
-            .checked_sub(txn.amount())
+            .checked_sub(required)
             .is_none()
         {
             return Err(TxnValidatorError::TxnAmountIncorrect);
@@ -1,6 +1,6 @@
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
-use primitives::{Address, KademliaPeerId};
+use primitives::{Address, KademliaPeerId, NodeId, PublicKey};
 use serde::{Deserialize, Serialize};
 
 use crate::BootstrapQuorumConfig;
@@ -20,6 +20,13 @@ pub struct BootstrapPeerData {
     pub udp_gossip_addr: SocketAddr,
     pub raptorq_gossip_addr: SocketAddr,
     pub kademlia_liveness_addr: SocketAddr,
+    /// Node ID of the bootstrap node
+    #[serde(default)]
+    pub node_id: Option<NodeId>,
+    /// Key the bootstrap node signs its network messages with. When unset,
+    /// messages from the bootstrap node are dropped until its claim is known
+    #[serde(default)]
+    pub validator_public_key: Option<PublicKey>,
 }
 
 impl Default for BootstrapPeerData {
@@ -30,6 +37,8 @@ impl Default for BootstrapPeerData {
             raptorq_gossip_addr: addr,
             kademlia_liveness_addr: addr,
             udp_gossip_addr: addr,
+            node_id: None,
+            validator_public_key: None,
         }
     }
 }
@@ -1,6 +1,7 @@
 mod bootstrap;
 pub mod bootstrap_quorum;
 mod node_config;
+pub mod peer_scoring_config;
 pub mod quorum;
 pub mod result;
 pub mod test_utils;
@@ -9,6 +10,7 @@ pub mod threshold_config;
 pub use bootstrap::*;
 pub use bootstrap_quorum::*;
 pub use node_config::*;
+pub use peer_scoring_config::*;
 pub use quorum::*;
 pub use result::*;
 pub use test_utils::*;
@@ -73,4 +75,20 @@ mod tests {
         let valid_config = valid_threshold_config();
         valid_config.validate().unwrap();
     }
+
+    #[test]
+    fn default_peer_scoring_config_is_valid() {
+        PeerScoringConfig::default().validate().unwrap();
+    }
+
+    #[test]
+    fn peer_scoring_config_requires_ban_score_below_throttle_score() {
+        let config = PeerScoringConfig {
+            ban_score: -10,
+            throttle_score: -20,
+            ..Default::default()
+        };
+
+        assert!(config.validate().is_err());
+    }
 }
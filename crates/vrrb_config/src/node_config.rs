@@ -5,14 +5,15 @@ use std::{
 };
 
 use derive_builder::Builder;
+use mempool::MempoolConfig;
 use primitives::{KademliaPeerId, NodeId, NodeType, DEFAULT_VRRB_DATA_DIR_PATH};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use vrrb_core::keypair::Keypair;
 
 use crate::{
-    bootstrap::BootstrapConfig, BootstrapPeerData, QuorumMember, QuorumMembershipConfig,
-    ThresholdConfig,
+    bootstrap::BootstrapConfig, BootstrapPeerData, PeerScoringConfig, QuorumMember,
+    QuorumMembershipConfig, ThresholdConfig,
 };
 
 #[derive(Builder, Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
@@ -72,6 +73,12 @@ pub struct NodeConfig {
     /// Address the node listens for JSON-RPC connections
     pub jsonrpc_server_address: SocketAddr,
 
+    #[builder(default)]
+    #[serde(default)]
+    /// Address the node listens for gRPC connections. The gRPC server is
+    /// disabled when unset
+    pub grpc_server_address: Option<SocketAddr>,
+
     // TODO: refactor env-aware options
     #[builder(default = "false")]
     pub preload_mock_state: bool,
@@ -104,8 +111,61 @@ pub struct NodeConfig {
     /// services
     pub enable_block_indexing: bool,
 
+    #[builder(default = "false")]
+    #[serde(default)]
+    /// Persists pending mempool transactions to a journal within db_path so
+    /// they are restored when the node restarts
+    pub enable_mempool_journal: bool,
+
+    #[builder(default)]
+    #[serde(default)]
+    /// Limits on the number, size and lifetime of the transactions held by
+    /// the mempool
+    pub mempool: MempoolConfig,
+
+    #[builder(default)]
+    #[serde(default)]
+    /// Amount transferred from the node's account to the callers of the
+    /// `faucetDrip` JSON-RPC method. The faucet is disabled when unset
+    pub faucet_drip_amount: Option<u128>,
+
+    #[builder(default = "false")]
+    #[serde(default)]
+    /// Keeps every version of the state tries instead of pruning the nodes
+    /// replaced by newer versions
+    pub archive_mode: bool,
+
+    #[builder(default)]
+    #[serde(default)]
+    /// Number of trie versions kept before stale nodes are pruned. Ignored in
+    /// archive mode, the database default is used when unset
+    pub state_pruning_window: Option<u64>,
+
+    #[builder(default)]
+    #[serde(default)]
+    /// Number of applied blocks whose state can still be queried, the
+    /// database default is used when unset
+    pub state_history_window: Option<usize>,
+
+    #[builder(default = "false")]
+    #[serde(default)]
+    /// Keeps the node's state in memory instead of db_path. Nothing survives
+    /// a restart, meant for tests
+    pub in_memory_storage: bool,
+
     pub threshold_config: ThresholdConfig,
 
+    #[builder(default)]
+    #[serde(default)]
+    /// Thresholds used to throttle and ban misbehaving peers
+    pub peer_scoring: PeerScoringConfig,
+
+    #[builder(default = "false")]
+    #[serde(default)]
+    /// Encrypts the events sent to a single peer once a handshake with it
+    /// completed. Broadcasts are signed but never encrypted
+    pub encrypt_direct_messages: bool,
+
     pub whitelisted_nodes: Vec<QuorumMember>,
 
     /// The IP address for binding Prometheus in the Versatus Protocol.
@@ -158,6 +218,7 @@ impl NodeConfig {
             http_api_version: self.http_api_version.clone(),
             http_api_shutdown_timeout: self.http_api_shutdown_timeout,
             jsonrpc_server_address: self.jsonrpc_server_address,
+            grpc_server_address: self.grpc_server_address,
             preload_mock_state: self.preload_mock_state,
             bootstrap_config: self.bootstrap_config.clone(),
             keypair: self.keypair.clone(),
@@ -194,6 +255,7 @@ impl Default for NodeConfig {
             http_api_version: String::from("v.0.1.0"),
             http_api_shutdown_timeout: None,
             jsonrpc_server_address: ipv4_localhost_with_random_port,
+            grpc_server_address: None,
             preload_mock_state: false,
             bootstrap_config: None,
             bootstrap_peer_data: None,
@@ -202,7 +264,16 @@ impl Default for NodeConfig {
             enable_ui: false,
             disable_networking: false,
             threshold_config: ThresholdConfig::default(),
+            peer_scoring: PeerScoringConfig::default(),
+            encrypt_direct_messages: false,
             enable_block_indexing: false,
+            enable_mempool_journal: false,
+            mempool: MempoolConfig::default(),
+            faucet_drip_amount: None,
+            archive_mode: false,
+            state_pruning_window: None,
+            state_history_window: None,
+            in_memory_storage: false,
             whitelisted_nodes: vec![],
             prometheus_bind_addr: String::from("127.0.0.1"),
             prometheus_bind_port: ipv4_localhost_with_random_port.port(),
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+/// Thresholds used to score the peers a node receives network events from.
+/// Peers lose points when they misbehave and get throttled, then banned, as
+/// their score drops.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Eq)]
+#[serde(default)]
+pub struct PeerScoringConfig {
+    /// Length of the window messages are counted in, in milliseconds
+    pub rate_limit_interval_ms: u64,
+
+    /// Messages accepted from a peer within a window
+    pub max_messages_per_interval: u32,
+
+    /// Messages accepted from a throttled peer within a window
+    pub throttled_messages_per_interval: u32,
+
+    /// Points a peer recovers for every window that goes by, up to a score of 0
+    pub score_recovery_per_interval: i64,
+
+    /// Score at or below which a peer is throttled
+    pub throttle_score: i64,
+
+    /// Score at or below which a peer is banned
+    pub ban_score: i64,
+
+    /// How long a ban lasts, in seconds. Peers start over once it's lifted
+    pub ban_duration_secs: u64,
+
+    /// Number of peers tracked at once. The least recently seen peers are
+    /// forgotten first
+    pub max_tracked_peers: usize,
+
+    pub invalid_signature_penalty: i64,
+
+    pub malformed_event_penalty: i64,
+
+    pub duplicate_message_penalty: i64,
+
+    pub rate_limit_penalty: i64,
+
+    /// Number of message digests remembered to drop duplicates
+    pub seen_message_capacity: usize,
+
+    /// How long a message digest is remembered, in milliseconds
+    pub seen_message_ttl_ms: u64,
+}
+
+impl Default for PeerScoringConfig {
+    fn default() -> Self {
+        PeerScoringConfig {
+            rate_limit_interval_ms: 1_000,
+            max_messages_per_interval: 200,
+            throttled_messages_per_interval: 20,
+            score_recovery_per_interval: 1,
+            throttle_score: -50,
+            ban_score: -100,
+            ban_duration_secs: 600,
+            max_tracked_peers: 10_000,
+            invalid_signature_penalty: 40,
+            malformed_event_penalty: 20,
+            duplicate_message_penalty: 1,
+            rate_limit_penalty: 5,
+            seen_message_capacity: 10_000,
+            seen_message_ttl_ms: 120_000,
+        }
+    }
+}
+
+impl PeerScoringConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.rate_limit_interval_ms == 0 || self.max_messages_per_interval == 0 {
+            return Err(ConfigError::Other(
+                "peer rate limit interval and message limit must be greater than 0".to_string(),
+            ));
+        }
+        if self.throttled_messages_per_interval > self.max_messages_per_interval {
+            return Err(ConfigError::Other(format!(
+                "throttled message limit {} > message limit {}",
+                self.throttled_messages_per_interval, self.max_messages_per_interval
+            )));
+        }
+        if self.ban_score >= self.throttle_score || self.throttle_score >= 0 {
+            return Err(ConfigError::Other(format!(
+                "peer ban score {} must be below throttle score {}, which must be below 0",
+                self.ban_score, self.throttle_score
+            )));
+        }
+        Ok(())
+    }
+}
@@ -53,6 +53,12 @@ pub struct Claim {
     pub node_id: NodeId,
     stake: u128,
     stake_txns: Vec<Stake>,
+    /// Key the node signs its network messages with, see
+    /// [`Claim::bind_validator_key`]
+    #[serde(default)]
+    validator_public_key: Option<PublicKey>,
+    #[serde(default)]
+    validator_key_signature: Option<String>,
 }
 
 // TODO: Remove None variant and use Option<Eligibility>.
@@ -83,15 +89,8 @@ impl Claim {
         signature: String,
         node_id: NodeId,
     ) -> Result<Claim> {
-        let mut hasher = Sha256::new();
-        hasher.update(public_key.to_string());
-        hasher.update(ip_address.to_string());
-        let result = hasher.finalize();
-        let hash = U256::from_big_endian(&result[..]);
-        let mut msg_hash: Vec<u8> = Vec::new();
-        hash.0.to_vec().iter().for_each(|x| {
-            msg_hash.extend(x.to_le_bytes().iter());
-        });
+        let hash = Claim::claim_hash(&public_key, &ip_address);
+        let msg_hash = Claim::signed_message_hash(&public_key, &ip_address);
         return match Claim::is_valid_claim(
             msg_hash.as_slice(),
             signature.clone(),
@@ -107,6 +106,8 @@ impl Claim {
                 node_id,
                 stake: 0,
                 stake_txns: vec![],
+                validator_public_key: None,
+                validator_key_signature: None,
             }),
             Err(e) => Err(e),
         };
@@ -138,15 +139,7 @@ impl Claim {
         ip_address: SocketAddr,
         secret_key: SerializedSecretKey,
     ) -> Result<String> {
-        let mut hasher = Sha256::new();
-        hasher.update(public_key.to_string());
-        hasher.update(ip_address.to_string());
-        let result = hasher.finalize();
-        let hash = U256::from_big_endian(&result[..]);
-        let mut msg_hash: Vec<u8> = Vec::new();
-        hash.0.to_vec().iter().for_each(|x| {
-            msg_hash.extend(x.to_le_bytes().iter());
-        });
+        let msg_hash = Claim::signed_message_hash(&public_key, &ip_address);
         Keypair::ecdsa_sign(msg_hash.as_slice(), secret_key).map_err(ClaimError::from)
     }
 
@@ -175,6 +168,111 @@ impl Claim {
         Keypair::verify_ecdsa_sign(signature, msg_hash, pub_key).map_err(ClaimError::from)
     }
 
+    /// Verifies the claim's signature over its public key and IP address
+    pub fn verify_signature(&self) -> Result<()> {
+        let msg_hash = Claim::signed_message_hash(&self.public_key, &self.ip_address);
+        Claim::is_valid_claim(
+            msg_hash.as_slice(),
+            self.signature.clone(),
+            self.public_key.serialize().to_vec(),
+        )
+    }
+
+    /// Hash of the public key and IP address a claim is identified by
+    fn claim_hash(public_key: &PublicKey, ip_address: &SocketAddr) -> U256 {
+        let mut hasher = Sha256::new();
+        hasher.update(public_key.to_string());
+        hasher.update(ip_address.to_string());
+        let result = hasher.finalize();
+
+        U256::from_big_endian(&result[..])
+    }
+
+    /// Hash of the public key and IP address a claim's signature is made over
+    fn signed_message_hash(public_key: &PublicKey, ip_address: &SocketAddr) -> Vec<u8> {
+        let hash = Claim::claim_hash(public_key, ip_address);
+        let mut msg_hash: Vec<u8> = Vec::new();
+        hash.0.to_vec().iter().for_each(|x| {
+            msg_hash.extend(x.to_le_bytes().iter());
+        });
+
+        msg_hash
+    }
+
+    /// Signs the abandonment of this claim with the secret key matching its
+    /// public key.
+    pub fn sign_abandonment(&self, secret_key: SerializedSecretKey) -> Result<String> {
+        let msg_hash = self.abandonment_message_hash();
+        Keypair::ecdsa_sign(msg_hash.as_slice(), secret_key).map_err(ClaimError::from)
+    }
+
+    /// Verifies that `signature` was made by the claim's key over the
+    /// abandonment of this claim
+    pub fn verify_abandonment(&self, signature: String) -> Result<()> {
+        let msg_hash = self.abandonment_message_hash();
+        Claim::is_valid_claim(
+            msg_hash.as_slice(),
+            signature,
+            self.public_key.serialize().to_vec(),
+        )
+    }
+
+    /// Hash of the claim hash an abandonment signature is made over, domain
+    /// separated from the claim's own signature
+    fn abandonment_message_hash(&self) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(b"abandon");
+        hasher.update(self.public_key.to_string());
+        hasher.update(self.hash.to_string());
+        hasher.finalize().to_vec()
+    }
+
+    /// Binds the validator key the node signs its network messages with to
+    /// this claim, signing it along with the node ID with the secret key
+    /// matching the claim's public key.
+    pub fn bind_validator_key(
+        &mut self,
+        validator_public_key: PublicKey,
+        secret_key: SerializedSecretKey,
+    ) -> Result<()> {
+        let msg_hash = self.validator_key_message_hash(&validator_public_key);
+        let signature =
+            Keypair::ecdsa_sign(msg_hash.as_slice(), secret_key).map_err(ClaimError::from)?;
+
+        self.validator_public_key = Some(validator_public_key);
+        self.validator_key_signature = Some(signature);
+
+        Ok(())
+    }
+
+    /// Validator key bound to this claim, if the binding was signed by the
+    /// claim's key
+    pub fn validator_key(&self) -> Option<PublicKey> {
+        let validator_public_key = self.validator_public_key?;
+        let signature = self.validator_key_signature.clone()?;
+        let msg_hash = self.validator_key_message_hash(&validator_public_key);
+
+        Claim::is_valid_claim(
+            msg_hash.as_slice(),
+            signature,
+            self.public_key.serialize().to_vec(),
+        )
+        .ok()?;
+
+        Some(validator_public_key)
+    }
+
+    /// Hash of the node ID and validator key a binding signature is made
+    /// over, domain separated from the claim's other signatures
+    fn validator_key_message_hash(&self, validator_public_key: &PublicKey) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(b"validator");
+        hasher.update(self.node_id.as_bytes());
+        hasher.update(validator_public_key.to_string());
+        hasher.update(self.hash.to_string());
+        hasher.finalize().to_vec()
+    }
+
     /// This function updates the IP address of a claim and verifies its
     /// validity using a signature and public key.
     ///
@@ -200,15 +298,7 @@ impl Claim {
         public_key: PublicKey,
         ip_address: SocketAddr,
     ) -> Result<()> {
-        let mut hasher = Sha256::new();
-        hasher.update(public_key.to_string());
-        hasher.update(ip_address.to_string());
-        let result = hasher.finalize();
-        let hash = U256::from_big_endian(&result[..]);
-        let mut msg_hash: Vec<u8> = Vec::new();
-        hash.0.to_vec().iter().for_each(|x| {
-            msg_hash.extend(x.to_le_bytes().iter());
-        });
+        let msg_hash = Claim::signed_message_hash(&public_key, &ip_address);
         Claim::is_valid_claim(
             msg_hash.as_slice(),
             signature,
@@ -243,13 +333,7 @@ impl Claim {
         }
 
         if stake_txn.get_certificate().is_some() {
-            let prev_stake = self.stake;
-            self.stake_txns.push(stake_txn);
-            self.stake = self.check_stake_utxo();
-
-            if self.stake == prev_stake {
-                self.stake_txns.pop();
-            }
+            self.push_stake_txn(stake_txn);
 
             return Ok(());
         }
@@ -257,8 +341,66 @@ impl Claim {
         Err(StakeError::UncertifiedStake)
     }
 
+    /// Applies a stake that was submitted as a `TransactionKind::Stake`,
+    /// `Withdrawal` or `Delegate` transaction. Those are certified by a
+    /// farmer quorum as a whole and vouched for by the certificate of the
+    /// convergence block that includes them, so unlike `update_stake` the
+    /// stake itself is not required to carry a threshold certificate.
+    pub fn apply_quorum_certified_stake(&mut self, stake_txn: Stake) -> crate::staking::Result<()> {
+        if !self.depositing_claim(&stake_txn) {
+            return Err(StakeError::Other(
+                "This claim is not the intended receiver of the stake transaction".to_string(),
+            ));
+        }
+
+        let staker = stake_txn.get_sender();
+        if staker != Address::new(stake_txn.get_pubkey()) {
+            return Err(StakeError::Other(
+                "the stake was not signed by the account it is posted from".to_string(),
+            ));
+        }
+
+        match stake_txn.get_amount() {
+            StakeUpdate::Withdrawal(amount) if amount > self.stake_of(&staker) => {
+                return Err(StakeError::Other(format!(
+                    "cannot withdraw {amount} from a contribution of {} to the claim",
+                    self.stake_of(&staker)
+                )));
+            }
+            StakeUpdate::Slash(_) => {
+                return Err(StakeError::Other(
+                    "slashing cannot be applied through a transaction".to_string(),
+                ));
+            }
+            _ => {}
+        }
+
+        stake_txn.verify()?;
+        self.push_stake_txn(stake_txn);
+
+        Ok(())
+    }
+
+    fn push_stake_txn(&mut self, stake_txn: Stake) {
+        // NOTE: a stake that was already applied to the claim is ignored so
+        // replaying a block or a transaction cannot count it twice
+        if self.stake_txns.contains(&stake_txn) {
+            return;
+        }
+
+        let prev_stake = self.stake;
+        self.stake_txns.push(stake_txn);
+        self.stake = self.check_stake_utxo();
+
+        if self.stake == prev_stake {
+            self.stake_txns.pop();
+        }
+    }
+
+    /// A claim receives a stake if it is either the claim of the staker or
+    /// the claim the stake was delegated to.
     fn depositing_claim(&self, stake_txn: &Stake) -> bool {
-        stake_txn.get_sender() == self.address
+        stake_txn.get_receiver() == self.address
     }
 
     /// Checks the cumulative value of a nodes stake by calculating
@@ -294,6 +436,23 @@ impl Claim {
         self.stake
     }
 
+    /// Returns the part of the claim's stake contributed by `staker`, which
+    /// is the most it can withdraw. Slashes cut every contribution alike.
+    pub fn stake_of(&self, staker: &Address) -> u128 {
+        self.stake_txns
+            .iter()
+            .fold(0u128, |acc, val| match val.get_amount() {
+                StakeUpdate::Add(value) if val.get_sender() == *staker => {
+                    acc.checked_add(value).unwrap_or(acc)
+                }
+                StakeUpdate::Withdrawal(value) if val.get_sender() == *staker => {
+                    acc.checked_sub(value).unwrap_or(acc)
+                }
+                StakeUpdate::Slash(pct) => self.slash_calculator(pct, acc),
+                _ => acc,
+            })
+    }
+
     pub fn get_stake_txns(&self) -> Vec<Stake> {
         self.stake_txns.clone()
     }
@@ -450,6 +609,8 @@ mod tests {
             node_id: NodeId::default(),
             stake: 0,
             stake_txns: vec![],
+            validator_public_key: None,
+            validator_key_signature: None,
         };
         let claim = Claim::new(
             public_key,
@@ -501,6 +662,33 @@ mod tests {
         assert_eq!(claim.ip_address, ip_address_new);
     }
 
+    #[test]
+    fn claim_signature_covers_its_ip_address() {
+        let kp = KeyPair::random();
+        let public_key = kp.miner_kp.1;
+        let address = Address::new(public_key);
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            kp.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+        let mut claim = Claim::new(
+            public_key,
+            address,
+            ip_address,
+            signature,
+            NodeId::default(),
+        )
+        .unwrap();
+
+        assert!(claim.verify_signature().is_ok());
+
+        claim.ip_address = "127.0.0.1:8081".parse().unwrap();
+        assert!(claim.verify_signature().is_err());
+    }
+
     #[test]
     fn stake_should_be_zero_by_default() {
         let kp = KeyPair::random();
@@ -898,4 +1086,259 @@ mod tests {
         assert_eq!(claim.get_stake(), 90_000u128);
         assert_eq!(claim.get_stake_txns().len(), 2);
     }
+
+    #[test]
+    fn should_apply_quorum_certified_stake_to_claim() {
+        let kp = KeyPair::random();
+        let public_key = kp.miner_kp.1;
+        let address = Address::new(public_key);
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            kp.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+        let mut claim = Claim::new(
+            public_key,
+            address.clone(),
+            ip_address,
+            signature,
+            NodeId::default(),
+        )
+        .unwrap();
+
+        let stake = Stake::new(
+            StakeUpdate::Add(10_000u128),
+            kp.miner_kp.0,
+            kp.miner_kp.1,
+            address.clone(),
+            None,
+        )
+        .unwrap();
+        assert!(claim.apply_quorum_certified_stake(stake).is_ok());
+        assert_eq!(claim.get_stake(), 10_000u128);
+
+        let stake = Stake::new(
+            StakeUpdate::Withdrawal(20_000u128),
+            kp.miner_kp.0,
+            kp.miner_kp.1,
+            address,
+            None,
+        )
+        .unwrap();
+        assert!(claim.apply_quorum_certified_stake(stake).is_err());
+        assert_eq!(claim.get_stake(), 10_000u128);
+    }
+
+    #[test]
+    fn should_apply_delegated_stake_to_receiving_claim() {
+        let kp = KeyPair::random();
+        let public_key = kp.miner_kp.1;
+        let address = Address::new(public_key);
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            kp.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+        let mut claim = Claim::new(
+            public_key,
+            address.clone(),
+            ip_address,
+            signature,
+            NodeId::default(),
+        )
+        .unwrap();
+
+        let delegator = KeyPair::random();
+        let delegator_address = Address::new(delegator.miner_kp.1);
+        let stake = Stake::new(
+            StakeUpdate::Add(10_000u128),
+            delegator.miner_kp.0,
+            delegator.miner_kp.1,
+            delegator_address,
+            Some(address),
+        )
+        .unwrap();
+
+        assert!(claim.apply_quorum_certified_stake(stake).is_ok());
+        assert_eq!(claim.get_stake(), 10_000u128);
+    }
+
+    #[test]
+    fn should_only_withdraw_stake_contributed_by_the_sender() {
+        let kp = KeyPair::random();
+        let public_key = kp.miner_kp.1;
+        let address = Address::new(public_key);
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            kp.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+        let mut claim = Claim::new(
+            public_key,
+            address.clone(),
+            ip_address,
+            signature,
+            NodeId::default(),
+        )
+        .unwrap();
+
+        let delegator = KeyPair::random();
+        let delegator_address = Address::new(delegator.miner_kp.1);
+        let stake = Stake::new(
+            StakeUpdate::Add(10_000u128),
+            delegator.miner_kp.0,
+            delegator.miner_kp.1,
+            delegator_address.clone(),
+            Some(address.clone()),
+        )
+        .unwrap();
+        assert!(claim.apply_quorum_certified_stake(stake).is_ok());
+
+        let stake = Stake::new(
+            StakeUpdate::Add(20_000u128),
+            kp.miner_kp.0,
+            kp.miner_kp.1,
+            address.clone(),
+            None,
+        )
+        .unwrap();
+        assert!(claim.apply_quorum_certified_stake(stake).is_ok());
+        assert_eq!(claim.get_stake(), 30_000u128);
+        assert_eq!(claim.stake_of(&delegator_address), 10_000u128);
+        assert_eq!(claim.stake_of(&address), 20_000u128);
+
+        // NOTE: a third party with no stake in the claim cannot withdraw any of it
+        let thief = KeyPair::random();
+        let stake = Stake::new(
+            StakeUpdate::Withdrawal(5_000u128),
+            thief.miner_kp.0,
+            thief.miner_kp.1,
+            Address::new(thief.miner_kp.1),
+            Some(address.clone()),
+        )
+        .unwrap();
+        assert!(claim.apply_quorum_certified_stake(stake).is_err());
+
+        // NOTE: nor can it withdraw on behalf of a staker
+        let stake = Stake::new(
+            StakeUpdate::Withdrawal(5_000u128),
+            thief.miner_kp.0,
+            thief.miner_kp.1,
+            delegator_address.clone(),
+            Some(address.clone()),
+        )
+        .unwrap();
+        assert!(claim.apply_quorum_certified_stake(stake).is_err());
+
+        // NOTE: the delegator cannot withdraw the claim owner's stake either
+        let stake = Stake::new(
+            StakeUpdate::Withdrawal(15_000u128),
+            delegator.miner_kp.0,
+            delegator.miner_kp.1,
+            delegator_address.clone(),
+            Some(address.clone()),
+        )
+        .unwrap();
+        assert!(claim.apply_quorum_certified_stake(stake).is_err());
+        assert_eq!(claim.get_stake(), 30_000u128);
+
+        let stake = Stake::new(
+            StakeUpdate::Withdrawal(10_000u128),
+            delegator.miner_kp.0,
+            delegator.miner_kp.1,
+            delegator_address.clone(),
+            Some(address),
+        )
+        .unwrap();
+        assert!(claim.apply_quorum_certified_stake(stake).is_ok());
+        assert_eq!(claim.get_stake(), 20_000u128);
+        assert_eq!(claim.stake_of(&delegator_address), 0);
+    }
+
+    #[test]
+    fn only_the_claim_key_can_abandon_a_claim() {
+        let kp = KeyPair::random();
+        let public_key = kp.miner_kp.1;
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            kp.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+        let claim = Claim::new(
+            public_key,
+            Address::new(public_key),
+            ip_address,
+            signature.clone(),
+            NodeId::default(),
+        )
+        .unwrap();
+
+        let abandonment = claim
+            .sign_abandonment(kp.get_miner_secret_key().secret_bytes().to_vec())
+            .unwrap();
+        assert!(claim.verify_abandonment(abandonment).is_ok());
+
+        // NOTE: the claim's own signature does not abandon it
+        assert!(claim.verify_abandonment(signature).is_err());
+
+        let other = KeyPair::random();
+        let forged = claim
+            .sign_abandonment(other.get_miner_secret_key().secret_bytes().to_vec())
+            .unwrap();
+        assert!(claim.verify_abandonment(forged).is_err());
+    }
+
+    #[test]
+    fn only_the_claim_key_can_bind_a_validator_key() {
+        let kp = KeyPair::random();
+        let public_key = kp.miner_kp.1;
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            kp.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+        let mut claim = Claim::new(
+            public_key,
+            Address::new(public_key),
+            ip_address,
+            signature,
+            "node-a".to_string(),
+        )
+        .unwrap();
+        assert_eq!(claim.validator_key(), None);
+
+        let validator_public_key = kp.validator_public_key_owned();
+        claim
+            .bind_validator_key(
+                validator_public_key,
+                kp.get_miner_secret_key().secret_bytes().to_vec(),
+            )
+            .unwrap();
+        assert_eq!(claim.validator_key(), Some(validator_public_key));
+
+        // NOTE: the binding covers the node ID
+        let mut renamed = claim.clone();
+        renamed.node_id = "node-b".to_string();
+        assert_eq!(renamed.validator_key(), None);
+
+        let other = KeyPair::random();
+        let mut forged = claim.clone();
+        forged
+            .bind_validator_key(
+                other.validator_public_key_owned(),
+                other.get_miner_secret_key().secret_bytes().to_vec(),
+            )
+            .unwrap();
+        assert_eq!(forged.validator_key(), None);
+    }
 }
@@ -1,7 +1,9 @@
+pub mod stake;
 pub mod transaction;
 pub mod transaction_kind;
 pub mod transfer;
 
+pub use stake::*;
 pub use transaction::*;
 pub use transaction_kind::*;
 pub use transfer::*;
@@ -0,0 +1,327 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+use ethereum_types::U256;
+use primitives::{Address, ByteVec, PublicKey, SecretKey};
+use secp256k1::{ecdsa::Signature, Message};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utils::hash_data;
+
+use crate::staking::{Stake, StakeUpdate};
+use crate::transactions::transaction::Transaction;
+use crate::transactions::{
+    Token, TransactionDigest, TransactionKind, TxAmount, TxNonce, TxTimestamp, BASE_FEE,
+};
+
+/// Produces the digest of a stake transaction out of the payload of the
+/// wrapped `Stake`, the hash of the claim it is applied to and the sender's
+/// account nonce.
+pub fn generate_stake_digest_vec(stake: &Stake, claim_hash: U256, nonce: TxNonce) -> ByteVec {
+    let mut claim_hash_bytes = [0u8; 32];
+    claim_hash.to_big_endian(&mut claim_hash_bytes);
+
+    let mut hasher = Sha256::new();
+    hasher.update(stake.get_payload());
+    hasher.update(claim_hash_bytes);
+    hasher.update(nonce.to_be_bytes());
+    let hash = hasher.finalize();
+
+    hash.to_vec()
+}
+
+/// A transaction that carries a `Stake` through the transaction pipeline so
+/// it can be validated, certified by a farmer quorum and applied to the
+/// `ClaimStore` once it is included in a `ConvergenceBlock`.
+///
+/// The same struct backs the `Stake`, `Withdrawal` and `Delegate` variants of
+/// `TransactionKind`; the variant is derived from the wrapped `StakeUpdate`
+/// and its receiver when built via `StakeTransactionBuilder::build_kind`.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq)]
+pub struct StakeTransaction {
+    pub id: TransactionDigest,
+    pub stake: Stake,
+    /// Hash of the claim the stake is applied to, which has to be owned by
+    /// the stake's receiver
+    pub claim_hash: U256,
+    pub signature: Signature,
+    pub validators: Option<HashMap<String, bool>>,
+    pub nonce: TxNonce,
+}
+
+#[derive(Clone, Default)]
+pub struct StakeTransactionBuilder {
+    stake: Option<Stake>,
+    claim_hash: Option<U256>,
+    signature: Option<Signature>,
+    validators: Option<HashMap<String, bool>>,
+    nonce: Option<TxNonce>,
+}
+
+impl StakeTransactionBuilder {
+    pub fn stake(mut self, stake: Stake) -> Self {
+        self.stake = Some(stake);
+        self
+    }
+
+    pub fn claim_hash(mut self, claim_hash: U256) -> Self {
+        self.claim_hash = Some(claim_hash);
+        self
+    }
+
+    pub fn signature(mut self, signature: Signature) -> Self {
+        self.signature = Some(signature);
+        self
+    }
+
+    pub fn validators(mut self, validators: HashMap<String, bool>) -> Self {
+        self.validators = Some(validators);
+        self
+    }
+
+    pub fn nonce(mut self, nonce: TxNonce) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    pub fn build(self) -> Result<StakeTransaction, &'static str> {
+        let stake = self.stake.ok_or("stake is missing")?;
+        let claim_hash = self.claim_hash.ok_or("claim hash is missing")?;
+        let nonce = self.nonce.ok_or("nonce is missing")?;
+        let id = generate_stake_digest_vec(&stake, claim_hash, nonce);
+
+        Ok(StakeTransaction {
+            id: TransactionDigest::from(id),
+            stake,
+            claim_hash,
+            signature: self.signature.ok_or("signature is missing")?,
+            validators: self.validators,
+            nonce,
+        })
+    }
+
+    /// Builds the transaction and wraps it in the `TransactionKind` variant
+    /// matching the wrapped `StakeUpdate`.
+    pub fn build_kind(self) -> Result<TransactionKind, &'static str> {
+        let txn = self.build()?;
+
+        match txn.stake.get_amount() {
+            StakeUpdate::Add(_) if txn.is_delegation() => Ok(TransactionKind::Delegate(txn)),
+            StakeUpdate::Add(_) => Ok(TransactionKind::Stake(txn)),
+            StakeUpdate::Withdrawal(_) => Ok(TransactionKind::Withdrawal(txn)),
+            StakeUpdate::Slash(_) => Err("slashing cannot be submitted as a transaction"),
+        }
+    }
+}
+
+impl StakeTransaction {
+    pub fn builder() -> StakeTransactionBuilder {
+        StakeTransactionBuilder::default()
+    }
+
+    /// Returns the wrapped `Stake`
+    pub fn stake(&self) -> &Stake {
+        &self.stake
+    }
+
+    /// Returns true if the stake is directed to a claim other than the one
+    /// owned by the sender.
+    pub fn is_delegation(&self) -> bool {
+        self.stake.get_sender() != self.stake.get_receiver()
+    }
+
+    pub fn generate_txn_digest_vec(&self) -> ByteVec {
+        generate_stake_digest_vec(&self.stake, self.claim_hash, self.nonce)
+    }
+
+    /// Returns the hash of the claim the stake is applied to
+    pub fn claim_hash(&self) -> U256 {
+        self.claim_hash
+    }
+
+    /// Serializes the transation into a byte array
+    pub fn encode(&self) -> Vec<u8> {
+        bincode::serialize(self).unwrap_or_default()
+    }
+}
+
+impl Transaction for StakeTransaction {
+    fn id(&self) -> TransactionDigest {
+        self.id.clone()
+    }
+
+    fn timestamp(&self) -> TxTimestamp {
+        self.stake.get_timestamp()
+    }
+
+    fn sender_address(&self) -> Address {
+        self.stake.get_sender()
+    }
+
+    fn sender_public_key(&self) -> PublicKey {
+        self.stake.get_pubkey()
+    }
+
+    fn receiver_address(&self) -> Address {
+        self.stake.get_receiver()
+    }
+
+    fn token(&self) -> Token {
+        Token::default()
+    }
+
+    fn amount(&self) -> TxAmount {
+        match self.stake.get_amount() {
+            StakeUpdate::Add(amount) | StakeUpdate::Withdrawal(amount) => amount,
+            StakeUpdate::Slash(_) => 0,
+        }
+    }
+
+    fn signature(&self) -> Signature {
+        self.signature
+    }
+
+    fn validators(&self) -> Option<HashMap<String, bool>> {
+        self.validators.clone()
+    }
+
+    fn nonce(&self) -> TxNonce {
+        self.nonce
+    }
+
+    fn fee(&self) -> u128 {
+        BASE_FEE
+    }
+
+    fn validator_fee_share(&self) -> u128 {
+        BASE_FEE / 2u128
+    }
+
+    fn proposer_fee_share(&self) -> u128 {
+        BASE_FEE / 2u128
+    }
+
+    fn build_payload(&self) -> String {
+        format!(
+            "{:x}",
+            hash_data!(self.stake.get_payload(), self.claim_hash, self.nonce)
+        )
+    }
+
+    fn digest(&self) -> TransactionDigest {
+        self.id()
+    }
+
+    fn sign(&mut self, sk: &SecretKey) {
+        let mut hasher = sha2::Sha256::new();
+        hasher.update(self.build_payload().as_bytes());
+        let result = hasher.finalize().to_vec();
+        let message = Message::from_slice(&result);
+        if let Ok(msg) = message {
+            let sig = sk.sign_ecdsa(msg);
+            self.signature = sig;
+        }
+    }
+}
+
+impl fmt::Display for StakeTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let txn_ser = serde_json::to_string_pretty(self).unwrap_or_default();
+
+        write!(f, "{}", txn_ser)
+    }
+}
+
+impl From<StakeTransaction> for TransactionDigest {
+    fn from(txn: StakeTransaction) -> Self {
+        txn.id()
+    }
+}
+
+impl Hash for StakeTransaction {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.stake.hash(state);
+        self.claim_hash.hash(state);
+        self.signature.hash(state);
+        self.nonce.hash(state);
+    }
+}
+
+impl PartialEq for StakeTransaction {
+    fn eq(&self, other: &Self) -> bool {
+        self.generate_txn_digest_vec() == other.generate_txn_digest_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keypair::KeyPair;
+
+    fn signed_stake_txn(amount: StakeUpdate, to: Option<Address>) -> TransactionKind {
+        let keypair = KeyPair::random();
+        let sk = keypair.miner_kp.0;
+        let pk = keypair.miner_kp.1;
+        let from = Address::new(pk);
+        let stake = Stake::new(amount, sk, pk, from, to).unwrap();
+        let signature = sk.sign_ecdsa(Message::from_slice(&[1u8; 32]).unwrap());
+
+        let mut txn = StakeTransaction::builder()
+            .stake(stake)
+            .claim_hash(U256::from(1))
+            .nonce(1)
+            .signature(signature)
+            .build_kind()
+            .unwrap();
+
+        txn.sign(&sk);
+        txn
+    }
+
+    #[test]
+    fn should_build_stake_variant_for_own_claim() {
+        let txn = signed_stake_txn(StakeUpdate::Add(10_000), None);
+
+        assert!(matches!(txn, TransactionKind::Stake(_)));
+        assert_eq!(txn.amount(), 10_000);
+        assert_eq!(txn.sender_address(), txn.receiver_address());
+    }
+
+    #[test]
+    fn should_build_delegate_variant_for_other_claim() {
+        let receiver = Address::new(KeyPair::random().miner_kp.1);
+        let txn = signed_stake_txn(StakeUpdate::Add(10_000), Some(receiver.clone()));
+
+        assert!(matches!(txn, TransactionKind::Delegate(_)));
+        assert_eq!(txn.receiver_address(), receiver);
+    }
+
+    #[test]
+    fn should_build_withdrawal_variant() {
+        let txn = signed_stake_txn(StakeUpdate::Withdrawal(5_000), None);
+
+        assert!(matches!(txn, TransactionKind::Withdrawal(_)));
+        assert_eq!(txn.amount(), 5_000);
+    }
+
+    #[test]
+    fn should_not_build_slash_as_transaction() {
+        let keypair = KeyPair::random();
+        let sk = keypair.miner_kp.0;
+        let pk = keypair.miner_kp.1;
+        let stake = Stake::new(StakeUpdate::Slash(10), sk, pk, Address::new(pk), None).unwrap();
+        let signature = sk.sign_ecdsa(Message::from_slice(&[1u8; 32]).unwrap());
+
+        let result = StakeTransaction::builder()
+            .stake(stake)
+            .claim_hash(U256::from(1))
+            .nonce(1)
+            .signature(signature)
+            .build_kind();
+
+        assert!(result.is_err());
+    }
+}
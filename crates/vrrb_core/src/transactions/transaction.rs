@@ -5,8 +5,8 @@ use primitives::{
     SecretKey, Signature, DIGEST_LENGTH,
 };
 use serde::{Deserialize, Serialize};
-use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap};
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::hash::Hash;
@@ -34,6 +34,60 @@ pub trait Transaction {
     fn sign(&mut self, sk: &SecretKey);
 }
 
+/// Orders `items` by the fee paid by their transaction, highest first, while
+/// keeping the transactions of every sender in ascending nonce order, so a
+/// transaction never comes before one of its sender with a lower nonce.
+///
+/// A sender's transactions compete on the fee of the lowest nonce one still
+/// left, and ties are settled by the position of the items in `items`.
+pub fn order_by_fee_and_nonce<T, F>(items: impl IntoIterator<Item = T>, txn: F) -> Vec<T>
+where
+    F: Fn(&T) -> &TransactionKind,
+{
+    let mut queues: HashMap<Address, Vec<(usize, T)>> = HashMap::new();
+    let mut len = 0;
+    for (position, item) in items.into_iter().enumerate() {
+        queues
+            .entry(txn(&item).sender_address())
+            .or_default()
+            .push((position, item));
+        len += 1;
+    }
+
+    // NOTE: queues are sorted by descending nonce so that the next
+    // transaction of every sender sits at the end of its queue
+    let mut heads = BinaryHeap::new();
+    for (sender, queue) in queues.iter_mut() {
+        queue.sort_by(|(a_position, a), (b_position, b)| {
+            txn(b)
+                .nonce()
+                .cmp(&txn(a).nonce())
+                .then_with(|| b_position.cmp(a_position))
+        });
+
+        if let Some((position, next)) = queue.last() {
+            heads.push((txn(next).fee(), Reverse(*position), sender.clone()));
+        }
+    }
+
+    let mut ordered = Vec::with_capacity(len);
+    while let Some((_, _, sender)) = heads.pop() {
+        let Some(queue) = queues.get_mut(&sender) else {
+            continue;
+        };
+
+        if let Some((_, item)) = queue.pop() {
+            ordered.push(item);
+        }
+
+        if let Some((position, next)) = queue.last() {
+            heads.push((txn(next).fee(), Reverse(*position), sender));
+        }
+    }
+
+    ordered
+}
+
 // TODO: Replace with `secp256k1::Message` struct or guarantee
 // that it is a stringified version of `secp256k1::Message`
 pub type TxPayload = String;
@@ -131,7 +185,7 @@ impl QuorumCertifiedTxn {
 
 pub type RpcTransactionDigest = String;
 
-#[derive(Debug, Default, Clone, Hash, Deserialize, Serialize, Eq, PartialEq)]
+#[derive(Debug, Default, Clone, Hash, Deserialize, Serialize, Eq, PartialEq, PartialOrd, Ord)]
 pub struct TransactionDigest {
     inner: PrimitiveDigest,
     digest_string: RpcTransactionDigest,
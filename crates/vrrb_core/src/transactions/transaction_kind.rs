@@ -1,6 +1,6 @@
 use crate::transactions::{
-    Token, Transaction, TransactionDigest, Transfer, TransferBuilder, TxAmount, TxNonce,
-    TxTimestamp,
+    StakeTransaction, StakeTransactionBuilder, Token, Transaction, TransactionDigest, Transfer,
+    TransferBuilder, TxAmount, TxNonce, TxTimestamp,
 };
 use primitives::{Address, PublicKey, SecretKey, Signature};
 use serde::{Deserialize, Serialize};
@@ -9,12 +9,38 @@ use std::collections::HashMap;
 #[derive(Hash, Debug, Deserialize, Clone, Serialize, Eq, PartialEq)]
 pub enum TransactionKind {
     Transfer(Transfer),
+    /// Adds stake to the claim owned by the sender
+    Stake(StakeTransaction),
+    /// Withdraws stake from the claim owned by the sender
+    Withdrawal(StakeTransaction),
+    /// Adds stake to a claim owned by another node
+    Delegate(StakeTransaction),
 }
 
 impl TransactionKind {
     pub fn transfer_builder() -> TransferBuilder {
         Transfer::builder()
     }
+
+    pub fn stake_builder() -> StakeTransactionBuilder {
+        StakeTransaction::builder()
+    }
+
+    /// Returns the inner `StakeTransaction` if this is a staking, withdrawal
+    /// or delegation transaction.
+    pub fn stake_transaction(&self) -> Option<&StakeTransaction> {
+        match self {
+            TransactionKind::Stake(txn)
+            | TransactionKind::Withdrawal(txn)
+            | TransactionKind::Delegate(txn) => Some(txn),
+            TransactionKind::Transfer(_) => None,
+        }
+    }
+
+    /// Returns true if this transaction updates the stake of a claim
+    pub fn is_stake_update(&self) -> bool {
+        self.stake_transaction().is_some()
+    }
 }
 
 impl Default for TransactionKind {
@@ -27,96 +53,144 @@ impl Transaction for TransactionKind {
     fn id(&self) -> TransactionDigest {
         match self {
             TransactionKind::Transfer(transfer) => transfer.id(),
+            TransactionKind::Stake(txn)
+            | TransactionKind::Withdrawal(txn)
+            | TransactionKind::Delegate(txn) => txn.id(),
         }
     }
 
     fn timestamp(&self) -> TxTimestamp {
         match self {
             TransactionKind::Transfer(transfer) => transfer.timestamp(),
+            TransactionKind::Stake(txn)
+            | TransactionKind::Withdrawal(txn)
+            | TransactionKind::Delegate(txn) => txn.timestamp(),
         }
     }
 
     fn sender_address(&self) -> Address {
         match self {
             TransactionKind::Transfer(transfer) => transfer.sender_address(),
+            TransactionKind::Stake(txn)
+            | TransactionKind::Withdrawal(txn)
+            | TransactionKind::Delegate(txn) => txn.sender_address(),
         }
     }
 
     fn sender_public_key(&self) -> PublicKey {
         match self {
             TransactionKind::Transfer(transfer) => transfer.sender_public_key(),
+            TransactionKind::Stake(txn)
+            | TransactionKind::Withdrawal(txn)
+            | TransactionKind::Delegate(txn) => txn.sender_public_key(),
         }
     }
 
     fn receiver_address(&self) -> Address {
         match self {
             TransactionKind::Transfer(transfer) => transfer.receiver_address(),
+            TransactionKind::Stake(txn)
+            | TransactionKind::Withdrawal(txn)
+            | TransactionKind::Delegate(txn) => txn.receiver_address(),
         }
     }
 
     fn token(&self) -> Token {
         match self {
             TransactionKind::Transfer(transfer) => transfer.token(),
+            TransactionKind::Stake(txn)
+            | TransactionKind::Withdrawal(txn)
+            | TransactionKind::Delegate(txn) => txn.token(),
         }
     }
 
     fn amount(&self) -> TxAmount {
         match self {
             TransactionKind::Transfer(transfer) => transfer.amount(),
+            TransactionKind::Stake(txn)
+            | TransactionKind::Withdrawal(txn)
+            | TransactionKind::Delegate(txn) => txn.amount(),
         }
     }
 
     fn signature(&self) -> Signature {
         match self {
             TransactionKind::Transfer(transfer) => transfer.signature(),
+            TransactionKind::Stake(txn)
+            | TransactionKind::Withdrawal(txn)
+            | TransactionKind::Delegate(txn) => txn.signature(),
         }
     }
 
     fn validators(&self) -> Option<HashMap<String, bool>> {
         match self {
             TransactionKind::Transfer(transfer) => transfer.validators(),
+            TransactionKind::Stake(txn)
+            | TransactionKind::Withdrawal(txn)
+            | TransactionKind::Delegate(txn) => txn.validators(),
         }
     }
 
     fn nonce(&self) -> TxNonce {
         match self {
             TransactionKind::Transfer(transfer) => transfer.nonce(),
+            TransactionKind::Stake(txn)
+            | TransactionKind::Withdrawal(txn)
+            | TransactionKind::Delegate(txn) => txn.nonce(),
         }
     }
 
     fn fee(&self) -> u128 {
         match self {
             TransactionKind::Transfer(transfer) => transfer.fee(),
+            TransactionKind::Stake(txn)
+            | TransactionKind::Withdrawal(txn)
+            | TransactionKind::Delegate(txn) => txn.fee(),
         }
     }
 
     fn validator_fee_share(&self) -> u128 {
         match self {
             TransactionKind::Transfer(transfer) => transfer.validator_fee_share(),
+            TransactionKind::Stake(txn)
+            | TransactionKind::Withdrawal(txn)
+            | TransactionKind::Delegate(txn) => txn.validator_fee_share(),
         }
     }
 
     fn proposer_fee_share(&self) -> u128 {
         match self {
             TransactionKind::Transfer(transfer) => transfer.proposer_fee_share(),
+            TransactionKind::Stake(txn)
+            | TransactionKind::Withdrawal(txn)
+            | TransactionKind::Delegate(txn) => txn.proposer_fee_share(),
         }
     }
 
     fn build_payload(&self) -> String {
         match self {
             TransactionKind::Transfer(transfer) => transfer.build_payload(),
+            TransactionKind::Stake(txn)
+            | TransactionKind::Withdrawal(txn)
+            | TransactionKind::Delegate(txn) => txn.build_payload(),
         }
     }
 
     fn digest(&self) -> TransactionDigest {
         match self {
             TransactionKind::Transfer(transfer) => transfer.id(),
+            TransactionKind::Stake(txn)
+            | TransactionKind::Withdrawal(txn)
+            | TransactionKind::Delegate(txn) => txn.id(),
         }
     }
 
     fn sign(&mut self, sk: &SecretKey) {
         match self {
             TransactionKind::Transfer(transfer) => transfer.sign(sk),
+            TransactionKind::Stake(txn)
+            | TransactionKind::Withdrawal(txn)
+            | TransactionKind::Delegate(txn) => txn.sign(sk),
         }
     }
 }
@@ -29,6 +29,7 @@ pub enum TransferTransactionError {
     InvalidTransferTransaction(String),
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn generate_transfer_digest_vec(
     timestamp: TxTimestamp,
     sender_address: String,
@@ -37,10 +38,20 @@ pub fn generate_transfer_digest_vec(
     token: Token,
     amount: TxAmount,
     nonce: TxNonce,
+    max_fee: Option<u128>,
+    priority_fee: u128,
 ) -> ByteVec {
     let payload_string = format!(
-        "{},{},{},{},{},{:?},{}",
-        &timestamp, &sender_address, &sender_public_key, &receiver_address, &amount, &token, &nonce
+        "{},{},{},{},{},{:?},{},{:?},{}",
+        &timestamp,
+        &sender_address,
+        &sender_public_key,
+        &receiver_address,
+        &amount,
+        &token,
+        &nonce,
+        &max_fee,
+        &priority_fee
     );
 
     let mut hasher = Sha256::new();
@@ -67,6 +78,13 @@ pub struct Transfer {
     pub signature: Signature,
     pub validators: Option<HashMap<String, bool>>,
     pub nonce: TxNonce,
+    /// The most the sender is willing to pay in fees for this transfer. If
+    /// `None`, the fee is the base fee plus the priority fee.
+    #[serde(default)]
+    pub max_fee: Option<u128>,
+    /// The tip paid on top of the base fee to have the transfer prioritized.
+    #[serde(default)]
+    pub priority_fee: u128,
 }
 
 #[derive(Clone, Default)]
@@ -80,6 +98,8 @@ pub struct TransferBuilder {
     signature: Option<Signature>,
     validators: Option<HashMap<String, bool>>,
     nonce: Option<TxNonce>,
+    max_fee: Option<u128>,
+    priority_fee: Option<u128>,
 }
 
 impl TransferBuilder {
@@ -126,7 +146,9 @@ impl TransferBuilder {
                 self.receiver_address.clone(),
                 self.token.clone(),
                 self.amount.clone(),
-                self.nonce.clone()
+                self.nonce.clone(),
+                self.max_fee,
+                self.priority_fee.unwrap_or_default()
             )
         )
     }
@@ -141,6 +163,16 @@ impl TransferBuilder {
         self
     }
 
+    pub fn max_fee(mut self, max_fee: u128) -> Self {
+        self.max_fee = Some(max_fee);
+        self
+    }
+
+    pub fn priority_fee(mut self, priority_fee: u128) -> Self {
+        self.priority_fee = Some(priority_fee);
+        self
+    }
+
     pub fn build(self) -> Result<Transfer, &'static str> {
         let id = generate_transfer_digest_vec(
             self.timestamp.ok_or("timestamp is missing")?,
@@ -157,6 +189,8 @@ impl TransferBuilder {
             self.token.clone().unwrap_or_default(),
             self.amount.ok_or("amount is missing")?,
             self.nonce.ok_or("nonce is missing")?,
+            self.max_fee,
+            self.priority_fee.unwrap_or_default(),
         );
 
         Ok(Transfer {
@@ -170,6 +204,8 @@ impl TransferBuilder {
             signature: self.signature.ok_or("signature is missing")?,
             validators: self.validators,
             nonce: self.nonce.unwrap(),
+            max_fee: self.max_fee,
+            priority_fee: self.priority_fee.unwrap_or_default(),
         })
     }
 
@@ -214,6 +250,8 @@ impl Transfer {
             token.clone(),
             args.amount,
             args.nonce,
+            None,
+            0,
         );
 
         let digest = TransactionDigest::from(digest_vec);
@@ -230,6 +268,8 @@ impl Transfer {
             signature: args.signature,
             validators: args.validators,
             nonce: args.nonce,
+            max_fee: None,
+            priority_fee: 0,
         }
     }
 
@@ -247,6 +287,8 @@ impl Transfer {
             Token::default(),
             0,
             0,
+            None,
+            0,
         );
 
         let digest = TransactionDigest::from(digest_vec);
@@ -277,6 +319,8 @@ impl Transfer {
             signature,
             validators: None,
             nonce: 0,
+            max_fee: None,
+            priority_fee: 0,
         }
     }
 
@@ -289,6 +333,8 @@ impl Transfer {
             self.token(),
             self.amount(),
             self.nonce(),
+            self.max_fee,
+            self.priority_fee,
         );
 
         digest.into()
@@ -322,6 +368,8 @@ impl Transfer {
             self.token(),
             self.amount(),
             self.nonce(),
+            self.max_fee,
+            self.priority_fee,
         )
     }
 
@@ -387,16 +435,25 @@ impl Transaction for Transfer {
         self.nonce
     }
 
+    /// The base fee plus the priority fee, capped at `max_fee` if the sender
+    /// set one.
     fn fee(&self) -> u128 {
-        BASE_FEE
+        let fee = BASE_FEE.saturating_add(self.priority_fee);
+
+        match self.max_fee {
+            Some(max_fee) => fee.min(max_fee),
+            None => fee,
+        }
     }
 
     fn validator_fee_share(&self) -> u128 {
-        BASE_FEE / 2u128
+        self.fee() / 2u128
     }
 
+    /// The proposer receives the remainder of the fee so that both shares
+    /// always add up to the fee paid.
     fn proposer_fee_share(&self) -> u128 {
-        BASE_FEE / 2u128
+        self.fee() - self.validator_fee_share()
     }
 
     fn build_payload(&self) -> String {
@@ -408,7 +465,9 @@ impl Transaction for Transfer {
                 self.receiver_address.clone(),
                 self.token.clone(),
                 self.amount.clone(),
-                self.nonce.clone()
+                self.nonce.clone(),
+                self.max_fee,
+                self.priority_fee
             )
         )
     }
@@ -491,6 +550,8 @@ impl Hash for Transfer {
         self.amount.hash(state);
         self.signature.hash(state);
         self.nonce.hash(state);
+        self.max_fee.hash(state);
+        self.priority_fee.hash(state);
     }
 
     fn hash_slice<H: Hasher>(data: &[Self], state: &mut H)
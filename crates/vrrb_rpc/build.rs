@@ -0,0 +1,18 @@
+const PROTOS: &[&str] = &[
+    "../../infra/proto/node_read_service/v1/node_read_service.proto",
+    "../../infra/proto/node_write_service/v1/node_write_service.proto",
+];
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Fall back to a vendored protoc so the crate builds on machines without one
+    if std::env::var_os("PROTOC").is_none() {
+        std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    }
+
+    tonic_build::configure()
+        .build_server(true)
+        .build_client(true)
+        .compile(PROTOS, &["../../infra/proto"])?;
+
+    Ok(())
+}
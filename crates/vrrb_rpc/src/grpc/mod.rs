@@ -0,0 +1,21 @@
+mod read_service;
+mod server;
+mod write_service;
+
+pub use read_service::*;
+pub use server::*;
+pub use write_service::*;
+
+/// Types and stubs generated out of `infra/proto/node_read_service`
+pub mod node_read_service {
+    pub mod v1 {
+        tonic::include_proto!("node_read_service.v1");
+    }
+}
+
+/// Types and stubs generated out of `infra/proto/node_write_service`
+pub mod node_write_service {
+    pub mod v1 {
+        tonic::include_proto!("node_write_service.v1");
+    }
+}
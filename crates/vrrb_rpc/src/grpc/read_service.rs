@@ -0,0 +1,258 @@
+use std::str::FromStr;
+
+use block::{header::BlockHeader as NodeBlockHeader, Block as NodeBlock};
+use mempool::MempoolReadHandleFactory;
+use primitives::{Address, NodeType};
+use storage::vrrbdb::VrrbDbReadHandle;
+use tonic::{Request, Response, Status};
+use vrrb_core::{
+    account::Account as NodeAccount,
+    claim::Claim as NodeClaim,
+    transactions::{Transaction, TransactionDigest, TransactionKind},
+};
+
+use crate::{
+    grpc::node_read_service::v1::{
+        node_read_service_server::NodeReadService, Account, Block, BlockHeader, BlockKind, Claim,
+        GetAccountRequest, GetBlockRequest, GetBlocksRequest, GetBlocksResponse,
+        GetClaimsByAccountIdRequest, GetClaimsResponse, GetFullMempoolRequest,
+        GetFullMempoolResponse, GetNodeTypeRequest, GetNodeTypeResponse, GetTransactionRequest,
+        Token, TransactionRecord,
+    },
+    rpc::api::MAX_BLOCKS_PER_PAGE,
+};
+
+#[derive(Debug, Clone)]
+pub struct NodeReadServiceImpl {
+    pub node_id: String,
+    pub node_type: NodeType,
+    pub vrrbdb_read_handle: VrrbDbReadHandle,
+    pub mempool_read_handle_factory: MempoolReadHandleFactory,
+}
+
+#[tonic::async_trait]
+impl NodeReadService for NodeReadServiceImpl {
+    async fn get_node_type(
+        &self,
+        _request: Request<GetNodeTypeRequest>,
+    ) -> Result<Response<GetNodeTypeResponse>, Status> {
+        Ok(Response::new(GetNodeTypeResponse {
+            id: self.node_id.clone(),
+            result: self.node_type.to_string(),
+        }))
+    }
+
+    async fn get_full_mempool(
+        &self,
+        _request: Request<GetFullMempoolRequest>,
+    ) -> Result<Response<GetFullMempoolResponse>, Status> {
+        let transaction_records = self
+            .mempool_read_handle_factory
+            .values()
+            .iter()
+            .map(transaction_record)
+            .collect();
+
+        Ok(Response::new(GetFullMempoolResponse {
+            transaction_records,
+        }))
+    }
+
+    async fn get_transaction(
+        &self,
+        request: Request<GetTransactionRequest>,
+    ) -> Result<Response<TransactionRecord>, Status> {
+        let id = request.into_inner().id;
+        let digest = id
+            .parse::<TransactionDigest>()
+            .map_err(|_| Status::invalid_argument(format!("unable to parse digest {id}")))?;
+
+        let confirmed_txn = self
+            .vrrbdb_read_handle
+            .get_transaction_by_digest(&digest)
+            .ok();
+
+        let txn = confirmed_txn
+            .or_else(|| {
+                self.mempool_read_handle_factory
+                    .get(&digest)
+                    .map(|record| record.txn)
+            })
+            .ok_or_else(|| Status::not_found(format!("unable to find transaction {id}")))?;
+
+        Ok(Response::new(transaction_record(&txn)))
+    }
+
+    async fn get_account(
+        &self,
+        request: Request<GetAccountRequest>,
+    ) -> Result<Response<Account>, Status> {
+        let address = parse_address(&request.into_inner().address)?;
+
+        let account = self
+            .vrrbdb_read_handle
+            .get_account_by_address(&address)
+            .map_err(|err| Status::not_found(format!("unable to find account {address}: {err}")))?;
+
+        Ok(Response::new(Account::from(&account)))
+    }
+
+    async fn get_block(
+        &self,
+        request: Request<GetBlockRequest>,
+    ) -> Result<Response<Block>, Status> {
+        let hash = request.into_inner().hash;
+
+        let block = self
+            .vrrbdb_read_handle
+            .get_block(&hash)
+            .map_err(|err| Status::internal(err.to_string()))?
+            .ok_or_else(|| Status::not_found(format!("unable to find block {hash}")))?;
+
+        Ok(Response::new(Block::try_from(&block)?))
+    }
+
+    async fn get_blocks(
+        &self,
+        request: Request<GetBlocksRequest>,
+    ) -> Result<Response<GetBlocksResponse>, Status> {
+        let GetBlocksRequest {
+            start_height,
+            limit,
+        } = request.into_inner();
+
+        let blocks = self
+            .vrrbdb_read_handle
+            .get_blocks(
+                start_height as u128,
+                (limit as usize).min(MAX_BLOCKS_PER_PAGE),
+            )
+            .map_err(|err| Status::internal(err.to_string()))?
+            .iter()
+            .map(Block::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Response::new(GetBlocksResponse { blocks }))
+    }
+
+    async fn get_claims_by_account_id(
+        &self,
+        request: Request<GetClaimsByAccountIdRequest>,
+    ) -> Result<Response<GetClaimsResponse>, Status> {
+        let address = parse_address(&request.into_inner().address)?;
+
+        let claims = self
+            .vrrbdb_read_handle
+            .claim_store_values()
+            .map_err(|err| Status::internal(format!("failed to read claims: {err}")))?
+            .values()
+            .filter(|claim| claim.address == address)
+            .map(Claim::from)
+            .collect();
+
+        Ok(Response::new(GetClaimsResponse { claims }))
+    }
+}
+
+fn parse_address(address: &str) -> Result<Address, Status> {
+    Address::from_str(address)
+        .map_err(|err| Status::invalid_argument(format!("invalid address {address}: {err}")))
+}
+
+/// Converts a transaction into its gRPC representation
+fn transaction_record(txn: &TransactionKind) -> TransactionRecord {
+    let token = txn.token();
+
+    TransactionRecord {
+        id: txn.id().digest_string(),
+        timestamp: txn.timestamp(),
+        sender_address: txn.sender_address().to_string(),
+        sender_public_key: txn.sender_public_key().to_string(),
+        receiver_address: txn.receiver_address().to_string(),
+        token: Some(Token {
+            name: token.name,
+            symbol: token.symbol,
+            decimals: token.decimals as u32,
+        }),
+        amount: txn.amount().to_string(),
+        signature: txn.signature().to_string(),
+        validators: txn.validators().unwrap_or_default(),
+        nonce: txn.nonce().to_string(),
+        fee: txn.fee().to_string(),
+    }
+}
+
+impl From<&NodeAccount> for Account {
+    fn from(account: &NodeAccount) -> Self {
+        Self {
+            address: account.address().to_string(),
+            hash: account.hash().to_string(),
+            nonce: account.nonce().to_string(),
+            credits: account.credits().to_string(),
+            debits: account.debits().to_string(),
+            storage: account.storage().clone(),
+            package_address: account.package_address().clone(),
+        }
+    }
+}
+
+impl From<&NodeClaim> for Claim {
+    fn from(claim: &NodeClaim) -> Self {
+        Self {
+            public_key: claim.public_key.to_string(),
+            address: claim.address.to_string(),
+            hash: claim.hash.to_string(),
+            eligibility: claim.eligibility.to_string(),
+            ip_address: claim.ip_address.to_string(),
+            signature: claim.signature.clone(),
+            node_id: claim.node_id.to_string(),
+            stake: claim.get_stake().to_string(),
+        }
+    }
+}
+
+impl From<&NodeBlockHeader> for BlockHeader {
+    fn from(header: &NodeBlockHeader) -> Self {
+        Self {
+            ref_hashes: header.ref_hashes.clone(),
+            epoch: header.epoch.to_string(),
+            round: header.round.to_string(),
+            block_height: header.block_height.to_string(),
+            timestamp: header.timestamp,
+            txn_hash: header.txn_hash.clone(),
+            claim_list_hash: header.claim_list_hash.clone(),
+            state_root_hash: header.state_root_hash.clone(),
+            miner_address: header.miner_claim.address.to_string(),
+        }
+    }
+}
+
+impl TryFrom<&NodeBlock> for Block {
+    type Error = Status;
+
+    fn try_from(block: &NodeBlock) -> Result<Self, Self::Error> {
+        let (hash, kind, header) = match block {
+            NodeBlock::Genesis { block } => (
+                block.hash.clone(),
+                BlockKind::Genesis,
+                Some(BlockHeader::from(&block.header)),
+            ),
+            NodeBlock::Proposal { block } => (block.hash.clone(), BlockKind::Proposal, None),
+            NodeBlock::Convergence { block } => (
+                block.hash.clone(),
+                BlockKind::Convergence,
+                Some(BlockHeader::from(&block.header)),
+            ),
+        };
+
+        let encoded = serde_json::to_string(block)
+            .map_err(|err| Status::internal(format!("failed to encode block {hash}: {err}")))?;
+
+        Ok(Self {
+            hash,
+            kind: kind as i32,
+            header,
+            encoded,
+        })
+    }
+}
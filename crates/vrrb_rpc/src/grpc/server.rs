@@ -0,0 +1,112 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use events::{EventPublisher, DEFAULT_BUFFER};
+use mempool::{LeftRightMempool, MempoolReadHandleFactory};
+use primitives::NodeType;
+use storage::vrrbdb::{VrrbDb, VrrbDbConfig, VrrbDbReadHandle};
+use tokio::{
+    net::TcpListener,
+    sync::{mpsc::channel, oneshot},
+    task::JoinHandle,
+};
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::Server;
+
+use crate::grpc::{
+    node_read_service::v1::node_read_service_server::NodeReadServiceServer,
+    node_write_service::v1::node_write_service_server::NodeWriteServiceServer, NodeReadServiceImpl,
+    NodeWriteServiceImpl,
+};
+
+#[derive(Debug, Clone)]
+pub struct GrpcServerConfig {
+    pub address: SocketAddr,
+    pub node_id: String,
+    pub node_type: NodeType,
+    pub vrrbdb_read_handle: VrrbDbReadHandle,
+    pub mempool_read_handle_factory: MempoolReadHandleFactory,
+    pub events_tx: EventPublisher,
+}
+
+impl Default for GrpcServerConfig {
+    fn default() -> GrpcServerConfig {
+        let address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 0);
+
+        let mut vrrbdb_config = VrrbDbConfig::default();
+        vrrbdb_config.path =
+            std::env::temp_dir().join(vrrb_core::helpers::generate_random_string());
+
+        let vrrbdb = VrrbDb::new(vrrbdb_config).expect("failed to open the default database");
+        let mempool = LeftRightMempool::default();
+        let (events_tx, _) = channel(DEFAULT_BUFFER);
+
+        GrpcServerConfig {
+            address,
+            node_id: String::default(),
+            node_type: NodeType::Full,
+            vrrbdb_read_handle: vrrbdb.read_handle(),
+            mempool_read_handle_factory: mempool.factory(),
+            events_tx,
+        }
+    }
+}
+
+/// Handle to a running GrpcServer, used to shut it down
+#[derive(Debug)]
+pub struct GrpcServerHandle {
+    shutdown_tx: oneshot::Sender<()>,
+    server_handle: JoinHandle<Result<(), tonic::transport::Error>>,
+}
+
+impl GrpcServerHandle {
+    /// Stops accepting connections and waits for the in-flight requests to
+    /// complete
+    pub async fn stop(self) -> anyhow::Result<()> {
+        // NOTE: sending only fails if the server already stopped on its own
+        let _ = self.shutdown_tx.send(());
+        self.server_handle.await??;
+
+        Ok(())
+    }
+}
+
+/// Serves the NodeReadService and NodeWriteService gRPC services.
+#[derive(Debug)]
+pub struct GrpcServer;
+
+impl GrpcServer {
+    pub async fn run(config: &GrpcServerConfig) -> anyhow::Result<(GrpcServerHandle, SocketAddr)> {
+        let listener = TcpListener::bind(config.address).await?;
+        let addr = listener.local_addr()?;
+
+        let read_service = NodeReadServiceImpl {
+            node_id: config.node_id.clone(),
+            node_type: config.node_type,
+            vrrbdb_read_handle: config.vrrbdb_read_handle.clone(),
+            mempool_read_handle_factory: config.mempool_read_handle_factory.clone(),
+        };
+
+        let write_service = NodeWriteServiceImpl {
+            events_tx: config.events_tx.clone(),
+        };
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+
+        let server = Server::builder()
+            .add_service(NodeReadServiceServer::new(read_service))
+            .add_service(NodeWriteServiceServer::new(write_service))
+            .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async {
+                let _ = shutdown_rx.await;
+            });
+
+        let server_handle = tokio::spawn(server);
+
+        Ok((
+            GrpcServerHandle {
+                shutdown_tx,
+                server_handle,
+            },
+            addr,
+        ))
+    }
+}
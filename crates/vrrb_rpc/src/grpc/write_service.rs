@@ -0,0 +1,110 @@
+use std::str::FromStr;
+
+use events::{Event, EventPublisher};
+use primitives::{Address, PublicKey};
+use secp256k1::ecdsa::Signature;
+use tonic::{Request, Response, Status};
+use vrrb_core::transactions::{Token as NodeToken, Transaction, TransactionKind};
+
+use crate::grpc::node_write_service::v1::{
+    node_write_service_server::NodeWriteService, CreateTransactionRequest, Token, TransactionRecord,
+};
+
+#[derive(Debug, Clone)]
+pub struct NodeWriteServiceImpl {
+    pub events_tx: EventPublisher,
+}
+
+#[tonic::async_trait]
+impl NodeWriteService for NodeWriteServiceImpl {
+    async fn create_transaction(
+        &self,
+        request: Request<CreateTransactionRequest>,
+    ) -> Result<Response<TransactionRecord>, Status> {
+        let txn = transaction_from_request(request.into_inner())?;
+
+        self.events_tx
+            .send(Event::NewTxnCreated(txn.clone()).into())
+            .await
+            .map_err(|err| {
+                Status::unavailable(format!("could not queue transaction to mempool: {err}"))
+            })?;
+
+        let token = txn.token();
+
+        Ok(Response::new(TransactionRecord {
+            id: txn.id().digest_string(),
+            timestamp: txn.timestamp(),
+            sender_address: txn.sender_address().to_string(),
+            sender_public_key: txn.sender_public_key().to_string(),
+            receiver_address: txn.receiver_address().to_string(),
+            token: Some(Token {
+                name: token.name,
+                symbol: token.symbol,
+                decimals: token.decimals as u32,
+            }),
+            amount: txn.amount().to_string(),
+            signature: txn.signature().to_string(),
+            validators: txn.validators().unwrap_or_default(),
+            nonce: txn.nonce().to_string(),
+            fee: txn.fee().to_string(),
+        }))
+    }
+}
+
+/// Builds the transfer described by a CreateTransactionRequest
+fn transaction_from_request(request: CreateTransactionRequest) -> Result<TransactionKind, Status> {
+    let sender_address = Address::from_str(&request.sender_address)
+        .map_err(|err| Status::invalid_argument(format!("invalid sender address: {err}")))?;
+    let receiver_address = Address::from_str(&request.receiver_address)
+        .map_err(|err| Status::invalid_argument(format!("invalid receiver address: {err}")))?;
+    let sender_public_key = PublicKey::from_str(&request.sender_public_key)
+        .map_err(|err| Status::invalid_argument(format!("invalid sender public key: {err}")))?;
+    let signature = Signature::from_str(&request.signature)
+        .map_err(|err| Status::invalid_argument(format!("invalid signature: {err}")))?;
+
+    let token = match request.token {
+        Some(token) => NodeToken {
+            name: token.name,
+            symbol: token.symbol,
+            decimals: u8::try_from(token.decimals).map_err(|err| {
+                Status::invalid_argument(format!("invalid token decimals: {err}"))
+            })?,
+        },
+        None => NodeToken::default(),
+    };
+
+    let amount = parse_decimal("amount", &request.amount)?;
+    let nonce = parse_decimal("nonce", &request.nonce)?;
+    let priority_fee = if request.priority_fee.is_empty() {
+        0
+    } else {
+        parse_decimal("priority fee", &request.priority_fee)?
+    };
+
+    let mut builder = TransactionKind::transfer_builder()
+        .timestamp(request.timestamp)
+        .sender_address(sender_address)
+        .sender_public_key(sender_public_key)
+        .receiver_address(receiver_address)
+        .token(token)
+        .amount(amount)
+        .signature(signature)
+        .validators(request.validators)
+        .nonce(nonce)
+        .priority_fee(priority_fee);
+
+    if let Some(max_fee) = request.max_fee {
+        builder = builder.max_fee(parse_decimal("max fee", &max_fee)?);
+    }
+
+    builder
+        .build_kind()
+        .map_err(|err| Status::invalid_argument(err.to_string()))
+}
+
+fn parse_decimal(field: &str, value: &str) -> Result<u128, Status> {
+    value
+        .parse::<u128>()
+        .map_err(|err| Status::invalid_argument(format!("invalid {field} {value}: {err}")))
+}
@@ -2,6 +2,8 @@ use std::{fmt::Debug, net::SocketAddr, time::Duration};
 
 use axum_server::tls_rustls::RustlsConfig;
 
+use crate::http::HttpApiState;
+
 /// Configuration store for an HttpApiServer
 // Source<: https://doc.rust-lang.org/1.0.0/style/ownership/builders.html
 
@@ -12,6 +14,7 @@ pub struct HttpApiServerConfig {
     pub(crate) api_version: String,
     pub(crate) server_timeout: Option<Duration>,
     pub(crate) tls_config: Option<RustlsConfig>,
+    pub(crate) state: HttpApiState,
 }
 
 impl From<HttpApiServerConfigBuilder> for HttpApiServerConfig {
@@ -22,6 +25,7 @@ impl From<HttpApiServerConfigBuilder> for HttpApiServerConfig {
             api_version: value.api_version.expect("expected server api version"),
             server_timeout: value.server_timeout,
             tls_config: value.tls_config,
+            state: value.state.unwrap_or_default(),
         }
     }
 }
@@ -33,6 +37,7 @@ pub struct HttpApiServerConfigBuilder {
     api_version: Option<String>,
     server_timeout: Option<Duration>,
     tls_config: Option<RustlsConfig>,
+    state: Option<HttpApiState>,
 }
 
 impl HttpApiServerConfigBuilder {
@@ -56,6 +61,10 @@ impl HttpApiServerConfigBuilder {
         self.tls_config = tls_config;
         self
     }
+    pub fn state(mut self, state: HttpApiState) -> Self {
+        self.state = Some(state);
+        self
+    }
     pub fn build(self) -> HttpApiServerConfig {
         self.into()
     }
@@ -69,6 +78,7 @@ pub struct HttpApiRouterConfig {
     pub api_title: String,
     pub api_version: String,
     pub server_timeout: Option<Duration>,
+    pub state: HttpApiState,
 }
 
 impl From<HttpApiRouterConfigBuilder> for HttpApiRouterConfig {
@@ -78,6 +88,7 @@ impl From<HttpApiRouterConfigBuilder> for HttpApiRouterConfig {
             api_title: value.api_title.expect("expected router api title"),
             api_version: value.api_version.expect("expected router api version"),
             server_timeout: value.server_timeout,
+            state: value.state.unwrap_or_default(),
         }
     }
 }
@@ -88,6 +99,7 @@ pub struct HttpApiRouterConfigBuilder {
     api_title: Option<String>,
     api_version: Option<String>,
     server_timeout: Option<Duration>,
+    state: Option<HttpApiState>,
 }
 
 impl HttpApiRouterConfigBuilder {
@@ -107,6 +119,10 @@ impl HttpApiRouterConfigBuilder {
         self.server_timeout = server_timeout;
         self
     }
+    pub fn state(mut self, state: HttpApiState) -> Self {
+        self.state = Some(state);
+        self
+    }
     pub fn build(self) -> HttpApiRouterConfig {
         self.into()
     }
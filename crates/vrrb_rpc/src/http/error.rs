@@ -0,0 +1,51 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+
+pub type HttpApiResult<T> = std::result::Result<T, HttpApiError>;
+
+/// Errors returned by the routes of an HttpApiServer. Each one is rendered as
+/// a JSON body carrying its status code and message.
+#[derive(Debug, thiserror::Error)]
+pub enum HttpApiError {
+    #[error("{0}")]
+    BadRequest(String),
+
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl HttpApiError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            HttpApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            HttpApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            HttpApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for HttpApiError {
+    fn into_response(self) -> Response {
+        let status = self.status_code();
+
+        if let HttpApiError::Internal(err) = &self {
+            telemetry::error!("HTTP API error: {err}");
+        }
+
+        let body = Json(json!({
+            "error": {
+                "code": status.as_u16(),
+                "message": self.to_string(),
+            }
+        }));
+
+        (status, body).into_response()
+    }
+}
@@ -1,8 +1,14 @@
 mod config;
+mod error;
+mod openapi;
 mod router;
 mod routes;
 mod server;
+mod state;
 
 pub use config::*;
+pub use error::*;
+pub use openapi::*;
 pub use router::*;
 pub use server::*;
+pub use state::*;
@@ -0,0 +1,136 @@
+use serde_json::{json, Value};
+
+use crate::http::HttpApiRouterConfig;
+
+/// Builds the OpenAPI description of the routes served by an HttpApiServer
+pub fn openapi_spec(config: &HttpApiRouterConfig) -> Value {
+    let error_response = |description: &str| {
+        json!({
+            "description": description,
+            "content": {
+                "application/json": { "schema": { "$ref": "#/components/schemas/Error" } }
+            }
+        })
+    };
+
+    let json_response = |description: &str| {
+        json!({
+            "description": description,
+            "content": { "application/json": { "schema": { "type": "object" } } }
+        })
+    };
+
+    let path_param = |name: &str, description: &str| {
+        json!({
+            "name": name,
+            "in": "path",
+            "required": true,
+            "description": description,
+            "schema": { "type": "string" }
+        })
+    };
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": config.api_title,
+            "version": config.api_version,
+        },
+        "paths": {
+            "/health": {
+                "get": {
+                    "summary": "Reports whether the node is up",
+                    "responses": { "200": json_response("The node is up") }
+                }
+            },
+            "/accounts/{address}": {
+                "get": {
+                    "summary": "Returns an account from state",
+                    "parameters": [path_param("address", "Hex encoded address, prefixed by 0x")],
+                    "responses": {
+                        "200": json_response("The account"),
+                        "400": error_response("The address is malformed"),
+                        "404": error_response("The account does not exist"),
+                    }
+                }
+            },
+            "/transactions": {
+                "post": {
+                    "summary": "Submits a signed transaction to the mempool",
+                    "requestBody": {
+                        "required": true,
+                        "content": { "application/json": { "schema": { "type": "object" } } }
+                    },
+                    "responses": {
+                        "202": json_response("The transaction was queued"),
+                        "400": error_response("The transaction is malformed"),
+                    }
+                }
+            },
+            "/transactions/{digest}": {
+                "get": {
+                    "summary": "Returns a confirmed or pending transaction",
+                    "parameters": [path_param("digest", "Transaction digest")],
+                    "responses": {
+                        "200": json_response("The transaction"),
+                        "400": error_response("The digest is malformed"),
+                        "404": error_response("The transaction does not exist"),
+                    }
+                }
+            },
+            "/blocks": {
+                "get": {
+                    "summary": "Returns confirmed blocks ordered by height",
+                    "parameters": [
+                        {
+                            "name": "from",
+                            "in": "query",
+                            "description": "Height of the first block returned",
+                            "schema": { "type": "integer", "minimum": 0 }
+                        },
+                        {
+                            "name": "limit",
+                            "in": "query",
+                            "description": "Maximum number of blocks returned",
+                            "schema": {
+                                "type": "integer",
+                                "minimum": 0,
+                                "maximum": crate::rpc::api::MAX_BLOCKS_PER_PAGE,
+                            }
+                        }
+                    ],
+                    "responses": {
+                        "200": json_response("The blocks"),
+                        "400": error_response("The query is malformed"),
+                    }
+                }
+            },
+            "/blocks/{hash}": {
+                "get": {
+                    "summary": "Returns a block known to the node by its hash",
+                    "parameters": [path_param("hash", "Block hash")],
+                    "responses": {
+                        "200": json_response("The block"),
+                        "404": error_response("The block does not exist"),
+                    }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "Error": {
+                    "type": "object",
+                    "properties": {
+                        "error": {
+                            "type": "object",
+                            "properties": {
+                                "code": { "type": "integer" },
+                                "message": { "type": "string" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
@@ -1,17 +1,24 @@
-use axum::{routing::get, Router};
+use axum::{routing::get, Extension, Json, Router};
 use tower::ServiceBuilder;
 use tower_http::trace::TraceLayer;
 
 use crate::http::{
-    routes::{accounts, health},
+    openapi_spec,
+    routes::{accounts, blocks, health, transactions},
     HttpApiRouterConfig,
 };
 
-pub fn create_router(_config: &HttpApiRouterConfig) -> Router {
+pub fn create_router(config: &HttpApiRouterConfig) -> Router {
+    let spec = openapi_spec(config);
+
     Router::new()
         .route("/", get(|| async { "index" }))
         .route("/health", get(health::health_check))
+        .route("/openapi.json", get(move || async move { Json(spec) }))
         .nest("/accounts", accounts::create_account_router())
+        .nest("/transactions", transactions::create_transaction_router())
+        .nest("/blocks", blocks::create_block_router())
+        .layer(Extension(config.state.clone()))
         .layer(ServiceBuilder::new().layer(TraceLayer::new_for_http()))
 }
 
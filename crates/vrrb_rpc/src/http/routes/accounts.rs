@@ -1,58 +1,27 @@
-use axum::{
-    routing::{get, post, put},
-    Extension, Json, Router,
-};
-use serde_json::{json, Value};
+use std::str::FromStr;
 
-pub fn create_account_router() -> Router {
-    Router::new()
-        .route("/:id", get(get_account))
-        .route("/:id", put(update_account))
-        .route("/", post(create_account))
-        .layer(Extension(String::from("account route")))
-}
-
-async fn get_account(Extension(state): Extension<String>) -> Json<Value> {
-    Json(json!({
-        "account": "dummy_account_status",
-        "state": state,
-    }))
-}
-
-async fn create_account() {
-    todo!()
-}
-
-async fn update_account() {
-    todo!()
-}
-
-async fn _create_key_router() {
-    todo!()
-}
+use axum::{extract::Path, routing::get, Extension, Json, Router};
+use primitives::Address;
+use vrrb_core::account::Account;
 
-async fn _create_token_router() {
-    todo!()
-}
-
-async fn _get_key() {
-    todo!()
-}
-
-async fn _create_token() {
-    todo!()
-}
+use crate::http::{HttpApiError, HttpApiResult, HttpApiState};
 
-async fn _update_token() {
-    todo!()
-}
-
-async fn _get_token() {
-    todo!()
+pub fn create_account_router() -> Router {
+    Router::new().route("/:address", get(get_account))
 }
 
-async fn _delete_token() {
-    todo!()
+async fn get_account(
+    Extension(state): Extension<HttpApiState>,
+    Path(address): Path<String>,
+) -> HttpApiResult<Json<Account>> {
+    let address = Address::from_str(&address)
+        .map_err(|err| HttpApiError::BadRequest(format!("invalid address {address}: {err}")))?;
+
+    state
+        .vrrbdb_read_handle
+        .get_account_by_address(&address)
+        .map(Json)
+        .map_err(|err| HttpApiError::NotFound(format!("unable to find account {address}: {err}")))
 }
 
 #[cfg(test)]
@@ -61,16 +30,36 @@ mod tests {
         body::Body,
         http::{Request, StatusCode},
     };
+    use primitives::generate_account_keypair;
+    use storage::vrrbdb::{VrrbDb, VrrbDbConfig};
     use tower::{Service, ServiceExt};
 
     use super::*;
 
+    fn create_test_router(state: HttpApiState) -> Router {
+        create_account_router().layer(Extension(state))
+    }
+
     #[tokio::test]
-    async fn get_account_returns_available_accounts() {
-        let mut router = create_account_router();
+    async fn get_account_returns_known_accounts() {
+        let mut vrrbdb_config = VrrbDbConfig::default();
+        vrrbdb_config.path =
+            std::env::temp_dir().join(vrrb_core::helpers::generate_random_string());
+        let mut vrrbdb = VrrbDb::new(vrrbdb_config).unwrap();
+
+        let (_, public_key) = generate_account_keypair();
+        let address = Address::new(public_key);
+        vrrbdb
+            .insert_account(address.clone(), Account::new(address.clone()))
+            .unwrap();
+
+        let mut router = create_test_router(HttpApiState {
+            vrrbdb_read_handle: vrrbdb.read_handle(),
+            ..Default::default()
+        });
 
         let request = Request::builder()
-            .uri("/:id")
+            .uri(format!("/{address}"))
             .method("GET")
             .body(Body::empty())
             .unwrap();
@@ -79,4 +68,28 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    #[tokio::test]
+    async fn get_account_rejects_unknown_and_malformed_addresses() {
+        let mut router = create_test_router(HttpApiState::default());
+
+        let (_, public_key) = generate_account_keypair();
+        let request = Request::builder()
+            .uri(format!("/{}", Address::new(public_key)))
+            .method("GET")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = router.ready().await.unwrap().call(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let request = Request::builder()
+            .uri("/not_an_address")
+            .method("GET")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = router.ready().await.unwrap().call(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
 }
@@ -0,0 +1,59 @@
+use axum::{
+    extract::{rejection::QueryRejection, Path, Query},
+    routing::get,
+    Extension, Json, Router,
+};
+use block::Block;
+use serde::Deserialize;
+
+use crate::{
+    http::{HttpApiError, HttpApiResult, HttpApiState},
+    rpc::api::MAX_BLOCKS_PER_PAGE,
+};
+
+#[derive(Debug, Default, Deserialize)]
+struct BlocksQuery {
+    from: Option<u128>,
+    limit: Option<usize>,
+}
+
+pub fn create_block_router() -> Router {
+    Router::new()
+        .route("/", get(get_blocks))
+        .route("/:hash", get(get_block))
+}
+
+/// Returns confirmed blocks ordered by height, starting at `from`. At most
+/// `MAX_BLOCKS_PER_PAGE` blocks are returned
+async fn get_blocks(
+    Extension(state): Extension<HttpApiState>,
+    query: Result<Query<BlocksQuery>, QueryRejection>,
+) -> HttpApiResult<Json<Vec<Block>>> {
+    let Query(query) =
+        query.map_err(|err| HttpApiError::BadRequest(format!("invalid query: {err}")))?;
+
+    let from = query.from.unwrap_or_default();
+    let limit = query
+        .limit
+        .unwrap_or(MAX_BLOCKS_PER_PAGE)
+        .min(MAX_BLOCKS_PER_PAGE);
+
+    let blocks = state
+        .vrrbdb_read_handle
+        .get_blocks(from, limit)
+        .map_err(|err| HttpApiError::Internal(err.to_string()))?;
+
+    Ok(Json(blocks))
+}
+
+async fn get_block(
+    Extension(state): Extension<HttpApiState>,
+    Path(hash): Path<String>,
+) -> HttpApiResult<Json<Block>> {
+    state
+        .vrrbdb_read_handle
+        .get_block(&hash)
+        .map_err(|err| HttpApiError::Internal(err.to_string()))?
+        .map(Json)
+        .ok_or_else(|| HttpApiError::NotFound(format!("unable to find block {hash}")))
+}
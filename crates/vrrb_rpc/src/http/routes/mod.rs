@@ -1,2 +1,4 @@
 pub mod accounts;
+pub mod blocks;
 pub mod health;
+pub mod transactions;
@@ -0,0 +1,63 @@
+use axum::{
+    extract::{rejection::JsonRejection, Path},
+    http::StatusCode,
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use events::Event;
+use vrrb_core::transactions::{TransactionDigest, TransactionKind};
+
+use crate::{
+    http::{HttpApiError, HttpApiResult, HttpApiState},
+    rpc::api::RpcTransactionRecord,
+};
+
+pub fn create_transaction_router() -> Router {
+    Router::new()
+        .route("/", post(create_transaction))
+        .route("/:digest", get(get_transaction))
+}
+
+/// Looks a transaction up within the ledger first and the mempool after, so
+/// pending transactions can be retrieved as well
+async fn get_transaction(
+    Extension(state): Extension<HttpApiState>,
+    Path(digest): Path<String>,
+) -> HttpApiResult<Json<RpcTransactionRecord>> {
+    let parsed_digest = digest.parse::<TransactionDigest>().map_err(|_| {
+        HttpApiError::BadRequest(format!("unable to parse transaction digest {digest}"))
+    })?;
+
+    let confirmed_txn = state
+        .vrrbdb_read_handle
+        .get_transaction_by_digest(&parsed_digest)
+        .ok();
+
+    confirmed_txn
+        .or_else(|| {
+            state
+                .mempool_read_handle_factory
+                .get(&parsed_digest)
+                .map(|record| record.txn)
+        })
+        .map(|txn| Json(RpcTransactionRecord::from(txn)))
+        .ok_or_else(|| HttpApiError::NotFound(format!("unable to find transaction {digest}")))
+}
+
+async fn create_transaction(
+    Extension(state): Extension<HttpApiState>,
+    payload: Result<Json<TransactionKind>, JsonRejection>,
+) -> HttpApiResult<(StatusCode, Json<RpcTransactionRecord>)> {
+    let Json(txn) =
+        payload.map_err(|err| HttpApiError::BadRequest(format!("invalid transaction: {err}")))?;
+
+    state
+        .events_tx
+        .send(Event::NewTxnCreated(txn.clone()).into())
+        .await
+        .map_err(|err| {
+            HttpApiError::Internal(format!("could not queue transaction to mempool: {err}"))
+        })?;
+
+    Ok((StatusCode::ACCEPTED, Json(RpcTransactionRecord::from(txn))))
+}
@@ -38,6 +38,7 @@ impl HttpApiServer {
             .api_title(&config.api_title)
             .api_version(&config.api_version)
             .server_timeout(config.server_timeout)
+            .state(config.state)
             .build();
         let listener = TcpListener::bind(address).map_err(|err| {
             ApiError::Other(format!("unable to bind to address {address}: {err}"))
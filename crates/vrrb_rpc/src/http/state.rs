@@ -0,0 +1,31 @@
+use events::{EventPublisher, DEFAULT_BUFFER};
+use mempool::{LeftRightMempool, MempoolReadHandleFactory};
+use storage::vrrbdb::{VrrbDb, VrrbDbConfig, VrrbDbReadHandle};
+use tokio::sync::mpsc::channel;
+
+/// Node state served by the routes of an HttpApiServer. It wraps the same
+/// read handles the JSON-RPC server is built upon.
+#[derive(Debug, Clone)]
+pub struct HttpApiState {
+    pub vrrbdb_read_handle: VrrbDbReadHandle,
+    pub mempool_read_handle_factory: MempoolReadHandleFactory,
+    pub events_tx: EventPublisher,
+}
+
+impl Default for HttpApiState {
+    fn default() -> Self {
+        let mut vrrbdb_config = VrrbDbConfig::default();
+        vrrbdb_config.path =
+            std::env::temp_dir().join(vrrb_core::helpers::generate_random_string());
+
+        let vrrbdb = VrrbDb::new(vrrbdb_config).expect("failed to open the default database");
+        let mempool = LeftRightMempool::default();
+        let (events_tx, _) = channel(DEFAULT_BUFFER);
+
+        Self {
+            vrrbdb_read_handle: vrrbdb.read_handle(),
+            mempool_read_handle_factory: mempool.factory(),
+            events_tx,
+        }
+    }
+}
@@ -2,6 +2,7 @@ use std::net::SocketAddr;
 
 use jsonrpsee::types::ErrorObjectOwned as RpseeError;
 
+pub mod grpc;
 pub mod http;
 pub mod rpc;
 
@@ -1,12 +1,19 @@
 use std::collections::HashMap;
 
 use block::block::Block;
+use block::header::BlockHeader;
 use block::ClaimHash;
-use jsonrpsee::{proc_macros::rpc, types::ErrorObjectOwned as RpseeError};
+use jsonrpsee::{
+    core::SubscriptionResult, proc_macros::rpc, types::ErrorObjectOwned as RpseeError,
+};
+use mempool::TxnStatus;
 use primitives::{Address, NodeType, Round};
 use secp256k1::PublicKey;
 use serde::{Deserialize, Serialize};
-use storage::vrrbdb::Claims;
+use storage::{
+    storage_proofs::InclusionProof,
+    vrrbdb::{AddressTransaction, Claims, TransactionLocation},
+};
 use vrrb_config::QuorumMembershipConfig;
 use vrrb_core::account::Account;
 use vrrb_core::node_health_report::NodeHealthReport;
@@ -21,6 +28,21 @@ pub type ExampleStorageKey = Vec<u8>;
 pub type FullStateSnapshot = HashMap<Address, Account>;
 pub type FullMempoolSnapshot = Vec<RpcTransactionRecord>;
 
+/// Maximum number of blocks returned by a single `getBlocks` call
+pub const MAX_BLOCKS_PER_PAGE: usize = 100;
+
+/// Maximum number of transactions returned by a single
+/// `getTransactionsByAddress` call
+pub const MAX_TRANSACTIONS_PER_PAGE: usize = 100;
+
+/// Error code returned when the requested transaction, account, block or
+/// program does not exist. Server errors range from -32000 to -32099, this
+/// one is not used by jsonrpsee itself
+pub const NOT_FOUND_CODE: i32 = -32004;
+
+/// Error code returned when a client has to wait before repeating a request
+pub const RATE_LIMITED_CODE: i32 = -32012;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionRecord;
 
@@ -60,6 +82,43 @@ impl From<TransactionKind> for RpcTransactionRecord {
     }
 }
 
+/// Transaction sent or received by an address, along with the height of the
+/// block that included it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RpcAddressTransaction {
+    pub id: RpcTransactionDigest,
+    pub block_height: u128,
+}
+
+impl From<AddressTransaction> for RpcAddressTransaction {
+    fn from(txn: AddressTransaction) -> Self {
+        Self {
+            id: txn.digest.digest_string(),
+            block_height: txn.block_height,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RpcProgramRecord {
+    pub address: Address,
+    pub package_address: String,
+    pub storage: Option<String>,
+}
+
+impl RpcProgramRecord {
+    /// Returns the program held by an account, if it holds one
+    pub fn from_account(account: &Account) -> Option<Self> {
+        let package_address = account.package_address().clone()?;
+
+        Some(Self {
+            address: account.address().clone(),
+            package_address,
+            storage: account.storage().clone(),
+        })
+    }
+}
+
 #[rpc(server, client, namespace = "state")]
 #[async_trait]
 pub trait RpcApi {
@@ -86,6 +145,13 @@ pub trait RpcApi {
         transaction_digest: RpcTransactionDigest,
     ) -> Result<RpcTransactionRecord, RpseeError>;
 
+    /// Get the lifecycle status of a transaction
+    #[method(name = "getTransactionStatus")]
+    async fn get_transaction_status(
+        &self,
+        transaction_digest: RpcTransactionDigest,
+    ) -> Result<TxnStatus, RpseeError>;
+
     /// List a group of transactions
     #[method(name = "listTransactions")]
     async fn list_transactions(
@@ -99,30 +165,81 @@ pub trait RpcApi {
     #[method(name = "updateAccount")]
     async fn update_account(&self, account: Account) -> Result<(), RpseeError>;
 
+    /// Returns an account as of the latest state, or as it was right after
+    /// the block at height `at_block` was applied if that block is still
+    /// within the node's retained state history
     #[method(name = "getAccount")]
-    async fn get_account(&self, address: Address) -> Result<Account, RpseeError>;
+    async fn get_account(
+        &self,
+        address: Address,
+        at_block: Option<u128>,
+    ) -> Result<Account, RpseeError>;
 
+    /// Returns a proof that the account is included in the latest state,
+    /// verifiable against the state root hash
+    #[method(name = "getAccountProof")]
+    async fn get_account_proof(&self, address: Address) -> Result<InclusionProof, RpseeError>;
+
+    /// Returns a proof that the transaction is included in the latest
+    /// transaction trie, verifiable against the transactions root hash
+    #[method(name = "getTransactionProof")]
+    async fn get_transaction_proof(
+        &self,
+        transaction_digest: RpcTransactionDigest,
+    ) -> Result<InclusionProof, RpseeError>;
+
+    /// Sends tokens from the node's faucet account to the given address, at
+    /// most once per drip interval. Returns the digest of the transfer
     #[method(name = "faucetDrip")]
-    async fn faucet_drip(&self, address: Address) -> Result<(), RpseeError>;
+    async fn faucet_drip(&self, address: Address) -> Result<RpcTransactionDigest, RpseeError>;
 
     #[method(name = "signTransaction")]
     async fn sign_transaction(&self, sign_opts: SignOpts) -> Result<String, RpseeError>;
 
+    /// Returns the round of the last confirmed block
     #[method(name = "getRound")]
     async fn get_round(&self) -> Result<Round, RpseeError>;
 
+    /// Returns up to `limit` confirmed blocks, ordered by height, starting at
+    /// `start_height`. At most `MAX_BLOCKS_PER_PAGE` blocks are returned
     #[method(name = "getBlocks")]
-    async fn get_blocks(&self) -> Result<Vec<Block>, RpseeError>;
+    async fn get_blocks(&self, start_height: u128, limit: usize) -> Result<Vec<Block>, RpseeError>;
 
-    #[method(name = "getProgram")]
-    async fn get_program(&self) -> Result<(), RpseeError>;
+    /// Returns the confirmed block at the given height
+    #[method(name = "getBlockByHeight")]
+    async fn get_block_by_height(&self, height: u128) -> Result<Block, RpseeError>;
 
-    #[method(name = "callProgram")]
-    async fn call_program(&self) -> Result<(), RpseeError>;
+    /// Returns any block known to the node, confirmed or not, by its hash
+    #[method(name = "getBlockByHash")]
+    async fn get_block_by_hash(&self, block_hash: String) -> Result<Block, RpseeError>;
 
+    /// Returns the program deployed at the given address
+    #[method(name = "getProgram")]
+    async fn get_program(&self, address: Address) -> Result<RpcProgramRecord, RpseeError>;
+
+    /// Returns the number of confirmed transactions sent or received by an
+    /// address
     #[method(name = "getTransactionCount")]
     async fn get_transaction_count(&self, account: Address) -> Result<usize, RpseeError>;
 
+    /// Returns up to `limit` confirmed transactions sent or received by an
+    /// address, oldest first, skipping the first `offset`. At most
+    /// `MAX_TRANSACTIONS_PER_PAGE` transactions are returned
+    #[method(name = "getTransactionsByAddress")]
+    async fn get_transactions_by_address(
+        &self,
+        address: Address,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<RpcAddressTransaction>, RpseeError>;
+
+    /// Returns the hash and height of the block that included a transaction
+    #[method(name = "getTransactionBlock")]
+    async fn get_transaction_block(
+        &self,
+        transaction_digest: RpcTransactionDigest,
+    ) -> Result<TransactionLocation, RpseeError>;
+
     #[method(name = "getNodeHealth")]
     async fn get_node_health(&self) -> Result<NodeHealthReport, RpseeError>;
 
@@ -140,4 +257,41 @@ pub trait RpcApi {
 
     #[method(name = "getLastBlock")]
     async fn get_last_block(&self) -> Result<Option<Block>, RpseeError>;
+
+    /// Notifies the header of every genesis or convergence block appended to
+    /// the DAG
+    #[subscription(
+        name = "subscribeNewHeads" => "newHeads",
+        unsubscribe = "unsubscribeNewHeads",
+        item = BlockHeader
+    )]
+    async fn subscribe_new_heads(&self) -> SubscriptionResult;
+
+    /// Notifies every transaction added to the mempool
+    #[subscription(
+        name = "subscribePendingTransactions" => "pendingTransactions",
+        unsubscribe = "unsubscribePendingTransactions",
+        item = RpcTransactionRecord
+    )]
+    async fn subscribe_pending_transactions(&self) -> SubscriptionResult;
+
+    /// Notifies the current state of an account, then every change made to it
+    #[subscription(
+        name = "subscribeAccountChanges" => "accountChanges",
+        unsubscribe = "unsubscribeAccountChanges",
+        item = Account
+    )]
+    async fn subscribe_account_changes(&self, address: Address) -> SubscriptionResult;
+
+    /// Notifies the current lifecycle status of a transaction, then every
+    /// change made to it
+    #[subscription(
+        name = "subscribeTransactionStatus" => "transactionStatus",
+        unsubscribe = "unsubscribeTransactionStatus",
+        item = TxnStatus
+    )]
+    async fn subscribe_transaction_status(
+        &self,
+        transaction_digest: RpcTransactionDigest,
+    ) -> SubscriptionResult;
 }
@@ -1,20 +1,45 @@
-use events::{EventPublisher, DEFAULT_BUFFER};
+use block::Block;
+use bulldag::graph::BullDag;
+use events::{Event, EventPublisher, DEFAULT_BUFFER};
 use jsonrpsee::server::{ServerBuilder, ServerHandle};
 use mempool::{LeftRightMempool, MempoolReadHandleFactory};
-use primitives::NodeType;
+use primitives::{NodeType, SecretKey};
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use storage::vrrbdb::{VrrbDb, VrrbDbConfig, VrrbDbReadHandle};
-use tokio::sync::mpsc::channel;
+use tokio::sync::{broadcast, mpsc::channel, Mutex};
+use vrrb_core::transactions::TxAmount;
 
-use crate::rpc::{api::RpcApiServer, server_impl::RpcServerImpl};
+use crate::rpc::{
+    api::RpcApiServer,
+    server_impl::{FaucetDrips, RpcServerImpl},
+};
 
 #[derive(Debug, Clone)]
 pub struct JsonRpcServerConfig {
     pub address: SocketAddr,
     pub vrrbdb_read_handle: VrrbDbReadHandle,
     pub mempool_read_handle_factory: MempoolReadHandleFactory,
+    pub dag: Arc<RwLock<BullDag<Block, String>>>,
     pub node_type: NodeType,
     pub events_tx: EventPublisher,
+    /// Node events feeding the subscriptions made by clients
+    pub subscription_events: broadcast::Sender<Event>,
+    /// Funds the `faucetDrip` method, which is disabled when unset
+    pub faucet: Option<FaucetDripConfig>,
+}
+
+/// Time an address has to wait between two faucet drips by default
+pub const DEFAULT_FAUCET_DRIP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Account the `faucetDrip` method transfers tokens from, how many are sent on
+/// each drip and how long an address has to wait before requesting another
+#[derive(Debug, Clone)]
+pub struct FaucetDripConfig {
+    pub secret_key: SecretKey,
+    pub drip_amount: TxAmount,
+    pub drip_interval: Duration,
 }
 
 #[derive(Debug)]
@@ -29,6 +54,10 @@ impl JsonRpcServer {
             events_tx: config.events_tx.clone(),
             vrrbdb_read_handle: config.vrrbdb_read_handle.clone(),
             mempool_read_handle_factory: config.mempool_read_handle_factory.clone(),
+            dag: config.dag.clone(),
+            subscription_events: config.subscription_events.clone(),
+            faucet: config.faucet.clone(),
+            faucet_drips: Arc::new(Mutex::new(FaucetDrips::default())),
         };
 
         let addr = server.local_addr()?;
@@ -51,21 +80,27 @@ impl Default for JsonRpcServerConfig {
 
         vrrbdb_config.path = db_path;
 
-        let vrrbdb = VrrbDb::new(vrrbdb_config);
+        let vrrbdb = VrrbDb::new(vrrbdb_config).expect("failed to open the default database");
         let vrrbdb_read_handle = vrrbdb.read_handle();
 
         let mempool = LeftRightMempool::default();
         let mempool_read_handle_factory = mempool.factory();
 
+        let dag = Arc::new(RwLock::new(BullDag::new()));
+
         let node_type = NodeType::Full;
         let (events_tx, _) = channel(DEFAULT_BUFFER);
+        let (subscription_events, _) = broadcast::channel(DEFAULT_BUFFER);
 
         JsonRpcServerConfig {
             address,
             vrrbdb_read_handle,
             mempool_read_handle_factory,
+            dag,
             node_type,
             events_tx,
+            subscription_events,
+            faucet: None,
         }
     }
 }
@@ -1,19 +1,37 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{Arc, RwLock},
+    time::Instant,
+};
 
 use async_trait::async_trait;
 use block::block::Block;
 use block::ClaimHash;
+use bulldag::graph::BullDag;
 use events::{Event, EventPublisher};
-use jsonrpsee::types::{
-    error::{INTERNAL_ERROR_CODE, PARSE_ERROR_CODE},
-    ErrorObjectOwned as RpseeError,
+use jsonrpsee::{
+    core::SubscriptionResult,
+    types::{
+        error::{INTERNAL_ERROR_CODE, PARSE_ERROR_CODE},
+        ErrorObjectOwned as RpseeError,
+    },
+    PendingSubscriptionSink, SubscriptionMessage,
 };
-use mempool::MempoolReadHandleFactory;
-use primitives::{Address, NodeType, Round};
-use secp256k1::{Message, SecretKey};
+use mempool::{MempoolReadHandleFactory, TxnStatus};
+use primitives::{Address, NodeType, PublicKey, Round};
+use secp256k1::{Message, Secp256k1, SecretKey};
+use serde::Serialize;
 use sha2::{Digest, Sha256};
-use storage::vrrbdb::{Claims, VrrbDbReadHandle};
-use telemetry::{debug, error};
+use storage::{
+    storage_proofs::InclusionProof,
+    vrrbdb::{Claims, TransactionLocation, VrrbDbReadHandle},
+};
+use telemetry::{debug, error, warn};
+use tokio::sync::{
+    broadcast::{self, error::RecvError},
+    Mutex,
+};
 use vrrb_config::QuorumMembershipConfig;
 use vrrb_core::node_health_report::NodeHealthReport;
 use vrrb_core::transactions::{
@@ -22,8 +40,11 @@ use vrrb_core::transactions::{
 use vrrb_core::{account::Account, serde_helpers::encode_to_binary};
 
 use super::{
-    api::{FullMempoolSnapshot, RpcApiServer},
-    SignOpts,
+    api::{
+        FullMempoolSnapshot, RpcAddressTransaction, RpcApiServer, RpcProgramRecord,
+        MAX_BLOCKS_PER_PAGE, MAX_TRANSACTIONS_PER_PAGE, NOT_FOUND_CODE, RATE_LIMITED_CODE,
+    },
+    FaucetDripConfig, SignOpts,
 };
 use crate::rpc::api::{FullStateSnapshot, RpcTransactionRecord};
 
@@ -32,7 +53,101 @@ pub struct RpcServerImpl {
     pub node_type: NodeType,
     pub vrrbdb_read_handle: VrrbDbReadHandle,
     pub mempool_read_handle_factory: MempoolReadHandleFactory,
+    pub dag: Arc<RwLock<BullDag<Block, String>>>,
     pub events_tx: EventPublisher,
+    pub subscription_events: broadcast::Sender<Event>,
+    pub faucet: Option<FaucetDripConfig>,
+    pub faucet_drips: Arc<Mutex<FaucetDrips>>,
+}
+
+/// Drips sent by the faucet, used to pick the nonce of the next drip and to
+/// rate limit the addresses requesting them
+#[derive(Debug, Default)]
+pub struct FaucetDrips {
+    last_nonce: u128,
+    sent_at: HashMap<Address, Instant>,
+}
+
+impl RpcServerImpl {
+    /// Returns the confirmed block with the greatest height, if any
+    fn last_confirmed_block(&self) -> Result<Option<Block>, RpseeError> {
+        self.vrrbdb_read_handle
+            .get_last_confirmed_block()
+            .map_err(|err| RpseeError::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>))
+    }
+
+    /// Accepts a subscription and notifies `initial`, if any, then every item
+    /// `next_item` produces out of the node's events, until the client
+    /// unsubscribes or disconnects.
+    async fn pipe_events<T, F>(
+        &self,
+        pending: PendingSubscriptionSink,
+        initial: Option<T>,
+        mut next_item: F,
+    ) -> SubscriptionResult
+    where
+        T: Serialize,
+        F: FnMut(&Event) -> Option<T>,
+    {
+        let mut events_rx = self.subscription_events.subscribe();
+        let sink = pending.accept().await?;
+
+        if let Some(item) = initial {
+            sink.send(SubscriptionMessage::from_json(&item)?).await?;
+        }
+
+        loop {
+            let event = tokio::select! {
+                _ = sink.closed() => break,
+                event = events_rx.recv() => event,
+            };
+
+            let event = match event {
+                Ok(event) => event,
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(
+                        "subscription {:?} skipped {skipped} events",
+                        sink.subscription_id()
+                    );
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+
+            if let Some(item) = next_item(&event) {
+                if sink
+                    .send(SubscriptionMessage::from_json(&item)?)
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the nonce the next faucet transfer has to use, accounting for
+    /// the faucet transfers still pending within the mempool
+    fn next_faucet_nonce(&self, faucet_address: &Address) -> u128 {
+        let account_nonce = self
+            .vrrbdb_read_handle
+            .get_account_by_address(faucet_address)
+            .map(|account| account.nonce())
+            .unwrap_or_default();
+
+        let pending_nonce = self
+            .mempool_read_handle_factory
+            .values()
+            .iter()
+            .filter(|txn| &txn.sender_address() == faucet_address)
+            .map(|txn| txn.nonce())
+            .max()
+            .unwrap_or_default();
+
+        account_nonce.max(pending_nonce) + 1
+    }
 }
 
 #[async_trait]
@@ -116,7 +231,7 @@ impl RpcApiServer for RpcServerImpl {
             None => {
                 return Err({
                     RpseeError::owned(
-                        INTERNAL_ERROR_CODE,
+                        NOT_FOUND_CODE,
                         "unable to find transaction".to_string(),
                         None::<()>,
                     )
@@ -125,6 +240,48 @@ impl RpcApiServer for RpcServerImpl {
         }
     }
 
+    async fn get_transaction_status(
+        &self,
+        transaction_digest: RpcTransactionDigest,
+    ) -> Result<TxnStatus, RpseeError> {
+        debug!("Received a getTransactionStatus RPC request");
+
+        let parsed_digest = transaction_digest
+            .parse::<TransactionDigest>()
+            .map_err(|_e| {
+                RpseeError::owned(
+                    PARSE_ERROR_CODE,
+                    "unable to parse transaction digest".to_string(),
+                    None::<()>,
+                )
+            })?;
+
+        if let Some(status) = self.mempool_read_handle_factory.txn_status(&parsed_digest) {
+            return Ok(status);
+        }
+
+        // NOTE: statuses are only tracked in memory, transactions applied before the node
+        // restarted or evicted from the tracker are looked up in storage instead
+        let finalized = self
+            .vrrbdb_read_handle
+            .get_transaction_block(&parsed_digest)
+            .is_ok()
+            || self
+                .vrrbdb_read_handle
+                .get_transaction_by_digest(&parsed_digest)
+                .is_ok();
+
+        if finalized {
+            return Ok(TxnStatus::Finalized);
+        }
+
+        Err(RpseeError::owned(
+            NOT_FOUND_CODE,
+            "unable to find transaction".to_string(),
+            None::<()>,
+        ))
+    }
+
     async fn list_transactions(
         &self,
         digests: Vec<RpcTransactionDigest>,
@@ -200,9 +357,26 @@ impl RpcApiServer for RpcServerImpl {
         Ok(())
     }
 
-    async fn get_account(&self, address: Address) -> Result<Account, RpseeError> {
+    async fn get_account(
+        &self,
+        address: Address,
+        at_block: Option<u128>,
+    ) -> Result<Account, RpseeError> {
         telemetry::info!("retrieving account {address}");
 
+        if let Some(block_height) = at_block {
+            return self
+                .vrrbdb_read_handle
+                .get_account_at_height(&address, block_height)
+                .map_err(|err| {
+                    RpseeError::owned(
+                        NOT_FOUND_CODE,
+                        format!("unable to find account at block {block_height}: {err}"),
+                        None::<()>,
+                    )
+                });
+        }
+
         let values = self.vrrbdb_read_handle.state_store_values().map_err(|e| {
             RpseeError::owned(
                 INTERNAL_ERROR_CODE,
@@ -220,8 +394,8 @@ impl RpcApiServer for RpcServerImpl {
             None => {
                 return Err({
                     RpseeError::owned(
-                        INTERNAL_ERROR_CODE,
-                        format!("unable to find account: {value:?}"),
+                        NOT_FOUND_CODE,
+                        format!("unable to find account {address}"),
                         None::<()>,
                     )
                 })
@@ -229,8 +403,122 @@ impl RpcApiServer for RpcServerImpl {
         }
     }
 
-    async fn faucet_drip(&self, _address: Address) -> Result<(), RpseeError> {
-        todo!()
+    async fn get_account_proof(&self, address: Address) -> Result<InclusionProof, RpseeError> {
+        debug!("Received a getAccountProof RPC request");
+
+        self.vrrbdb_read_handle
+            .get_account_proof(&address)
+            .map_err(|err| {
+                RpseeError::owned(
+                    INTERNAL_ERROR_CODE,
+                    format!("unable to prove account {address}: {err}"),
+                    None::<()>,
+                )
+            })
+    }
+
+    async fn get_transaction_proof(
+        &self,
+        transaction_digest: RpcTransactionDigest,
+    ) -> Result<InclusionProof, RpseeError> {
+        debug!("Received a getTransactionProof RPC request");
+
+        let parsed_digest = transaction_digest
+            .parse::<TransactionDigest>()
+            .map_err(|_e| {
+                RpseeError::owned(
+                    PARSE_ERROR_CODE,
+                    "unable to parse transaction digest".to_string(),
+                    None::<()>,
+                )
+            })?;
+
+        self.vrrbdb_read_handle
+            .get_transaction_proof(&parsed_digest)
+            .map_err(|err| {
+                RpseeError::owned(
+                    INTERNAL_ERROR_CODE,
+                    format!("unable to prove transaction {transaction_digest}: {err}"),
+                    None::<()>,
+                )
+            })
+    }
+
+    async fn faucet_drip(&self, address: Address) -> Result<RpcTransactionDigest, RpseeError> {
+        debug!("Received a faucetDrip RPC request");
+
+        let faucet = self.faucet.as_ref().ok_or_else(|| {
+            RpseeError::owned(
+                INTERNAL_ERROR_CODE,
+                "faucet is not enabled on this node".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        // NOTE: drips are sent one at a time so that each of them gets its own nonce, even
+        // before the previous ones reach the mempool
+        let mut drips = self.faucet_drips.lock().await;
+
+        let now = Instant::now();
+        drips
+            .sent_at
+            .retain(|_, sent_at| now.duration_since(*sent_at) < faucet.drip_interval);
+
+        if drips.sent_at.contains_key(&address) {
+            return Err(RpseeError::owned(
+                RATE_LIMITED_CODE,
+                format!("{address} already received a faucet drip recently, try again later"),
+                None::<()>,
+            ));
+        }
+
+        let faucet_public_key = PublicKey::from_secret_key(&Secp256k1::new(), &faucet.secret_key);
+        let faucet_address = Address::new(faucet_public_key);
+        let nonce = self
+            .next_faucet_nonce(&faucet_address)
+            .max(drips.last_nonce + 1);
+
+        let transfer_builder = TransactionKind::transfer_builder()
+            .timestamp(chrono::Utc::now().timestamp())
+            .sender_address(faucet_address)
+            .sender_public_key(faucet_public_key)
+            .receiver_address(address.clone())
+            .amount(faucet.drip_amount)
+            .validators(HashMap::new())
+            .nonce(nonce);
+
+        type H = secp256k1::hashes::sha256::Hash;
+        let msg = Message::from_hashed_data::<H>(transfer_builder.build_payload().as_bytes());
+        let signature = faucet.secret_key.sign_ecdsa(msg);
+
+        let txn = transfer_builder
+            .signature(signature)
+            .build_kind()
+            .map_err(|err| {
+                RpseeError::owned(
+                    INTERNAL_ERROR_CODE,
+                    format!("could not build faucet transfer: {err}"),
+                    None::<()>,
+                )
+            })?;
+
+        self.events_tx
+            .send(Event::NewTxnCreated(txn.clone()).into())
+            .await
+            .map_err(|e| {
+                RpseeError::owned(
+                    INTERNAL_ERROR_CODE,
+                    format!("could not queue transaction to mempool: {e}"),
+                    None::<()>,
+                )
+            })?;
+
+        drips.last_nonce = nonce;
+        drips.sent_at.insert(address.clone(), now);
+
+        telemetry::info!("sent faucet drip to {address}");
+
+        Ok(txn.id().digest_string())
     }
 
     async fn sign_transaction(&self, sign_opts: SignOpts) -> Result<String, RpseeError> {
@@ -271,27 +559,132 @@ impl RpcApiServer for RpcServerImpl {
     }
 
     async fn get_round(&self) -> Result<Round, RpseeError> {
-        todo!()
+        let round = match self.last_confirmed_block()? {
+            Some(Block::Genesis { block }) => block.header.round,
+            Some(Block::Convergence { block }) => block.header.round,
+            _ => {
+                return Err(RpseeError::owned(
+                    NOT_FOUND_CODE,
+                    "no blocks have been confirmed yet".to_string(),
+                    None::<()>,
+                ))
+            }
+        };
+
+        Ok(round)
     }
 
-    async fn get_blocks(&self) -> Result<Vec<Block>, RpseeError> {
-        error!("getBlocks is not implemented");
-        Ok(Vec::new())
+    async fn get_blocks(&self, start_height: u128, limit: usize) -> Result<Vec<Block>, RpseeError> {
+        self.vrrbdb_read_handle
+            .get_blocks(start_height, limit.min(MAX_BLOCKS_PER_PAGE))
+            .map_err(|err| RpseeError::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>))
     }
 
-    async fn get_program(&self) -> Result<(), RpseeError> {
-        error!("getProgram is not implemented");
-        Ok(())
+    async fn get_block_by_height(&self, height: u128) -> Result<Block, RpseeError> {
+        self.vrrbdb_read_handle
+            .get_block_by_height(height)
+            .map_err(|err| RpseeError::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>))?
+            .ok_or_else(|| {
+                RpseeError::owned(
+                    NOT_FOUND_CODE,
+                    format!("unable to find block at height {height}"),
+                    None::<()>,
+                )
+            })
     }
 
-    async fn call_program(&self) -> Result<(), RpseeError> {
-        error!("callProgram is not implemented");
-        Ok(())
+    async fn get_block_by_hash(&self, block_hash: String) -> Result<Block, RpseeError> {
+        self.vrrbdb_read_handle
+            .get_block(&block_hash)
+            .map_err(|err| RpseeError::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>))?
+            .ok_or_else(|| {
+                RpseeError::owned(
+                    NOT_FOUND_CODE,
+                    format!("unable to find block {block_hash}"),
+                    None::<()>,
+                )
+            })
+    }
+
+    async fn get_program(&self, address: Address) -> Result<RpcProgramRecord, RpseeError> {
+        let account = self
+            .vrrbdb_read_handle
+            .get_account_by_address(&address)
+            .map_err(|err| RpseeError::owned(NOT_FOUND_CODE, err.to_string(), None::<()>))?;
+
+        RpcProgramRecord::from_account(&account).ok_or_else(|| {
+            RpseeError::owned(
+                NOT_FOUND_CODE,
+                format!("no program is deployed at {address}"),
+                None::<()>,
+            )
+        })
     }
 
-    async fn get_transaction_count(&self, _account: Address) -> Result<usize, RpseeError> {
-        error!("getTransactionCount is not implemented");
-        Ok(0)
+    async fn get_transaction_count(&self, account: Address) -> Result<usize, RpseeError> {
+        debug!("Received a getTransactionCount RPC request");
+
+        self.vrrbdb_read_handle
+            .get_transaction_count(&account)
+            .map_err(|err| {
+                RpseeError::owned(
+                    INTERNAL_ERROR_CODE,
+                    format!("unable to count transactions of {account}: {err}"),
+                    None::<()>,
+                )
+            })
+    }
+
+    async fn get_transactions_by_address(
+        &self,
+        address: Address,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<RpcAddressTransaction>, RpseeError> {
+        debug!("Received a getTransactionsByAddress RPC request");
+
+        let transactions = self
+            .vrrbdb_read_handle
+            .get_transactions_by_address(&address, offset, limit.min(MAX_TRANSACTIONS_PER_PAGE))
+            .map_err(|err| {
+                RpseeError::owned(
+                    INTERNAL_ERROR_CODE,
+                    format!("unable to read transactions of {address}: {err}"),
+                    None::<()>,
+                )
+            })?;
+
+        Ok(transactions
+            .into_iter()
+            .map(RpcAddressTransaction::from)
+            .collect())
+    }
+
+    async fn get_transaction_block(
+        &self,
+        transaction_digest: RpcTransactionDigest,
+    ) -> Result<TransactionLocation, RpseeError> {
+        debug!("Received a getTransactionBlock RPC request");
+
+        let parsed_digest = transaction_digest
+            .parse::<TransactionDigest>()
+            .map_err(|_e| {
+                RpseeError::owned(
+                    PARSE_ERROR_CODE,
+                    "unable to parse transaction digest".to_string(),
+                    None::<()>,
+                )
+            })?;
+
+        self.vrrbdb_read_handle
+            .get_transaction_block(&parsed_digest)
+            .map_err(|err| {
+                RpseeError::owned(
+                    NOT_FOUND_CODE,
+                    format!("unable to find the block of transaction {transaction_digest}: {err}"),
+                    None::<()>,
+                )
+            })
     }
 
     async fn get_node_health(&self) -> Result<NodeHealthReport, RpseeError> {
@@ -355,7 +748,105 @@ impl RpcApiServer for RpcServerImpl {
     }
 
     async fn get_last_block(&self) -> Result<Option<Block>, RpseeError> {
-        error!("getLastBlock is not implemented");
-        Ok(None)
+        self.last_confirmed_block()
+    }
+
+    async fn subscribe_new_heads(&self, pending: PendingSubscriptionSink) -> SubscriptionResult {
+        let dag = self.dag.clone();
+
+        self.pipe_events(pending, None, |event| {
+            let Event::BlockAppended(block_hash) = event else {
+                return None;
+            };
+
+            let guard = dag.read().ok()?;
+            match guard.get_vertex(block_hash.clone())?.get_data() {
+                Block::Genesis { block } => Some(block.header),
+                Block::Convergence { block } => Some(block.header),
+                Block::Proposal { .. } => None,
+            }
+        })
+        .await
+    }
+
+    async fn subscribe_pending_transactions(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> SubscriptionResult {
+        let mempool_read_handle_factory = self.mempool_read_handle_factory.clone();
+
+        self.pipe_events(pending, None, |event| {
+            let Event::TxnAddedToMempool(digest) = event else {
+                return None;
+            };
+
+            mempool_read_handle_factory
+                .get(digest)
+                .map(|record| RpcTransactionRecord::from(record.txn))
+        })
+        .await
+    }
+
+    async fn subscribe_account_changes(
+        &self,
+        pending: PendingSubscriptionSink,
+        address: Address,
+    ) -> SubscriptionResult {
+        let vrrbdb_read_handle = self.vrrbdb_read_handle.clone();
+        let account = vrrbdb_read_handle.get_account_by_address(&address).ok();
+        let mut last_hash = account.as_ref().map(|account| account.hash().to_string());
+
+        self.pipe_events(pending, account, move |event| {
+            if !matches!(
+                event,
+                Event::BlockAppended(_) | Event::UpdateState(_) | Event::BuildProposalBlock(_)
+            ) {
+                return None;
+            }
+
+            let account = vrrbdb_read_handle.get_account_by_address(&address).ok()?;
+            let hash = Some(account.hash().to_string());
+            if hash == last_hash {
+                return None;
+            }
+
+            last_hash = hash;
+            Some(account)
+        })
+        .await
+    }
+
+    async fn subscribe_transaction_status(
+        &self,
+        pending: PendingSubscriptionSink,
+        transaction_digest: RpcTransactionDigest,
+    ) -> SubscriptionResult {
+        let Ok(digest) = transaction_digest.parse::<TransactionDigest>() else {
+            pending
+                .reject(RpseeError::owned(
+                    PARSE_ERROR_CODE,
+                    "unable to parse transaction digest".to_string(),
+                    None::<()>,
+                ))
+                .await;
+            return Ok(());
+        };
+
+        let mempool_read_handle_factory = self.mempool_read_handle_factory.clone();
+        let status = mempool_read_handle_factory.txn_status(&digest);
+        let mut last_status = status.clone();
+
+        // NOTE: statuses are updated by several modules without emitting events of their own,
+        // so they are checked again whenever the node handles any event
+        self.pipe_events(pending, status, move |_| {
+            let status = mempool_read_handle_factory.txn_status(&digest);
+            if status == last_status {
+                return None;
+            }
+
+            last_status = status.clone();
+            status
+        })
+        .await
     }
 }
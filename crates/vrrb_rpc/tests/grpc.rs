@@ -0,0 +1,161 @@
+use block::Block;
+use events::{Event, DEFAULT_BUFFER};
+use miner::test_helpers::mine_genesis;
+use primitives::{generate_mock_account_keypair, Address};
+use secp256k1::Message;
+use storage::vrrbdb::{VrrbDb, VrrbDbConfig};
+use tokio::sync::mpsc::channel;
+use tonic::Code;
+use vrrb_core::{
+    account::Account,
+    transactions::{Transaction, TransactionKind, BASE_FEE},
+};
+use vrrb_rpc::grpc::{
+    node_read_service::v1::{
+        node_read_service_client::NodeReadServiceClient, BlockKind, GetAccountRequest,
+        GetBlockRequest, GetBlocksRequest, GetNodeTypeRequest,
+    },
+    node_write_service::v1::{
+        node_write_service_client::NodeWriteServiceClient, CreateTransactionRequest,
+    },
+    GrpcServer, GrpcServerConfig,
+};
+
+#[tokio::test]
+async fn server_serves_node_state() {
+    let mut vrrbdb_config = VrrbDbConfig::default();
+    vrrbdb_config.path = std::env::temp_dir().join(vrrb_core::helpers::generate_random_string());
+    let mut vrrbdb = VrrbDb::new(vrrbdb_config).unwrap();
+
+    let genesis = mine_genesis().unwrap();
+    let genesis_hash = genesis.hash.clone();
+    vrrbdb
+        .insert_block(&Block::Genesis { block: genesis }, &[])
+        .unwrap();
+
+    let (_, public_key) = generate_mock_account_keypair();
+    let address = Address::new(public_key);
+    vrrbdb
+        .insert_account(address.clone(), Account::new(address.clone()))
+        .unwrap();
+
+    let config = GrpcServerConfig {
+        vrrbdb_read_handle: vrrbdb.read_handle(),
+        ..Default::default()
+    };
+
+    let (handle, addr) = GrpcServer::run(&config).await.unwrap();
+    let mut client = NodeReadServiceClient::connect(format!("http://{addr}"))
+        .await
+        .unwrap();
+
+    let node_type = client
+        .get_node_type(GetNodeTypeRequest {})
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(node_type.result, "Full");
+
+    let account = client
+        .get_account(GetAccountRequest {
+            address: address.to_string(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(account.address, address.to_string());
+    assert_eq!(account.credits, "0");
+
+    let status = client
+        .get_account(GetAccountRequest {
+            address: "0x1234".to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+
+    let block = client
+        .get_block(GetBlockRequest {
+            hash: genesis_hash.clone(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(block.kind(), BlockKind::Genesis);
+    assert_eq!(block.header.unwrap().block_height, "0");
+
+    let status = client
+        .get_block(GetBlockRequest {
+            hash: "unknown".to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+
+    let blocks = client
+        .get_blocks(GetBlocksRequest {
+            start_height: 0,
+            limit: 10,
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .blocks;
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].hash, genesis_hash);
+
+    handle.stop().await.unwrap();
+}
+
+#[tokio::test]
+async fn server_queues_created_transactions() {
+    let (events_tx, mut events_rx) = channel(DEFAULT_BUFFER);
+
+    let config = GrpcServerConfig {
+        events_tx,
+        ..Default::default()
+    };
+
+    let (handle, addr) = GrpcServer::run(&config).await.unwrap();
+    let mut client = NodeWriteServiceClient::connect(format!("http://{addr}"))
+        .await
+        .unwrap();
+
+    let (secret_key, public_key) = generate_mock_account_keypair();
+    let (_, recv_public_key) = generate_mock_account_keypair();
+    let signature = secret_key
+        .sign_ecdsa(Message::from_hashed_data::<secp256k1::hashes::sha256::Hash>(b"vrrb"));
+
+    let record = client
+        .create_transaction(CreateTransactionRequest {
+            timestamp: 0,
+            sender_address: Address::new(public_key).to_string(),
+            sender_public_key: public_key.to_string(),
+            receiver_address: Address::new(recv_public_key).to_string(),
+            token: None,
+            // NOTE: amounts beyond 64 bits are sent as decimal strings
+            amount: "100000000000000000000".to_string(),
+            signature: signature.to_string(),
+            validators: Default::default(),
+            nonce: "1".to_string(),
+            max_fee: None,
+            priority_fee: "5".to_string(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    let event: Event = events_rx.recv().await.unwrap().into();
+    let Event::NewTxnCreated(txn) = event else {
+        panic!("expected a NewTxnCreated event, got {event:?}");
+    };
+
+    assert_eq!(record.id, txn.id().digest_string());
+    assert_eq!(txn.amount(), 100_000_000_000_000_000_000);
+    assert_eq!(record.amount, "100000000000000000000");
+    assert_eq!(txn.fee(), BASE_FEE + 5);
+    assert_eq!(record.fee, (BASE_FEE + 5).to_string());
+    assert!(matches!(txn, TransactionKind::Transfer(_)));
+
+    handle.stop().await.unwrap();
+}
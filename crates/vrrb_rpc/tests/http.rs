@@ -1,9 +1,19 @@
 use crate::{HttpApiServer, HttpApiServerConfig, HttpApiServerConfigBuilder};
 use axum::{body::Body, http::Request};
 
-use events::Event;
+use block::Block;
+use events::{Event, DEFAULT_BUFFER};
 use hyper::{Client, StatusCode};
-use tokio::sync::broadcast::channel;
+use mempool::LeftRightMempool;
+use miner::test_helpers::mine_genesis;
+use primitives::{generate_mock_account_keypair, Address};
+use secp256k1::Message;
+use storage::vrrbdb::{VrrbDb, VrrbDbConfig};
+use tokio::sync::{broadcast::channel, mpsc};
+use vrrb_core::{
+    account::Account,
+    transactions::{Transaction, TransactionKind},
+};
 use vrrb_rpc::http::*;
 
 mod common;
@@ -45,3 +55,132 @@ async fn server_starts_and_stops() {
     ctrl_tx.send(Event::Stop).unwrap();
     server_handle.await.unwrap();
 }
+
+async fn get_json(
+    client: &Client<hyper::client::HttpConnector>,
+    uri: String,
+) -> (StatusCode, serde_json::Value) {
+    let response = client.get(uri.parse().unwrap()).await.unwrap();
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn server_serves_accounts_transactions_and_blocks() {
+    let mut vrrbdb_config = VrrbDbConfig::default();
+    vrrbdb_config.path = std::env::temp_dir().join(vrrb_core::helpers::generate_random_string());
+    let mut vrrbdb = VrrbDb::new(vrrbdb_config).unwrap();
+
+    let genesis = mine_genesis().unwrap();
+    let genesis_hash = genesis.hash.clone();
+    vrrbdb
+        .insert_block(&Block::Genesis { block: genesis }, &[])
+        .unwrap();
+
+    let (secret_key, public_key) = generate_mock_account_keypair();
+    let (_, recv_public_key) = generate_mock_account_keypair();
+    let address = Address::new(public_key);
+
+    vrrbdb
+        .insert_account(address.clone(), Account::new(address.clone()))
+        .unwrap();
+
+    let txn = TransactionKind::transfer_builder()
+        .timestamp(0)
+        .sender_address(address.clone())
+        .sender_public_key(public_key)
+        .receiver_address(Address::new(recv_public_key))
+        .amount(10)
+        .signature(
+            secret_key
+                .sign_ecdsa(Message::from_hashed_data::<secp256k1::hashes::sha256::Hash>(b"vrrb")),
+        )
+        .nonce(1)
+        .build_kind()
+        .expect("failed to build transfer transaction");
+
+    let mut mempool = LeftRightMempool::default();
+    mempool.insert(txn.clone()).unwrap();
+
+    let (events_tx, mut events_rx) = mpsc::channel(DEFAULT_BUFFER);
+
+    let config: HttpApiServerConfig = HttpApiServerConfigBuilder::default()
+        .address("127.0.0.1:0")
+        .api_title("Node HTTP API")
+        .api_version("1.0")
+        .server_timeout(None)
+        .tls_config(None)
+        .state(HttpApiState {
+            vrrbdb_read_handle: vrrbdb.read_handle(),
+            mempool_read_handle_factory: mempool.factory(),
+            events_tx,
+        })
+        .build();
+
+    let api = HttpApiServer::new(config).unwrap();
+    let addr = api.address().unwrap();
+    let (ctrl_tx, mut ctrl_rx) = channel(1);
+
+    let server_handle = tokio::spawn(async move {
+        api.start(&mut ctrl_rx).await.unwrap();
+    });
+
+    let client = Client::new();
+
+    let (status, account) = get_json(&client, format!("http://{addr}/accounts/{address}")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        serde_json::from_value::<Account>(account)
+            .unwrap()
+            .address(),
+        &address
+    );
+
+    let (status, body) = get_json(&client, format!("http://{addr}/accounts/0x1234")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], 400);
+
+    let digest = txn.id().digest_string();
+    let (status, body) = get_json(&client, format!("http://{addr}/transactions/{digest}")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["id"], digest);
+
+    let (status, body) = get_json(&client, format!("http://{addr}/blocks/{genesis_hash}")).await;
+    assert_eq!(status, StatusCode::OK);
+    let block: Block = serde_json::from_value(body).unwrap();
+    assert!(matches!(block, Block::Genesis { block } if block.hash == genesis_hash));
+
+    let (status, body) = get_json(&client, format!("http://{addr}/blocks/unknown")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"]["code"], 404);
+
+    let (status, body) = get_json(&client, format!("http://{addr}/blocks?from=0&limit=10")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.as_array().unwrap().len(), 1);
+
+    let (status, body) = get_json(&client, format!("http://{addr}/openapi.json")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["paths"]["/transactions/{digest}"].is_object());
+
+    let response = client
+        .request(
+            Request::builder()
+                .method("POST")
+                .uri(format!("http://{addr}/transactions"))
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&txn).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+    let event: Event = events_rx.recv().await.unwrap().into();
+    assert_eq!(event, Event::NewTxnCreated(txn));
+
+    ctrl_tx.send(Event::Stop).unwrap();
+    server_handle.await.unwrap();
+}
@@ -1,13 +1,28 @@
-use std::{collections::HashMap, net::SocketAddr};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, RwLock},
+};
 
-use events::{EventMessage, DEFAULT_BUFFER};
+use block::{Block, InnerBlock};
+use bulldag::graph::BullDag;
+use events::{Event, EventMessage, DEFAULT_BUFFER};
+use jsonrpsee::core::ClientError;
+use mempool::{LeftRightMempool, TxnStatus};
+use miner::test_helpers::{add_genesis_to_dag, mine_next_convergence_block, MinerDag};
 use primitives::{generate_mock_account_keypair, Address};
 use secp256k1::Message;
-use storage::storage_utils::remove_vrrb_data_dir;
-use tokio::sync::mpsc::channel;
-use vrrb_core::transactions::{generate_transfer_digest_vec, Token, TransactionKind};
+use storage::{
+    storage_utils::remove_vrrb_data_dir,
+    vrrbdb::{VrrbDb, VrrbDbConfig},
+};
+use tokio::sync::{broadcast, mpsc::channel};
+use vrrb_core::account::{Account, AccountField};
+use vrrb_core::transactions::{generate_transfer_digest_vec, Token, Transaction, TransactionKind};
 use vrrb_rpc::rpc::{
-    api::{RpcApiClient, RpcTransactionRecord},
+    api::{
+        RpcApiClient, RpcProgramRecord, RpcTransactionRecord, NOT_FOUND_CODE, RATE_LIMITED_CODE,
+    },
     client::create_client,
     *,
 };
@@ -54,6 +69,8 @@ async fn server_can_publish_transactions_to_be_created() {
         token,
         amount,
         nonce,
+        None,
+        0,
     );
 
     type H = secp256k1::hashes::sha256::Hash;
@@ -74,7 +91,7 @@ async fn server_can_publish_transactions_to_be_created() {
     let rec = client.create_txn(txn.clone()).await.unwrap();
 
     let mock_digest =
-        "d9e444c59d773094d8aa755b9f412383ce0c15a99bc342d39b025a7cbf0b3d1a".to_string();
+        "a3fbe45974e545c015ac7d862a32953cc65e26c96b8d293704eac2f729758f8a".to_string();
 
     let mock_record = RpcTransactionRecord {
         id: mock_digest,
@@ -96,3 +113,320 @@ async fn server_can_publish_transactions_to_be_created() {
 
     handle.stop().expect("Unable to stop server");
 }
+
+#[tokio::test]
+async fn server_serves_blocks_from_the_block_store() {
+    remove_vrrb_data_dir();
+
+    let mut dag: MinerDag = Arc::new(RwLock::new(BullDag::new()));
+    let genesis_hash = add_genesis_to_dag(&mut dag).unwrap();
+    let convergence_hash = mine_next_convergence_block(dag.clone()).unwrap();
+
+    let mut vrrbdb_config = VrrbDbConfig::default();
+    vrrbdb_config.path = std::env::temp_dir().join(vrrb_core::helpers::generate_random_string());
+    let vrrbdb = VrrbDb::new(vrrbdb_config).unwrap();
+
+    for hash in [&genesis_hash, &convergence_hash] {
+        let block = dag
+            .read()
+            .unwrap()
+            .get_vertex(hash.clone())
+            .unwrap()
+            .get_data();
+        vrrbdb.insert_block(&block, &[]).unwrap();
+    }
+
+    let json_rpc_server_config = JsonRpcServerConfig {
+        address: "127.0.0.1:0".parse().unwrap(),
+        vrrbdb_read_handle: vrrbdb.read_handle(),
+        ..Default::default()
+    };
+
+    let (handle, rpc_server_address) = JsonRpcServer::run(&json_rpc_server_config).await.unwrap();
+
+    let client = create_client(rpc_server_address).await.unwrap();
+
+    let blocks = client.get_blocks(0, 10).await.unwrap();
+    let hashes: Vec<String> = blocks.iter().map(|block| block.hash()).collect();
+    assert_eq!(hashes, vec![genesis_hash.clone(), convergence_hash.clone()]);
+
+    let Block::Convergence { block: convergence } = blocks[1].clone() else {
+        panic!("expected a convergence block");
+    };
+
+    assert_eq!(
+        client
+            .get_blocks(convergence.header.block_height, 10)
+            .await
+            .unwrap(),
+        vec![blocks[1].clone()]
+    );
+    assert_eq!(
+        client.get_blocks(0, 1).await.unwrap(),
+        vec![blocks[0].clone()]
+    );
+
+    assert_eq!(
+        client
+            .get_block_by_height(convergence.header.block_height)
+            .await
+            .unwrap(),
+        blocks[1]
+    );
+    assert_eq!(
+        client.get_block_by_hash(genesis_hash).await.unwrap(),
+        blocks[0]
+    );
+    assert!(matches!(
+        client.get_block_by_hash("unknown".to_string()).await,
+        Err(ClientError::Call(err)) if err.code() == NOT_FOUND_CODE
+    ));
+
+    assert_eq!(client.get_round().await.unwrap(), convergence.header.round);
+    assert_eq!(
+        client.get_last_block().await.unwrap(),
+        Some(blocks[1].clone())
+    );
+
+    handle.stop().expect("Unable to stop server");
+}
+
+#[tokio::test]
+async fn server_drips_from_faucet_when_enabled() {
+    remove_vrrb_data_dir();
+
+    let (events_tx, mut events_rx) = channel::<EventMessage>(DEFAULT_BUFFER);
+    let (faucet_secret_key, faucet_public_key) = generate_mock_account_keypair();
+    let (_, recv_public_key) = generate_mock_account_keypair();
+    let recv_address = Address::new(recv_public_key);
+
+    let disabled_config = JsonRpcServerConfig {
+        address: "127.0.0.1:0".parse().unwrap(),
+        events_tx: events_tx.clone(),
+        ..Default::default()
+    };
+
+    let (disabled_handle, disabled_address) = JsonRpcServer::run(&disabled_config).await.unwrap();
+    let disabled_client = create_client(disabled_address).await.unwrap();
+
+    assert!(disabled_client
+        .faucet_drip(recv_address.clone())
+        .await
+        .is_err());
+
+    disabled_handle.stop().expect("Unable to stop server");
+
+    let json_rpc_server_config = JsonRpcServerConfig {
+        address: "127.0.0.1:0".parse().unwrap(),
+        events_tx,
+        faucet: Some(FaucetDripConfig {
+            secret_key: faucet_secret_key,
+            drip_amount: 100,
+            drip_interval: DEFAULT_FAUCET_DRIP_INTERVAL,
+        }),
+        ..Default::default()
+    };
+
+    let (handle, rpc_server_address) = JsonRpcServer::run(&json_rpc_server_config).await.unwrap();
+    let client = create_client(rpc_server_address).await.unwrap();
+
+    let digest = client.faucet_drip(recv_address.clone()).await.unwrap();
+
+    let event: Event = events_rx.recv().await.unwrap().into();
+    let Event::NewTxnCreated(txn) = event else {
+        panic!("expected the faucet transfer to be queued");
+    };
+
+    assert_eq!(txn.id().digest_string(), digest);
+    assert_eq!(txn.sender_address(), Address::new(faucet_public_key));
+    assert_eq!(txn.receiver_address(), recv_address);
+    assert_eq!(txn.amount(), 100);
+    assert_eq!(txn.nonce(), 1);
+
+    assert!(matches!(
+        client.faucet_drip(recv_address.clone()).await,
+        Err(ClientError::Call(err)) if err.code() == RATE_LIMITED_CODE
+    ));
+
+    // Drips do not wait for the previous ones to reach the mempool to get their nonce
+    let (_, other_public_key) = generate_mock_account_keypair();
+    client
+        .faucet_drip(Address::new(other_public_key))
+        .await
+        .unwrap();
+
+    let event: Event = events_rx.recv().await.unwrap().into();
+    let Event::NewTxnCreated(txn) = event else {
+        panic!("expected the faucet transfer to be queued");
+    };
+    assert_eq!(txn.nonce(), 2);
+
+    handle.stop().expect("Unable to stop server");
+}
+
+#[tokio::test]
+async fn server_returns_deployed_programs() {
+    remove_vrrb_data_dir();
+
+    let mut vrrbdb_config = VrrbDbConfig::default();
+    vrrbdb_config.path = std::env::temp_dir().join(vrrb_core::helpers::generate_random_string());
+    let mut vrrbdb = VrrbDb::new(vrrbdb_config).unwrap();
+
+    let (_, program_public_key) = generate_mock_account_keypair();
+    let (_, user_public_key) = generate_mock_account_keypair();
+    let program_address = Address::new(program_public_key);
+    let user_address = Address::new(user_public_key);
+
+    let mut program_account = Account::new(program_address.clone());
+    program_account
+        .update_field(AccountField::PackageAddress(Some("package".to_string())))
+        .unwrap();
+
+    vrrbdb
+        .insert_account(program_address.clone(), program_account)
+        .unwrap();
+    vrrbdb
+        .insert_account(user_address.clone(), Account::new(user_address.clone()))
+        .unwrap();
+
+    let json_rpc_server_config = JsonRpcServerConfig {
+        address: "127.0.0.1:0".parse().unwrap(),
+        vrrbdb_read_handle: vrrbdb.read_handle(),
+        ..Default::default()
+    };
+
+    let (handle, rpc_server_address) = JsonRpcServer::run(&json_rpc_server_config).await.unwrap();
+    let client = create_client(rpc_server_address).await.unwrap();
+
+    assert_eq!(
+        client.get_program(program_address.clone()).await.unwrap(),
+        RpcProgramRecord {
+            address: program_address.clone(),
+            package_address: "package".to_string(),
+            storage: None,
+        }
+    );
+    assert!(matches!(
+        client.get_program(user_address).await,
+        Err(ClientError::Call(err)) if err.code() == NOT_FOUND_CODE
+    ));
+
+    handle.stop().expect("Unable to stop server");
+}
+
+#[tokio::test]
+async fn server_notifies_subscribers_of_node_events() {
+    remove_vrrb_data_dir();
+
+    let mut dag: MinerDag = Arc::new(RwLock::new(BullDag::new()));
+    let genesis_hash = add_genesis_to_dag(&mut dag).unwrap();
+
+    let mut vrrbdb_config = VrrbDbConfig::default();
+    vrrbdb_config.path = std::env::temp_dir().join(vrrb_core::helpers::generate_random_string());
+    let mut vrrbdb = VrrbDb::new(vrrbdb_config).unwrap();
+
+    let (secret_key, public_key) = generate_mock_account_keypair();
+    let (_, recv_public_key) = generate_mock_account_keypair();
+    let address = Address::new(public_key);
+
+    let txn = TransactionKind::transfer_builder()
+        .timestamp(0)
+        .sender_address(address.clone())
+        .sender_public_key(public_key)
+        .receiver_address(Address::new(recv_public_key))
+        .amount(10)
+        .signature(
+            secret_key
+                .sign_ecdsa(Message::from_hashed_data::<secp256k1::hashes::sha256::Hash>(b"vrrb")),
+        )
+        .nonce(1)
+        .build_kind()
+        .expect("failed to build transfer transaction");
+
+    let mut mempool = LeftRightMempool::default();
+    mempool.insert(txn.clone()).unwrap();
+
+    let account = Account::new(address.clone());
+    vrrbdb
+        .insert_account(address.clone(), account.clone())
+        .unwrap();
+
+    let (subscription_events, _) = broadcast::channel(DEFAULT_BUFFER);
+
+    let json_rpc_server_config = JsonRpcServerConfig {
+        address: "127.0.0.1:0".parse().unwrap(),
+        vrrbdb_read_handle: vrrbdb.read_handle(),
+        mempool_read_handle_factory: mempool.factory(),
+        dag: dag.clone(),
+        subscription_events: subscription_events.clone(),
+        ..Default::default()
+    };
+
+    let (handle, rpc_server_address) = JsonRpcServer::run(&json_rpc_server_config).await.unwrap();
+    let client = create_client(rpc_server_address).await.unwrap();
+
+    let mut new_heads = client.subscribe_new_heads().await.unwrap();
+    let mut pending_txns = client.subscribe_pending_transactions().await.unwrap();
+    let mut account_changes = client
+        .subscribe_account_changes(address.clone())
+        .await
+        .unwrap();
+    let mut txn_status = client
+        .subscribe_transaction_status(txn.id().digest_string())
+        .await
+        .unwrap();
+
+    assert_eq!(account_changes.next().await.unwrap().unwrap(), account);
+    assert_eq!(
+        txn_status.next().await.unwrap().unwrap(),
+        TxnStatus::Pending
+    );
+
+    subscription_events
+        .send(Event::BlockAppended(genesis_hash.clone()))
+        .unwrap();
+    subscription_events
+        .send(Event::TxnAddedToMempool(txn.id()))
+        .unwrap();
+
+    let genesis = miner::test_helpers::get_genesis_block_from_dag(dag).unwrap();
+    assert_eq!(new_heads.next().await.unwrap().unwrap(), genesis.header);
+    assert_eq!(
+        pending_txns.next().await.unwrap().unwrap().id,
+        txn.id().digest_string()
+    );
+
+    let mut updated_account = account.clone();
+    updated_account
+        .update_field(AccountField::Credits(100))
+        .unwrap();
+    vrrbdb
+        .insert_account(address.clone(), updated_account.clone())
+        .unwrap();
+    mempool
+        .status_tracker()
+        .update(&txn.id(), TxnStatus::Voting);
+
+    subscription_events
+        .send(Event::BlockAppended(genesis.get_hash()))
+        .unwrap();
+
+    assert_eq!(
+        account_changes.next().await.unwrap().unwrap(),
+        updated_account
+    );
+    assert_eq!(txn_status.next().await.unwrap().unwrap(), TxnStatus::Voting);
+
+    drop(client);
+
+    // Subscriptions are dropped along with the connection
+    tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        while subscription_events.receiver_count() > 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("subscriptions were not cleaned up");
+
+    handle.stop().expect("Unable to stop server");
+}
@@ -214,11 +214,15 @@ impl Wallet {
     }
 
     pub async fn get_account(&mut self, address: Address) -> WalletResult<Account> {
-        let account = self.client.get_account(address).await.map_err(|err| {
-            error!("{:?}", err.to_string());
+        let account = self
+            .client
+            .get_account(address, None)
+            .await
+            .map_err(|err| {
+                error!("{:?}", err.to_string());
 
-            WalletError::Custom(format!("API Error: {err}"))
-        })?;
+                WalletError::Custom(format!("API Error: {err}"))
+            })?;
 
         Ok(account)
     }
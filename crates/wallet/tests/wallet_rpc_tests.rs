@@ -83,7 +83,7 @@ pub async fn wallet_sends_txn_to_rpc_server() {
 
     assert_eq!(
         &txn_digest.to_string(),
-        "a327da47d1cc14f96c0048ce2e7ee70d704581ced66d1f9feef06a85eac5b137"
+        "79b407b093c5f98f88149dfebba3a5541587130576c143c6a3a6776e05b240a6"
     );
 }
 